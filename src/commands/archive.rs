@@ -0,0 +1,341 @@
+use anyhow::{Context, Result};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use crate::config::WorktreeConfig;
+use crate::error::WorktreeError;
+use crate::git::GitRepo;
+use crate::output;
+use crate::provision::{self, config_files};
+use crate::storage::{WorktreeStorage, read_worktree_head_branch, unix_now};
+
+/// Metadata stored next to each archive as `<archive name>.toml`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveInfo {
+    pub feature: String,
+    /// Branch checked out in the worktree, if HEAD was not detached
+    pub branch: Option<String>,
+    /// HEAD commit of the worktree when it was archived
+    pub commit: Option<String>,
+    /// Seconds since the Unix epoch
+    pub created: u64,
+}
+
+/// Archives a worktree of the current repository into
+/// `<storage>/<repo>/.archives/` without removing it
+///
+/// # Errors
+/// Returns an error if the worktree doesn't exist or the archive cannot be written
+pub fn archive_worktree(feature_name: &str) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let git_repo = GitRepo::open(&current_dir)?;
+    let repo_path = git_repo.get_repo_path();
+
+    let storage = WorktreeStorage::new()?;
+    let repo_name = WorktreeStorage::get_repo_name(repo_path)?;
+    let worktree_path = storage.get_worktree_path(&repo_name, feature_name);
+
+    if !worktree_path.exists() {
+        return Err(WorktreeError::WorktreeMissing {
+            path: worktree_path,
+        }
+        .into());
+    }
+
+    let config = WorktreeConfig::load_from_repo(repo_path)?;
+    let archive_path = create_archive(&storage, &repo_name, feature_name, &worktree_path, &config)?;
+
+    println!(
+        "📦 Archived '{}' to {}",
+        feature_name,
+        archive_path.display()
+    );
+
+    Ok(())
+}
+
+/// Writes a gzipped tarball of a worktree's files (everything except the `.git` link
+/// and paths matching the copy exclude patterns) plus its metadata. Returns the path
+/// of the archive.
+///
+/// # Errors
+/// Returns an error if the archive directory cannot be created or the archive cannot
+/// be written
+pub fn create_archive(
+    storage: &WorktreeStorage,
+    repo_name: &str,
+    feature_name: &str,
+    worktree_path: &Path,
+    config: &WorktreeConfig,
+) -> Result<PathBuf> {
+    let archive_dir = storage.get_archive_dir(repo_name);
+    std::fs::create_dir_all(&archive_dir).with_context(|| {
+        format!(
+            "Failed to create archive directory: {}",
+            archive_dir.display()
+        )
+    })?;
+
+    let info = ArchiveInfo {
+        feature: feature_name.to_string(),
+        branch: read_worktree_head_branch(worktree_path),
+        commit: GitRepo::open(worktree_path).ok().and_then(|repo| {
+            repo.resolve_reference("HEAD")
+                .ok()
+                .map(|c| c.id().to_string())
+        }),
+        created: unix_now(),
+    };
+
+    let archive_path = unused_archive_path(&archive_dir, feature_name, info.created);
+    let file = File::create(&archive_path)
+        .with_context(|| format!("Failed to create archive: {}", archive_path.display()))?;
+
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    builder.follow_symlinks(false);
+    let excludes = config.copy_patterns.exclude.as_deref().unwrap_or_default();
+    append_dir(&mut builder, worktree_path, Path::new(""), excludes)?;
+    builder
+        .into_inner()
+        .and_then(GzEncoder::finish)
+        .with_context(|| format!("Failed to write archive: {}", archive_path.display()))?;
+
+    std::fs::write(info_path(&archive_path), toml::to_string(&info)?)?;
+
+    Ok(archive_path)
+}
+
+/// Restores an archived worktree of the current repository. Without a feature name,
+/// lists the available archives instead.
+///
+/// The worktree is recreated on its archived branch (or, if that branch is gone, on
+/// a new branch at the archived commit) and the archived files are unpacked over it.
+///
+/// # Errors
+/// Returns an error if:
+/// - No archive exists for the feature
+/// - The worktree already exists
+/// - Git operations fail
+/// - The archive cannot be unpacked
+pub fn restore_worktree(feature_name: Option<&str>) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let git_repo = GitRepo::open(&current_dir)?;
+    let repo_path = git_repo.get_repo_path();
+
+    let storage = WorktreeStorage::new()?;
+    let repo_name = WorktreeStorage::get_repo_name(repo_path)?;
+    let archives = list_archives(&storage.get_archive_dir(&repo_name))?;
+
+    let Some(feature_name) = feature_name else {
+        if archives.is_empty() {
+            println!("No archives found for {}", repo_name);
+            return Ok(());
+        }
+        println!("Archives for {}:", repo_name);
+        for (path, info) in &archives {
+            let branch_info = info
+                .branch
+                .as_ref()
+                .map(|b| format!(" ({})", b))
+                .unwrap_or_default();
+            println!("   {}{} - {}", info.feature, branch_info, path.display());
+        }
+        return Ok(());
+    };
+
+    let (archive_path, info) = archives
+        .into_iter()
+        .rev()
+        .find(|(_, info)| info.feature == feature_name)
+        .ok_or_else(|| anyhow::anyhow!("No archive found for '{}'", feature_name))?;
+
+    let worktree_path = storage.get_worktree_path(&repo_name, feature_name);
+    if worktree_path.exists() {
+        return Err(WorktreeError::WorktreeExists {
+            feature: feature_name.to_string(),
+            path: worktree_path,
+        }
+        .into());
+    }
+
+    println!(
+        "Restoring '{}' from {}",
+        feature_name,
+        archive_path.display()
+    );
+
+    let branch_name = info.branch.as_deref().unwrap_or(feature_name);
+    if git_repo.branch_exists(branch_name)? {
+        println!("Using existing branch: {}", branch_name);
+        git_repo.create_worktree(branch_name, &worktree_path, false)?;
+    } else {
+        let commit = info.commit.as_deref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Branch '{}' no longer exists and the archive records no commit",
+                branch_name
+            )
+        })?;
+        println!("Recreating branch {} at {}", branch_name, commit);
+        git_repo.create_worktree_from(branch_name, &worktree_path, true, Some(commit))?;
+    }
+
+    if WorktreeConfig::load_from_repo(repo_path)?.inherits_git_config() {
+        if let Err(e) = git_repo.inherit_config(&worktree_path) {
+            warn!("Failed to inherit git config: {}", e);
+        }
+    }
+
+    let file = File::open(&archive_path)
+        .with_context(|| format!("Failed to open archive: {}", archive_path.display()))?;
+    tar::Archive::new(GzDecoder::new(file))
+        .unpack(&worktree_path)
+        .with_context(|| format!("Failed to unpack archive: {}", archive_path.display()))?;
+
+    provision::store_origin_info(&storage, &repo_name, feature_name, repo_path)?;
+
+    output::status("✓ Worktree restored successfully!");
+    println!("  Branch: {}", branch_name);
+    println!("  Path: {}", worktree_path.display());
+
+    Ok(())
+}
+
+/// Lists archives in `archive_dir` with their metadata, oldest first.
+/// Archives without readable metadata are skipped.
+///
+/// # Errors
+/// Returns an error if the archive directory exists but cannot be read
+pub fn list_archives(archive_dir: &Path) -> Result<Vec<(PathBuf, ArchiveInfo)>> {
+    if !archive_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut archives = Vec::new();
+    for entry in std::fs::read_dir(archive_dir)? {
+        let path = entry?.path();
+        if !path.to_string_lossy().ends_with(".tar.gz") {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(info_path(&path)) else {
+            continue;
+        };
+        if let Ok(info) = toml::from_str::<ArchiveInfo>(&content) {
+            archives.push((path, info));
+        }
+    }
+
+    archives.sort_by(|(a_path, a), (b_path, b)| a.created.cmp(&b.created).then(a_path.cmp(b_path)));
+    Ok(archives)
+}
+
+fn append_dir<W: std::io::Write>(
+    builder: &mut tar::Builder<W>,
+    root: &Path,
+    relative: &Path,
+    excludes: &[String],
+) -> Result<()> {
+    for entry in std::fs::read_dir(root.join(relative))? {
+        let entry = entry?;
+        let relative_path = relative.join(entry.file_name());
+        let file_type = entry.file_type()?;
+
+        if relative.as_os_str().is_empty() && entry.file_name() == ".git" {
+            continue;
+        }
+
+        if file_type.is_dir() {
+            let dir_pattern_path = PathBuf::from(format!("{}/", relative_path.display()));
+            if config_files::should_exclude_file(&dir_pattern_path, excludes)? {
+                continue;
+            }
+            builder.append_dir(&relative_path, entry.path())?;
+            append_dir(builder, root, &relative_path, excludes)?;
+        } else {
+            if config_files::should_exclude_file(&relative_path, excludes)? {
+                continue;
+            }
+            builder.append_path_with_name(entry.path(), &relative_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn unused_archive_path(archive_dir: &Path, feature_name: &str, created: u64) -> PathBuf {
+    let mut path = archive_dir.join(format!("{}-{}.tar.gz", feature_name, created));
+    let mut counter = 1;
+    while path.exists() {
+        path = archive_dir.join(format!("{}-{}-{}.tar.gz", feature_name, created, counter));
+        counter += 1;
+    }
+    path
+}
+
+fn info_path(archive_path: &Path) -> PathBuf {
+    PathBuf::from(format!("{}.toml", archive_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_archive_skips_git_link_and_excludes() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = WorktreeStorage::with_root_dir(tmp.path().join("worktrees"))?;
+        let worktree = storage.get_worktree_path("myrepo", "feat");
+        std::fs::create_dir_all(worktree.join("src"))?;
+        std::fs::create_dir_all(worktree.join("node_modules").join("pkg"))?;
+        std::fs::write(worktree.join(".git"), "gitdir: /elsewhere")?;
+        std::fs::write(worktree.join(".env"), "SECRET=1")?;
+        std::fs::write(worktree.join("src").join("lib.rs"), "fn main() {}")?;
+        std::fs::write(worktree.join("node_modules").join("pkg").join("x.js"), "")?;
+        std::fs::write(worktree.join("debug.log"), "noise")?;
+
+        let archive = create_archive(
+            &storage,
+            "myrepo",
+            "feat",
+            &worktree,
+            &WorktreeConfig::default(),
+        )?;
+
+        let mut names: Vec<String> = tar::Archive::new(GzDecoder::new(File::open(&archive)?))
+            .entries()?
+            .map(|e| {
+                Ok(e?
+                    .path()?
+                    .to_string_lossy()
+                    .trim_end_matches('/')
+                    .to_string())
+            })
+            .collect::<Result<_>>()?;
+        names.sort();
+        assert_eq!(names, [".env", "src", "src/lib.rs"]);
+
+        let archives = list_archives(&storage.get_archive_dir("myrepo"))?;
+        assert_eq!(archives.len(), 1);
+        assert_eq!(archives[0].0, archive);
+        assert_eq!(archives[0].1.feature, "feat");
+        Ok(())
+    }
+
+    #[test]
+    fn test_unused_archive_path_avoids_collisions() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let first = unused_archive_path(tmp.path(), "feat", 42);
+        assert_eq!(first, tmp.path().join("feat-42.tar.gz"));
+        std::fs::write(&first, "")?;
+        assert_eq!(
+            unused_archive_path(tmp.path(), "feat", 42),
+            tmp.path().join("feat-42-1.tar.gz")
+        );
+        Ok(())
+    }
+}
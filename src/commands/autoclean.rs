@@ -0,0 +1,295 @@
+use anyhow::Result;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use crate::commands::cleanup;
+use crate::config::{Retention, WorktreeConfig};
+use crate::git::GitRepo;
+use crate::output;
+use crate::selection::{self, RealSelectionProvider, SelectionProvider};
+use crate::storage::{WorktreeStorage, read_worktree_head_branch, unix_now};
+use crate::traits::GitOperations;
+
+const SECS_PER_DAY: u64 = 86_400;
+
+/// A managed worktree considered by the retention policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetentionCandidate {
+    pub feature_name: String,
+    pub path: PathBuf,
+    /// Most recent activity (seconds since the Unix epoch): the later of the HEAD commit
+    /// time and the worktree's creation
+    pub last_activity: u64,
+}
+
+/// Why a worktree falls outside the retention policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RetentionReason {
+    /// No activity for longer than `max-age-days`
+    TooOld { idle_days: u64 },
+    /// More than `max-worktrees` more recently active worktrees exist
+    OverLimit { limit: usize },
+}
+
+impl fmt::Display for RetentionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetentionReason::TooOld { idle_days } => write!(f, "idle {} days", idle_days),
+            RetentionReason::OverLimit { limit } => {
+                write!(f, "beyond the {} most recently active", limit)
+            }
+        }
+    }
+}
+
+/// Removes the oldest or stalest worktrees of the current repository according to the
+/// `[retention]` policy in `.worktree-config.toml`
+///
+/// Worktrees with uncommitted changes or unpushed commits, worktrees on protected
+/// branches, and the worktree containing the current directory are never removed.
+/// Branches are kept.
+///
+/// # Errors
+/// Returns an error if git or storage access fails
+pub fn autoclean(dry_run: bool) -> Result<()> {
+    autoclean_with_provider(dry_run, &RealSelectionProvider)
+}
+
+/// Applies the retention policy with a custom selection provider (for testing)
+///
+/// # Errors
+/// Returns an error if git or storage access fails, or the confirmation prompt fails
+pub fn autoclean_with_provider(dry_run: bool, provider: &dyn SelectionProvider) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let git_repo = GitRepo::open(&current_dir)?;
+    let repo_path = git_repo.get_repo_path();
+
+    let storage = WorktreeStorage::new()?;
+    let repo_name = WorktreeStorage::get_repo_name(repo_path)?;
+    let config = WorktreeConfig::load_from_repo(repo_path)?;
+
+    if !config.retention.is_configured() {
+        println!("No retention policy configured. Add one to .worktree-config.toml:");
+        println!();
+        println!("[retention]");
+        println!("max-worktrees = 15");
+        println!("max-age-days = 45");
+        return Ok(());
+    }
+
+    print_policy(&config.retention);
+
+    let protected = config.protected_branches(git_repo.default_branch().as_deref());
+    let mut candidates = Vec::new();
+    for feature_name in storage.list_repo_worktrees(&repo_name)? {
+        let path = storage.get_worktree_path(&repo_name, &feature_name);
+        if current_dir.starts_with(&path) {
+            continue;
+        }
+        if read_worktree_head_branch(&path).is_some_and(|b| protected.is_protected(&b)) {
+            continue;
+        }
+        candidates.push(RetentionCandidate {
+            last_activity: storage
+                .worktree_activity(&repo_name, &feature_name)
+                .last_activity(&git_repo, &path),
+            feature_name,
+            path,
+        });
+    }
+
+    let expired = select_expired(candidates, &config.retention, unix_now());
+    if expired.is_empty() {
+        println!("✨ All worktrees are within the retention policy.");
+        return Ok(());
+    }
+
+    let mut removable = Vec::new();
+    for (candidate, reason) in expired {
+        let branch = read_worktree_head_branch(&candidate.path);
+
+        if let Some(why) = unsafe_to_remove(&git_repo, &candidate.path, branch.as_deref()) {
+            output::status(format_args!(
+                "⚠ Keeping {} ({}): {}",
+                candidate.feature_name, reason, why
+            ));
+            continue;
+        }
+
+        if dry_run {
+            println!("   • would remove {} ({})", candidate.feature_name, reason);
+            continue;
+        }
+
+        removable.push((candidate, reason, branch));
+    }
+
+    if !removable.is_empty() {
+        let summary: Vec<String> = std::iter::once("Autoclean will remove:".to_string())
+            .chain(removable.iter().map(|(candidate, reason, _)| {
+                format!("   • {} ({})", candidate.feature_name, reason)
+            }))
+            .collect();
+        let prompt = format!("Remove {} worktree(s)?", removable.len());
+        if !selection::confirm(provider, &summary, &prompt)? {
+            println!("Cancelled — nothing was removed.");
+            return Ok(());
+        }
+    }
+
+    let mut removed = 0;
+    for (candidate, reason, branch) in removable {
+        match cleanup::remove_managed_worktree(
+            &git_repo,
+            &storage,
+            &repo_name,
+            &candidate.feature_name,
+            &candidate.path,
+        ) {
+            Ok(()) => {
+                let kept = branch
+                    .map(|b| format!("; branch {} kept", b))
+                    .unwrap_or_default();
+                output::status(format_args!(
+                    "✓ Removed {} ({}{})",
+                    candidate.feature_name, reason, kept
+                ));
+                removed += 1;
+            }
+            Err(e) => warn!("Could not remove {}: {}", candidate.feature_name, e),
+        }
+    }
+
+    println!();
+    if dry_run {
+        println!("📝 Dry run — nothing was removed.");
+    } else {
+        println!("✅ Autoclean complete! Removed {} worktree(s)", removed);
+    }
+
+    Ok(())
+}
+
+/// Picks the candidates that exceed the retention policy, most recently active first.
+/// Candidates are ranked by last activity; ties are broken by name.
+#[must_use]
+pub fn select_expired(
+    mut candidates: Vec<RetentionCandidate>,
+    retention: &Retention,
+    now: u64,
+) -> Vec<(RetentionCandidate, RetentionReason)> {
+    candidates.sort_by(|a, b| {
+        b.last_activity
+            .cmp(&a.last_activity)
+            .then_with(|| a.feature_name.cmp(&b.feature_name))
+    });
+
+    let mut expired = Vec::new();
+    for (rank, candidate) in candidates.into_iter().enumerate() {
+        let idle_days = now.saturating_sub(candidate.last_activity) / SECS_PER_DAY;
+
+        let reason = match (retention.max_age_days, retention.max_worktrees) {
+            (Some(max_age), _) if idle_days > max_age => RetentionReason::TooOld { idle_days },
+            (_, Some(limit)) if rank >= limit => RetentionReason::OverLimit { limit },
+            _ => continue,
+        };
+        expired.push((candidate, reason));
+    }
+
+    expired
+}
+
+fn print_policy(retention: &Retention) {
+    let mut limits = Vec::new();
+    if let Some(max) = retention.max_worktrees {
+        limits.push(format!("keep at most {} worktrees", max));
+    }
+    if let Some(days) = retention.max_age_days {
+        limits.push(format!("remove after {} idle days", days));
+    }
+    println!("🧹 Retention policy: {}", limits.join(", "));
+}
+
+/// Returns why a worktree must be kept despite the policy, if it must
+fn unsafe_to_remove(git: &dyn GitOperations, path: &Path, branch: Option<&str>) -> Option<String> {
+    match git.is_dirty(path) {
+        Ok(false) => {}
+        Ok(true) => return Some("uncommitted changes".to_string()),
+        Err(e) => return Some(format!("could not check for changes ({})", e)),
+    }
+
+    let branch = branch?;
+    match git.unpushed_commits(branch) {
+        Ok(0) => None,
+        Ok(n) => Some(format!("{} unpushed commit(s) on {}", n, branch)),
+        Err(e) => Some(format!("could not check for unpushed commits ({})", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(name: &str, last_activity: u64) -> RetentionCandidate {
+        RetentionCandidate {
+            feature_name: name.to_string(),
+            path: PathBuf::from(format!("/tmp/{}", name)),
+            last_activity,
+        }
+    }
+
+    fn names(expired: &[(RetentionCandidate, RetentionReason)]) -> Vec<&str> {
+        expired
+            .iter()
+            .map(|(c, _)| c.feature_name.as_str())
+            .collect()
+    }
+
+    const NOW: u64 = 100 * SECS_PER_DAY;
+
+    #[test]
+    fn test_select_expired_by_age() {
+        let retention = Retention {
+            max_age_days: Some(30),
+            ..Retention::default()
+        };
+        let candidates = vec![
+            candidate("recent", NOW - SECS_PER_DAY),
+            candidate("stale", NOW - 40 * SECS_PER_DAY),
+        ];
+
+        let expired = select_expired(candidates, &retention, NOW);
+        assert_eq!(names(&expired), ["stale"]);
+        assert_eq!(expired[0].1, RetentionReason::TooOld { idle_days: 40 });
+    }
+
+    #[test]
+    fn test_select_expired_by_count_keeps_most_recent() {
+        let retention = Retention {
+            max_worktrees: Some(2),
+            ..Retention::default()
+        };
+        let candidates = vec![
+            candidate("oldest", NOW - 3 * SECS_PER_DAY),
+            candidate("newest", NOW),
+            candidate("middle", NOW - SECS_PER_DAY),
+            candidate("older", NOW - 2 * SECS_PER_DAY),
+        ];
+
+        let expired = select_expired(candidates, &retention, NOW);
+        assert_eq!(names(&expired), ["older", "oldest"]);
+        assert!(
+            expired
+                .iter()
+                .all(|(_, r)| *r == RetentionReason::OverLimit { limit: 2 })
+        );
+    }
+
+    #[test]
+    fn test_select_expired_without_limits_keeps_everything() {
+        let candidates = vec![candidate("a", 0), candidate("b", 0)];
+        assert!(select_expired(candidates, &Retention::default(), NOW).is_empty());
+    }
+}
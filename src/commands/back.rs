@@ -1,17 +1,25 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::path::PathBuf;
 
-use crate::storage::WorktreeStorage;
+use crate::storage::{WorktreeStorage, history};
 
-/// Navigate back to the original repository that this worktree was created from
+/// Navigate back to the previous directory in this shell's jump history, or to the
+/// original repository that this worktree was created from when there is no history
 ///
 /// # Errors
-/// Returns an error if not in a managed worktree directory, origin info is missing,
-/// or the origin path no longer exists.
+/// Returns an error if there is no history and not in a managed worktree directory,
+/// origin info is missing, or the origin path no longer exists.
 pub fn back_to_origin() -> Result<()> {
     let current_dir = std::env::current_dir()?;
     let storage = WorktreeStorage::new()?;
 
+    if let Some(session) = history::session_id() {
+        if let Some(previous) = history::pop(storage.get_root_dir(), session, &current_dir)? {
+            println!("{}", previous.display());
+            return Ok(());
+        }
+    }
+
     let (repo_name, feature_name) = determine_current_worktree(&current_dir, &storage)?;
 
     let origin_path = storage
@@ -41,30 +49,29 @@ pub fn back_to_origin() -> Result<()> {
     Ok(())
 }
 
+/// The path of the managed worktree containing the current directory, for commands
+/// whose target defaults to the worktree you are in
+///
+/// # Errors
+/// Returns an error if the current directory is not inside a managed worktree.
+pub fn current_worktree_path(storage: &WorktreeStorage) -> Result<PathBuf> {
+    let current_dir = std::env::current_dir()?;
+    let (repo_name, feature_name) = determine_current_worktree(&current_dir, storage)
+        .context("Not inside a worktree; pass the worktree as an argument")?;
+    Ok(storage.get_worktree_path(&repo_name, &feature_name))
+}
+
 /// Determines the current worktree from the current directory path.
 /// Returns (repo_name, feature_name) where feature_name is the directory name.
 ///
 /// # Errors
 /// Returns an error if not in a worktree directory managed by this tool.
-fn determine_current_worktree(
+pub fn determine_current_worktree(
     current_dir: &std::path::Path,
     storage: &WorktreeStorage,
 ) -> Result<(String, String)> {
-    let storage_root = storage
-        .get_root_dir()
-        .canonicalize()
-        .unwrap_or_else(|_| storage.get_root_dir().clone());
-    let canonical_current = current_dir
-        .canonicalize()
-        .unwrap_or_else(|_| current_dir.to_path_buf());
-
-    if let Ok(relative_path) = canonical_current.strip_prefix(&storage_root) {
-        let components: Vec<_> = relative_path.components().collect();
-        if components.len() >= 2 {
-            let repo_name = components[0].as_os_str().to_string_lossy().to_string();
-            let feature_name = components[1].as_os_str().to_string_lossy().to_string();
-            return Ok((repo_name, feature_name));
-        }
+    if let Some(found) = storage.locate_worktree(current_dir) {
+        return Ok(found);
     }
 
     anyhow::bail!(
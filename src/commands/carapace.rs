@@ -0,0 +1,287 @@
+//! Carapace completion spec.
+//!
+//! [Carapace](https://carapace.sh) completes commands from a YAML spec describing their
+//! flags, subcommands, and argument values. The spec is generated from the clap
+//! definitions, so it always matches the CLI. Arguments with dynamic candidates
+//! (worktree names, git references, repositories) call back into `worktree-bin` through
+//! the same `COMPLETE` protocol the shell integration uses, which prints one
+//! `value<TAB>description` line per candidate.
+
+use clap::{Arg, ArgAction, Command, ValueHint};
+use clap_complete::ArgValueCandidates;
+
+use crate::commands::init::{COMMAND_NAME, COMPLETE_ENV, COMPLETER};
+
+/// Carapace spec for `cmd` and all of its visible subcommands
+#[must_use]
+pub fn spec(cmd: &Command) -> String {
+    let mut out = String::new();
+    write_command(&mut out, cmd, &[COMMAND_NAME], "", "");
+    out
+}
+
+/// Writes `cmd` as a spec mapping; `first` prefixes the first line (`- ` inside a
+/// `commands` list) and `indent` every following line
+fn write_command(out: &mut String, cmd: &Command, path: &[&str], first: &str, indent: &str) {
+    out.push_str(&format!("{}name: {}\n", first, quote(path[path.len() - 1])));
+    let aliases: Vec<String> = cmd.get_visible_aliases().map(quote).collect();
+    if !aliases.is_empty() {
+        out.push_str(&format!("{}aliases: [{}]\n", indent, aliases.join(", ")));
+    }
+    if let Some(about) = cmd.get_about() {
+        out.push_str(&format!(
+            "{}description: {}\n",
+            indent,
+            quote(&about.to_string())
+        ));
+    }
+
+    let visible = |arg: &&Arg| !arg.is_hide_set() && !arg.is_positional();
+    let (global, local): (Vec<&Arg>, Vec<&Arg>) = cmd
+        .get_arguments()
+        .filter(visible)
+        .partition(|arg| arg.is_global_set());
+    write_flags(out, "persistentflags", &global, indent);
+    write_flags(out, "flags", &local, indent);
+    write_completion(out, cmd, path, indent);
+
+    let subcommands: Vec<&Command> = cmd
+        .get_subcommands()
+        .filter(|sub| !sub.is_hide_set())
+        .collect();
+    if !subcommands.is_empty() {
+        out.push_str(&format!("{}commands:\n", indent));
+        let first = format!("{}  - ", indent);
+        let nested = format!("{}    ", indent);
+        for sub in subcommands {
+            let mut sub_path = path.to_vec();
+            sub_path.push(sub.get_name());
+            write_command(out, sub, &sub_path, &first, &nested);
+        }
+    }
+}
+
+fn write_flags(out: &mut String, section: &str, args: &[&Arg], indent: &str) {
+    if args.is_empty() {
+        return;
+    }
+    out.push_str(&format!("{}{}:\n", indent, section));
+    for arg in args {
+        let mut names = Vec::new();
+        if let Some(short) = arg.get_short() {
+            names.push(format!("-{}", short));
+        }
+        if let Some(long) = arg.get_long() {
+            names.push(format!("--{}", long));
+        }
+        let mut key = names.join(", ");
+        if arg.get_action().takes_values() {
+            key.push('=');
+        }
+        if matches!(arg.get_action(), ArgAction::Append | ArgAction::Count) {
+            key.push('*');
+        }
+        out.push_str(&format!(
+            "{}  {}: {}\n",
+            indent,
+            quote(&key),
+            quote(&help(arg))
+        ));
+    }
+}
+
+/// Writes the `completion` section: values for flags, positional arguments, and a
+/// trailing variadic argument
+fn write_completion(out: &mut String, cmd: &Command, path: &[&str], indent: &str) {
+    let flags: Vec<(String, Vec<String>)> = cmd
+        .get_arguments()
+        .filter(|arg| !arg.is_hide_set() && !arg.is_positional())
+        .filter_map(|arg| {
+            let name = arg
+                .get_long()
+                .map(str::to_string)
+                .or_else(|| arg.get_short().map(String::from))?;
+            let actions = actions(arg, &flag_words(path, &name));
+            (!actions.is_empty()).then_some((name, actions))
+        })
+        .collect();
+
+    let mut positional = Vec::new();
+    let mut positionalany = Vec::new();
+    for (index, arg) in cmd
+        .get_positionals()
+        .filter(|arg| !arg.is_hide_set())
+        .enumerate()
+    {
+        let actions = actions(arg, &positional_words(path, index));
+        if matches!(arg.get_action(), ArgAction::Append) {
+            positionalany = actions;
+            break;
+        }
+        positional.push(actions);
+    }
+    while positional.last().is_some_and(Vec::is_empty) {
+        positional.pop();
+    }
+
+    if flags.is_empty() && positional.is_empty() && positionalany.is_empty() {
+        return;
+    }
+    out.push_str(&format!("{}completion:\n", indent));
+    if !flags.is_empty() {
+        out.push_str(&format!("{}  flag:\n", indent));
+        for (name, actions) in flags {
+            out.push_str(&format!("{}    {}: {}\n", indent, name, list(&actions)));
+        }
+    }
+    if !positional.is_empty() {
+        out.push_str(&format!("{}  positional:\n", indent));
+        for actions in positional {
+            out.push_str(&format!("{}    - {}\n", indent, list(&actions)));
+        }
+    }
+    if !positionalany.is_empty() {
+        out.push_str(&format!(
+            "{}  positionalany: {}\n",
+            indent,
+            list(&positionalany)
+        ));
+    }
+}
+
+/// Command words completing the value of flag `name`
+fn flag_words(path: &[&str], name: &str) -> Vec<String> {
+    let mut words: Vec<String> = path.iter().copied().map(String::from).collect();
+    words.push(if name.len() == 1 {
+        format!("-{}", name)
+    } else {
+        format!("--{}", name)
+    });
+    words
+}
+
+/// Command words completing the positional argument at `index`, with placeholders for
+/// the arguments before it
+fn positional_words(path: &[&str], index: usize) -> Vec<String> {
+    let mut words: Vec<String> = path.iter().copied().map(String::from).collect();
+    words.extend(std::iter::repeat("_".to_string()).take(index));
+    words
+}
+
+/// Carapace actions completing `arg`'s values: a callback for dynamic candidates, the
+/// possible values of an enum, or files and directories from the value hint
+fn actions(arg: &Arg, words: &[String]) -> Vec<String> {
+    if !arg.get_action().takes_values() {
+        return Vec::new();
+    }
+    if arg.get::<ArgValueCandidates>().is_some() {
+        // Completing an empty word also offers flags for positionals; keep only values
+        let filter = if arg.is_positional() {
+            " | grep -v '^-'"
+        } else {
+            ""
+        };
+        return vec![format!(
+            "$({}=fish {} -- {} ''{})",
+            COMPLETE_ENV,
+            COMPLETER,
+            words.join(" "),
+            filter
+        )];
+    }
+    let values: Vec<String> = arg
+        .get_possible_values()
+        .iter()
+        .filter(|value| !value.is_hide_set())
+        .map(|value| value.get_name().to_string())
+        .collect();
+    if !values.is_empty() {
+        return values;
+    }
+    match arg.get_value_hint() {
+        ValueHint::DirPath => vec!["$directories".to_string()],
+        ValueHint::FilePath | ValueHint::AnyPath => vec!["$files".to_string()],
+        _ => Vec::new(),
+    }
+}
+
+fn help(arg: &Arg) -> String {
+    arg.get_help()
+        .map(ToString::to_string)
+        .and_then(|help| help.lines().next().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn list(items: &[String]) -> String {
+    let quoted: Vec<String> = items.iter().map(|item| quote(item)).collect();
+    format!("[{}]", quoted.join(", "))
+}
+
+/// Double-quoted YAML scalar
+fn quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Arg;
+
+    fn candidates() -> Vec<clap_complete::CompletionCandidate> {
+        Vec::new()
+    }
+
+    #[test]
+    fn test_spec_describes_flags_subcommands_and_values() {
+        let cmd = Command::new("worktree")
+            .about("Manage worktrees")
+            .arg(
+                Arg::new("yes")
+                    .short('y')
+                    .long("yes")
+                    .global(true)
+                    .action(ArgAction::SetTrue),
+            )
+            .subcommand(
+                Command::new("jump")
+                    .visible_alias("switch")
+                    .about("Jump to a worktree")
+                    .arg(Arg::new("target").add(ArgValueCandidates::new(candidates)))
+                    .arg(
+                        Arg::new("repo")
+                            .long("repo")
+                            .help("Repository \"name\"")
+                            .add(ArgValueCandidates::new(candidates)),
+                    )
+                    .arg(Arg::new("internal").long("internal").hide(true)),
+            )
+            .subcommand(
+                Command::new("init")
+                    .arg(Arg::new("shell").value_parser(["bash", "zsh"]))
+                    .arg(Arg::new("dir").long("dir").value_hint(ValueHint::DirPath)),
+            );
+
+        let spec = spec(&cmd);
+
+        assert!(spec.starts_with("name: \"worktree\"\ndescription: \"Manage worktrees\"\n"));
+        assert!(spec.contains("persistentflags:\n  \"-y, --yes\": \"\"\n"));
+        assert!(spec.contains("  - name: \"jump\"\n    aliases: [\"switch\"]\n"));
+        assert!(spec.contains("\"--repo=\": \"Repository \\\"name\\\"\""));
+        assert!(
+            spec.contains("repo: [\"$(COMPLETE=fish worktree-bin -- worktree jump --repo '')\"]")
+        );
+        assert!(
+            spec.contains(
+                "- [\"$(COMPLETE=fish worktree-bin -- worktree jump '' | grep -v '^-')\"]"
+            )
+        );
+        assert!(!spec.contains("internal"));
+        assert!(spec.contains("- [\"bash\", \"zsh\"]"));
+        assert!(spec.contains("dir: [\"$directories\"]"));
+    }
+}
@@ -1,90 +1,706 @@
 use anyhow::Result;
+use serde::Serialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use tracing::warn;
 
+use crate::config::{ProtectedBranches, WorktreeConfig};
 use crate::git::GitRepo;
-use crate::storage::WorktreeStorage;
+use crate::manager::find_origin_repo;
+use crate::output;
+use crate::selection::{self, RealSelectionProvider, SelectionProvider};
+use crate::storage::{WorktreeStorage, read_worktree_head_branch};
+use crate::traits::GitOperations;
+
+/// A single orphan found by cleanup analysis
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum CleanupItem {
+    /// A git worktree reference whose directory no longer exists
+    StaleGitReference { name: String, path: PathBuf },
+    /// A `.worktree-origins` entry for a worktree directory that no longer exists
+    StaleOrigin {
+        repo_name: String,
+        feature_name: String,
+    },
+    /// A `.managed-branches` entry for a branch that no longer exists, such as one
+    /// deleted outside this tool
+    StaleManagedBranch { repo_name: String, branch: String },
+    /// A repository storage directory with no worktrees left in it
+    EmptyRepoDir { repo_name: String, path: PathBuf },
+    /// A clean worktree whose branch is merged into the default branch; removing it
+    /// also deletes the branch
+    MergedWorktree {
+        repo_name: String,
+        feature_name: String,
+        branch: String,
+        into: String,
+        path: PathBuf,
+    },
+}
+
+impl fmt::Display for CleanupItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CleanupItem::StaleGitReference { name, path } => {
+                write!(f, "git worktree reference '{}' ({})", name, path.display())
+            }
+            CleanupItem::StaleOrigin {
+                repo_name,
+                feature_name,
+            } => write!(f, "origin entry '{}' ({})", feature_name, repo_name),
+            CleanupItem::StaleManagedBranch { repo_name, branch } => write!(
+                f,
+                "managed branch entry '{}' ({}; branch no longer exists)",
+                branch, repo_name
+            ),
+            CleanupItem::EmptyRepoDir { path, .. } => {
+                write!(f, "empty storage directory ({})", path.display())
+            }
+            CleanupItem::MergedWorktree {
+                feature_name,
+                branch,
+                into,
+                ..
+            } => write!(
+                f,
+                "merged worktree '{}' and branch {} (merged into {})",
+                feature_name, branch, into
+            ),
+        }
+    }
+}
+
+/// Result of analyzing a repository for orphaned state
+#[derive(Debug, Default)]
+pub struct CleanupPlan {
+    /// Items that cleanup would remove
+    pub items: Vec<CleanupItem>,
+    /// Managed worktree directories that git does not know about (reported, never removed)
+    pub unregistered: Vec<(String, PathBuf)>,
+    /// Branches cleanup must never delete
+    pub protected: ProtectedBranches,
+}
 
 /// Cleans up orphaned worktree references and directories
 ///
+/// With `dry_run`, only reports what would be removed. With `interactive`, lets the
+/// user pick which candidates to remove. With `all`, every repository under the storage
+/// root is processed instead of just the current one. With `merged`, clean worktrees
+/// whose branches are merged into the default branch are removed along with the branch.
+///
 /// # Errors
-/// Returns an error if git or storage access fails.
-pub fn cleanup_worktrees() -> Result<()> {
-    let current_dir = std::env::current_dir()?;
-    let git_repo = GitRepo::open(&current_dir)?;
-    let repo_path = git_repo.get_repo_path();
+/// Returns an error if git or storage access fails, or interactive selection is cancelled.
+pub fn cleanup_worktrees(dry_run: bool, interactive: bool, all: bool, merged: bool) -> Result<()> {
+    cleanup_worktrees_with_provider(dry_run, interactive, all, merged, &RealSelectionProvider)
+}
 
+/// Cleans up orphaned worktree state with a custom selection provider (for testing)
+///
+/// # Errors
+/// Returns an error if git or storage access fails, or interactive selection is cancelled.
+pub fn cleanup_worktrees_with_provider(
+    dry_run: bool,
+    interactive: bool,
+    all: bool,
+    merged: bool,
+    provider: &dyn SelectionProvider,
+) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
     let storage = WorktreeStorage::new()?;
-    let repo_name = WorktreeStorage::get_repo_name(repo_path)?;
+
+    if all {
+        return cleanup_all_repos(
+            &storage,
+            &current_dir,
+            dry_run,
+            interactive,
+            merged,
+            provider,
+        );
+    }
+
+    let git_repo = GitRepo::open(&current_dir)?;
+    let repo_name = WorktreeStorage::get_repo_name(git_repo.get_repo_path())?;
 
     println!("🔍 Analyzing worktree state...");
 
-    let mut cleaned = Vec::new();
+    let mut plan = analyze(&git_repo, &storage, &repo_name, &current_dir);
+    if merged {
+        plan.items.extend(find_merged(
+            &git_repo,
+            &storage,
+            &repo_name,
+            &plan.protected,
+            &current_dir,
+        ));
+    }
+    run_plan(
+        Some(&git_repo),
+        &storage,
+        plan,
+        dry_run,
+        interactive,
+        provider,
+    )
+}
 
-    // Clean up any git worktree references that point to non-existent directories
-    match git_repo.list_worktrees_with_paths() {
-        Ok(worktrees) => {
-            for (name, path, is_prunable) in worktrees {
-                if path == current_dir {
-                    continue;
-                }
+/// Runs cleanup for every repository under the storage root, locating each main
+/// repository through its recorded worktree origins
+fn cleanup_all_repos(
+    storage: &WorktreeStorage,
+    current_dir: &Path,
+    dry_run: bool,
+    interactive: bool,
+    merged: bool,
+    provider: &dyn SelectionProvider,
+) -> Result<()> {
+    let mut repo_names: Vec<String> = storage
+        .list_all_worktrees()?
+        .into_iter()
+        .map(|(repo_name, _)| repo_name)
+        .collect();
+    repo_names.sort();
 
-                if is_prunable || !path.exists() {
-                    println!(
-                        "🗑️  Found orphaned git worktree reference: {}",
-                        path.display()
-                    );
-                    match git_repo.remove_worktree(&name) {
-                        Ok(_) => {
-                            println!("   ✓ Removed git worktree reference: {}", name);
-                            cleaned.push(name);
-                        }
-                        Err(e) => println!(
-                            "   ⚠ Warning: Could not remove git worktree reference {}: {}",
-                            name, e
-                        ),
-                    }
-                }
+    if repo_names.is_empty() {
+        println!("✨ No repositories found in storage.");
+        return Ok(());
+    }
+
+    for repo_name in repo_names {
+        let Some(git_repo) = find_origin_repo(storage, &repo_name) else {
+            // Without its repository only storage metadata can be cleaned up
+            let plan = CleanupPlan {
+                items: analyze_storage(storage, &repo_name),
+                ..CleanupPlan::default()
+            };
+            if plan.items.is_empty() {
+                println!();
+                output::status(format_args!(
+                    "⚠ Skipping {}: no recorded origin points to an existing repository",
+                    repo_name
+                ));
+            } else {
+                println!("\n📦 {} (repository not found)", repo_name);
+                run_plan(None, storage, plan, dry_run, interactive, provider)?;
             }
+            continue;
+        };
+
+        println!(
+            "\n📦 {} ({})",
+            repo_name,
+            git_repo.get_repo_path().display()
+        );
+        println!("🔍 Analyzing worktree state...");
+
+        let mut plan = analyze(&git_repo, storage, &repo_name, current_dir);
+        if merged {
+            plan.items.extend(find_merged(
+                &git_repo,
+                storage,
+                &repo_name,
+                &plan.protected,
+                current_dir,
+            ));
         }
-        Err(e) => {
-            println!("   ⚠ Warning: Could not check git worktree list: {}", e);
+        run_plan(
+            Some(&git_repo),
+            storage,
+            plan,
+            dry_run,
+            interactive,
+            provider,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Reports a cleanup plan and, unless `dry_run`, removes its (selected) items.
+/// `git_repo` is `None` when the repository could not be located; such plans contain
+/// storage items only.
+fn run_plan(
+    git_repo: Option<&GitRepo>,
+    storage: &WorktreeStorage,
+    plan: CleanupPlan,
+    dry_run: bool,
+    interactive: bool,
+    provider: &dyn SelectionProvider,
+) -> Result<()> {
+    for (feature_name, path) in &plan.unregistered {
+        println!(
+            "ℹ️  Worktree directory exists but may not be registered with git: {} ({})",
+            feature_name,
+            path.display()
+        );
+    }
+
+    if dry_run && git_repo.is_some() {
+        println!(
+            "🛡️  Protected branches (never deleted): {}",
+            plan.protected.patterns().join(", ")
+        );
+    }
+
+    if plan.items.is_empty() {
+        println!("✨ Everything looks clean! No orphaned worktree state found.");
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("\n📝 Dry run — nothing was removed. Cleanup would remove:");
+        for item in &plan.items {
+            println!("   • {}", item);
         }
+        println!("\nRun again without --dry-run to apply.");
+        return Ok(());
     }
 
-    // Prune worktree directories for feature worktrees whose git reference no longer exists
-    // (i.e., the dir exists but git doesn't know about that worktree anymore)
-    if let Ok(repo_worktrees) = storage.list_repo_worktrees(&repo_name) {
-        let git_worktree_paths: Vec<_> = git_repo
-            .list_worktrees_with_paths()
-            .unwrap_or_default()
-            .into_iter()
-            .map(|(_, path, _)| path)
+    let items = if interactive {
+        let selected = choose_items(plan.items, provider)?;
+        if selected.is_empty() {
+            println!("\nNothing selected — nothing was removed.");
+            return Ok(());
+        }
+        selected
+    } else {
+        let summary: Vec<String> = std::iter::once("Cleanup will remove:".to_string())
+            .chain(plan.items.iter().map(|item| format!("   • {}", item)))
             .collect();
+        let prompt = format!("Remove {} item(s)?", plan.items.len());
+        if !selection::confirm(provider, &summary, &prompt)? {
+            println!("Cancelled — nothing was removed.");
+            return Ok(());
+        }
+        plan.items
+    };
+
+    let cleaned = apply(git_repo, storage, &items);
+
+    if cleaned == 0 {
+        println!();
+        output::status("⚠ Nothing could be cleaned up.");
+    } else {
+        println!("\n✅ Cleanup complete!");
+        println!("   Removed {} orphaned item(s)", cleaned);
+    }
+
+    Ok(())
+}
+
+/// Finds orphaned state for a repository without changing anything
+#[must_use]
+pub fn analyze(
+    git_repo: &GitRepo,
+    storage: &WorktreeStorage,
+    repo_name: &str,
+    current_dir: &Path,
+) -> CleanupPlan {
+    let mut plan = CleanupPlan::default();
+
+    let config = WorktreeConfig::load_from_repo(git_repo.get_repo_path()).unwrap_or_else(|e| {
+        warn!("Could not load config: {}", e);
+        WorktreeConfig::default()
+    });
+    plan.protected = config.protected_branches(git_repo.default_branch().as_deref());
+
+    // Git worktree references that point to non-existent directories
+    let git_worktrees = match git_repo.list_worktrees_with_paths() {
+        Ok(worktrees) => worktrees,
+        Err(e) => {
+            warn!("Could not check git worktree list: {}", e);
+            Vec::new()
+        }
+    };
+
+    for (name, path, is_prunable) in &git_worktrees {
+        if path == current_dir {
+            continue;
+        }
+
+        if *is_prunable || !path.exists() {
+            plan.items.push(CleanupItem::StaleGitReference {
+                name: name.clone(),
+                path: path.clone(),
+            });
+        }
+    }
+
+    // Branches recorded as created by this tool that have since been deleted
+    match storage.managed_branches(repo_name) {
+        Ok(branches) => {
+            for branch in branches {
+                if !git_repo.branch_exists(&branch).unwrap_or(true) {
+                    plan.items.push(CleanupItem::StaleManagedBranch {
+                        repo_name: repo_name.to_string(),
+                        branch,
+                    });
+                }
+            }
+        }
+        Err(e) => warn!("Could not read managed branches: {}", e),
+    }
+
+    plan.items.extend(analyze_storage(storage, repo_name));
+
+    // Managed worktree directories that git doesn't know about. These may be newly
+    // created worktrees or orphans; for safety they are only reported.
+    if let Ok(repo_worktrees) = storage.list_repo_worktrees(repo_name) {
+        let git_worktree_paths: Vec<_> = git_worktrees.iter().map(|(_, path, _)| path).collect();
 
         for feature_name in repo_worktrees {
-            let path = storage.get_worktree_path(&repo_name, &feature_name);
-            if path.exists() && !git_worktree_paths.contains(&path) {
-                // Check if git even knows about this path
-                // It may be a newly created worktree or an orphan
-                // Only remove if we're confident it's orphaned (git prune would handle it)
-                // For safety, just report it
-                println!(
-                    "ℹ️  Worktree directory exists but may not be registered with git: {} ({})",
-                    feature_name,
-                    path.display()
-                );
+            let path = storage.get_worktree_path(repo_name, &feature_name);
+            if path.exists() && !git_worktree_paths.contains(&&path) {
+                plan.unregistered.push((feature_name, path));
             }
         }
     }
 
-    if cleaned.is_empty() {
-        println!("✨ Everything looks clean! No orphaned worktree references found.");
-    } else {
-        println!("\n✅ Cleanup complete!");
+    plan
+}
+
+/// Finds managed worktrees whose branch has been merged into the default branch, locally
+/// or on `origin`. Worktrees on protected branches, with uncommitted changes, or
+/// containing `current_dir` are left alone.
+#[must_use]
+pub fn find_merged(
+    git_repo: &GitRepo,
+    storage: &WorktreeStorage,
+    repo_name: &str,
+    protected: &ProtectedBranches,
+    current_dir: &Path,
+) -> Vec<CleanupItem> {
+    let Some(scan) = scan_merged(git_repo, storage, repo_name, protected, current_dir) else {
+        warn!("Could not determine the default branch; skipping --merged");
+        return Vec::new();
+    };
+    for feature_name in scan.dirty {
         println!(
-            "   Removed {} orphaned git worktree reference(s)",
-            cleaned.len()
+            "ℹ️  {} is merged into {} but has uncommitted changes; keeping it",
+            feature_name, scan.default_branch
         );
     }
+    scan.items
+}
+
+/// Finds everything `cleanup --merged` would remove for a repository (the orphans
+/// [`analyze`] finds plus merged worktrees), without changing or printing anything
+#[must_use]
+pub fn preview(
+    git_repo: &GitRepo,
+    storage: &WorktreeStorage,
+    repo_name: &str,
+    current_dir: &Path,
+) -> Vec<CleanupItem> {
+    let mut plan = analyze(git_repo, storage, repo_name, current_dir);
+    if let Some(scan) = scan_merged(git_repo, storage, repo_name, &plan.protected, current_dir) {
+        plan.items.extend(scan.items);
+    }
+    plan.items
+}
+
+/// Merged worktrees found by [`scan_merged`]
+struct MergedScan {
+    default_branch: String,
+    /// Worktrees [`find_merged`] removes
+    items: Vec<CleanupItem>,
+    /// Merged worktrees kept for their uncommitted changes
+    dirty: Vec<String>,
+}
+
+/// Finds the merged worktrees of a repository without printing anything. `None` if there
+/// is no default branch.
+fn scan_merged(
+    git_repo: &GitRepo,
+    storage: &WorktreeStorage,
+    repo_name: &str,
+    protected: &ProtectedBranches,
+    current_dir: &Path,
+) -> Option<MergedScan> {
+    let default_branch = git_repo.integration_branch()?;
+    let targets = [
+        default_branch.clone(),
+        format!("refs/remotes/origin/{}", default_branch),
+    ];
+
+    let mut features = storage.list_repo_worktrees(repo_name).unwrap_or_default();
+    features.sort();
+
+    let mut items = Vec::new();
+    let mut dirty = Vec::new();
+    for feature_name in features {
+        let path = storage.get_worktree_path(repo_name, &feature_name);
+        let Some(branch) = read_worktree_head_branch(&path) else {
+            continue;
+        };
+        if protected.is_protected(&branch) || current_dir.starts_with(&path) {
+            continue;
+        }
+
+        let Some(into) = targets
+            .iter()
+            .find(|target| git_repo.is_merged_into(&branch, target).unwrap_or(false))
+        else {
+            continue;
+        };
+
+        if git_repo.is_dirty(&path).unwrap_or(true) {
+            dirty.push(feature_name);
+            continue;
+        }
+
+        items.push(CleanupItem::MergedWorktree {
+            repo_name: repo_name.to_string(),
+            feature_name,
+            branch,
+            into: into
+                .strip_prefix("refs/remotes/")
+                .unwrap_or(into)
+                .to_string(),
+            path,
+        });
+    }
+
+    Some(MergedScan {
+        default_branch,
+        items,
+        dirty,
+    })
+}
+
+/// Finds stale storage metadata for a repository: origin entries whose worktree
+/// directory is gone, and a storage directory left with no worktrees in it
+fn analyze_storage(storage: &WorktreeStorage, repo_name: &str) -> Vec<CleanupItem> {
+    let mut items = Vec::new();
+
+    match storage.list_worktree_origins(repo_name) {
+        Ok(origins) => {
+            for (feature_name, _) in origins {
+                if !storage.get_worktree_path(repo_name, &feature_name).exists() {
+                    items.push(CleanupItem::StaleOrigin {
+                        repo_name: repo_name.to_string(),
+                        feature_name,
+                    });
+                }
+            }
+        }
+        Err(e) => warn!("Could not read worktree origins: {}", e),
+    }
+
+    if storage.is_repo_dir_empty(repo_name) {
+        items.push(CleanupItem::EmptyRepoDir {
+            repo_name: repo_name.to_string(),
+            path: storage.get_repo_storage_dir(repo_name),
+        });
+    }
+
+    items
+}
+
+/// Asks which cleanup candidates to remove. All candidates start out selected, so the
+/// user only needs to deselect the ones to keep.
+fn choose_items(
+    items: Vec<CleanupItem>,
+    provider: &dyn SelectionProvider,
+) -> Result<Vec<CleanupItem>> {
+    let options: Vec<String> = items.iter().map(ToString::to_string).collect();
+    let selected = provider.select_many("Select items to remove:", options.clone(), true)?;
+
+    Ok(items
+        .into_iter()
+        .zip(options)
+        .filter(|(_, label)| selected.contains(label))
+        .map(|(item, _)| item)
+        .collect())
+}
+
+/// Removes the given items, printing progress. Returns how many were removed.
+fn apply(git_repo: Option<&GitRepo>, storage: &WorktreeStorage, items: &[CleanupItem]) -> usize {
+    let mut cleaned = 0;
+
+    for item in items {
+        match item {
+            CleanupItem::StaleGitReference { name, path } => {
+                let Some(git_repo) = git_repo else {
+                    continue;
+                };
+                println!(
+                    "🗑️  Found orphaned git worktree reference: {}",
+                    path.display()
+                );
+                match git_repo.remove_worktree(name) {
+                    Ok(()) => {
+                        output::status(format_args!(
+                            "   ✓ Removed git worktree reference: {}",
+                            name
+                        ));
+                        cleaned += 1;
+                    }
+                    Err(e) => warn!("Could not remove git worktree reference {}: {}", name, e),
+                }
+            }
+            CleanupItem::StaleOrigin {
+                repo_name,
+                feature_name,
+            } => match storage
+                .remove_worktree_origin(repo_name, feature_name)
+                .and_then(|()| storage.release_slot(repo_name, feature_name))
+                .and_then(|()| storage.forget_activity(repo_name, feature_name))
+                .and_then(|()| storage.forget_labels(repo_name, feature_name))
+                .and_then(|()| storage.forget_note(repo_name, feature_name))
+                .and_then(|()| storage.forget_copied_files(repo_name, feature_name))
+            {
+                Ok(()) => {
+                    output::status(format_args!(
+                        "   ✓ Removed stale origin entry: {}",
+                        feature_name
+                    ));
+                    cleaned += 1;
+                }
+                Err(e) => warn!("Could not remove origin entry {}: {}", feature_name, e),
+            },
+            CleanupItem::MergedWorktree {
+                repo_name,
+                feature_name,
+                branch,
+                path,
+                ..
+            } => {
+                let Some(git_repo) = git_repo else {
+                    continue;
+                };
+                if let Err(e) =
+                    remove_managed_worktree(git_repo, storage, repo_name, feature_name, path)
+                {
+                    warn!("Could not remove merged worktree {}: {}", feature_name, e);
+                    continue;
+                }
+                match git_repo.delete_branch(branch) {
+                    Ok(()) => {
+                        output::status(format_args!(
+                            "   ✓ Removed merged worktree: {} (branch {} deleted)",
+                            feature_name, branch
+                        ));
+                        if let Err(e) = storage.forget_managed_branch(repo_name, branch) {
+                            warn!("Failed to update managed branches: {}", e);
+                        }
+                    }
+                    Err(e) => output::status(format_args!(
+                        "   ✓ Removed merged worktree: {} (⚠ could not delete branch {}: {})",
+                        feature_name, branch, e
+                    )),
+                }
+                cleaned += 1;
+            }
+            CleanupItem::StaleManagedBranch { repo_name, branch } => {
+                match storage.forget_managed_branch(repo_name, branch) {
+                    Ok(()) => {
+                        output::status(format_args!(
+                            "   ✓ Removed stale managed branch entry: {}",
+                            branch
+                        ));
+                        cleaned += 1;
+                    }
+                    Err(e) => warn!("Could not remove managed branch entry {}: {}", branch, e),
+                }
+            }
+            CleanupItem::EmptyRepoDir { repo_name, path } => {
+                match storage.remove_repo_dir_if_empty(repo_name) {
+                    Ok(true) => {
+                        output::status(format_args!(
+                            "   ✓ Removed empty storage directory: {}",
+                            path.display()
+                        ));
+                        cleaned += 1;
+                    }
+                    Ok(false) => println!(
+                        "   ℹ️  Kept storage directory (no longer empty): {}",
+                        path.display()
+                    ),
+                    Err(e) => warn!(
+                        "Could not remove storage directory {}: {}",
+                        path.display(),
+                        e
+                    ),
+                }
+            }
+        }
+    }
+
+    cleaned
+}
 
+/// Removes a managed worktree's directory, git reference, origin entry, and slot.
+/// The branch is left alone.
+///
+/// # Errors
+/// Returns an error if any of the removals fails
+pub fn remove_managed_worktree(
+    git_repo: &GitRepo,
+    storage: &WorktreeStorage,
+    repo_name: &str,
+    feature_name: &str,
+    path: &Path,
+) -> Result<()> {
+    std::fs::remove_dir_all(path)?;
+    git_repo.remove_worktree(feature_name)?;
+    storage.remove_worktree_origin(repo_name, feature_name)?;
+    storage.release_slot(repo_name, feature_name)?;
+    storage.forget_activity(repo_name, feature_name)?;
+    storage.forget_labels(repo_name, feature_name)?;
+    storage.forget_note(repo_name, feature_name)?;
+    storage.forget_copied_files(repo_name, feature_name)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::selection::MockSelectionProvider;
+
+    fn stale(name: &str) -> CleanupItem {
+        CleanupItem::StaleGitReference {
+            name: name.to_string(),
+            path: PathBuf::from(format!("/tmp/{}", name)),
+        }
+    }
+
+    #[test]
+    fn test_choose_items_keeps_deselected() -> Result<()> {
+        let items = vec![stale("a"), stale("b"), stale("c")];
+        let provider = MockSelectionProvider::new(format!("{}\n{}", stale("a"), stale("c")));
+
+        let chosen = choose_items(items, &provider)?;
+        assert_eq!(chosen, vec![stale("a"), stale("c")]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_choose_items_none_selected() -> Result<()> {
+        let provider = MockSelectionProvider::new("");
+        let chosen = choose_items(vec![stale("a")], &provider)?;
+        assert!(chosen.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_plan_declined_confirmation_removes_nothing() -> Result<()> {
+        let tmp = tempfile::TempDir::new()?;
+        let storage = WorktreeStorage::with_root_dir(tmp.path().to_path_buf())?;
+        storage.store_worktree_origin("myrepo", "gone", "/nowhere")?;
+        let plan = CleanupPlan {
+            items: vec![CleanupItem::StaleOrigin {
+                repo_name: "myrepo".to_string(),
+                feature_name: "gone".to_string(),
+            }],
+            ..CleanupPlan::default()
+        };
+
+        let provider = MockSelectionProvider::new("n");
+        run_plan(None, &storage, plan, false, false, &provider)?;
+        assert!(storage.get_worktree_origin("myrepo", "gone")?.is_some());
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use anyhow::{Context, Result};
+
+use crate::git::GitRepo;
+use crate::manager::WorktreeManager;
+use crate::output;
+use crate::storage::WorktreeStorage;
+
+/// Clones `url` as a bare repository into storage and checks out its default branch as
+/// the first managed worktree
+///
+/// The bare repository lives at `<storage>/<repo>/.bare`, so every checkout, including
+/// the default branch, is a worktree next to it.
+///
+/// # Errors
+/// Returns an error if:
+/// - No repository name can be derived from `url` and none was given
+/// - The repository already exists in storage
+/// - The clone fails
+/// - The default branch's worktree cannot be created
+pub fn clone_repository(url: &str, name: Option<&str>) -> Result<()> {
+    let repo_name = match name {
+        Some(name) => name.to_string(),
+        None => repo_name_from_url(url).with_context(|| {
+            format!("Cannot derive a repository name from '{url}'; pass --name")
+        })?,
+    };
+    WorktreeStorage::validate_feature_name(&repo_name)
+        .with_context(|| format!("Invalid repository name '{repo_name}'"))?;
+
+    let storage = WorktreeStorage::new()?;
+    let repo_dir = storage.get_repo_storage_dir(&repo_name);
+    if repo_dir.exists() {
+        anyhow::bail!(
+            "Repository '{}' already exists in storage at {}",
+            repo_name,
+            repo_dir.display()
+        );
+    }
+
+    let bare_path = storage.get_bare_repo_path(&repo_name);
+    println!("Cloning {} into {}", url, bare_path.display());
+    let git_repo = GitRepo::clone_bare(url, &bare_path)?;
+
+    let branch = git_repo
+        .head_branch()
+        .context("The cloned repository has no default branch")?;
+    let feature_name = branch.rsplit('/').next().unwrap_or(&branch).to_string();
+
+    let manager = WorktreeManager::with_storage(git_repo, storage)?;
+    let created = manager.create(&feature_name, Some(&branch), None)?;
+
+    output::status("✓ Repository cloned successfully!");
+    println!("  Repository: {}", repo_name);
+    println!("  Default branch: {}", branch);
+    println!("  Path: {}", created.info.path.display());
+    println!();
+    println!(
+        "Use 'worktree jump {}' to switch to it and 'worktree create' from there to add more.",
+        created.info.feature_name
+    );
+
+    Ok(())
+}
+
+/// Derives a repository name from the last path segment of a clone URL
+/// (`https://host/org/repo.git` and `git@host:org/repo` both give `repo`)
+fn repo_name_from_url(url: &str) -> Option<String> {
+    let trimmed = url.trim_end_matches(['/', '\\']);
+    let last = trimmed.rsplit(['/', '\\', ':']).next()?;
+    let name = last.strip_suffix(".git").unwrap_or(last);
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_name_from_url() {
+        assert_eq!(
+            repo_name_from_url("https://github.com/org/repo.git").as_deref(),
+            Some("repo")
+        );
+        assert_eq!(
+            repo_name_from_url("git@github.com:org/repo").as_deref(),
+            Some("repo")
+        );
+        assert_eq!(
+            repo_name_from_url("/tmp/some/repo/").as_deref(),
+            Some("repo")
+        );
+        assert_eq!(repo_name_from_url("https://host/.git"), None);
+    }
+}
@@ -0,0 +1,170 @@
+//! Dynamic shell completion.
+//!
+//! The shell integration registers `worktree-bin` as the completer for `worktree`
+//! through clap_complete's `COMPLETE=<shell>` protocol, so the binary completes its own
+//! arguments. The candidate functions here fill in values clap cannot know: worktree
+//! names, git references, repository names, and `[open]` targets. Completion must never fail loudly, so
+//! errors yield no candidates.
+//!
+//! Candidate lists are cached in storage for a few seconds (see
+//! [`crate::storage::completion_cache`]) so repeated tab presses stay instant.
+
+use anyhow::Result;
+use clap_complete::CompletionCandidate;
+
+use crate::commands::{create, jump, open, root, sync_config};
+use crate::config::{GlobalConfig, WorktreeConfig};
+use crate::storage::WorktreeStorage;
+
+/// Returns the candidates cached under `key`, or computes and caches them
+///
+/// Falls back to computing without a cache when storage is unavailable.
+///
+/// # Errors
+/// Returns an error if `compute` fails.
+pub fn cached(key: &str, compute: impl FnOnce() -> Result<Vec<String>>) -> Result<Vec<String>> {
+    match WorktreeStorage::new() {
+        Ok(storage) => cached_in(&storage, key, compute),
+        Err(_) => compute(),
+    }
+}
+
+/// Like [`cached`], with an already opened storage
+///
+/// # Errors
+/// Returns an error if `compute` fails.
+pub fn cached_in(
+    storage: &WorktreeStorage,
+    key: &str,
+    compute: impl FnOnce() -> Result<Vec<String>>,
+) -> Result<Vec<String>> {
+    if let Some(candidates) = storage.cached_completions(key) {
+        return Ok(candidates);
+    }
+    let candidates = compute()?;
+    storage.cache_completions(key, &candidates);
+    Ok(candidates)
+}
+
+/// Cache key for a candidate list that depends on the current directory
+fn directory_key(kind: &str) -> Result<String> {
+    Ok(format!("{} {}", kind, std::env::current_dir()?.display()))
+}
+
+/// Feature names of managed worktrees, optionally limited to the current repository or
+/// to `repo`
+///
+/// This is the `--list-completions` path of `jump` and `remove`: it only reads the storage
+/// directory, and opens the git repository only when `current_repo_only` needs its name.
+///
+/// # Errors
+/// Returns an error if storage access fails.
+pub fn feature_names(current_repo_only: bool, repo: Option<&str>) -> Result<Vec<String>> {
+    let storage = WorktreeStorage::new()?;
+    let scope = if current_repo_only {
+        directory_key("current")?
+    } else {
+        repo.unwrap_or_default().to_string()
+    };
+    cached_in(&storage, &format!("features {}", scope), || {
+        Ok(
+            jump::get_available_worktrees(&storage, current_repo_only, repo)?
+                .into_iter()
+                .map(|(_, feature_name, _)| feature_name)
+                .collect(),
+        )
+    })
+}
+
+/// Branches, remote branches, and tags of the repository at the current directory
+///
+/// # Errors
+/// Returns an error if the current directory is not in a git repository.
+pub fn current_git_ref_names() -> Result<Vec<String>> {
+    let current_dir = std::env::current_dir()?;
+    cached(&directory_key("refs")?, || {
+        create::git_ref_names(&current_dir)
+    })
+}
+
+/// Feature names of every managed worktree, with the repository as help text
+#[must_use]
+pub fn worktree_names() -> Vec<CompletionCandidate> {
+    let Ok(storage) = WorktreeStorage::new() else {
+        return Vec::new();
+    };
+    let entries = cached_in(&storage, "worktrees", || {
+        Ok(jump::get_available_worktrees(&storage, false, None)?
+            .into_iter()
+            .map(|(repo, feature_name, _)| format!("{}\t{}", feature_name, repo))
+            .collect())
+    });
+    entries
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|entry| {
+            let (feature_name, repo) = entry.split_once('\t')?;
+            Some(
+                CompletionCandidate::new(feature_name.to_string())
+                    .help(Some(repo.to_string().into())),
+            )
+        })
+        .collect()
+}
+
+/// Feature names of the current repository's worktrees
+#[must_use]
+pub fn current_repo_worktree_names() -> Vec<CompletionCandidate> {
+    sync_config::current_repo_worktree_names()
+        .unwrap_or_default()
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+/// Branches, remote branches, and tags of the current repository
+#[must_use]
+pub fn git_refs() -> Vec<CompletionCandidate> {
+    current_git_ref_names()
+        .unwrap_or_default()
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+/// Names of the repositories with worktrees in storage
+#[must_use]
+pub fn repo_names() -> Vec<CompletionCandidate> {
+    let Ok(storage) = WorktreeStorage::new() else {
+        return Vec::new();
+    };
+    cached_in(&storage, "repos", || {
+        Ok(storage
+            .list_all_worktrees()?
+            .into_iter()
+            .map(|(repo, _)| repo)
+            .collect())
+    })
+    .unwrap_or_default()
+    .into_iter()
+    .map(CompletionCandidate::new)
+    .collect()
+}
+
+/// Names of the `[open]` targets configured for the current repository and globally
+#[must_use]
+pub fn open_targets() -> Vec<CompletionCandidate> {
+    let Ok(current_dir) = std::env::current_dir() else {
+        return Vec::new();
+    };
+    let repo_root = WorktreeStorage::new()
+        .ok()
+        .and_then(|storage| root::find_root(&current_dir, &storage).ok())
+        .unwrap_or(current_dir);
+    let repo_config = WorktreeConfig::load_from_repo(&repo_root).unwrap_or_default();
+    let global_config = GlobalConfig::load().unwrap_or_default();
+    open::open_target_names(&repo_config, &global_config)
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
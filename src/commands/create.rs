@@ -1,16 +1,39 @@
 use anyhow::{Context, Result};
 use inquire::validator::Validation;
 use std::error::Error;
-use std::path::Path;
-
-use crate::config::WorktreeConfig;
-use crate::git::GitRepo;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use crate::commands::progress::ProgressPrinter;
+use crate::commands::{completion, workspace};
+use crate::config::{GlobalConfig, WorktreeConfig};
+use crate::git::{self, GitRepo};
+use crate::manager::WorktreeManager;
+use crate::output::Target;
 use crate::selection::{
-    RealSelectionProvider, SelectionProvider, select_git_reference_interactive,
+    RealSelectionProvider, SELECT_ENV, SelectionProvider, select_git_reference_interactive,
 };
 use crate::storage::WorktreeStorage;
 
-/// Creates a new worktree for the specified feature
+/// Options for `worktree create`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CreateOptions {
+    /// Don't copy the parent repository's git config into the worktree, as with
+    /// `inherit-git-config = false`
+    pub no_inherit_config: bool,
+    /// Written to `branch.<name>.description`. `None` uses the `[branch-description]`
+    /// config section for new branches.
+    pub description: Option<String>,
+    /// Fetch from the remotes before creating. `None` uses `fetch` from the `[create]`
+    /// config section.
+    pub fetch: Option<bool>,
+    /// Where the progress lines and the stdout of hooks and installers go: stdout,
+    /// stderr (`jump --create`), or nowhere (`create --quiet`)
+    pub output: Target,
+}
+
+/// Creates a new worktree for the specified feature and returns its path
 ///
 /// # Errors
 /// Returns an error if:
@@ -18,13 +41,33 @@ use crate::storage::WorktreeStorage;
 /// - The feature name is invalid
 /// - The worktree path already exists
 /// - Git operations fail
-pub fn create_worktree(feature_name: &str, branch: Option<&str>, from: Option<&str>) -> Result<()> {
-    let current_dir = std::env::current_dir()?;
-    let git_repo = GitRepo::open(&current_dir)?;
-    create_worktree_internal(&git_repo, feature_name, branch, from)
+pub fn create_worktree(
+    feature_name: &str,
+    branch: Option<&str>,
+    from: Option<&str>,
+    options: &CreateOptions,
+) -> Result<PathBuf> {
+    create_worktree_at(
+        &std::env::current_dir()?,
+        feature_name,
+        branch,
+        from,
+        options,
+    )
+}
+
+fn create_worktree_at(
+    dir: &Path,
+    feature_name: &str,
+    branch: Option<&str>,
+    from: Option<&str>,
+    options: &CreateOptions,
+) -> Result<PathBuf> {
+    let git = git::open_backend(dir)?;
+    create_worktree_internal(git.as_ref(), feature_name, branch, from, options)
 }
 
-/// Test version that accepts a mock git repository
+/// Test version that accepts a mock git repository, returning the new worktree's path
 ///
 /// # Errors
 /// Returns an error if worktree creation fails
@@ -33,8 +76,9 @@ pub fn create_worktree_with_git(
     feature_name: &str,
     branch: Option<&str>,
     from: Option<&str>,
-) -> Result<()> {
-    create_worktree_internal(git_repo, feature_name, branch, from)
+    options: &CreateOptions,
+) -> Result<PathBuf> {
+    create_worktree_internal(git_repo, feature_name, branch, from, options)
 }
 
 fn create_worktree_internal(
@@ -42,421 +86,158 @@ fn create_worktree_internal(
     feature_name: &str,
     branch: Option<&str>,
     from: Option<&str>,
-) -> Result<()> {
-    // Validate feature name
-    WorktreeStorage::validate_feature_name(feature_name)?;
-
-    let branch_name = branch.unwrap_or(feature_name);
-
-    let repo_path = git_repo.get_repo_path();
-    let storage = WorktreeStorage::new()?;
-    let repo_name = WorktreeStorage::get_repo_name(&repo_path)?;
-    let worktree_path = storage.get_worktree_path(&repo_name, feature_name);
-
-    // Pre-flight check
-    if worktree_path.exists() {
-        anyhow::bail!(
-            "Worktree '{}' already exists at: {}",
-            feature_name,
-            worktree_path.display()
-        );
-    }
-
-    let branch_exists = git_repo.branch_exists(branch_name)?;
-
-    // Ensure parent directory exists
-    if let Some(parent) = worktree_path.parent() {
-        std::fs::create_dir_all(parent)
-            .with_context(|| format!("Failed to create parent directory: {}", parent.display()))?;
+    options: &CreateOptions,
+) -> Result<PathBuf> {
+    let mut manager = WorktreeManager::with_storage(git_repo, WorktreeStorage::new()?)?;
+    let out = options.output;
+    let printer = ProgressPrinter::new(out);
+    manager.on_event(move |event| printer.print(event));
+    manager.set_command_stdout(out);
+    if options.no_inherit_config {
+        manager.config_mut().inherit_git_config = Some(false);
+    }
+    apply_global_branch_prefix(manager.config_mut())?;
+    if options.fetch.unwrap_or(manager.config().create.fetch) {
+        out.line("Fetching...");
+        git::fetch_all(manager.repo_path())?;
+    }
+    let mut plan = manager.plan_create(feature_name, branch)?;
+    plan.description = options.description.clone();
+    if plan.create_branch
+        && plan.description.is_none()
+        && manager.config().branch_description.prompt
+    {
+        plan.description = prompt_branch_description(&RealSelectionProvider)?;
     }
 
-    println!(
+    out.line(format_args!(
         "Creating worktree '{}' at: {}",
-        feature_name,
-        worktree_path.display()
-    );
-
-    let create_branch = !branch_exists;
-
-    if create_branch {
-        println!("Creating new branch: {}", branch_name);
+        plan.feature_name,
+        plan.path.display()
+    ));
+    if plan.create_branch {
+        out.line(format_args!("Creating new branch: {}", plan.branch));
     } else {
-        println!("Using existing branch: {}", branch_name);
+        out.line(format_args!("Using existing branch: {}", plan.branch));
     }
 
-    git_repo.create_worktree_from(branch_name, &worktree_path, create_branch, from)?;
+    let created = manager.create_planned(plan, from)?;
+    workspace::refresh_vscode_workspace(
+        manager.storage(),
+        manager.repo_name(),
+        manager.repo_path(),
+    );
 
-    // Inherit git configuration from parent repository
-    println!("Inheriting git configuration from parent repository...");
-    if let Err(e) = git_repo.inherit_config(&worktree_path) {
-        eprintln!("Warning: Failed to inherit git config: {}", e);
+    if let Some(e) = &created.inherit_config_error {
+        warn!("Failed to inherit git config: {}", e);
         eprintln!("Worktree will use default git configuration.");
-    } else {
-        println!("✓ Git configuration inherited successfully");
+    } else if created.inherited_config {
+        out.status("✓ Git configuration inherited from parent repository");
+    }
+
+    out.status("✓ Worktree created successfully!");
+    out.line(format_args!("  Feature: {}", created.info.feature_name));
+    out.line(format_args!(
+        "  Branch: {}",
+        created.info.branch.as_deref().unwrap_or_default()
+    ));
+    out.line(format_args!("  Path: {}", created.info.path.display()));
+    if let Some(description) = created
+        .info
+        .branch
+        .as_deref()
+        .and_then(|branch| git::branch_description(manager.repo_path(), branch))
+    {
+        out.line(format_args!("  Description: {}", description));
     }
 
-    let config = WorktreeConfig::load_from_repo(&repo_path)?;
-
-    // Create symlinks first (takes precedence over copy)
-    create_symlinks(&repo_path, &worktree_path, &config)?;
-
-    // Copy config files, skipping any that are covered by symlinks
-    copy_config_files(&repo_path, &worktree_path, &config)?;
-
-    // Store origin information for back navigation
-    store_origin_info(&storage, &repo_name, feature_name, &repo_path)?;
-
-    // Run post-create hooks
-    run_on_create_hooks(&worktree_path, &config)?;
-
-    println!("✓ Worktree created successfully!");
-    println!("  Feature: {}", feature_name);
-    println!("  Branch: {}", branch_name);
-    println!("  Path: {}", worktree_path.display());
-
-    Ok(())
+    Ok(created.info.path)
 }
 
-/// Creates symlinks in the worktree for patterns listed in `[symlink-patterns]`.
-/// Symlinks point to the absolute path in the origin repo.
+/// The branch `create <feature>` uses instead of asking for one: the feature name behind
+/// the configured `branch-prefix`, or `None` when no prefix is configured
 ///
 /// # Errors
-/// Returns an error if symlink creation fails for reasons other than missing origin path.
-pub fn create_symlinks(
-    source_path: &Path,
-    target_path: &Path,
-    config: &WorktreeConfig,
-) -> Result<()> {
-    let patterns = match config.symlink_patterns.include.as_deref() {
-        Some(p) if !p.is_empty() => p,
-        _ => return Ok(()),
-    };
+/// Returns an error if the current directory is not a git repository or a config file
+/// cannot be read
+pub fn prefixed_branch(feature_name: &str) -> Result<Option<String>> {
+    let git_repo = GitRepo::open(&std::env::current_dir()?)?;
+    let mut config = WorktreeConfig::load_from_repo(git_repo.get_repo_path())?;
+    apply_global_branch_prefix(&mut config)?;
+    Ok(config
+        .branch_prefix()
+        .map(|prefix| format!("{}{}", prefix, feature_name)))
+}
 
-    println!("Creating symlinks...");
-
-    for pattern in patterns {
-        if let Some(matches) = find_matching_files(source_path, pattern)? {
-            for source_file in matches {
-                let relative_path = source_file.strip_prefix(source_path)?;
-                let target_link = target_path.join(relative_path);
-
-                // Canonicalize the origin path for the symlink target
-                let canonical_source = source_file.canonicalize().with_context(|| {
-                    format!(
-                        "Failed to canonicalize symlink source: {}",
-                        source_file.display()
-                    )
-                })?;
-
-                // Create parent dir if needed
-                if let Some(parent) = target_link.parent() {
-                    std::fs::create_dir_all(parent)?;
-                }
-
-                // Skip if already exists (e.g. already copied)
-                if target_link.exists() || target_link.symlink_metadata().is_ok() {
-                    continue;
-                }
-
-                std::os::unix::fs::symlink(&canonical_source, &target_link).with_context(|| {
-                    format!(
-                        "Failed to create symlink {} -> {}",
-                        target_link.display(),
-                        canonical_source.display()
-                    )
-                })?;
-
-                println!(
-                    "  Symlinked: {} -> {}",
-                    relative_path.display(),
-                    canonical_source.display()
-                );
-            }
-        } else {
-            eprintln!(
-                "Warning: Symlink pattern '{}' did not match any files in origin repo — skipping",
-                pattern
-            );
-        }
+/// Falls back to `branch-prefix` from the global config when the repository sets none
+fn apply_global_branch_prefix(config: &mut WorktreeConfig) -> Result<()> {
+    if config.branch_prefix.is_none() {
+        config.branch_prefix = GlobalConfig::load()?.branch_prefix;
     }
-
     Ok(())
 }
 
-/// Copies configuration files from source to target based on config patterns,
-/// skipping any paths that are covered by symlink patterns.
+/// Asks for a description of the new branch (`[branch-description] prompt`). Without a
+/// terminal or a scripted answer there is nobody to ask, so none is written.
 ///
 /// # Errors
-/// Returns an error if file operations fail.
-pub fn copy_config_files(
-    source_path: &Path,
-    target_path: &Path,
-    config: &WorktreeConfig,
-) -> Result<()> {
-    println!("Copying configuration files...");
-
-    let symlink_patterns = config.symlink_patterns.include.as_deref().unwrap_or(&[]);
-
-    for pattern in config.copy_patterns.include.as_deref().unwrap_or_default() {
-        if let Some(matches) = find_matching_files(source_path, pattern)? {
-            for source_file in matches {
-                if should_exclude_file(
-                    &source_file,
-                    config.copy_patterns.exclude.as_deref().unwrap_or_default(),
-                )? {
-                    continue;
-                }
-
-                // Skip if already covered by a symlink pattern
-                if is_covered_by_symlink_pattern(&source_file, source_path, symlink_patterns) {
-                    continue;
-                }
-
-                let relative_path = source_file.strip_prefix(source_path)?;
-                let target_file = target_path.join(relative_path);
-
-                // Skip if a symlink already exists at the target (defer to create_symlinks)
-                if target_file
-                    .symlink_metadata()
-                    .is_ok_and(|m| m.file_type().is_symlink())
-                {
-                    continue;
-                }
-
-                if let Some(parent) = target_file.parent() {
-                    std::fs::create_dir_all(parent)?;
-                }
-
-                if source_file.is_file() {
-                    std::fs::copy(&source_file, &target_file)
-                        .with_context(|| format!("Failed to copy {}", relative_path.display()))?;
-                    println!("  Copied: {}", relative_path.display());
-                } else if source_file.is_dir() {
-                    copy_dir_recursive(&source_file, &target_file)?;
-                    println!("  Copied directory: {}", relative_path.display());
-                }
-            }
-        }
+/// Returns an error if the prompt fails or is cancelled
+pub fn prompt_branch_description(provider: &dyn SelectionProvider) -> Result<Option<String>> {
+    if !std::io::stdin().is_terminal() && std::env::var_os(SELECT_ENV).is_none() {
+        return Ok(None);
     }
-
-    Ok(())
+    let description = provider.get_text_input("Branch description (optional):", None)?;
+    Ok(Some(description).filter(|d| !d.trim().is_empty()))
 }
 
-/// Checks if a file path is covered by any symlink pattern
-fn is_covered_by_symlink_pattern(
-    file_path: &Path,
-    base_path: &Path,
-    symlink_patterns: &[String],
-) -> bool {
-    if symlink_patterns.is_empty() {
-        return false;
-    }
-
-    let Ok(relative) = file_path.strip_prefix(base_path) else {
-        return false;
-    };
-
-    let rel_str = relative.to_string_lossy();
-
-    for pattern in symlink_patterns {
-        // Check if the relative path starts with the pattern (prefix match for dirs)
-        let normalized_pattern = pattern.trim_end_matches('/');
-        if rel_str == *pattern
-            || rel_str == normalized_pattern
-            || rel_str.starts_with(&format!("{}/", normalized_pattern))
-        {
-            return true;
-        }
-
-        // Glob match
-        if pattern.contains('*') {
-            if let Ok(p) = glob::Pattern::new(pattern) {
-                if p.matches(&rel_str) {
-                    return true;
-                }
-            }
-        }
-    }
-
-    false
-}
-
-/// Runs post-create hooks defined in `[on-create] commands`.
-/// On first failure, remaining commands are skipped and a warning is printed.
-/// The worktree remains created regardless.
+/// Lists all git references (branches and tags) for shell completion
 ///
 /// # Errors
-/// Never returns Err — hook failures are warnings, not errors.
-#[allow(clippy::unnecessary_wraps)]
-pub fn run_on_create_hooks(worktree_path: &Path, config: &WorktreeConfig) -> Result<()> {
-    let commands = match config.on_create.commands.as_deref() {
-        Some(c) if !c.is_empty() => c,
-        _ => return Ok(()),
-    };
-
-    println!("Running post-create hooks...");
-
-    for cmd_str in commands {
-        println!("  Running: {}", cmd_str);
-
-        let status = std::process::Command::new("sh")
-            .args(["-c", cmd_str.as_str()])
-            .current_dir(worktree_path)
-            .stdin(std::process::Stdio::null())
-            .stdout(std::process::Stdio::inherit())
-            .stderr(std::process::Stdio::inherit())
-            .status();
-
-        match status {
-            Ok(s) if s.success() => {
-                println!("  ✓ Done: {}", cmd_str);
-            }
-            Ok(s) => {
-                eprintln!(
-                    "⚠ Warning: Hook command failed with exit code {}: {}",
-                    s.code().unwrap_or(-1),
-                    cmd_str
-                );
-                eprintln!("  Remaining post-create commands skipped.");
-                break;
-            }
-            Err(e) => {
-                eprintln!("⚠ Warning: Failed to run hook command '{}': {}", cmd_str, e);
-                eprintln!("  Remaining post-create commands skipped.");
-                break;
-            }
-        }
-    }
-
-    Ok(())
-}
-
-fn find_matching_files(base_path: &Path, pattern: &str) -> Result<Option<Vec<std::path::PathBuf>>> {
-    let mut matches = Vec::new();
-
-    if pattern.contains('*') {
-        for entry in glob::glob(&base_path.join(pattern).to_string_lossy())? {
-            matches.push(entry?);
-        }
-    } else {
-        let path = base_path.join(pattern);
-        if path.exists() {
-            matches.push(path);
-        }
-    }
-
-    if matches.is_empty() {
-        Ok(None)
-    } else {
-        Ok(Some(matches))
-    }
-}
-
-fn should_exclude_file(file_path: &Path, exclude_patterns: &[String]) -> Result<bool> {
-    let file_str = file_path.to_string_lossy();
-
-    for pattern in exclude_patterns {
-        if pattern.contains('*') {
-            if glob::Pattern::new(pattern)?.matches(&file_str) {
-                return Ok(true);
-            }
-        } else if file_str.contains(pattern.as_str()) {
-            return Ok(true);
-        }
-    }
-
-    Ok(false)
-}
-
-fn copy_dir_recursive(source: &Path, target: &Path) -> Result<()> {
-    std::fs::create_dir_all(target)?;
-
-    for entry in std::fs::read_dir(source)? {
-        let entry = entry?;
-        let source_path = entry.path();
-        let target_path = target.join(entry.file_name());
-
-        if source_path.is_dir() {
-            copy_dir_recursive(&source_path, &target_path)?;
-        } else {
-            std::fs::copy(&source_path, &target_path)?;
-        }
+/// Returns an error if git operations fail.
+pub fn list_git_ref_completions() -> Result<()> {
+    for reference in completion::current_git_ref_names()? {
+        println!("{}", reference);
     }
 
     Ok(())
 }
 
-/// Stores the origin repository path in storage metadata for back navigation
+/// Returns the local branches, remote branches, and tags of the repository at `dir`, in
+/// that order
 ///
 /// # Errors
-/// Returns an error if storing origin information fails.
-fn store_origin_info(
-    storage: &WorktreeStorage,
-    repo_name: &str,
-    feature_name: &str,
-    repo_path: &Path,
-) -> Result<()> {
-    let canonical_repo_path = repo_path.canonicalize().with_context(|| {
-        format!(
-            "Failed to canonicalize repository path: {}",
-            repo_path.display()
-        )
-    })?;
-
-    storage
-        .store_worktree_origin(
-            repo_name,
-            feature_name,
-            &canonical_repo_path.to_string_lossy(),
-        )
-        .context("Failed to store worktree origin information")?;
+/// Returns an error if `dir` is not in a git repository or listing references fails.
+pub fn git_ref_names(dir: &Path) -> Result<Vec<String>> {
+    let git_repo = git::open_backend(dir)?;
 
-    Ok(())
-}
-
-/// Lists all git references (branches and tags) for shell completion
-///
-/// # Errors
-/// Returns an error if git operations fail.
-pub fn list_git_ref_completions() -> Result<()> {
-    let current_dir = std::env::current_dir()?;
-    let git_repo = GitRepo::open(&current_dir)?;
-
-    let local_branches = git_repo
+    let mut references = git_repo
         .list_local_branches()
         .context("Failed to list local branches")?;
-    let remote_branches = git_repo
-        .list_remote_branches()
-        .context("Failed to list remote branches")?;
-    let tags = git_repo.list_tags().context("Failed to list tags")?;
-
-    for branch in local_branches {
-        println!("{}", branch);
-    }
-    for branch in remote_branches {
-        println!("{}", branch);
-    }
-    for tag in tags {
-        println!("{}", tag);
-    }
-
-    Ok(())
+    references.extend(
+        git_repo
+            .list_remote_branches()
+            .context("Failed to list remote branches")?,
+    );
+    references.extend(git_repo.list_tags().context("Failed to list tags")?);
+    Ok(references)
 }
 
-/// Handle interactive selection for --from flag
+/// Handle interactive selection for --from flag, returning the new worktree's path
 ///
 /// # Errors
 /// Returns an error if interactive selection fails.
-pub fn interactive_from_selection(feature_name: &str, branch: Option<&str>) -> Result<()> {
+pub fn interactive_from_selection(
+    feature_name: &str,
+    branch: Option<&str>,
+    options: &CreateOptions,
+) -> Result<PathBuf> {
     let current_dir = std::env::current_dir()?;
     let git_repo = GitRepo::open(&current_dir)?;
 
     let provider = RealSelectionProvider;
     let selected_ref = select_git_reference_interactive(&git_repo, &provider)?;
 
-    create_worktree(feature_name, branch, Some(&selected_ref))?;
-
-    Ok(())
+    create_worktree(feature_name, branch, Some(&selected_ref), options)
 }
 
 /// Feature name validator for interactive input
@@ -525,69 +306,97 @@ pub fn validate_branch_name(input: &str) -> Result<Validation, Box<dyn Error + S
     Ok(validate_branch_name_internal(input))
 }
 
-/// Handle the full interactive create workflow (no args provided)
+/// Handle the full interactive create workflow (no args provided), returning the new
+/// worktree's path
 ///
 /// # Errors
 /// Returns an error if interactive prompts fail or worktree creation fails.
-pub fn interactive_create_workflow() -> Result<()> {
-    let provider = RealSelectionProvider;
+pub fn interactive_create_workflow(options: &CreateOptions) -> Result<PathBuf> {
+    let current_dir = std::env::current_dir()?;
+    let git_repo = GitRepo::open(&current_dir)?;
+    let (feature_name, branch_name, from_ref) =
+        prompt_create_inputs(&git_repo, None, &RealSelectionProvider)?;
+
+    create_worktree(
+        &feature_name,
+        Some(&branch_name),
+        from_ref.as_deref(),
+        options,
+    )
+}
 
+/// Prompts for what `create` needs: the feature name (unless given), the starting
+/// branch, and, if that branch is new, the reference to start it from. Returns
+/// `(feature name, branch name, base reference)`.
+///
+/// # Errors
+/// Returns an error if a prompt fails or is cancelled, or git references cannot be
+/// listed.
+pub fn prompt_create_inputs(
+    git_repo: &GitRepo,
+    feature_name: Option<&str>,
+    provider: &dyn SelectionProvider,
+) -> Result<(String, String, Option<String>)> {
     // Step 1: Get feature name
-    let feature_name = provider.get_text_input(
-        "Feature name (used as the worktree directory name):",
-        Some(validate_feature_name),
-    )?;
+    let feature_name = match feature_name {
+        Some(name) => name.to_string(),
+        None => provider.get_text_input(
+            "Feature name (used as the worktree directory name):",
+            Some(validate_feature_name),
+        )?,
+    };
 
     // Step 2: Get starting branch name
     let branch_name =
         provider.get_text_input("Starting branch name:", Some(validate_branch_name))?;
 
-    let current_dir = std::env::current_dir()?;
-    let git_repo = GitRepo::open(&current_dir)?;
-    let branch_exists = git_repo.branch_exists(&branch_name)?;
-
-    // Step 3: If branch is new, optionally get a base ref
-    let from_ref = if !branch_exists {
-        let selected_ref = select_git_reference_interactive(&git_repo, &provider)?;
-        Some(selected_ref)
-    } else {
+    // Step 3: If branch is new, get a base ref
+    let from_ref = if git_repo.branch_exists(&branch_name)? {
         None
+    } else {
+        Some(select_git_reference_interactive(git_repo, provider)?)
     };
 
-    create_worktree(&feature_name, Some(&branch_name), from_ref.as_deref())?;
-
-    Ok(())
+    Ok((feature_name, branch_name, from_ref))
 }
 
-/// Interactive workflow when feature name is known but branch is not provided
+/// Interactive workflow when feature name is known but branch is not provided,
+/// returning the new worktree's path
 ///
 /// # Errors
 /// Returns an error if interactive prompts fail or worktree creation fails.
-pub fn interactive_create_with_feature(feature_name: &str) -> Result<()> {
-    let provider = RealSelectionProvider;
+pub fn interactive_create_with_feature(
+    feature_name: &str,
+    options: &CreateOptions,
+) -> Result<PathBuf> {
+    interactive_create_in(&std::env::current_dir()?, feature_name, options)
+}
 
+/// Like [`interactive_create_with_feature`], for the repository containing `dir` instead
+/// of the current directory
+///
+/// # Errors
+/// Returns an error if `dir` is not in a git repository, interactive prompts fail, or
+/// worktree creation fails.
+pub fn interactive_create_in(
+    dir: &Path,
+    feature_name: &str,
+    options: &CreateOptions,
+) -> Result<PathBuf> {
     // Validate feature name first
     WorktreeStorage::validate_feature_name(feature_name)?;
 
-    // Step 1: Get starting branch name
-    let branch_name =
-        provider.get_text_input("Starting branch name:", Some(validate_branch_name))?;
-
-    let current_dir = std::env::current_dir()?;
-    let git_repo = GitRepo::open(&current_dir)?;
-    let branch_exists = git_repo.branch_exists(&branch_name)?;
-
-    // Step 2: If branch is new, get a base ref
-    let from_ref = if !branch_exists {
-        let selected_ref = select_git_reference_interactive(&git_repo, &provider)?;
-        Some(selected_ref)
-    } else {
-        None
-    };
-
-    create_worktree(feature_name, Some(&branch_name), from_ref.as_deref())?;
+    let git_repo = GitRepo::open(dir)?;
+    let (_, branch_name, from_ref) =
+        prompt_create_inputs(&git_repo, Some(feature_name), &RealSelectionProvider)?;
 
-    Ok(())
+    create_worktree_at(
+        dir,
+        feature_name,
+        Some(&branch_name),
+        from_ref.as_deref(),
+        options,
+    )
 }
 
 #[cfg(test)]
@@ -595,203 +404,44 @@ mod tests {
     #![allow(clippy::unwrap_used)]
 
     use super::*;
-    use crate::config::{OnCreate, SymlinkPatterns, WorktreeConfig};
-    use std::fs;
+    use crate::selection::MockSelectionProvider;
     use tempfile::TempDir;
+    use test_support::git;
 
-    fn make_config_with_symlinks(patterns: Vec<String>) -> WorktreeConfig {
-        WorktreeConfig {
-            copy_patterns: crate::config::CopyPatterns {
-                include: Some(vec![]),
-                exclude: Some(vec![]),
-            },
-            symlink_patterns: SymlinkPatterns {
-                include: Some(patterns),
-            },
-            on_create: OnCreate { commands: None },
-        }
-    }
-
-    fn make_config_with_hooks(commands: Vec<String>) -> WorktreeConfig {
-        WorktreeConfig {
-            copy_patterns: crate::config::CopyPatterns {
-                include: Some(vec![]),
-                exclude: Some(vec![]),
-            },
-            symlink_patterns: SymlinkPatterns { include: None },
-            on_create: OnCreate {
-                commands: Some(commands),
-            },
-        }
-    }
-
-    // ── create_symlinks ──────────────────────────────────────────────────────
-
-    #[test]
-    fn test_create_symlinks_creates_symlink_for_matching_path() {
-        let tmp = TempDir::new().unwrap();
-        let origin = tmp.path().join("origin");
-        let worktree = tmp.path().join("worktree");
-        fs::create_dir_all(&origin).unwrap();
-        fs::create_dir_all(&worktree).unwrap();
-
-        // Create a file in the origin that will be symlinked
-        fs::write(origin.join("shared-data.txt"), "content").unwrap();
-
-        let config = make_config_with_symlinks(vec!["shared-data.txt".to_string()]);
-        create_symlinks(&origin, &worktree, &config).unwrap();
-
-        let link = worktree.join("shared-data.txt");
-        assert!(link.symlink_metadata().is_ok(), "symlink should exist");
-        assert!(
-            link.symlink_metadata().unwrap().file_type().is_symlink(),
-            "should be a symlink not a copy"
-        );
-        assert_eq!(fs::read_to_string(&link).unwrap(), "content");
-    }
-
-    #[test]
-    fn test_create_symlinks_skips_missing_origin_path_with_no_error() {
-        let tmp = TempDir::new().unwrap();
-        let origin = tmp.path().join("origin");
-        let worktree = tmp.path().join("worktree");
-        fs::create_dir_all(&origin).unwrap();
-        fs::create_dir_all(&worktree).unwrap();
-
-        // Pattern matches nothing in origin — should not error, should not create anything
-        let config = make_config_with_symlinks(vec!["does-not-exist.txt".to_string()]);
-        let result = create_symlinks(&origin, &worktree, &config);
-
-        assert!(
-            result.is_ok(),
-            "missing origin path should not cause an error"
-        );
-        assert!(
-            !worktree.join("does-not-exist.txt").exists(),
-            "no symlink should be created for missing path"
-        );
-    }
-
-    #[test]
-    fn test_create_symlinks_takes_precedence_over_copy() {
-        let tmp = TempDir::new().unwrap();
-        let origin = tmp.path().join("origin");
-        let worktree = tmp.path().join("worktree");
-        fs::create_dir_all(&origin).unwrap();
-        fs::create_dir_all(&worktree).unwrap();
-
-        fs::write(origin.join(".env"), "ORIGIN=1").unwrap();
-
-        // Config: symlink .env AND copy .env* — symlink should take precedence
-        let config = WorktreeConfig {
-            copy_patterns: crate::config::CopyPatterns {
-                include: Some(vec![".env*".to_string()]),
-                exclude: Some(vec![]),
-            },
-            symlink_patterns: SymlinkPatterns {
-                include: Some(vec![".env".to_string()]),
-            },
-            on_create: OnCreate { commands: None },
-        };
-
-        // First create symlinks (as in create_worktree_internal)
-        create_symlinks(&origin, &worktree, &config).unwrap();
-        // Then copy (should skip .env because it's already symlinked)
-        copy_config_files(&origin, &worktree, &config).unwrap();
-
-        let target = worktree.join(".env");
-        assert!(target.symlink_metadata().is_ok(), ".env should exist");
-        assert!(
-            target.symlink_metadata().unwrap().file_type().is_symlink(),
-            ".env should be a symlink, not a copy"
-        );
-    }
-
-    // ── run_on_create_hooks ──────────────────────────────────────────────────
-
-    #[test]
-    fn test_run_on_create_hooks_runs_commands_in_order() {
-        let tmp = TempDir::new().unwrap();
-        let worktree = tmp.path().join("worktree");
-        fs::create_dir_all(&worktree).unwrap();
-
-        // Commands write to a file in sequence to verify ordering
-        let marker = worktree.join("order.txt");
-        let config = make_config_with_hooks(vec![
-            format!("sh -c 'echo first >> {}'", marker.display()),
-            format!("sh -c 'echo second >> {}'", marker.display()),
-            format!("sh -c 'echo third >> {}'", marker.display()),
-        ]);
-
-        run_on_create_hooks(&worktree, &config).unwrap();
-
-        let content = fs::read_to_string(&marker).unwrap();
-        let lines: Vec<&str> = content.lines().collect();
-        assert_eq!(lines, vec!["first", "second", "third"]);
-    }
+    // ── prompt_create_inputs ─────────────────────────────────────────────────
 
     #[test]
-    fn test_run_on_create_hooks_stops_on_first_failure() {
+    fn test_prompt_create_inputs_walks_every_step() {
         let tmp = TempDir::new().unwrap();
-        let worktree = tmp.path().join("worktree");
-        fs::create_dir_all(&worktree).unwrap();
-
-        let marker = worktree.join("ran.txt");
-        let config = make_config_with_hooks(vec![
-            format!("sh -c 'echo before-fail >> {}'", marker.display()),
-            "sh -c 'exit 1'".to_string(), // fails here
-            format!("sh -c 'echo after-fail >> {}'", marker.display()),
-        ]);
-
-        // Should succeed (hook failure is non-fatal to the create operation)
-        let result = run_on_create_hooks(&worktree, &config);
-        assert!(result.is_ok(), "hook failure should not propagate as Err");
-
-        let content = fs::read_to_string(&marker).unwrap_or_default();
-        let lines: Vec<&str> = content.lines().collect();
-        // First command ran, third command did not
-        assert!(
-            lines.contains(&"before-fail"),
-            "command before failure should have run"
+        git(tmp.path(), &["init", "-b", "main"]).unwrap();
+        git(tmp.path(), &["config", "user.name", "Test User"]).unwrap();
+        git(tmp.path(), &["config", "user.email", "test@example.com"]).unwrap();
+        git(tmp.path(), &["commit", "--allow-empty", "-m", "init"]).unwrap();
+        git(tmp.path(), &["tag", "v1.0"]).unwrap();
+        let git_repo = GitRepo::open(tmp.path()).unwrap();
+
+        let provider = MockSelectionProvider::scripted()
+            .expect(
+                "Feature name (used as the worktree directory name):",
+                "auth",
+            )
+            .expect("Starting branch name:", "feature/auth")
+            .expect("Select git reference to create worktree from:", "v1.0");
+        let inputs = prompt_create_inputs(&git_repo, None, &provider).unwrap();
+        assert_eq!(
+            inputs,
+            (
+                "auth".to_string(),
+                "feature/auth".to_string(),
+                Some("v1.0".to_string())
+            )
         );
-        assert!(
-            !lines.contains(&"after-fail"),
-            "command after failure should NOT have run"
-        );
-    }
-
-    #[test]
-    fn test_run_on_create_hooks_worktree_intact_after_failure() {
-        let tmp = TempDir::new().unwrap();
-        let worktree = tmp.path().join("worktree");
-        fs::create_dir_all(&worktree).unwrap();
-
-        // Put a file in the worktree before hooks run
-        fs::write(worktree.join("important.txt"), "do not delete").unwrap();
-
-        let config = make_config_with_hooks(vec!["sh -c 'exit 42'".to_string()]);
-
-        run_on_create_hooks(&worktree, &config).unwrap();
-
-        // Worktree directory and its contents must still exist
-        assert!(
-            worktree.exists(),
-            "worktree directory should still exist after hook failure"
-        );
-        assert!(
-            worktree.join("important.txt").exists(),
-            "worktree contents should be intact after hook failure"
-        );
-    }
-
-    #[test]
-    fn test_run_on_create_hooks_no_commands_is_noop() {
-        let tmp = TempDir::new().unwrap();
-        let worktree = tmp.path().join("worktree");
-        fs::create_dir_all(&worktree).unwrap();
+        provider.verify_consumed().unwrap();
 
-        let config = WorktreeConfig::default();
-        let result = run_on_create_hooks(&worktree, &config);
-        assert!(result.is_ok());
+        // An existing branch needs no base reference, and a given feature name no prompt
+        let provider = MockSelectionProvider::scripted().expect("Starting branch name:", "main");
+        let inputs = prompt_create_inputs(&git_repo, Some("hotfix"), &provider).unwrap();
+        assert_eq!(inputs, ("hotfix".to_string(), "main".to_string(), None));
+        provider.verify_consumed().unwrap();
     }
 }
@@ -0,0 +1,214 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use std::cmp::Reverse;
+use std::path::Path;
+
+use crate::storage::WorktreeStorage;
+
+/// Ordering for the disk usage report
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DuSort {
+    /// Alphabetical by repository and feature name
+    #[default]
+    Name,
+    /// Largest first
+    Size,
+}
+
+/// Disk usage of one repository's worktrees
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoUsage {
+    pub repo_name: String,
+    /// `(feature name, bytes)` for each worktree
+    pub worktrees: Vec<(String, u64)>,
+}
+
+impl RepoUsage {
+    /// Total bytes across all worktrees of the repository
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.worktrees.iter().map(|(_, size)| size).sum()
+    }
+}
+
+/// Reports per-worktree and per-repository disk usage under the storage root
+///
+/// # Errors
+/// Returns an error if the storage directory cannot be read
+pub fn show_disk_usage(sort: DuSort) -> Result<()> {
+    let storage = WorktreeStorage::new()?;
+
+    let mut repos: Vec<RepoUsage> = storage
+        .list_all_worktrees()?
+        .into_iter()
+        .filter(|(_, worktrees)| !worktrees.is_empty())
+        .map(|(repo_name, worktrees)| {
+            let worktrees = worktrees
+                .into_iter()
+                .map(|feature_name| {
+                    let size = dir_size(&storage.get_worktree_path(&repo_name, &feature_name));
+                    (feature_name, size)
+                })
+                .collect::<Vec<_>>();
+            storage.cache_sizes(&repo_name, &worktrees);
+            RepoUsage {
+                repo_name,
+                worktrees,
+            }
+        })
+        .collect();
+
+    if repos.is_empty() {
+        println!("No worktrees found in {}", storage.get_root_dir().display());
+        return Ok(());
+    }
+
+    sort_usage(&mut repos, sort);
+
+    let name_width = repos
+        .iter()
+        .flat_map(|repo| repo.worktrees.iter().map(|(name, _)| name.len() + 3))
+        .chain(repos.iter().map(|repo| repo.repo_name.len()))
+        .max()
+        .unwrap_or(0);
+
+    println!("Disk usage in {}:", storage.get_root_dir().display());
+    for repo in &repos {
+        println!();
+        println!(
+            "{:<width$}  {:>10}",
+            repo.repo_name,
+            format_size(repo.total()),
+            width = name_width
+        );
+        for (feature_name, size) in &repo.worktrees {
+            println!(
+                "   {:<width$}  {:>10}",
+                feature_name,
+                format_size(*size),
+                width = name_width - 3
+            );
+        }
+    }
+
+    let total: u64 = repos.iter().map(RepoUsage::total).sum();
+    println!();
+    println!(
+        "{:<width$}  {:>10}",
+        "Total",
+        format_size(total),
+        width = name_width
+    );
+
+    Ok(())
+}
+
+/// Orders repositories and their worktrees by name or by size (largest first)
+pub fn sort_usage(repos: &mut [RepoUsage], sort: DuSort) {
+    match sort {
+        DuSort::Name => {
+            repos.sort_by(|a, b| a.repo_name.cmp(&b.repo_name));
+            for repo in repos.iter_mut() {
+                repo.worktrees.sort_by(|a, b| a.0.cmp(&b.0));
+            }
+        }
+        DuSort::Size => {
+            repos.sort_by_key(|repo| Reverse(repo.total()));
+            for repo in repos.iter_mut() {
+                repo.worktrees.sort_by_key(|(_, size)| Reverse(*size));
+            }
+        }
+    }
+}
+
+/// Total size in bytes of the files under `path`. Symlinks are not followed and
+/// unreadable entries are skipped.
+#[must_use]
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => dir_size(&entry.path()),
+            Ok(file_type) if file_type.is_file() => entry.metadata().map_or(0, |m| m.len()),
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Formats a byte count with binary units, e.g. `1.5 MiB`
+#[must_use]
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    #[allow(clippy::cast_precision_loss)] // Display only
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1024), "1.0 KiB");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MiB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+
+    #[test]
+    fn test_dir_size_recurses_without_following_symlinks() -> Result<()> {
+        let tmp = TempDir::new()?;
+        std::fs::write(tmp.path().join("a"), [0u8; 100])?;
+        std::fs::create_dir(tmp.path().join("sub"))?;
+        std::fs::write(tmp.path().join("sub").join("b"), [0u8; 50])?;
+
+        let outside = TempDir::new()?;
+        std::fs::write(outside.path().join("big"), [0u8; 1000])?;
+        std::os::unix::fs::symlink(outside.path(), tmp.path().join("link"))?;
+
+        assert_eq!(dir_size(tmp.path()), 150);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_usage_by_size() {
+        let mut repos = vec![
+            RepoUsage {
+                repo_name: "small".to_string(),
+                worktrees: vec![("x".to_string(), 10)],
+            },
+            RepoUsage {
+                repo_name: "big".to_string(),
+                worktrees: vec![("a".to_string(), 5), ("b".to_string(), 500)],
+            },
+        ];
+
+        sort_usage(&mut repos, DuSort::Size);
+        assert_eq!(repos[0].repo_name, "big");
+        assert_eq!(repos[0].worktrees[0].0, "b");
+
+        sort_usage(&mut repos, DuSort::Name);
+        assert_eq!(repos[0].repo_name, "big");
+        assert_eq!(repos[0].worktrees[0].0, "a");
+        assert_eq!(repos[1].repo_name, "small");
+    }
+}
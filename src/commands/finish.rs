@@ -0,0 +1,156 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use crate::commands::{back, workspace};
+use crate::config::{FinishStrategy, GlobalConfig};
+use crate::error::WorktreeError;
+use crate::git::{GitRepo, conflicted_files, git_output, run_git};
+use crate::manager::WorktreeManager;
+use crate::output;
+use crate::storage::WorktreeStorage;
+use crate::traits::GitOperations;
+
+/// Options for `worktree finish`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FinishOptions {
+    /// Merge, squash, or fast-forward. `None` uses `strategy` from the `[finish]` config
+    /// section.
+    pub strategy: Option<FinishStrategy>,
+    /// Merge into this branch instead of the default branch
+    pub into: Option<String>,
+    /// Keep the worktree's branch after merging it
+    pub keep_branch: bool,
+}
+
+/// Merges a worktree's branch into the default branch where that branch is checked out
+/// (usually the main repository), then removes the worktree and its branch. Without a
+/// target, finishes the worktree containing the current directory. Nothing is removed
+/// unless the merge succeeds.
+///
+/// # Errors
+/// Returns an error if the worktree is not found or has uncommitted changes, the target
+/// branch is not checked out anywhere or has uncommitted changes, the merge fails or
+/// hits conflicts, or the worktree cannot be removed
+pub fn finish_worktree(target: Option<&str>, options: &FinishOptions) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let manager =
+        WorktreeManager::with_storage(GitRepo::open(&current_dir)?, WorktreeStorage::new()?)?;
+    let info = match target {
+        Some(target) => manager.find_with(target, GlobalConfig::load()?.match_mode)?,
+        None => {
+            let (_, feature_name) =
+                back::determine_current_worktree(&current_dir, manager.storage())?;
+            manager.find(&feature_name)?
+        }
+    };
+    let branch = info
+        .branch
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("No branch is checked out in {}", info.path.display()))?;
+    if manager.git().is_dirty(&info.path)? {
+        return Err(WorktreeError::DirtyWorktree { path: info.path }.into());
+    }
+
+    let into = options
+        .into
+        .clone()
+        .or_else(|| manager.git().integration_branch())
+        .ok_or_else(|| anyhow::anyhow!("Could not determine the default branch; pass --into"))?;
+    anyhow::ensure!(branch != into, "{} is the branch to merge into", branch);
+
+    let into_dir = checkout_dir(manager.repo_path(), &into)?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "{} is not checked out; check it out in the main repository first",
+            into
+        )
+    })?;
+    if manager.git().is_dirty(&into_dir)? {
+        return Err(WorktreeError::DirtyWorktree { path: into_dir }.into());
+    }
+
+    let strategy = options.strategy.unwrap_or(manager.config().finish.strategy);
+    println!(
+        "Merging {} into {} ({})...",
+        branch,
+        into,
+        match strategy {
+            FinishStrategy::Squash => "squash",
+            FinishStrategy::FfOnly => "fast-forward",
+            _ => "merge",
+        }
+    );
+    merge_branch(&into_dir, &branch, strategy)?;
+    output::status(format_args!("✓ Merged {} into {}", branch, into));
+
+    let protected = manager
+        .config()
+        .protected_branches(manager.git().default_branch().as_deref());
+    let delete_branch = !options.keep_branch && !protected.is_protected(&branch);
+    let removed = manager.remove(&info.feature_name, delete_branch)?;
+    workspace::refresh_vscode_workspace(
+        manager.storage(),
+        manager.repo_name(),
+        manager.repo_path(),
+    );
+
+    output::status(format_args!("✓ Removed worktree {}", info.feature_name));
+    if removed.branch_deleted {
+        output::status(format_args!("✓ Deleted branch {}", branch));
+    } else {
+        println!("  Kept branch {}", branch);
+    }
+
+    Ok(())
+}
+
+/// Merges `branch` into the branch checked out in `dir`. On failure, the merge is
+/// undone and the conflicting files are listed.
+fn merge_branch(dir: &Path, branch: &str, strategy: FinishStrategy) -> Result<()> {
+    let args: &[&str] = match strategy {
+        FinishStrategy::Squash => &["merge", "--squash", branch],
+        FinishStrategy::FfOnly => &["merge", "--ff-only", branch],
+        _ => &["merge", "--no-edit", branch],
+    };
+    let output = git_output(dir, args)?;
+    if !output.status.success() {
+        let conflicts = conflicted_files(dir)?;
+        run_git(dir, ["reset", "--merge"])?;
+        if conflicts.is_empty() {
+            anyhow::bail!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        output::status_err(format_args!("✗ Conflicts merging {} in:", branch));
+        for file in &conflicts {
+            eprintln!("   {}", file);
+        }
+        anyhow::bail!(
+            "The merge was aborted and the worktree kept; update it (`worktree update`) and try again"
+        );
+    }
+
+    // A squash merge only stages the changes
+    let staged = !git_output(dir, ["diff", "--cached", "--quiet"])?
+        .status
+        .success();
+    if strategy == FinishStrategy::Squash && staged {
+        run_git(dir, ["commit", "--no-edit"])?;
+    }
+    Ok(())
+}
+
+/// The working directory where `branch` is checked out: the main repository or one of
+/// its worktrees
+fn checkout_dir(repo_path: &Path, branch: &str) -> Result<Option<PathBuf>> {
+    let listing = run_git(repo_path, ["worktree", "list", "--porcelain"])?;
+    let branch_line = format!("branch refs/heads/{}", branch);
+    Ok(listing.split("\n\n").find_map(|entry| {
+        let mut lines = entry.lines();
+        let path = lines.next()?.strip_prefix("worktree ")?;
+        lines
+            .any(|line| line == branch_line)
+            .then(|| PathBuf::from(path))
+    }))
+}
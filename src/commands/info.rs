@@ -0,0 +1,233 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::commands::{back, jump};
+use crate::git;
+use crate::storage::activity::{Activity, format_age};
+use crate::storage::{WorktreeStorage, read_worktree_head_branch};
+
+/// Everything known about one worktree, as shown by `worktree info`
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct WorktreeInfo {
+    /// Repository name (its storage directory)
+    pub repo: String,
+    /// Feature name (directory name under the storage root)
+    pub feature: String,
+    /// Worktree directory
+    pub path: PathBuf,
+    /// Whether the worktree directory exists
+    pub exists: bool,
+    /// Main repository the worktree was created from
+    pub origin: Option<PathBuf>,
+    /// Checked-out branch, `None` when detached or missing
+    pub branch: Option<String>,
+    /// Branch the worktree's branch was started from, when `create --from` recorded it
+    pub base: Option<String>,
+    /// Branch description
+    pub description: Option<String>,
+    /// Upstream of the branch, such as `origin/feature/auth`
+    pub upstream: Option<String>,
+    /// Commits on the branch that are not on its upstream
+    pub ahead: Option<usize>,
+    /// Commits on the upstream that are not on the branch
+    pub behind: Option<usize>,
+    /// Files with uncommitted changes, relative to the worktree root
+    pub dirty_files: Vec<PathBuf>,
+    /// When the worktree was created, in seconds since the Unix epoch
+    pub created: Option<u64>,
+    /// When the worktree was last used, in seconds since the Unix epoch
+    pub last_used: Option<u64>,
+    /// Latest commit, creation, or uncommitted file change, in seconds since the Unix
+    /// epoch
+    pub last_activity: Option<u64>,
+    /// Slot number of the worktree
+    pub slot: Option<u32>,
+    /// Labels of the worktree
+    pub labels: Vec<String>,
+    /// Note attached to the worktree
+    pub note: Option<String>,
+    /// Config files `create` copied into the worktree; `None` for worktrees created
+    /// before the manifest was recorded
+    pub copied_files: Option<Vec<PathBuf>>,
+}
+
+/// Shows everything known about the worktree named by `target`, or the worktree the
+/// current directory is in.
+///
+/// # Errors
+/// Returns an error if the worktree is not found, or without `target`, the current
+/// directory is not in a managed worktree, or storage cannot be read.
+pub fn show_info(target: Option<&str>, json: bool) -> Result<()> {
+    let storage = WorktreeStorage::new()?;
+    let (repo_name, feature_name) = match target {
+        Some(target) => jump::resolve_worktree_name(&storage, target)?,
+        None => back::determine_current_worktree(&std::env::current_dir()?, &storage)
+            .context("Not inside a worktree; pass the worktree as an argument")?,
+    };
+
+    let info = collect_info(&storage, &repo_name, &feature_name)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&info)?);
+    } else {
+        print_info(&info);
+    }
+    Ok(())
+}
+
+/// Gathers the [`WorktreeInfo`] of a managed worktree
+///
+/// # Errors
+/// Returns an error if the storage metadata cannot be read
+pub fn collect_info(
+    storage: &WorktreeStorage,
+    repo_name: &str,
+    feature_name: &str,
+) -> Result<WorktreeInfo> {
+    let path = storage.get_worktree_path(repo_name, feature_name);
+    let exists = path.exists();
+    let activity = storage.worktree_activity(repo_name, feature_name);
+    let branch = exists.then(|| read_worktree_head_branch(&path)).flatten();
+
+    let mut info = WorktreeInfo {
+        repo: repo_name.to_string(),
+        feature: feature_name.to_string(),
+        exists,
+        origin: storage
+            .get_worktree_origin(repo_name, feature_name)?
+            .map(PathBuf::from),
+        base: None,
+        description: None,
+        upstream: None,
+        ahead: None,
+        behind: None,
+        dirty_files: Vec::new(),
+        created: activity.created,
+        last_used: activity.last_used,
+        last_activity: None,
+        slot: storage.get_slot(repo_name, feature_name)?,
+        labels: storage
+            .list_labels(repo_name)?
+            .remove(feature_name)
+            .map(|labels| labels.into_iter().collect())
+            .unwrap_or_default(),
+        note: storage.get_note(repo_name, feature_name),
+        copied_files: storage.copied_files(repo_name, feature_name)?,
+        branch,
+        path,
+    };
+
+    let Some(git_repo) = exists.then(|| git::open_backend(&info.path).ok()).flatten() else {
+        return Ok(info);
+    };
+    if let Some(branch) = info.branch.as_deref() {
+        info.base = git::branch_base(&info.path, branch);
+        info.description = git::branch_description(&info.path, branch);
+        info.upstream = git_repo.upstream(branch).ok().flatten();
+        if let Ok(Some(ab)) = git_repo.ahead_behind(branch) {
+            info.ahead = Some(ab.ahead);
+            info.behind = Some(ab.behind);
+        }
+    }
+    info.dirty_files = git_repo.changed_files(&info.path).unwrap_or_default();
+    let activity = Activity {
+        created: info.created,
+        last_used: info.last_used,
+    };
+    info.last_activity = Some(activity.last_activity(git_repo.as_ref(), &info.path));
+    Ok(info)
+}
+
+fn print_info(info: &WorktreeInfo) {
+    println!("{}/{}", info.repo, info.feature);
+    print_field("Path", &info.path.display().to_string());
+    if !info.exists {
+        print_field("", "(directory is missing)");
+    }
+    if let Some(origin) = &info.origin {
+        print_field("Origin", &origin.display().to_string());
+    }
+
+    match &info.branch {
+        Some(branch) => print_field("Branch", &branch_state(branch, info)),
+        None if info.exists => print_field("Branch", "(detached)"),
+        None => {}
+    }
+    if let Some(base) = &info.base {
+        print_field("Base", base);
+    }
+    if let Some(description) = &info.description {
+        print_field("Description", description.trim());
+    }
+    if let Some(slot) = info.slot {
+        print_field("Slot", &slot.to_string());
+    }
+
+    for (label, time) in [
+        ("Created", info.created),
+        ("Last used", info.last_used),
+        ("Last activity", info.last_activity),
+    ] {
+        if let Some(time) = time {
+            print_field(label, &format_age(time));
+        }
+    }
+    if !info.labels.is_empty() {
+        print_field("Labels", &info.labels.join(", "));
+    }
+    if let Some(note) = &info.note {
+        print_field("Note", note);
+    }
+
+    if info.exists {
+        print_files("Uncommitted changes", Some(&info.dirty_files));
+    }
+    print_files("Copied at creation", info.copied_files.as_deref());
+}
+
+/// The branch with its upstream and ahead/behind counts, like
+/// `feature/auth (2 ahead, 1 behind origin/feature/auth)`
+fn branch_state(branch: &str, info: &WorktreeInfo) -> String {
+    match (info.ahead, info.behind, &info.upstream) {
+        (Some(0), Some(0), Some(upstream)) => format!("{} (up to date with {})", branch, upstream),
+        (Some(ahead), Some(0), Some(upstream)) => {
+            format!("{} ({} ahead of {})", branch, ahead, upstream)
+        }
+        (Some(0), Some(behind), Some(upstream)) => {
+            format!("{} ({} behind {})", branch, behind, upstream)
+        }
+        (Some(ahead), Some(behind), Some(upstream)) => format!(
+            "{} ({} ahead, {} behind {})",
+            branch, ahead, behind, upstream
+        ),
+        _ => format!("{} (no upstream)", branch),
+    }
+}
+
+/// Prints `value` after an aligned `label:`, indenting its continuation lines
+fn print_field(label: &str, value: &str) {
+    let label = if label.is_empty() {
+        String::new()
+    } else {
+        format!("{}:", label)
+    };
+    let mut lines = value.lines();
+    println!("  {:<14} {}", label, lines.next().unwrap_or_default());
+    for line in lines {
+        println!("  {:<14} {}", "", line);
+    }
+}
+
+fn print_files(heading: &str, files: Option<&[PathBuf]>) {
+    match files {
+        None => println!("  {}: not recorded", heading),
+        Some([]) => println!("  {}: none", heading),
+        Some(files) => {
+            println!("  {} ({}):", heading, files.len());
+            for file in files {
+                println!("    {}", file.display());
+            }
+        }
+    }
+}
@@ -1,67 +1,305 @@
 use anyhow::Result;
-use std::path::PathBuf;
+use std::collections::{BTreeSet, HashMap};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use tracing::warn;
 
-use crate::git::GitRepo;
-use crate::selection::{RealSelectionProvider, SelectionProvider};
-use crate::storage::{WorktreeStorage, read_worktree_head_branch};
+use crate::commands::back::determine_current_worktree;
+use crate::commands::create::{self, CreateOptions};
+use crate::commands::{completion, zellij};
+use crate::config::{GlobalConfig, MatchMode, WorktreeConfig};
+use crate::error::WorktreeError;
+use crate::git::{self, GitRepo};
+use crate::manager::WorktreeManager;
+use crate::output;
+use crate::selection::{self, RealSelectionProvider, SELECT_ENV, SelectionProvider};
+use crate::storage::frecency::sort_by_frecency;
+use crate::storage::labels::{self, LabelFilter};
+use crate::storage::{WorktreeStorage, history, read_worktree_head_branch};
+use crate::traits::GitOperations;
 
-/// Jump to a worktree directory
+/// Target that jumps to the previously visited location, like `cd -`
+pub const PREVIOUS_TARGET: &str = "-";
+
+/// Options for `worktree jump`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JumpOptions {
+    /// Pick the worktree interactively even if a target is given
+    pub interactive: bool,
+    /// Print worktree names for shell completion instead of jumping
+    pub list_completions: bool,
+    /// Only consider worktrees of the current repository
+    pub current_repo_only: bool,
+    /// Only consider worktrees of this repository (same as a `repo/name` target), so
+    /// worktrees of another repository can be addressed when names clash
+    pub repo: Option<String>,
+    /// Open the worktree in Zellij instead of printing its path; `None` follows `auto`
+    /// under `[zellij]` in the global config
+    pub zellij: Option<bool>,
+    /// Only consider worktrees passing every filter (see [`LabelFilter`])
+    pub labels: Vec<LabelFilter>,
+    /// When no worktree matches but the target names a local or remote branch of the
+    /// current repository, create its worktree without asking first
+    pub create: bool,
+}
+
+/// Jump to a worktree directory. A target of [`PREVIOUS_TARGET`] (`-`) jumps to where
+/// the last jump in this shell started from.
 ///
 /// # Errors
-/// Returns an error if storage access fails, the target is not found, or interactive
-/// selection fails.
-pub fn jump_worktree(
-    target: Option<&str>,
-    interactive: bool,
-    list_completions: bool,
-    current_repo_only: bool,
-) -> Result<()> {
-    jump_worktree_with_provider(
-        target,
-        interactive,
-        list_completions,
-        current_repo_only,
-        &RealSelectionProvider,
-    )
+/// Returns an error if storage access fails, the target is not found, interactive
+/// selection fails, or opening the worktree in Zellij fails.
+pub fn jump_worktree(target: Option<&str>, options: &JumpOptions) -> Result<()> {
+    jump_worktree_with_provider(target, options, &RealSelectionProvider)
 }
 
 /// Jump to a worktree directory with a custom selection provider (for testing)
 ///
 /// # Errors
-/// Returns an error if storage access fails, the target is not found, or interactive
-/// selection fails.
+/// Returns an error if storage access fails, the target is not found, interactive
+/// selection fails, or opening the worktree in Zellij fails.
 pub fn jump_worktree_with_provider(
     target: Option<&str>,
-    interactive: bool,
-    list_completions: bool,
-    current_repo_only: bool,
+    options: &JumpOptions,
     provider: &dyn SelectionProvider,
 ) -> Result<()> {
+    let repo = options.repo.as_deref();
+    if options.list_completions {
+        return list_worktree_completions(options.current_repo_only, repo);
+    }
+
     let storage = WorktreeStorage::new()?;
 
-    if list_completions {
-        list_worktree_completions(&storage, current_repo_only)?;
+    let target_path = if target == Some(PREVIOUS_TARGET) && !options.interactive {
+        previous_location(&storage)?
+    } else {
+        let target = target.filter(|_| !options.interactive);
+        match resolve_worktree(
+            &storage,
+            target,
+            options.current_repo_only,
+            repo,
+            &options.labels,
+            provider,
+        ) {
+            Err(e)
+                if matches!(
+                    WorktreeError::find(&e),
+                    Some(WorktreeError::NoMatchingWorktree { .. })
+                ) =>
+            {
+                let Some(target) = target else {
+                    return Err(e);
+                };
+                let (_, name) = storage.split_repo_target(target);
+                create_missing(name, options.create, provider)?.ok_or(e)?
+            }
+            result => result?,
+        }
+    };
+
+    let zellij_config = GlobalConfig::load()?.zellij;
+    if options
+        .zellij
+        .unwrap_or(zellij_config.auto && zellij::in_session())
+    {
+        let name = match determine_current_worktree(&target_path, &storage) {
+            Ok((_, feature_name)) => feature_name,
+            Err(_) => target_path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        };
+        zellij::open(&target_path, &name, zellij_config.mode)?;
+        // The shell stays where it is, so only the visit is recorded
+        record_visit(&storage, &target_path);
         return Ok(());
     }
 
-    let target_path = if interactive || target.is_none() {
-        select_worktree_interactive(&storage, current_repo_only, provider)?
-    } else if let Some(target_name) = target {
-        find_worktree_by_name(&storage, target_name, current_repo_only)?
-    } else {
-        anyhow::bail!("No target specified for worktree jump");
-    };
+    record_jump(&storage, &target_path);
 
     // Output just the path (shell function will handle cd)
     println!("{}", target_path.display());
     Ok(())
 }
 
-fn list_worktree_completions(storage: &WorktreeStorage, current_repo_only: bool) -> Result<()> {
-    let worktrees = get_available_worktrees(storage, current_repo_only)?;
+/// Finds the worktree named by `target` (a feature name, branch, or `repo/name`), or
+/// lets the user pick one when there is no target. Only worktrees passing every label
+/// filter are considered.
+///
+/// # Errors
+/// Returns an error if storage access fails, the target is not found or ambiguous, or
+/// interactive selection fails.
+pub fn resolve_worktree(
+    storage: &WorktreeStorage,
+    target: Option<&str>,
+    current_repo_only: bool,
+    repo: Option<&str>,
+    labels: &[LabelFilter],
+    provider: &dyn SelectionProvider,
+) -> Result<PathBuf> {
+    let (repo, target) = match target {
+        Some(target) if repo.is_none() => {
+            let (repo, name) = storage.split_repo_target(target);
+            (repo, Some(name))
+        }
+        _ => (repo, target),
+    };
+
+    match target {
+        Some(target_name) => {
+            find_worktree_by_name(storage, target_name, current_repo_only, repo, labels)
+        }
+        None => select_worktree_interactive(storage, current_repo_only, repo, labels, provider),
+    }
+}
+
+/// The `(repo, feature)` of the worktree named by `target` (a feature name, branch, or
+/// `repo/name`)
+///
+/// # Errors
+/// Returns an error if storage access fails or the target is not found or ambiguous.
+pub fn resolve_worktree_name(storage: &WorktreeStorage, target: &str) -> Result<(String, String)> {
+    let path = resolve_worktree(
+        storage,
+        Some(target),
+        false,
+        None,
+        &[],
+        &RealSelectionProvider,
+    )?;
+    determine_current_worktree(&path, storage)
+}
+
+/// Creates a worktree for `target` when it names a local or remote branch of the
+/// current repository: right away with `create`, else after asking. Returns `None` when
+/// there is no such branch, nobody to ask, or the user declines.
+fn create_missing(
+    target: &str,
+    create: bool,
+    provider: &dyn SelectionProvider,
+) -> Result<Option<PathBuf>> {
+    let Ok(git) = git::open_backend(&std::env::current_dir()?) else {
+        return Ok(None);
+    };
+    let Some((branch, from)) = branch_for_target(git.as_ref(), target)? else {
+        return Ok(None);
+    };
+    let feature_name = WorktreeManager::with_storage(git.as_ref(), WorktreeStorage::new()?)?
+        .feature_name_for_branch(&branch);
+
+    if !create {
+        if !std::io::stdin().is_terminal() && std::env::var_os(SELECT_ENV).is_none() {
+            return Ok(None);
+        }
+        // No summary lines: they would go to stdout, which the shell function captures
+        let prompt = format!(
+            "No worktree for branch '{}'. Create worktree '{}'?",
+            branch, feature_name
+        );
+        if !selection::confirm(provider, &[], &prompt)? {
+            return Ok(None);
+        }
+    }
 
-    for (_, feature_name, _) in worktrees {
-        // Emit feature names for completions
+    // Creation output stays on screen; only the path goes to stdout
+    let path = create::create_worktree_with_git(
+        git.as_ref(),
+        &feature_name,
+        Some(&branch),
+        from.as_deref(),
+        &CreateOptions {
+            output: output::Target::Stderr,
+            ..CreateOptions::default()
+        },
+    )?;
+    if let Some(upstream) = &from {
+        if let Err(e) = git::set_branch_upstream(&git.get_repo_path(), &branch, upstream) {
+            warn!("Failed to track {}: {}", upstream, e);
+        }
+    }
+    Ok(Some(path))
+}
+
+/// The `(branch, start point)` for a worktree of `target`: the local branch of that
+/// name, else a new local branch from the remote-tracking branch `<remote>/<target>`
+/// (preferring `origin`). `target` may also name the remote-tracking branch itself.
+fn branch_for_target(
+    git: &dyn GitOperations,
+    target: &str,
+) -> Result<Option<(String, Option<String>)>> {
+    if git.branch_exists(target)? {
+        return Ok(Some((target.to_string(), None)));
+    }
+    let remote_branches = git.list_remote_branches()?;
+    if let Some((_, name)) = target
+        .split_once('/')
+        .filter(|_| remote_branches.iter().any(|b| b == target))
+    {
+        return Ok(Some((name.to_string(), Some(target.to_string()))));
+    }
+    let mut candidates: Vec<&String> = remote_branches
+        .iter()
+        .filter(|b| b.split_once('/').is_some_and(|(_, name)| name == target))
+        .collect();
+    candidates.sort_by_key(|b| !b.starts_with("origin/"));
+    Ok(candidates
+        .first()
+        .map(|remote| (target.to_string(), Some((*remote).clone()))))
+}
+
+/// Where this shell was before its last jump, without consuming the history
+fn previous_location(storage: &WorktreeStorage) -> Result<PathBuf> {
+    let session = history::session_id().ok_or_else(|| {
+        anyhow::anyhow!(
+            "'worktree jump -' needs the shell integration to track jump history.\n\
+            Add `eval \"$(worktree-bin init <shell>)\"` to your shell config."
+        )
+    })?;
+    let current_dir = std::env::current_dir()?;
+    history::peek(storage.get_root_dir(), session, &current_dir)?
+        .ok_or_else(|| anyhow::anyhow!("No previous location in this shell's jump history"))
+}
+
+/// Records a jump the shell is about to make to `target_path`: the directory left
+/// behind goes on the navigation history for `back`, and the visit counts towards the
+/// worktree's frecency. Failures only warn.
+pub fn record_jump(storage: &WorktreeStorage, target_path: &Path) {
+    record_departure(storage, target_path);
+    record_visit(storage, target_path);
+}
+
+/// Counts the jump towards the target worktree's frecency. Failures only warn.
+fn record_visit(storage: &WorktreeStorage, target_path: &Path) {
+    if let Ok((repo_name, feature_name)) = determine_current_worktree(target_path, storage) {
+        if let Err(e) = storage
+            .record_visit(&repo_name, &feature_name)
+            .and_then(|()| storage.record_use(&repo_name, &feature_name))
+        {
+            warn!("Failed to record worktree visit: {}", e);
+        }
+    }
+}
+
+/// Pushes the directory being left onto the shell's navigation history so `back` can
+/// return to it. Failures only warn: they must not stop the jump.
+fn record_departure(storage: &WorktreeStorage, target_path: &Path) {
+    let Some(session) = history::session_id() else {
+        return;
+    };
+    let Ok(current_dir) = std::env::current_dir() else {
+        return;
+    };
+    if current_dir == target_path {
+        return;
+    }
+    if let Err(e) = history::push(storage.get_root_dir(), session, &current_dir) {
+        warn!("Failed to record navigation history: {}", e);
+    }
+}
+
+fn list_worktree_completions(current_repo_only: bool, repo: Option<&str>) -> Result<()> {
+    for feature_name in completion::feature_names(current_repo_only, repo)? {
         println!("{}", feature_name);
     }
 
@@ -71,29 +309,23 @@ fn list_worktree_completions(storage: &WorktreeStorage, current_repo_only: bool)
 fn select_worktree_interactive(
     storage: &WorktreeStorage,
     current_repo_only: bool,
+    repo: Option<&str>,
+    labels: &[LabelFilter],
     provider: &dyn SelectionProvider,
 ) -> Result<PathBuf> {
-    let worktrees = get_available_worktrees(storage, current_repo_only)?;
+    let mut worktrees = get_available_worktrees(storage, current_repo_only, repo)?;
+    retain_labeled(storage, &mut worktrees, labels)?;
 
     if worktrees.is_empty() {
         anyhow::bail!("No worktrees found");
     }
+    sort_by_frecency(&mut worktrees, &storage.visits()?, |(repo, feature, _)| {
+        (repo, feature)
+    });
 
-    // Format: "repo/feature-name (current-branch)  /path"
     let options: Vec<String> = worktrees
         .iter()
-        .map(|(repo, feature_name, path)| {
-            let branch_info = read_worktree_head_branch(path)
-                .map(|b| format!(" ({})", b))
-                .unwrap_or_default();
-            format!(
-                "{}/{}{} ({})",
-                repo,
-                feature_name,
-                branch_info,
-                path.display()
-            )
-        })
+        .map(|(repo, feature_name, path)| picker_option(storage, repo, feature_name, path))
         .collect();
 
     let selection = provider.select("Jump to worktree:", options.clone())?;
@@ -110,8 +342,11 @@ fn find_worktree_by_name(
     storage: &WorktreeStorage,
     target: &str,
     current_repo_only: bool,
+    repo: Option<&str>,
+    labels: &[LabelFilter],
 ) -> Result<PathBuf> {
-    let worktrees = get_available_worktrees(storage, current_repo_only)?;
+    let mut worktrees = get_available_worktrees(storage, current_repo_only, repo)?;
+    retain_labeled(storage, &mut worktrees, labels)?;
 
     // Try exact match against feature name (directory name)
     for (_repo, feature_name, path) in &worktrees {
@@ -120,56 +355,153 @@ fn find_worktree_by_name(
         }
     }
 
-    // Try partial match against feature name
-    let matches: Vec<_> = worktrees
-        .iter()
-        .filter(|(_, feature_name, _)| feature_name.contains(target))
-        .collect();
+    // Then against the branch checked out in each worktree (e.g. `api/feature/auth`)
+    for (_repo, _feature_name, path) in &worktrees {
+        if read_worktree_head_branch(path).as_deref() == Some(target) {
+            return Ok(path.clone());
+        }
+    }
+
+    // Then a prefix or substring of a feature name, per `match` in the config
+    let mode = match_mode()?;
+    let mut matches = mode.best_matches(target, worktrees.iter().collect(), |(_, name, _)| {
+        name.as_str()
+    });
+    sort_by_frecency(&mut matches, &storage.visits()?, |(repo, feature, _)| {
+        (repo, feature)
+    });
 
     match matches.len() {
-        0 => anyhow::bail!("No worktree found matching '{}'", target),
+        0 => Err(WorktreeError::NoMatchingWorktree {
+            target: target.to_string(),
+        }
+        .into()),
         1 => Ok(matches[0].2.clone()),
         _ => {
             eprintln!(
                 "Multiple worktrees match '{}'. Please be more specific:",
                 target
             );
-            for (repo, feature_name, _) in matches {
-                eprintln!("  {}/{}", repo, feature_name);
+            let candidates: Vec<String> = matches
+                .iter()
+                .map(|(repo, feature_name, _)| format!("{}/{}", repo, feature_name))
+                .collect();
+            for candidate in &candidates {
+                eprintln!("  {}", candidate);
+            }
+            Err(WorktreeError::AmbiguousWorktree {
+                target: target.to_string(),
+                candidates,
             }
-            anyhow::bail!("Ambiguous worktree name");
+            .into())
         }
     }
 }
 
-fn get_available_worktrees(
+/// `match` from the `[jump]` section of the current repository's config, else (outside
+/// a repository or without the key) from the global config
+fn match_mode() -> Result<MatchMode> {
+    let repo_mode = match GitRepo::open(&std::env::current_dir()?) {
+        Ok(repo) => {
+            WorktreeConfig::load_from_repo(repo.get_repo_path())?
+                .jump
+                .match_mode
+        }
+        Err(_) => None,
+    };
+    match repo_mode {
+        Some(mode) => Ok(mode),
+        None => Ok(GlobalConfig::load()?.match_mode),
+    }
+}
+
+/// How a worktree is shown in interactive pickers:
+/// `repo/feature-name (current-branch) (/path)`, followed by the first line of its note
+#[must_use]
+pub fn picker_option(
+    storage: &WorktreeStorage,
+    repo: &str,
+    feature_name: &str,
+    path: &Path,
+) -> String {
+    let branch_info = read_worktree_head_branch(path)
+        .map(|b| format!(" ({})", b))
+        .unwrap_or_default();
+    let note = storage
+        .get_note(repo, feature_name)
+        .and_then(|note| note.lines().next().map(|line| format!(" — {}", line)))
+        .unwrap_or_default();
+    format!(
+        "{}/{}{} ({}){}",
+        repo,
+        feature_name,
+        branch_info,
+        path.display(),
+        note
+    )
+}
+
+/// Keeps the `(repo, feature, path)` worktrees passing every label filter
+fn retain_labeled(
+    storage: &WorktreeStorage,
+    worktrees: &mut Vec<(String, String, PathBuf)>,
+    filters: &[LabelFilter],
+) -> Result<()> {
+    if filters.is_empty() {
+        return Ok(());
+    }
+    let mut repo_labels = HashMap::new();
+    for (repo, _, _) in worktrees.iter() {
+        if !repo_labels.contains_key(repo) {
+            repo_labels.insert(repo.clone(), storage.list_labels(repo)?);
+        }
+    }
+    let no_labels = BTreeSet::new();
+    worktrees.retain(|(repo, feature, _)| {
+        let worktree_labels = repo_labels
+            .get(repo)
+            .and_then(|labels| labels.get(feature))
+            .unwrap_or(&no_labels);
+        labels::matches_all(filters, worktree_labels)
+    });
+    Ok(())
+}
+
+/// Lists existing worktrees as `(repo, feature, path)`: those of `repo` if given,
+/// else of the current repository with `current_repo_only`, else of every repository
+///
+/// # Errors
+/// Returns an error if the storage directory cannot be read
+pub fn get_available_worktrees(
     storage: &WorktreeStorage,
     current_repo_only: bool,
+    repo: Option<&str>,
 ) -> Result<Vec<(String, String, PathBuf)>> {
-    let mut worktrees = Vec::new();
-
-    if current_repo_only {
+    let repos = if let Some(repo_name) = repo {
+        vec![(
+            repo_name.to_string(),
+            storage.list_repo_worktrees(repo_name)?,
+        )]
+    } else if current_repo_only {
         let current_dir = std::env::current_dir()?;
-        if let Ok(git_repo) = GitRepo::open(&current_dir) {
-            let repo_path = git_repo.get_repo_path();
-            let repo_name = WorktreeStorage::get_repo_name(repo_path)?;
-
-            let repo_worktrees = storage.list_repo_worktrees(&repo_name)?;
-            for feature_name in repo_worktrees {
-                let worktree_path = storage.get_worktree_path(&repo_name, &feature_name);
-                if worktree_path.exists() {
-                    worktrees.push((repo_name.clone(), feature_name, worktree_path));
-                }
+        match GitRepo::open(&current_dir) {
+            Ok(git_repo) => {
+                let repo_name = WorktreeStorage::get_repo_name(git_repo.get_repo_path())?;
+                let repo_worktrees = storage.list_repo_worktrees(&repo_name)?;
+                vec![(repo_name, repo_worktrees)]
             }
+            Err(_) => Vec::new(),
         }
     } else {
-        let all_worktrees = storage.list_all_worktrees()?;
-        for (repo_name, repo_worktrees) in all_worktrees {
-            for feature_name in repo_worktrees {
-                let worktree_path = storage.get_worktree_path(&repo_name, &feature_name);
-                if worktree_path.exists() {
-                    worktrees.push((repo_name.clone(), feature_name, worktree_path));
-                }
+        storage.list_all_worktrees()?
+    };
+
+    let mut worktrees = Vec::new();
+    for (repo_name, repo_worktrees) in repos {
+        for feature_name in repo_worktrees {
+            let worktree_path = storage.get_worktree_path(&repo_name, &feature_name);
+            if worktree_path.exists() {
+                worktrees.push((repo_name.clone(), feature_name, worktree_path));
             }
         }
     }
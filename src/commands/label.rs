@@ -0,0 +1,149 @@
+use anyhow::Result;
+use clap::{Subcommand, ValueHint};
+use clap_complete::ArgValueCandidates;
+use std::collections::BTreeMap;
+
+use crate::commands::{completion, jump};
+use crate::output;
+use crate::storage::WorktreeStorage;
+use crate::storage::labels::is_valid_label;
+
+#[derive(Subcommand, Clone)]
+#[non_exhaustive]
+pub enum LabelAction {
+    /// Add labels to a worktree
+    Add {
+        /// Feature name, branch, or `repo/name` of the worktree
+        #[arg(value_hint = ValueHint::Other, add = ArgValueCandidates::new(completion::worktree_names))]
+        target: String,
+        /// Labels to add (e.g. `review`, `experiment`, `client-x`)
+        #[arg(required = true)]
+        labels: Vec<String>,
+    },
+    /// Remove labels from a worktree
+    Remove {
+        /// Feature name, branch, or `repo/name` of the worktree
+        #[arg(value_hint = ValueHint::Other, add = ArgValueCandidates::new(completion::worktree_names))]
+        target: String,
+        /// Labels to remove
+        #[arg(required = true)]
+        labels: Vec<String>,
+    },
+    /// Show a worktree's labels, or every label in use and the worktrees carrying it
+    List {
+        /// Feature name, branch, or `repo/name` of the worktree
+        #[arg(value_hint = ValueHint::Other, add = ArgValueCandidates::new(completion::worktree_names))]
+        target: Option<String>,
+    },
+}
+
+/// Dispatches the `worktree label` subcommand.
+///
+/// # Errors
+/// Returns an error if the worktree is not found, a label is invalid, or the label
+/// records cannot be read or written.
+pub fn run_label_command(action: &LabelAction) -> Result<()> {
+    let storage = WorktreeStorage::new()?;
+    match action {
+        LabelAction::Add { target, labels } => add_labels(&storage, target, labels),
+        LabelAction::Remove { target, labels } => remove_labels(&storage, target, labels),
+        LabelAction::List {
+            target: Some(target),
+        } => list_worktree_labels(&storage, target),
+        LabelAction::List { target: None } => list_all_labels(&storage),
+    }
+}
+
+/// Adds labels to the worktree named by `target`
+///
+/// # Errors
+/// Returns an error if the worktree is not found, a label is empty, contains whitespace,
+/// or starts with `!`, or the label records cannot be updated.
+pub fn add_labels(storage: &WorktreeStorage, target: &str, labels: &[String]) -> Result<()> {
+    if let Some(invalid) = labels.iter().find(|label| !is_valid_label(label)) {
+        anyhow::bail!(
+            "Invalid label '{}': labels cannot be empty, contain whitespace, or start with '!'",
+            invalid
+        );
+    }
+    let (repo_name, feature_name) = jump::resolve_worktree_name(storage, target)?;
+    for label in labels {
+        if storage.add_label(&repo_name, &feature_name, label)? {
+            output::status(format_args!(
+                "✓ Labeled {}/{} '{}'",
+                repo_name, feature_name, label
+            ));
+        } else {
+            println!(
+                "• {}/{} is already labeled '{}'",
+                repo_name, feature_name, label
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Removes labels from the worktree named by `target`
+///
+/// # Errors
+/// Returns an error if the worktree is not found or the label records cannot be updated.
+pub fn remove_labels(storage: &WorktreeStorage, target: &str, labels: &[String]) -> Result<()> {
+    let (repo_name, feature_name) = jump::resolve_worktree_name(storage, target)?;
+    for label in labels {
+        if storage.remove_label(&repo_name, &feature_name, label)? {
+            output::status(format_args!(
+                "✓ Removed label '{}' from {}/{}",
+                label, repo_name, feature_name
+            ));
+        } else {
+            println!(
+                "• {}/{} is not labeled '{}'",
+                repo_name, feature_name, label
+            );
+        }
+    }
+    Ok(())
+}
+
+fn list_worktree_labels(storage: &WorktreeStorage, target: &str) -> Result<()> {
+    let (repo_name, feature_name) = jump::resolve_worktree_name(storage, target)?;
+    let labels = storage.list_labels(&repo_name)?;
+    match labels.get(&feature_name) {
+        Some(labels) => {
+            for label in labels {
+                println!("{}", label);
+            }
+        }
+        None => eprintln!("{}/{} has no labels", repo_name, feature_name),
+    }
+    Ok(())
+}
+
+/// Prints each label in use with the `repo/feature` worktrees carrying it
+fn list_all_labels(storage: &WorktreeStorage) -> Result<()> {
+    let mut worktrees_by_label: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (repo_name, _) in storage.list_all_worktrees()? {
+        for (feature_name, labels) in storage.list_labels(&repo_name)? {
+            for label in labels {
+                worktrees_by_label
+                    .entry(label)
+                    .or_default()
+                    .push(format!("{}/{}", repo_name, feature_name));
+            }
+        }
+    }
+
+    if worktrees_by_label.is_empty() {
+        println!("No labeled worktrees.");
+        return Ok(());
+    }
+    let width = worktrees_by_label
+        .keys()
+        .map(String::len)
+        .max()
+        .unwrap_or(0);
+    for (label, worktrees) in worktrees_by_label {
+        println!("{:<width$}  {}", label, worktrees.join(", "), width = width);
+    }
+    Ok(())
+}
@@ -1,69 +1,342 @@
 use anyhow::Result;
+use clap::ValueEnum;
+use std::collections::BTreeSet;
 
-use crate::git::GitRepo;
+use crate::commands::{du, pr};
+use crate::config::WorktreeConfig;
+use crate::git::{self, GitRepo};
+use crate::manager::{WorktreeManager, find_origin_repo};
+use crate::output;
+use crate::storage::activity::{Activity, format_age};
+use crate::storage::labels::{self, LabelFilter};
 use crate::storage::{WorktreeStorage, read_worktree_head_branch};
 
+/// Orderings for `worktree list`
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ListSort {
+    /// Alphabetical by feature name
+    #[default]
+    Name,
+    /// Most recently created first
+    Created,
+    /// Most recently jumped to first
+    LastUsed,
+    /// Largest first
+    Size,
+}
+
+/// Options for `worktree list`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListOptions {
+    /// Only list worktrees of the current repository
+    pub current_repo_only: bool,
+    /// Show each branch's pull request state (also on with `show-status` in a
+    /// repository's `[pr]` config)
+    pub show_pr: bool,
+    /// How worktrees are ordered within each repository
+    pub sort: ListSort,
+    /// Reverse the order
+    pub reverse: bool,
+    /// Only list worktrees passing every filter (see [`LabelFilter`])
+    pub labels: Vec<LabelFilter>,
+    /// Also show each worktree's note
+    pub verbose: bool,
+    /// Print one line per worktree rendered from this template instead of the usual
+    /// layout (see [`render_format`])
+    pub format: Option<String>,
+}
+
 /// Lists all worktrees, optionally filtered to current repository only
 ///
 /// # Errors
 /// Returns an error if storage access or git operations fail.
-pub fn list_worktrees(current_repo_only: bool) -> Result<()> {
+pub fn list_worktrees(options: &ListOptions) -> Result<()> {
     let storage = WorktreeStorage::new()?;
 
-    if current_repo_only {
-        list_current_repo_worktrees(&storage)?;
+    if let Some(format) = &options.format {
+        list_formatted(&storage, options, &unescape_format(format))?;
+    } else if options.current_repo_only {
+        list_current_repo_worktrees(&storage, options)?;
     } else {
-        list_all_worktrees(&storage)?;
+        list_all_worktrees(&storage, options)?;
+    }
+
+    Ok(())
+}
+
+/// Orders one repository's worktrees: by name (A to Z), or newest, most recently used,
+/// or largest first, with ties by name. `reverse` flips the order. Times come from the
+/// activity records (see [`crate::storage::activity`]) and sizes from the size cache,
+/// measuring (and caching) worktrees without a recent size.
+pub fn sort_worktrees<T>(
+    storage: &WorktreeStorage,
+    repo_name: &str,
+    items: &mut Vec<T>,
+    feature: impl Fn(&T) -> &str,
+    sort: ListSort,
+    reverse: bool,
+) {
+    if sort == ListSort::Name {
+        items.sort_by(|a, b| feature(a).cmp(feature(b)));
+    } else {
+        let mut measured = Vec::new();
+        let mut keyed: Vec<(u64, T)> = items
+            .drain(..)
+            .map(|item| {
+                let name = feature(&item);
+                let key = match sort {
+                    ListSort::Created => storage
+                        .worktree_activity(repo_name, name)
+                        .created
+                        .unwrap_or(0),
+                    ListSort::LastUsed => storage
+                        .worktree_activity(repo_name, name)
+                        .last_used
+                        .unwrap_or(0),
+                    _ => storage.cached_size(repo_name, name).unwrap_or_else(|| {
+                        let size = du::dir_size(&storage.get_worktree_path(repo_name, name));
+                        measured.push((name.to_string(), size));
+                        size
+                    }),
+                };
+                (key, item)
+            })
+            .collect();
+        storage.cache_sizes(repo_name, &measured);
+        keyed.sort_by(|(key_a, a), (key_b, b)| {
+            key_b.cmp(key_a).then_with(|| feature(a).cmp(feature(b)))
+        });
+        items.extend(keyed.into_iter().map(|(_, item)| item));
+    }
+    if reverse {
+        items.reverse();
+    }
+}
+
+/// Renders a `list --format` template for one worktree. Placeholders:
+///
+/// - `{repo}`, `{feature}`, `{branch}` (empty when detached), and `{path}`
+/// - `{dirty}`: `*` when there are uncommitted changes
+/// - `{ahead}` and `{behind}`: commits relative to the upstream (empty without one)
+/// - `{age}`: how long ago the worktree was created, like `3w ago`
+/// - `{labels}`: comma-separated labels; `{note}`: the first line of the note
+/// - `{managed}`: `managed` when `create` made the branch, `adopted` when the worktree
+///   checked out an existing one (empty when detached)
+///
+/// Status checks only run for placeholders that need them.
+#[must_use]
+pub fn render_format(
+    storage: &WorktreeStorage,
+    format: &str,
+    repo_name: &str,
+    feature_name: &str,
+) -> String {
+    let path = storage.get_worktree_path(repo_name, feature_name);
+    let branch = read_worktree_head_branch(&path);
+    let backend =
+        (format.contains("{dirty}") || format.contains("{ahead}") || format.contains("{behind}"))
+            .then(|| git::open_backend(&path).ok())
+            .flatten();
+    let dirty = backend
+        .as_ref()
+        .filter(|_| format.contains("{dirty}"))
+        .and_then(|backend| backend.is_dirty(&path).ok())
+        .unwrap_or(false);
+    let ahead_behind = backend
+        .as_ref()
+        .zip(branch.as_deref())
+        .and_then(|(backend, branch)| backend.ahead_behind(branch).ok().flatten());
+    let age = format
+        .contains("{age}")
+        .then(|| storage.worktree_activity(repo_name, feature_name).created)
+        .flatten()
+        .map(format_age)
+        .unwrap_or_default();
+    let labels = storage
+        .list_labels(repo_name)
+        .ok()
+        .and_then(|mut labels| labels.remove(feature_name))
+        .unwrap_or_default();
+    let note = storage
+        .get_note(repo_name, feature_name)
+        .and_then(|note| note.lines().next().map(str::to_string))
+        .unwrap_or_default();
+
+    let managed = branch
+        .as_deref()
+        .filter(|_| format.contains("{managed}"))
+        .map(|branch| {
+            let managed = storage
+                .managed_branches(repo_name)
+                .is_ok_and(|managed| managed.contains(branch));
+            if managed { "managed" } else { "adopted" }
+        })
+        .unwrap_or_default();
+
+    format
+        .replace("{repo}", repo_name)
+        .replace("{feature}", feature_name)
+        .replace("{branch}", branch.as_deref().unwrap_or_default())
+        .replace("{path}", &path.display().to_string())
+        .replace("{dirty}", if dirty { "*" } else { "" })
+        .replace(
+            "{ahead}",
+            &ahead_behind
+                .map(|ab| ab.ahead.to_string())
+                .unwrap_or_default(),
+        )
+        .replace(
+            "{behind}",
+            &ahead_behind
+                .map(|ab| ab.behind.to_string())
+                .unwrap_or_default(),
+        )
+        .replace("{age}", &age)
+        .replace(
+            "{labels}",
+            &labels.into_iter().collect::<Vec<_>>().join(","),
+        )
+        .replace("{note}", &note)
+        .replace("{managed}", managed)
+}
+
+/// Turns the `\t`, `\n`, and `\\` escapes of a `--format` argument into tabs, line
+/// breaks, and backslashes, since shells pass them through single quotes verbatim
+fn unescape_format(format: &str) -> String {
+    let mut out = String::with_capacity(format.len());
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// `list --format`: one rendered line per worktree with a directory, no headings
+fn list_formatted(storage: &WorktreeStorage, options: &ListOptions, format: &str) -> Result<()> {
+    let repos = if options.current_repo_only {
+        let current_dir = std::env::current_dir()?;
+        let manager = WorktreeManager::with_storage(GitRepo::open(&current_dir)?, storage.clone())?;
+        let repo_name = manager.repo_name().to_string();
+        let worktrees = storage.list_repo_worktrees(&repo_name)?;
+        vec![(repo_name, worktrees)]
+    } else {
+        storage.list_all_worktrees()?
+    };
+
+    for (repo_name, mut worktrees) in repos {
+        let repo_labels = storage.list_labels(&repo_name)?;
+        worktrees.retain(|feature_name| {
+            storage.get_worktree_path(&repo_name, feature_name).exists()
+                && has_labels(&repo_labels, feature_name, options)
+        });
+        sort_worktrees(
+            storage,
+            &repo_name,
+            &mut worktrees,
+            String::as_str,
+            options.sort,
+            options.reverse,
+        );
+        for feature_name in worktrees {
+            println!(
+                "{}",
+                render_format(storage, format, &repo_name, &feature_name)
+            );
+        }
     }
 
     Ok(())
 }
 
-fn list_current_repo_worktrees(storage: &WorktreeStorage) -> Result<()> {
+fn list_current_repo_worktrees(storage: &WorktreeStorage, options: &ListOptions) -> Result<()> {
     let current_dir = std::env::current_dir()?;
-    let git_repo = GitRepo::open(&current_dir)?;
-    let repo_path = git_repo.get_repo_path();
-    let repo_name = WorktreeStorage::get_repo_name(repo_path)?;
+    let manager = WorktreeManager::with_storage(GitRepo::open(&current_dir)?, storage.clone())?;
+    let show_pr = options.show_pr || manager.config().pr.show_status;
 
-    println!("Worktrees for repository: {}", repo_name);
+    println!("Worktrees for repository: {}", manager.repo_name());
     println!("{}", "=".repeat(40));
 
-    let worktrees = storage.list_repo_worktrees(&repo_name)?;
+    let mut worktrees = manager.list()?;
 
     if worktrees.is_empty() {
         println!("No worktrees found for this repository.");
         return Ok(());
     }
+    let repo_labels = storage.list_labels(manager.repo_name())?;
+    let managed_branches = storage.managed_branches(manager.repo_name())?;
+    worktrees.retain(|worktree| has_labels(&repo_labels, &worktree.feature_name, options));
+    if worktrees.is_empty() {
+        println!("No worktrees match the label filters.");
+        return Ok(());
+    }
+    sort_worktrees(
+        storage,
+        manager.repo_name(),
+        &mut worktrees,
+        |worktree| &worktree.feature_name,
+        options.sort,
+        options.reverse,
+    );
 
-    for feature_name in worktrees {
-        let worktree_path = storage.get_worktree_path(&repo_name, &feature_name);
-        let status = if worktree_path.exists() {
-            "✓ Active"
-        } else {
-            "✗ Missing"
-        };
-
-        let branch_info = if worktree_path.exists() {
-            read_worktree_head_branch(&worktree_path)
-                .map(|b| format!(" ({})", b))
-                .unwrap_or_else(|| " (detached)".to_string())
+    for worktree in worktrees {
+        let description = worktree
+            .branch
+            .as_deref()
+            .and_then(|branch| git::branch_description(manager.repo_path(), branch));
+        let pull_request = worktree
+            .branch
+            .as_deref()
+            .filter(|_| show_pr)
+            .and_then(|branch| {
+                pr::pull_request_status(storage, manager.repo_path(), manager.config(), branch)
+            });
+        let (status, branch_info) = if worktree.exists() {
+            (
+                "✓ Active",
+                branch_info(worktree.branch.as_deref(), &managed_branches),
+            )
         } else {
-            String::new()
+            ("✗ Missing", String::new())
         };
 
-        println!(
+        output::status(format_args!(
             "  {} {}{}  {}",
             status,
-            feature_name,
+            worktree.feature_name,
             branch_info,
-            worktree_path.display()
-        );
+            worktree.path.display()
+        ));
+        print_description(description.as_deref());
+        print_labels(repo_labels.get(&worktree.feature_name));
+        print_activity(&storage.worktree_activity(manager.repo_name(), &worktree.feature_name));
+        print_pull_request(pull_request.as_ref());
+        if options.verbose {
+            print_note(
+                storage
+                    .get_note(manager.repo_name(), &worktree.feature_name)
+                    .as_deref(),
+            );
+        }
     }
 
     Ok(())
 }
 
-fn list_all_worktrees(storage: &WorktreeStorage) -> Result<()> {
+fn list_all_worktrees(storage: &WorktreeStorage, options: &ListOptions) -> Result<()> {
     println!("All managed worktrees:");
     println!("{}", "=".repeat(40));
 
@@ -74,33 +347,139 @@ fn list_all_worktrees(storage: &WorktreeStorage) -> Result<()> {
         return Ok(());
     }
 
-    for (repo_name, worktrees) in all_worktrees {
+    let mut listed_any = false;
+    for (repo_name, mut worktrees) in all_worktrees {
+        let repo_labels = storage.list_labels(&repo_name)?;
+        let managed_branches = storage.managed_branches(&repo_name)?;
+        worktrees.retain(|feature_name| has_labels(&repo_labels, feature_name, options));
         if worktrees.is_empty() {
             continue;
         }
+        listed_any = true;
+        sort_worktrees(
+            storage,
+            &repo_name,
+            &mut worktrees,
+            String::as_str,
+            options.sort,
+            options.reverse,
+        );
 
         println!("\n📁 {}", repo_name);
+        let origin_repo = find_origin_repo(storage, &repo_name);
+        let config = origin_repo
+            .as_ref()
+            .and_then(|repo| WorktreeConfig::load_from_repo(repo.get_repo_path()).ok())
+            .filter(|config| options.show_pr || config.pr.show_status);
         for feature_name in worktrees {
             let worktree_path = storage.get_worktree_path(&repo_name, &feature_name);
             let status = if worktree_path.exists() { "✓" } else { "✗" };
 
+            let branch = worktree_path
+                .exists()
+                .then(|| read_worktree_head_branch(&worktree_path))
+                .flatten();
+            let description =
+                origin_repo
+                    .as_ref()
+                    .zip(branch.as_deref())
+                    .and_then(|(origin_repo, branch)| {
+                        git::branch_description(origin_repo.get_repo_path(), branch)
+                    });
+            let pull_request = origin_repo
+                .as_ref()
+                .zip(config.as_ref())
+                .zip(branch.as_deref())
+                .and_then(|((origin_repo, config), branch)| {
+                    pr::pull_request_status(storage, origin_repo.get_repo_path(), config, branch)
+                });
             let branch_info = if worktree_path.exists() {
-                read_worktree_head_branch(&worktree_path)
-                    .map(|b| format!(" ({})", b))
-                    .unwrap_or_else(|| " (detached)".to_string())
+                branch_info(branch.as_deref(), &managed_branches)
             } else {
                 String::new()
             };
 
-            println!(
+            output::status(format_args!(
                 "  {} {}{}  {}",
                 status,
                 feature_name,
                 branch_info,
                 worktree_path.display()
-            );
+            ));
+            print_description(description.as_deref());
+            print_labels(repo_labels.get(&feature_name));
+            print_activity(&storage.worktree_activity(&repo_name, &feature_name));
+            print_pull_request(pull_request.as_ref());
+            if options.verbose {
+                print_note(storage.get_note(&repo_name, &feature_name).as_deref());
+            }
         }
     }
+    if !listed_any && !options.labels.is_empty() {
+        println!("No worktrees match the label filters.");
+    }
 
     Ok(())
 }
+
+/// The ` (branch)` shown after a worktree's name, marked `[adopted]` when the worktree
+/// checked out an existing branch instead of one `create` made
+fn branch_info(branch: Option<&str>, managed_branches: &BTreeSet<String>) -> String {
+    match branch {
+        Some(branch) if managed_branches.contains(branch) => format!(" ({})", branch),
+        Some(branch) => format!(" ({}) [adopted]", branch),
+        None => " (detached)".to_string(),
+    }
+}
+
+/// Whether a worktree passes the `--label` filters of `options`
+fn has_labels(repo_labels: &labels::LabelMap, feature_name: &str, options: &ListOptions) -> bool {
+    let no_labels = Default::default();
+    labels::matches_all(
+        &options.labels,
+        repo_labels.get(feature_name).unwrap_or(&no_labels),
+    )
+}
+
+/// Prints the first line of a branch description under its worktree
+fn print_description(description: Option<&str>) {
+    if let Some(line) = description.and_then(|d| d.lines().next()) {
+        println!("      {}", line);
+    }
+}
+
+/// Prints a worktree's labels under it
+fn print_labels(labels: Option<&BTreeSet<String>>) {
+    if let Some(labels) = labels.filter(|labels| !labels.is_empty()) {
+        let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+        println!("      labels: {}", labels.join(", "));
+    }
+}
+
+/// Prints a branch's pull request state under its worktree
+fn print_pull_request(status: Option<&pr::PullRequestStatus>) {
+    if let Some(status) = status {
+        println!("      {}", status);
+    }
+}
+
+/// Prints a worktree's note under it, line by line
+pub fn print_note(note: Option<&str>) {
+    for (i, line) in note.into_iter().flat_map(str::lines).enumerate() {
+        println!("      {} {}", if i == 0 { "note:" } else { "     " }, line);
+    }
+}
+
+/// Prints how long ago a worktree was created and last used under it
+pub fn print_activity(activity: &Activity) {
+    let ages: Vec<String> = [
+        ("created", activity.created),
+        ("last used", activity.last_used),
+    ]
+    .into_iter()
+    .filter_map(|(label, time)| time.map(|time| format!("{} {}", label, format_age(time))))
+    .collect();
+    if !ages.is_empty() {
+        println!("      {}", ages.join(", "));
+    }
+}
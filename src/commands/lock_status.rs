@@ -0,0 +1,71 @@
+use anyhow::Result;
+
+use crate::output;
+use crate::storage::WorktreeStorage;
+use crate::storage::lock::{self, LockInfo};
+
+/// Shows who holds the storage metadata lock, optionally breaking it
+///
+/// # Errors
+/// Returns an error if storage access fails, or if asked to break a lock whose holder
+/// is still running without `force`.
+pub fn show_lock_status(break_lock: bool, force: bool) -> Result<()> {
+    let storage = WorktreeStorage::new()?;
+    let root_dir = storage.get_root_dir();
+
+    println!("Storage root: {}", root_dir.display());
+    println!("Lock file: {}", lock::lock_file_path(root_dir).display());
+    println!("Lock timeout: {}s", lock::lock_timeout().as_secs());
+    println!();
+
+    let Some(info) = lock::read_lock_info(root_dir)? else {
+        output::status("✓ Storage is not locked");
+        return Ok(());
+    };
+
+    let running = info.is_holder_running();
+    print_holder(&info, running);
+
+    if !break_lock {
+        if !running {
+            println!();
+            println!("Run 'worktree lock-status --break' to remove the stale lock.");
+        }
+        return Ok(());
+    }
+
+    if running && !force {
+        anyhow::bail!(
+            "Refusing to break a lock held by running process PID {}. \
+             Pass --force to break it anyway.",
+            info.pid
+        );
+    }
+
+    if lock::break_lock(root_dir)? {
+        output::status("✓ Lock removed");
+    } else {
+        output::status("✓ Lock was released before it could be removed");
+    }
+
+    Ok(())
+}
+
+fn print_holder(info: &LockInfo, running: bool) {
+    if info.pid == 0 {
+        output::status("⚠ Lock file exists but its holder could not be determined");
+        return;
+    }
+
+    let state = if running {
+        "running"
+    } else {
+        "not running — stale"
+    };
+    println!(
+        "🔒 Locked by PID {} ({}), acquired {}s ago",
+        info.pid,
+        state,
+        info.age_secs()
+    );
+}
@@ -0,0 +1,39 @@
+//! Man page generation.
+//!
+//! `worktree manpages <dir>` is a hidden command for packagers: it renders a page for
+//! `worktree` and one per visible subcommand (`worktree-create.1`, ...) straight from
+//! the clap definitions, so the pages never drift from `--help`.
+
+use anyhow::{Context, Result};
+use clap::Command;
+use clap_mangen::Man;
+use std::path::{Path, PathBuf};
+
+/// Writes man pages for `cmd` and all of its visible subcommands into `out_dir`,
+/// creating the directory if needed, and prints each file written
+///
+/// # Errors
+/// Returns an error if the directory cannot be created or a page cannot be written.
+pub fn generate_manpages(cmd: Command, out_dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(out_dir)
+        .with_context(|| format!("Failed to create {}", out_dir.display()))?;
+
+    let mut cmd = cmd.disable_help_subcommand(true);
+    cmd.build();
+    let mut written = Vec::new();
+    write_pages(cmd, out_dir, &mut written)
+        .with_context(|| format!("Failed to write man pages to {}", out_dir.display()))?;
+
+    for path in written {
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+
+fn write_pages(cmd: Command, out_dir: &Path, written: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for sub in cmd.get_subcommands().filter(|sub| !sub.is_hide_set()) {
+        write_pages(sub.clone(), out_dir, written)?;
+    }
+    written.push(Man::new(cmd).generate_to(out_dir)?);
+    Ok(())
+}
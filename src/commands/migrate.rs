@@ -0,0 +1,49 @@
+use anyhow::Result;
+
+use crate::output;
+use crate::storage::WorktreeStorage;
+use crate::storage::version::CURRENT_VERSION;
+
+/// Upgrades the storage root to the current format, one migration at a time
+///
+/// With `dry_run`, only lists the migrations that would run.
+///
+/// # Errors
+/// Returns an error if:
+/// - Failed to access the storage system
+/// - The storage was written by a newer version of worktree
+/// - A migration fails; the version file then records the last one that completed
+pub fn migrate_storage(dry_run: bool) -> Result<()> {
+    let storage = WorktreeStorage::new()?;
+    let version = storage.storage_version()?;
+    let pending = storage.pending_migrations()?;
+
+    println!("Storage root: {}", storage.get_root_dir().display());
+    println!("Format version: {} (current: {})", version, CURRENT_VERSION);
+    println!();
+
+    if pending.is_empty() {
+        output::status("✓ Storage is up to date");
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("Would run {} migration(s):", pending.len());
+        for migration in &pending {
+            println!("  → {}: {}", migration.version, migration.description);
+        }
+        return Ok(());
+    }
+
+    for migration in pending {
+        let changed = storage.run_migration(migration)?;
+        output::status(format_args!(
+            "✓ {}: {} ({} changed)",
+            migration.version, migration.description, changed
+        ));
+    }
+    println!();
+    println!("✅ Storage is now at version {}", CURRENT_VERSION);
+
+    Ok(())
+}
@@ -1,10 +1,37 @@
+pub mod archive;
+pub mod autoclean;
 pub mod back;
+pub mod carapace;
 pub mod cleanup;
+pub mod clone;
+pub mod completion;
 pub mod create;
+pub mod du;
+pub mod finish;
+pub mod info;
 pub mod init;
 pub mod jump;
+pub mod label;
 pub mod list;
+pub mod lock_status;
+pub mod manpages;
+pub mod migrate;
+pub mod note;
+pub mod open;
+pub mod path;
+pub mod pr;
+pub mod progress;
+pub mod prompt;
+pub mod push;
+pub mod relocate;
 pub mod remove;
+pub mod repair;
+pub mod root;
 pub mod skill;
 pub mod status;
+pub mod sync;
 pub mod sync_config;
+pub mod ui;
+pub mod update;
+pub mod workspace;
+pub mod zellij;
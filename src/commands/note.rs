@@ -0,0 +1,46 @@
+use anyhow::Result;
+
+use crate::commands::jump;
+use crate::output;
+use crate::storage::WorktreeStorage;
+
+/// Attaches `message` as the note of the worktree named by `target`, replacing any
+/// earlier note. With `clear`, removes the note instead; with neither, prints it.
+///
+/// # Errors
+/// Returns an error if the worktree is not found, the message is empty, or the note
+/// records cannot be read or written.
+pub fn note_worktree(target: &str, message: Option<&str>, clear: bool) -> Result<()> {
+    let storage = WorktreeStorage::new()?;
+    let (repo_name, feature_name) = jump::resolve_worktree_name(&storage, target)?;
+
+    if clear {
+        if storage.get_note(&repo_name, &feature_name).is_some() {
+            storage.forget_note(&repo_name, &feature_name)?;
+            output::status(format_args!(
+                "✓ Cleared the note on {}/{}",
+                repo_name, feature_name
+            ));
+        } else {
+            println!("• {}/{} has no note", repo_name, feature_name);
+        }
+        return Ok(());
+    }
+
+    match message {
+        Some(message) => {
+            let message = message.trim();
+            anyhow::ensure!(
+                !message.is_empty(),
+                "The note is empty; use --clear to remove a note"
+            );
+            storage.set_note(&repo_name, &feature_name, message)?;
+            output::status(format_args!("✓ Noted on {}/{}", repo_name, feature_name));
+        }
+        None => match storage.get_note(&repo_name, &feature_name) {
+            Some(note) => println!("{}", note),
+            None => eprintln!("{}/{} has no note", repo_name, feature_name),
+        },
+    }
+    Ok(())
+}
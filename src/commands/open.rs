@@ -0,0 +1,261 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Stdio;
+
+use crate::commands::{jump, root};
+use crate::config::{GlobalConfig, WorktreeConfig};
+use crate::provision::template::run_shell;
+use crate::selection::{RealSelectionProvider, SelectionProvider};
+use crate::storage::WorktreeStorage;
+
+/// Placeholder replaced by the worktree path in editor command templates
+pub const PATH_PLACEHOLDER: &str = "{path}";
+
+/// Name of the `--with` target that falls back to the configured editor when no
+/// `[open.editor]` is defined
+pub const EDITOR_TARGET: &str = "editor";
+
+/// Opens a worktree in the configured editor, or with the named `[open]` target. Without
+/// a target worktree, it is picked interactively.
+///
+/// # Errors
+/// Returns an error if the worktree is not found, no editor or no such open target is
+/// configured, or the command fails.
+pub fn open_worktree(target: Option<&str>, repo: Option<&str>, with: Option<&str>) -> Result<()> {
+    open_worktree_with_provider(target, repo, with, &RealSelectionProvider)
+}
+
+/// Opens a worktree in the configured editor, or with the named `[open]` target, with a
+/// custom selection provider (for testing)
+///
+/// # Errors
+/// Returns an error if the worktree is not found, no editor or no such open target is
+/// configured, or the command fails.
+pub fn open_worktree_with_provider(
+    target: Option<&str>,
+    repo: Option<&str>,
+    with: Option<&str>,
+    provider: &dyn SelectionProvider,
+) -> Result<()> {
+    let storage = WorktreeStorage::new()?;
+    let path = jump::resolve_worktree(&storage, target, false, repo, &[], provider)?;
+    match with {
+        Some(name) => open_with(&path, name),
+        None => open_in_editor(&path),
+    }
+}
+
+/// Launches the configured editor on `path`
+///
+/// The editor comes from `editor` in the main repository's `.worktree-config.toml`,
+/// then `editor` in the global config, then `$VISUAL` and `$EDITOR`.
+///
+/// # Errors
+/// Returns an error if no editor is configured or the editor command fails.
+pub fn open_in_editor(path: &Path) -> Result<()> {
+    let repo_config = repo_config_for(path)?;
+    let editor = editor_command(&repo_config, &GlobalConfig::load()?).ok_or_else(|| {
+        anyhow::anyhow!(
+            "No editor configured. Set `editor` in .worktree-config.toml or the global config \
+            (e.g. editor = \"code\"), or set $VISUAL or $EDITOR."
+        )
+    })?;
+    run_open_command(&editor, path)
+}
+
+/// Opens `path` with the `[open.<name>]` target
+///
+/// Targets come from the main repository's `.worktree-config.toml`, then the global
+/// config. `editor` falls back to the configured editor when no target has that name.
+///
+/// # Errors
+/// Returns an error if no target named `name` exists or its command fails.
+pub fn open_with(path: &Path, name: &str) -> Result<()> {
+    let repo_config = repo_config_for(path)?;
+    let command = open_target_command(name, &repo_config, &GlobalConfig::load()?)?;
+    run_open_command(&command, path)
+}
+
+/// The command of the `[open.<name>]` target for this platform, from the repository
+/// config, then the global config
+///
+/// # Errors
+/// Returns an error listing the available targets if none is named `name`, or if the
+/// target has no command for this platform.
+pub fn open_target_command(
+    name: &str,
+    repo_config: &WorktreeConfig,
+    global_config: &GlobalConfig,
+) -> Result<String> {
+    let target = repo_config
+        .open
+        .get(name)
+        .or_else(|| global_config.open.get(name));
+    match target {
+        Some(target) => target
+            .platform_command()
+            .filter(|command| !command.trim().is_empty())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Open target '{}' has no command for {}",
+                    name,
+                    std::env::consts::OS
+                )
+            }),
+        None => {
+            if name == EDITOR_TARGET {
+                if let Some(editor) = editor_command(repo_config, global_config) {
+                    return Ok(editor);
+                }
+            }
+            let names = open_target_names(repo_config, global_config);
+            if names.is_empty() {
+                anyhow::bail!(
+                    "Unknown open target '{}'. Define it as [open.{}] with a `command` in \
+                    .worktree-config.toml or the global config.",
+                    name,
+                    name
+                );
+            }
+            anyhow::bail!(
+                "Unknown open target '{}'. Available: {}",
+                name,
+                names.join(", ")
+            )
+        }
+    }
+}
+
+/// Names of the `[open]` targets from both configs, sorted and deduplicated
+#[must_use]
+pub fn open_target_names(
+    repo_config: &WorktreeConfig,
+    global_config: &GlobalConfig,
+) -> Vec<String> {
+    let mut names: Vec<String> = repo_config
+        .open
+        .keys()
+        .chain(global_config.open.keys())
+        .cloned()
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Configuration of the main repository `path` belongs to
+fn repo_config_for(path: &Path) -> Result<WorktreeConfig> {
+    let repo_root = WorktreeStorage::new()
+        .ok()
+        .and_then(|storage| root::find_root(path, &storage).ok())
+        .unwrap_or_else(|| path.to_path_buf());
+    Ok(WorktreeConfig::load_from_repo(&repo_root)?)
+}
+
+/// Runs `command` (a program or `{path}` template) on `path`, from inside `path`
+fn run_open_command(command: &str, path: &Path) -> Result<()> {
+    let shell_command = editor_shell_command(command, path);
+    println!("Opening {} with: {}", path.display(), command);
+    run_shell(
+        &shell_command,
+        path,
+        &[],
+        Stdio::inherit(),
+        Stdio::inherit(),
+    )
+    .with_context(|| format!("Failed to open {}", path.display()))
+}
+
+/// The editor to use: the repository config, then the global config, then `$VISUAL`
+/// and `$EDITOR`
+#[must_use]
+pub fn editor_command(
+    repo_config: &WorktreeConfig,
+    global_config: &GlobalConfig,
+) -> Option<String> {
+    repo_config
+        .editor
+        .clone()
+        .or_else(|| global_config.editor.clone())
+        .or_else(|| std::env::var("VISUAL").ok())
+        .or_else(|| std::env::var("EDITOR").ok())
+        .filter(|editor| !editor.trim().is_empty())
+}
+
+/// Shell command running `editor` on `path`: `{path}` in the template is replaced by the
+/// quoted path, otherwise the path is appended
+#[must_use]
+pub fn editor_shell_command(editor: &str, path: &Path) -> String {
+    let quoted = shell_quote(&path.to_string_lossy());
+    if editor.contains(PATH_PLACEHOLDER) {
+        editor.replace(PATH_PLACEHOLDER, &quoted)
+    } else {
+        format!("{} {}", editor, quoted)
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editor_shell_command() {
+        let path = Path::new("/tmp/it's here");
+        assert_eq!(
+            editor_shell_command("code", path),
+            r"code '/tmp/it'\''s here'"
+        );
+        assert_eq!(
+            editor_shell_command("zed --new {path}", path),
+            r"zed --new '/tmp/it'\''s here'"
+        );
+    }
+
+    #[test]
+    fn test_repo_editor_overrides_global() -> anyhow::Result<()> {
+        let repo_config: WorktreeConfig = toml::from_str("editor = \"zed\"")?;
+        let global_config: GlobalConfig = toml::from_str("editor = \"code\"")?;
+        assert_eq!(
+            editor_command(&repo_config, &global_config).as_deref(),
+            Some("zed")
+        );
+        assert_eq!(
+            editor_command(&WorktreeConfig::default(), &global_config).as_deref(),
+            Some("code")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_target_precedence_and_fallback() -> anyhow::Result<()> {
+        let repo_config: WorktreeConfig =
+            toml::from_str("editor = \"zed\"\n[open.files]\ncommand = \"nautilus\"\n")?;
+        let global_config: GlobalConfig = toml::from_str(
+            "[open.files]\ncommand = \"thunar\"\n[open.term]\ncommand = \"kitty --directory {path}\"\n",
+        )?;
+
+        assert_eq!(
+            open_target_command("files", &repo_config, &global_config)?,
+            "nautilus"
+        );
+        assert_eq!(
+            open_target_command("term", &repo_config, &global_config)?,
+            "kitty --directory {path}"
+        );
+        assert_eq!(
+            open_target_command(EDITOR_TARGET, &repo_config, &global_config)?,
+            "zed"
+        );
+        let error = open_target_command("finder", &repo_config, &global_config)
+            .err()
+            .map(|e| e.to_string())
+            .unwrap_or_default();
+        assert!(error.contains("Available: files, term"), "{}", error);
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use anyhow::Result;
+
+use crate::commands::jump::resolve_worktree;
+use crate::selection::RealSelectionProvider;
+use crate::storage::WorktreeStorage;
+
+/// Prints the path of the worktree named by `target`, matched like `jump` (feature
+/// name, checked-out branch, or a unique partial name; `repo/name` to qualify)
+///
+/// Unlike `jump` it never prompts, creates, or records the visit, so scripts and editor
+/// configs get the same answer every time or a failure.
+///
+/// # Errors
+/// Returns an error if storage access fails or the target is not found or ambiguous
+pub fn print_path(target: &str, current_repo_only: bool, repo: Option<&str>) -> Result<()> {
+    let storage = WorktreeStorage::new()?;
+    let path = resolve_worktree(
+        &storage,
+        Some(target),
+        current_repo_only,
+        repo,
+        &[],
+        &RealSelectionProvider,
+    )?;
+    println!("{}", path.display());
+    Ok(())
+}
@@ -0,0 +1,379 @@
+use anyhow::{Context, Result};
+use clap::{Subcommand, ValueHint};
+use clap_complete::ArgValueCandidates;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use std::process::Command;
+
+use crate::commands::{back, completion, jump};
+use crate::config::{ForgeTool, WorktreeConfig};
+use crate::git::{self, GitRepo, git_output, run_git};
+use crate::output;
+use crate::selection::RealSelectionProvider;
+use crate::storage::{WorktreeStorage, read_worktree_head_branch};
+
+#[derive(Subcommand, Clone)]
+#[non_exhaustive]
+pub enum PrAction {
+    /// Push a worktree's branch and open a pull request for it with gh or glab
+    Create {
+        /// Feature name, branch, or `repo/name` of the worktree (default: the worktree you
+        /// are in)
+        #[arg(value_hint = ValueHint::Other, add = ArgValueCandidates::new(completion::worktree_names))]
+        target: Option<String>,
+        /// Branch to merge into (default: the branch the worktree was created from, else
+        /// the default branch)
+        #[arg(long, value_name = "BRANCH", add = ArgValueCandidates::new(completion::git_refs))]
+        base: Option<String>,
+        /// Open the pull request as a draft
+        #[arg(long)]
+        draft: bool,
+    },
+}
+
+/// Dispatches the `worktree pr` subcommand.
+///
+/// # Errors
+/// Returns an error if the worktree is not found, pushing fails, or the forge CLI is
+/// missing or fails.
+pub fn run_pr_command(action: &PrAction) -> Result<()> {
+    match action {
+        PrAction::Create {
+            target,
+            base,
+            draft,
+        } => create_pull_request(target.as_deref(), base.as_deref(), *draft),
+    }
+}
+
+/// Pushes a worktree's branch with upstream tracking and opens a pull request (a merge
+/// request with `glab`) for it, titled and described from its commits. The worktree is
+/// found through the storage metadata, so this works from inside the worktree, from
+/// the main repository, or from anywhere given a `repo/name` target.
+///
+/// # Errors
+/// Returns an error if the worktree is not found or has no branch, the repository has
+/// no remote, there are no commits to propose, pushing fails, or the forge CLI is missing
+/// or fails.
+pub fn create_pull_request(target: Option<&str>, base: Option<&str>, draft: bool) -> Result<()> {
+    let storage = WorktreeStorage::new()?;
+    let path = match target {
+        Some(target) => jump::resolve_worktree(
+            &storage,
+            Some(target),
+            false,
+            None,
+            &[],
+            &RealSelectionProvider,
+        )?,
+        None => back::current_worktree_path(&storage)?,
+    };
+    let git_repo = GitRepo::open(&path)?;
+    let config = WorktreeConfig::load_from_repo(git_repo.get_repo_path())?;
+
+    let branch = read_worktree_head_branch(&path)
+        .ok_or_else(|| anyhow::anyhow!("No branch is checked out in {}", path.display()))?;
+    let remote = git_repo.push_remote(&branch).ok_or_else(|| {
+        anyhow::anyhow!(
+            "No remote to push {} to; set its upstream or add an `origin` remote",
+            branch
+        )
+    })?;
+    let base = match base {
+        Some(base) => base.to_string(),
+        None => git::branch_base(git_repo.get_repo_path(), &branch)
+            .or_else(|| git_repo.integration_branch())
+            .ok_or_else(|| {
+                anyhow::anyhow!("Could not determine the branch to merge into; pass --base")
+            })?,
+    };
+    let base = base
+        .strip_prefix(&format!("{}/", remote))
+        .unwrap_or(&base)
+        .to_string();
+    anyhow::ensure!(branch != base, "{} is the branch to merge into", branch);
+
+    let (title, body) = describe_commits(&path, &remote, &base, &branch)?;
+
+    println!("Pushing {} to {}...", branch, remote);
+    let published = git_repo.publish_branch(&branch, false)?;
+    output::status(format_args!(
+        "✓ Pushed {} to {}",
+        branch, published.remote_branch
+    ));
+
+    let tool = forge_tool(&config, &path, &remote)?;
+    let draft = draft || config.pr.draft;
+    let mut command = match tool {
+        ForgeTool::Glab => {
+            let mut command = Command::new("glab");
+            command.args(["mr", "create", "--yes", "--source-branch", &branch]);
+            command.args(["--target-branch", &base, "--title", &title]);
+            command.args(["--description", &body]);
+            command
+        }
+        _ => {
+            let mut command = Command::new("gh");
+            command.args(["pr", "create", "--head", &branch, "--base", &base]);
+            command.args(["--title", &title, "--body", &body]);
+            command
+        }
+    };
+    if draft {
+        command.arg("--draft");
+    }
+    let program = command.get_program().to_string_lossy().to_string();
+    println!(
+        "Opening a pull request for {} into {} with {}...",
+        branch, base, program
+    );
+    let status = command.current_dir(&path).status().with_context(|| {
+        format!(
+            "Failed to run {}. Install it or set `tool` in the [pr] config",
+            program
+        )
+    })?;
+    if !status.success() {
+        anyhow::bail!(
+            "{} failed with exit code {}",
+            program,
+            status.code().unwrap_or(-1)
+        );
+    }
+
+    Ok(())
+}
+
+/// The title and body of the pull request: the commit's subject and message for a
+/// single commit, otherwise the branch name and a list of commit subjects
+fn describe_commits(
+    path: &Path,
+    remote: &str,
+    base: &str,
+    branch: &str,
+) -> Result<(String, String)> {
+    let remote_base = format!("{}/{}", remote, base);
+    let base_ref = if git_output(path, ["rev-parse", "--verify", "--quiet", &remote_base])?
+        .status
+        .success()
+    {
+        remote_base
+    } else {
+        base.to_string()
+    };
+    let log = run_git(
+        path,
+        [
+            "log",
+            "--reverse",
+            "--format=%s%x1f%b%x1e",
+            &format!("{}..{}", base_ref, branch),
+        ],
+    )?;
+    let commits: Vec<(&str, &str)> = log
+        .split('\x1e')
+        .filter_map(|entry| entry.trim().split_once('\x1f'))
+        .collect();
+
+    match commits.as_slice() {
+        [] => anyhow::bail!("{} has no commits that are not on {}", branch, base_ref),
+        [(subject, body)] => Ok(((*subject).to_string(), body.trim().to_string())),
+        _ => Ok((
+            branch_title(branch),
+            commits
+                .iter()
+                .map(|(subject, _)| format!("- {}", subject))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )),
+    }
+}
+
+/// A title from a branch name: `feature/add-login` becomes "Add login"
+fn branch_title(branch: &str) -> String {
+    let name = branch
+        .rsplit('/')
+        .next()
+        .unwrap_or(branch)
+        .replace(['-', '_'], " ");
+    let mut chars = name.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+/// The forge CLI for `remote`: `tool` from the `[pr]` config, else `glab` for remotes on a
+/// GitLab host and `gh` otherwise
+fn forge_tool(config: &WorktreeConfig, repo_path: &Path, remote: &str) -> Result<ForgeTool> {
+    if let Some(tool) = config.pr.tool {
+        return Ok(tool);
+    }
+    let url = run_git(repo_path, ["remote", "get-url", remote])?;
+    Ok(if url.contains("gitlab") {
+        ForgeTool::Glab
+    } else {
+        ForgeTool::Gh
+    })
+}
+
+/// The most recent pull request (or merge request) of a branch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct PullRequestStatus {
+    /// The pull request number (`!` number for GitLab)
+    pub number: u64,
+    /// Whether it is open, merged, or closed
+    pub state: PullRequestState,
+    /// Review decision of an open pull request, such as "approved"
+    pub review: Option<String>,
+}
+
+/// Where a pull request stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum PullRequestState {
+    /// Open and ready for review
+    Open,
+    /// Open as a draft
+    Draft,
+    /// Merged, so the worktree can usually be removed
+    Merged,
+    /// Closed without merging
+    Closed,
+}
+
+impl fmt::Display for PullRequestStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match self.state {
+            PullRequestState::Open => "open",
+            PullRequestState::Draft => "draft",
+            PullRequestState::Merged => "merged",
+            PullRequestState::Closed => "closed",
+        };
+        write!(f, "PR #{}: {}", self.number, state)?;
+        if let Some(review) = &self.review {
+            write!(f, ", {}", review)?;
+        }
+        Ok(())
+    }
+}
+
+/// Looks up the most recent pull request of `branch` with the repository's forge CLI,
+/// reusing answers from the last few minutes (see [`crate::storage::pr_cache`]). Returns
+/// `None` when the branch has no pull request or the forge cannot be asked, e.g. because
+/// the CLI is missing, logged out, or offline.
+#[must_use]
+pub fn pull_request_status(
+    storage: &WorktreeStorage,
+    repo_path: &Path,
+    config: &WorktreeConfig,
+    branch: &str,
+) -> Option<PullRequestStatus> {
+    let key = format!("{}\0{}", repo_path.display(), branch);
+    if let Some(cached) = storage.cached_pr_state(&key) {
+        if let Ok(status) = serde_json::from_str(&cached) {
+            return status;
+        }
+    }
+
+    let remote = GitRepo::open(repo_path).ok()?.push_remote(branch)?;
+    let status = match forge_tool(config, repo_path, &remote).ok()? {
+        ForgeTool::Glab => query_glab(repo_path, branch)?,
+        _ => query_gh(repo_path, branch)?,
+    };
+    if let Ok(json) = serde_json::to_string(&status) {
+        storage.cache_pr_state(&key, &json);
+    }
+    status
+}
+
+/// Runs a forge CLI in `repo_path` and parses its JSON output. `None` if it fails.
+fn forge_json(repo_path: &Path, program: &str, args: &[&str]) -> Option<serde_json::Value> {
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(repo_path)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    serde_json::from_slice(&output.stdout).ok()
+}
+
+/// Asks `gh` for the branch's most recent pull request. The outer `None` means `gh`
+/// could not be asked; the inner one that the branch has no pull request.
+fn query_gh(repo_path: &Path, branch: &str) -> Option<Option<PullRequestStatus>> {
+    let json = forge_json(
+        repo_path,
+        "gh",
+        &[
+            "pr",
+            "list",
+            "--head",
+            branch,
+            "--state",
+            "all",
+            "--limit",
+            "1",
+            "--json",
+            "number,state,isDraft,reviewDecision",
+        ],
+    )?;
+    let Some(pr) = json.as_array()?.first() else {
+        return Some(None);
+    };
+    let state = match pr["state"].as_str()? {
+        "MERGED" => PullRequestState::Merged,
+        "CLOSED" => PullRequestState::Closed,
+        _ if pr["isDraft"].as_bool() == Some(true) => PullRequestState::Draft,
+        _ => PullRequestState::Open,
+    };
+    let review = match pr["reviewDecision"].as_str() {
+        Some("APPROVED") => Some("approved"),
+        Some("CHANGES_REQUESTED") => Some("changes requested"),
+        Some("REVIEW_REQUIRED") => Some("review required"),
+        _ => None,
+    }
+    .filter(|_| matches!(state, PullRequestState::Open | PullRequestState::Draft));
+    Some(Some(PullRequestStatus {
+        number: pr["number"].as_u64()?,
+        state,
+        review: review.map(str::to_string),
+    }))
+}
+
+/// Asks `glab` for the branch's most recent merge request, like [`query_gh`]
+fn query_glab(repo_path: &Path, branch: &str) -> Option<Option<PullRequestStatus>> {
+    let json = forge_json(
+        repo_path,
+        "glab",
+        &[
+            "mr",
+            "list",
+            "--all",
+            "--source-branch",
+            branch,
+            "--per-page",
+            "1",
+            "--output",
+            "json",
+        ],
+    )?;
+    let Some(mr) = json.as_array()?.first() else {
+        return Some(None);
+    };
+    let state = match mr["state"].as_str()? {
+        "merged" => PullRequestState::Merged,
+        "closed" | "locked" => PullRequestState::Closed,
+        _ if mr["draft"].as_bool() == Some(true) => PullRequestState::Draft,
+        _ => PullRequestState::Open,
+    };
+    Some(Some(PullRequestStatus {
+        number: mr["iid"].as_u64()?,
+        state,
+        review: None,
+    }))
+}
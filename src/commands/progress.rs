@@ -0,0 +1,170 @@
+//! Human-readable progress output for `create` and `sync-config`.
+//!
+//! [`WorktreeManager`](crate::WorktreeManager) reports what it does as
+//! [`Event`]s; [`ProgressPrinter`] writes each one to stderr as JSON when
+//! `--json-events` is on and prints the matching status line to its [`Target`].
+
+use indicatif::{ProgressBar, ProgressStyle};
+use std::cell::RefCell;
+use std::io::IsTerminal;
+
+use crate::commands::du;
+use crate::events::{self, Event};
+use crate::output::Target;
+
+/// Copies of at least this many files show a progress bar instead of one line per path
+pub const PROGRESS_MIN_FILES: u64 = 200;
+
+/// Prints the events of a manager operation, for [`WorktreeManager::on_event`]
+///
+/// [`WorktreeManager::on_event`]: crate::WorktreeManager::on_event
+#[derive(Default)]
+pub struct ProgressPrinter {
+    target: Target,
+    /// Progress bar of the config copy in progress, with the files copied so far
+    copy: RefCell<Option<(ProgressBar, u64)>>,
+}
+
+impl ProgressPrinter {
+    /// A printer writing its status lines to `target`
+    #[must_use]
+    pub fn new(target: Target) -> Self {
+        Self {
+            target,
+            copy: RefCell::default(),
+        }
+    }
+
+    /// Writes `event` as JSON if events are enabled and prints its status line
+    pub fn print(&self, event: &Event<'_>) {
+        events::emit(event);
+        match *event {
+            Event::StepStarted {
+                step: "symlink-config",
+            } => self.target.line("Creating symlinks..."),
+            Event::StepStarted {
+                step: "copy-config",
+            } => self.target.line("Copying configuration files..."),
+            Event::StepStarted {
+                step: "on-create-hooks",
+            } => self.target.line("Running post-create hooks..."),
+            Event::Symlinked { path, target } => {
+                self.target.line(format_args!(
+                    "  Symlinked: {} -> {}",
+                    path.display(),
+                    target.display()
+                ));
+            }
+            Event::CopyStarted { files, bytes } => {
+                *self.copy.borrow_mut() =
+                    copy_progress(self.target, files, bytes).map(|bar| (bar, 0));
+            }
+            Event::BytesCopied { bytes } => {
+                if let Some((bar, files)) = self.copy.borrow_mut().as_mut() {
+                    *files += 1;
+                    bar.inc(bytes);
+                    bar.set_message(format!("{} files", files));
+                }
+            }
+            Event::FileCopied { path } if self.copy.borrow().is_none() => {
+                self.target
+                    .line(format_args!("  Copied: {}", path.display()));
+            }
+            Event::DirectoryCopied { path } if self.copy.borrow().is_none() => {
+                self.target
+                    .line(format_args!("  Copied directory: {}", path.display()));
+            }
+            Event::CopyFinished { files, bytes } => {
+                if let Some((bar, _)) = self.copy.borrow_mut().take() {
+                    bar.finish_and_clear();
+                    self.target.line(format_args!(
+                        "  Copied {} files ({})",
+                        files,
+                        du::format_size(bytes)
+                    ));
+                }
+            }
+            Event::CommandStarted { step, command } => match step {
+                "lfs" => self.target.line("Fetching Git LFS files..."),
+                "dependency-dirs" => self
+                    .target
+                    .line(format_args!("Installing dependencies: {}", command)),
+                "git-hooks" => self
+                    .target
+                    .line(format_args!("Installing git hooks: {}", command)),
+                "direnv" => {}
+                _ => self.target.line(format_args!("  Running: {}", command)),
+            },
+            Event::CommandFinished { step, command } => match step {
+                "lfs" => self.target.status("✓ Git LFS files checked out"),
+                "dependency-dirs" => self.target.status("✓ Dependencies installed"),
+                "git-hooks" => self.target.status("✓ Git hooks installed"),
+                "direnv" => self.target.status("✓ Allowed .envrc with direnv"),
+                _ => self.target.status(format_args!("  ✓ Done: {}", command)),
+            },
+            Event::FileWritten { step, path } => match step {
+                "direnv" => self
+                    .target
+                    .status(format_args!("✓ Wrote {} for direnv", path.display())),
+                "devcontainer" => self.target.status(format_args!(
+                    "✓ Adapted {} for this worktree",
+                    path.display()
+                )),
+                _ => self
+                    .target
+                    .line(format_args!("  Wrote: {}", path.display())),
+            },
+            Event::ComposeProjectNamed { name, file } => {
+                self.target.status(format_args!(
+                    "✓ Compose project name: {} (in {})",
+                    name, file
+                ));
+            }
+            Event::BuildCacheStarted { dir } => {
+                self.target.line(format_args!(
+                    "Setting up shared build cache in {}",
+                    dir.display()
+                ));
+            }
+            Event::CacheLinked { path, target } => {
+                self.target.line(format_args!(
+                    "  Linked: {} -> {}",
+                    path.display(),
+                    target.display()
+                ));
+            }
+            Event::DirectoryCloned { path, files } => self.target.status(format_args!(
+                "✓ Cloned {} ({} files hard-linked)",
+                path.display(),
+                files
+            )),
+            Event::HooksShared { dir } => {
+                self.target
+                    .status(format_args!("✓ Git hooks shared from {}", dir.display()));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A progress bar on stderr for copies of at least [`PROGRESS_MIN_FILES`] files, in
+/// place of the per-path lines. `None` when stderr is not a terminal, `--json-events`
+/// is writing events there, or progress output is discarded.
+fn copy_progress(target: Target, files: u64, bytes: u64) -> Option<ProgressBar> {
+    if files < PROGRESS_MIN_FILES
+        || target == Target::Discard
+        || !std::io::stderr().is_terminal()
+        || events::enabled()
+    {
+        return None;
+    }
+
+    let progress = ProgressBar::new(bytes);
+    if let Ok(style) =
+        ProgressStyle::with_template("  {bar:30} {bytes}/{total_bytes}, {msg} of {prefix}")
+    {
+        progress.set_style(style);
+    }
+    progress.set_prefix(files.to_string());
+    Some(progress)
+}
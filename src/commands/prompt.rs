@@ -0,0 +1,174 @@
+use std::path::{Path, PathBuf};
+
+use crate::commands::back::determine_current_worktree;
+use crate::git;
+use crate::storage::WorktreeStorage;
+
+/// Format used by `worktree prompt` without `--format`
+pub const DEFAULT_FORMAT: &str = "{marker} {repo}:{branch}{dirty}";
+
+/// Marker for a worktree managed by this tool
+pub const WORKTREE_MARKER: &str = "⎇";
+
+/// Marker for a main (origin) repository
+pub const ORIGIN_MARKER: &str = "⌂";
+
+/// What the prompt shows about the current location
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptInfo {
+    pub repo: String,
+    /// Feature name when inside a managed worktree, `None` in a main repository
+    pub feature: Option<String>,
+    /// Checked-out branch, or the short commit id when HEAD is detached
+    pub branch: String,
+    /// Whether there are uncommitted changes, when that was checked
+    pub dirty: Option<bool>,
+}
+
+impl PromptInfo {
+    /// Renders `format`, replacing `{repo}`, `{feature}`, `{branch}`, `{kind}`
+    /// (`worktree` or `origin`), `{marker}`, and `{dirty}` (`*` when dirty)
+    #[must_use]
+    pub fn render(&self, format: &str) -> String {
+        let (kind, marker) = if self.feature.is_some() {
+            ("worktree", WORKTREE_MARKER)
+        } else {
+            ("origin", ORIGIN_MARKER)
+        };
+        format
+            .replace("{repo}", &self.repo)
+            .replace("{feature}", self.feature.as_deref().unwrap_or_default())
+            .replace("{branch}", &self.branch)
+            .replace("{kind}", kind)
+            .replace("{marker}", marker)
+            .replace("{dirty}", if self.dirty == Some(true) { "*" } else { "" })
+    }
+}
+
+/// Prints prompt information for the current directory, or nothing outside a git
+/// repository
+///
+/// Only the storage layout and the repository's `HEAD` file are read, so this is cheap
+/// enough to run on every prompt. `check_dirty` adds a status check, which costs as
+/// much as `git status`.
+pub fn print_prompt(format: &str, check_dirty: bool) {
+    let Ok(current_dir) = std::env::current_dir() else {
+        return;
+    };
+    let storage = WorktreeStorage::new().ok();
+    if let Some(info) = prompt_info(&current_dir, storage.as_ref(), check_dirty) {
+        println!("{}", info.render(format));
+    }
+}
+
+/// Collects prompt information for `dir`, or `None` outside a git repository
+#[must_use]
+pub fn prompt_info(
+    dir: &Path,
+    storage: Option<&WorktreeStorage>,
+    check_dirty: bool,
+) -> Option<PromptInfo> {
+    let (top_level, git_dir) = find_git_dir(dir)?;
+    let branch = read_head(&git_dir)?;
+
+    let managed = storage.and_then(|storage| determine_current_worktree(dir, storage).ok());
+    let (repo, feature) = match managed {
+        Some((repo, feature)) => (repo, Some(feature)),
+        None => (WorktreeStorage::get_repo_name(&top_level).ok()?, None),
+    };
+
+    let dirty = check_dirty.then(|| {
+        git::open_backend(&top_level)
+            .and_then(|backend| backend.is_dirty(&top_level))
+            .unwrap_or(false)
+    });
+
+    Some(PromptInfo {
+        repo,
+        feature,
+        branch,
+        dirty,
+    })
+}
+
+/// Finds the working tree containing `dir` and its git directory, following the
+/// `gitdir:` pointer in a worktree's `.git` file
+fn find_git_dir(dir: &Path) -> Option<(PathBuf, PathBuf)> {
+    for candidate in dir.ancestors() {
+        let dot_git = candidate.join(".git");
+        if dot_git.is_dir() {
+            return Some((candidate.to_path_buf(), dot_git));
+        }
+        if dot_git.is_file() {
+            let content = std::fs::read_to_string(&dot_git).ok()?;
+            let git_dir = Path::new(content.strip_prefix("gitdir:")?.trim());
+            return Some((candidate.to_path_buf(), candidate.join(git_dir)));
+        }
+    }
+    None
+}
+
+/// Reads the branch name from `HEAD`, or the short commit id when it is detached
+fn read_head(git_dir: &Path) -> Option<String> {
+    let head = std::fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+    match head.strip_prefix("ref: ") {
+        Some(reference) => Some(
+            reference
+                .strip_prefix("refs/heads/")
+                .unwrap_or(reference)
+                .to_string(),
+        ),
+        None => Some(head.chars().take(7).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_render_placeholders() {
+        let mut info = PromptInfo {
+            repo: "app".to_string(),
+            feature: Some("auth".to_string()),
+            branch: "feature/auth".to_string(),
+            dirty: Some(true),
+        };
+        assert_eq!(info.render(DEFAULT_FORMAT), "⎇ app:feature/auth*");
+        assert_eq!(info.render("{kind} {repo}/{feature}"), "worktree app/auth");
+
+        info.feature = None;
+        info.dirty = None;
+        assert_eq!(info.render(DEFAULT_FORMAT), "⌂ app:feature/auth");
+    }
+
+    #[test]
+    fn test_head_of_worktree_and_detached_repo() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let main_git = tmp.path().join("main").join(".git");
+        let worktree_git = main_git.join("worktrees").join("auth");
+        std::fs::create_dir_all(&worktree_git)?;
+        std::fs::write(main_git.join("HEAD"), "0123456789abcdef\n")?;
+        std::fs::write(worktree_git.join("HEAD"), "ref: refs/heads/feature/auth\n")?;
+
+        let worktree = tmp.path().join("auth");
+        std::fs::create_dir_all(worktree.join("src"))?;
+        std::fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", worktree_git.display()),
+        )?;
+
+        let info = prompt_info(&worktree.join("src"), None, false);
+        assert_eq!(
+            info.map(|info| (info.repo, info.branch)),
+            Some(("auth".to_string(), "feature/auth".to_string()))
+        );
+
+        let info = prompt_info(&tmp.path().join("main"), None, false);
+        assert_eq!(info.map(|info| info.branch), Some("0123456".to_string()));
+        Ok(())
+    }
+}
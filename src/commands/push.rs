@@ -0,0 +1,80 @@
+use anyhow::Result;
+
+use crate::commands::{back, jump};
+use crate::config::WorktreeConfig;
+use crate::git::{GitError, GitRepo};
+use crate::output;
+use crate::selection::RealSelectionProvider;
+use crate::storage::{WorktreeStorage, read_worktree_head_branch};
+
+/// Pushes a worktree's branch to its remote (its upstream, else `origin`) and sets the
+/// upstream when missing. With `force_with_lease`, rewritten history is pushed as long as
+/// nobody else pushed to the remote branch since it was last fetched. Without a target,
+/// pushes the worktree containing the current directory.
+///
+/// # Errors
+/// Returns an error if the worktree is not found or has no branch, a protected branch
+/// would be force-pushed, or the push fails or is rejected.
+pub fn push_worktree(target: Option<&str>, force_with_lease: bool) -> Result<()> {
+    let storage = WorktreeStorage::new()?;
+    let path = match target {
+        Some(target) => jump::resolve_worktree(
+            &storage,
+            Some(target),
+            false,
+            None,
+            &[],
+            &RealSelectionProvider,
+        )?,
+        None => back::current_worktree_path(&storage)?,
+    };
+    let branch = read_worktree_head_branch(&path)
+        .ok_or_else(|| anyhow::anyhow!("No branch is checked out in {}", path.display()))?;
+    let git_repo = GitRepo::open(&path)?;
+
+    if force_with_lease {
+        let config = WorktreeConfig::load_from_repo(git_repo.get_repo_path())?;
+        let protected = config.protected_branches(git_repo.default_branch().as_deref());
+        anyhow::ensure!(
+            !protected.is_protected(&branch),
+            "Refusing to force-push protected branch {}",
+            branch
+        );
+    }
+
+    println!(
+        "Pushing {}{}...",
+        branch,
+        if force_with_lease {
+            " (force with lease)"
+        } else {
+            ""
+        }
+    );
+    let published = git_repo
+        .publish_branch(&branch, force_with_lease)
+        .map_err(|e| match &e {
+            GitError::PushFailed { source, .. }
+                if source.code() == git2::ErrorCode::NotFastForward =>
+            {
+                anyhow::Error::new(e).context(format!(
+                    "The remote branch has commits that {} lacks; pull them in first, or use \
+                     --force-with-lease if you rewrote its history",
+                    branch
+                ))
+            }
+            _ => e.into(),
+        })?;
+    output::status(format_args!(
+        "✓ Pushed {} to {}",
+        branch, published.remote_branch
+    ));
+    if published.upstream_set {
+        output::status(format_args!(
+            "✓ Set upstream to {}",
+            published.remote_branch
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,234 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use crate::config::{self, WorktreeConfig};
+use crate::git::GitRepo;
+use crate::output;
+use crate::provision::build_cache::relocate_build_cache;
+use crate::storage::history::HISTORY_DIR;
+use crate::storage::lock::LOCK_FILE_NAME;
+use crate::storage::{BARE_REPO_DIR, WorktreeStorage};
+
+/// Moves the whole storage root to `new_root` and keeps every worktree working
+///
+/// Renames the root, or copies it and removes the original when `new_root` is on
+/// another filesystem. Then rewrites the git links between each worktree and its main
+/// repository, points origins, history entries, and `[build-cache]` links and files
+/// inside the old root at the new one, and records `storage-root` in the global config. When `WORKTREE_STORAGE_ROOT` chose
+/// the old root, the config is left alone and the variable has to be updated instead.
+///
+/// # Errors
+/// Returns an error if:
+/// - Failed to access the storage system
+/// - `new_root` exists and is not an empty directory, or lies inside the current root
+/// - The storage root cannot be moved
+/// - The global config cannot be written
+pub fn relocate_storage(new_root: &Path) -> Result<()> {
+    let storage = WorktreeStorage::new()?;
+    let old_root = storage
+        .get_root_dir()
+        .canonicalize()
+        .context("Failed to resolve the storage root")?;
+    let new_root = absolute_target(new_root)?;
+
+    if new_root.starts_with(&old_root) {
+        anyhow::bail!(
+            "{} is inside the current storage root {}",
+            new_root.display(),
+            old_root.display()
+        );
+    }
+    if new_root.exists()
+        && std::fs::read_dir(&new_root)
+            .with_context(|| format!("Failed to read {}", new_root.display()))?
+            .next()
+            .is_some()
+    {
+        anyhow::bail!("{} exists and is not empty", new_root.display());
+    }
+
+    println!("🚚 Relocating storage");
+    println!("   From: {}", old_root.display());
+    println!("   To:   {}", new_root.display());
+    println!();
+
+    {
+        let _lock = storage.lock()?;
+        move_dir(&old_root, &new_root)?;
+    }
+    // The lock file moved along while held; nobody owns the copy
+    let _ = std::fs::remove_file(new_root.join(LOCK_FILE_NAME));
+    output::status("✓ Moved storage root");
+
+    let storage = WorktreeStorage::with_root_dir(new_root.clone())?;
+    let moved = |path: &Path| {
+        path.strip_prefix(&old_root)
+            .ok()
+            .map(|rest| new_root.join(rest))
+    };
+
+    let mut repaired = 0;
+    let mut cache_updates = 0;
+    for (repo_name, mut features) in storage.list_all_worktrees()? {
+        features.sort();
+        for feature_name in features {
+            let origin = storage.get_worktree_origin(&repo_name, &feature_name)?;
+            let main_repo = match origin.as_deref().map(Path::new) {
+                Some(origin) => match moved(origin) {
+                    Some(new_origin) => {
+                        storage.set_worktree_origin(
+                            &repo_name,
+                            &feature_name,
+                            &new_origin.to_string_lossy(),
+                        )?;
+                        new_origin
+                    }
+                    None => origin.to_path_buf(),
+                },
+                None => storage.get_repo_storage_dir(&repo_name).join(BARE_REPO_DIR),
+            };
+
+            let worktree_path = storage.get_worktree_path(&repo_name, &feature_name);
+            let result = GitRepo::open(&main_repo)
+                .map_err(anyhow::Error::from)
+                .and_then(|repo| Ok(repo.repair_worktree_links(&feature_name, &worktree_path)?));
+            match result {
+                Ok(_) => repaired += 1,
+                Err(e) => warn!("Could not repair {}/{}: {}", repo_name, feature_name, e),
+            }
+
+            let result = WorktreeConfig::load_from_repo(&main_repo)
+                .map_err(anyhow::Error::from)
+                .and_then(|config| {
+                    relocate_build_cache(&config.build_cache, &worktree_path, &old_root, &new_root)
+                });
+            match result {
+                Ok(updated) => cache_updates += updated,
+                Err(e) => warn!(
+                    "Could not update the build cache links of {}/{}: {:#}",
+                    repo_name, feature_name, e
+                ),
+            }
+        }
+    }
+    output::status(format_args!("✓ Repaired {} worktree(s)", repaired));
+    if cache_updates > 0 {
+        output::status(format_args!(
+            "✓ Pointed {} build cache link(s) and file(s) at the new root",
+            cache_updates
+        ));
+    }
+
+    rewrite_history(&new_root.join(HISTORY_DIR), &old_root, &new_root);
+    storage.invalidate_completion_cache();
+
+    let new_root_str = new_root.to_string_lossy();
+    if std::env::var_os("WORKTREE_STORAGE_ROOT").is_some_and(|root| !root.is_empty()) {
+        println!();
+        output::status(format_args!(
+            "⚠ WORKTREE_STORAGE_ROOT is set; point it at {} to use the new location",
+            new_root_str
+        ));
+    } else {
+        let config_path = config::set_global_value("storage-root", &new_root_str)?;
+        output::status(format_args!(
+            "✓ Set storage-root in {}",
+            config_path.display()
+        ));
+    }
+
+    println!();
+    println!("✅ Storage now lives in {}", new_root.display());
+    Ok(())
+}
+
+/// Resolves `path` against the current directory and its nearest existing ancestor,
+/// so it can be compared with the canonical storage root
+fn absolute_target(path: &Path) -> Result<PathBuf> {
+    let path = std::env::current_dir()?.join(path);
+    let mut existing = path.as_path();
+    let mut rest = Vec::new();
+    while !existing.exists() {
+        let (Some(parent), Some(name)) = (existing.parent(), existing.file_name()) else {
+            return Ok(path);
+        };
+        rest.push(name);
+        existing = parent;
+    }
+    let mut resolved = existing.canonicalize()?;
+    resolved.extend(rest.iter().rev());
+    Ok(resolved)
+}
+
+/// Renames `source` to `target`, falling back to copying and removing the original when
+/// they are on different filesystems
+fn move_dir(source: &Path, target: &Path) -> Result<()> {
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    if target.exists() {
+        std::fs::remove_dir(target)
+            .with_context(|| format!("Failed to replace {}", target.display()))?;
+    }
+    match std::fs::rename(source, target) {
+        Ok(()) => return Ok(()),
+        Err(e) if e.raw_os_error() == Some(libc::EXDEV) => {}
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to move {}", source.display()));
+        }
+    }
+
+    println!("   {} is on another filesystem; copying", target.display());
+    if let Err(e) = copy_tree(source, target) {
+        let _ = std::fs::remove_dir_all(target);
+        return Err(e).with_context(|| format!("Failed to copy to {}", target.display()));
+    }
+    std::fs::remove_dir_all(source)
+        .with_context(|| format!("Copied, but failed to remove {}", source.display()))
+}
+
+/// Copies a directory tree, recreating symlinks instead of following them
+fn copy_tree(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(target)?;
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        let source_path = entry.path();
+        let target_path = target.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            std::os::unix::fs::symlink(std::fs::read_link(&source_path)?, &target_path)?;
+        } else if file_type.is_dir() {
+            copy_tree(&source_path, &target_path)?;
+        } else {
+            std::fs::copy(&source_path, &target_path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Points `back` history entries inside the old root at the new one
+fn rewrite_history(history_dir: &Path, old_root: &Path, new_root: &Path) {
+    let Ok(entries) = std::fs::read_dir(history_dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let rewritten: String = content
+            .lines()
+            .map(|line| match Path::new(line).strip_prefix(old_root) {
+                Ok(rest) => format!("{}\n", new_root.join(rest).display()),
+                Err(_) => format!("{}\n", line),
+            })
+            .collect();
+        if rewritten != content {
+            if let Err(e) = std::fs::write(&path, rewritten) {
+                warn!("Could not update history {}: {}", path.display(), e);
+            }
+        }
+    }
+}
@@ -1,161 +1,577 @@
 use anyhow::{Context, Result};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use tracing::warn;
 
+use crate::commands::{archive, back, completion, jump, workspace};
+use crate::config::{GlobalConfig, WorktreeConfig};
+use crate::error::WorktreeError;
 use crate::git::GitRepo;
-use crate::selection::{RealSelectionProvider, SelectionProvider};
+use crate::manager::{WorktreeManager, find_origin_repo};
+use crate::output;
+use crate::selection::{self, RealSelectionProvider, SelectionProvider};
 use crate::storage::{WorktreeStorage, read_worktree_head_branch};
 
-/// Removes a worktree, preserving branches by default
+/// Options for `worktree remove`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemoveOptions {
+    /// Also delete the branch checked out in the worktree. `None` uses `delete-branch`
+    /// from the `[remove]` config section.
+    pub delete_branch: Option<bool>,
+    /// Delete the branch even if it has commits that exist nowhere else or is protected;
+    /// implies `delete_branch`
+    pub force_delete_branch: bool,
+    /// Also delete the branch on its remote. `None` uses `delete-remote` from the
+    /// `[remove]` config section.
+    pub delete_remote: Option<bool>,
+    /// Pick the worktree interactively even if a target was given
+    pub interactive: bool,
+    /// Print worktree names for shell completion instead of removing anything
+    pub list_completions: bool,
+    /// Without targets, remove the worktree containing the current directory. With
+    /// `interactive` or `list_completions`, limit choices to the current repository.
+    pub current: bool,
+    /// Archive the worktree's files first so it can be brought back with `restore`
+    pub archive: bool,
+    /// Remove worktrees of this repository in storage instead of the current one (same
+    /// as `repo/name` targets)
+    pub repo: Option<String>,
+}
+
+/// Removes worktrees, preserving branches by default. Deleting a branch with
+/// unpushed commits requires confirmation (or `force_delete_branch`); protected branches
+/// are only deleted with `force_delete_branch`.
+///
+/// With no targets (or `interactive`), the worktree is picked interactively. With
+/// several targets, each is removed in turn and a summary is printed at the end.
 ///
 /// # Errors
-/// Returns an error if the target worktree doesn't exist, storage access fails,
-/// git operations fail, or the worktree directory cannot be removed.
-pub fn remove_worktree(
-    target: Option<&str>,
-    delete_branch: bool,
-    interactive: bool,
-    list_completions: bool,
-    current_repo_only: bool,
-) -> Result<()> {
-    remove_worktree_with_provider(
-        target,
-        delete_branch,
-        interactive,
-        list_completions,
-        current_repo_only,
-        &RealSelectionProvider,
-    )
+/// Returns an error if a target worktree doesn't exist, storage access fails,
+/// git operations fail, or a worktree directory cannot be removed. With several
+/// targets, the others are still removed before the error is returned.
+pub fn remove_worktree(targets: &[String], options: &RemoveOptions) -> Result<()> {
+    remove_worktree_with_provider(targets, options, &RealSelectionProvider)
 }
 
-/// Removes a worktree with a custom selection provider (for testing)
+/// Removes worktrees with a custom selection provider (for testing)
 ///
 /// # Errors
-/// Returns an error if the target worktree doesn't exist, storage access fails,
-/// git operations fail, or the worktree directory cannot be removed.
+/// Returns an error if a target worktree doesn't exist, storage access fails,
+/// git operations fail, or a worktree directory cannot be removed.
 pub fn remove_worktree_with_provider(
-    target: Option<&str>,
-    delete_branch: bool,
-    interactive: bool,
-    list_completions: bool,
-    current_repo_only: bool,
+    targets: &[String],
+    options: &RemoveOptions,
     provider: &dyn SelectionProvider,
 ) -> Result<()> {
+    if options.list_completions {
+        return list_worktree_completions(options.current);
+    }
+
     let storage = WorktreeStorage::new()?;
 
-    if list_completions {
-        list_worktree_completions(&storage, current_repo_only)?;
-        return Ok(());
+    if options.current && !options.interactive && targets.is_empty() {
+        return remove_current_worktree(&storage, options, provider);
     }
 
+    let (repo, targets) = split_repo_targets(&storage, options.repo.as_deref(), targets)?;
+    let git_repo = open_target_repo(&storage, repo.as_deref())?;
+    let manager = WorktreeManager::with_storage(&git_repo, storage.clone())?;
+
+    let removal = Removal {
+        manager: &manager,
+        options,
+        provider,
+    };
+
+    match targets.as_slice() {
+        _ if options.interactive || targets.is_empty() => {
+            let (worktree_path, feature_name) =
+                select_worktree_for_removal(&storage, options.current, repo.as_deref(), provider)?;
+            removal.remove(&worktree_path, &feature_name)
+        }
+        [target] => {
+            let resolved = resolve_target(&manager, target)?;
+            if !removal.confirm(std::slice::from_ref(&resolved))? {
+                return Ok(());
+            }
+            removal.remove(&resolved.0, &resolved.1)
+        }
+        _ => remove_many(&removal, &targets),
+    }
+}
+
+/// Strips `repo/` prefixes from the targets and returns the one repository they (and
+/// `--repo`) name, if any
+fn split_repo_targets(
+    storage: &WorktreeStorage,
+    repo: Option<&str>,
+    targets: &[String],
+) -> Result<(Option<String>, Vec<String>)> {
+    let mut repo = repo.map(str::to_string);
+    let mut names = Vec::new();
+    for target in targets {
+        let (target_repo, name) = storage.split_repo_target(target);
+        if let Some(target_repo) = target_repo {
+            match &repo {
+                Some(existing) if existing != target_repo => anyhow::bail!(
+                    "Targets name several repositories ({} and {}); remove them in separate commands",
+                    existing,
+                    target_repo
+                ),
+                _ => repo = Some(target_repo.to_string()),
+            }
+        }
+        names.push(name.to_string());
+    }
+    Ok((repo, names))
+}
+
+/// Opens the repository to remove worktrees from: `repo` if given (located through
+/// its worktrees' recorded origins unless it is the current one), else the current one
+fn open_target_repo(storage: &WorktreeStorage, repo: Option<&str>) -> Result<GitRepo> {
     let current_dir = std::env::current_dir()?;
-    let git_repo = GitRepo::open(&current_dir)?;
-    let repo_path = git_repo.get_repo_path();
-    let repo_name = WorktreeStorage::get_repo_name(repo_path)?;
-
-    let (worktree_path, feature_name) = if interactive || target.is_none() {
-        select_worktree_for_removal(&storage, current_repo_only, provider)?
-    } else if let Some(target_str) = target {
-        resolve_target(target_str, &storage, &repo_name)?
-    } else {
-        anyhow::bail!("No target specified for worktree removal");
+    let Some(repo_name) = repo else {
+        return Ok(GitRepo::open(&current_dir)?);
     };
 
-    if !worktree_path.exists() {
-        anyhow::bail!("Worktree path does not exist: {}", worktree_path.display());
+    if let Ok(git_repo) = GitRepo::open(&current_dir) {
+        if WorktreeStorage::get_repo_name(git_repo.get_repo_path())
+            .ok()
+            .as_deref()
+            == Some(repo_name)
+        {
+            return Ok(git_repo);
+        }
+    }
+    find_origin_repo(storage, repo_name).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Could not locate the main repository of '{}' from its recorded worktree origins",
+            repo_name
+        )
+    })
+}
+
+/// Removes the worktree containing the current directory, then prints the origin
+/// repository path so the shell integration can `cd` back to it
+fn remove_current_worktree(
+    storage: &WorktreeStorage,
+    options: &RemoveOptions,
+    provider: &dyn SelectionProvider,
+) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let (repo_name, feature_name) = back::determine_current_worktree(&current_dir, storage)
+        .context("remove --current must be run from inside a managed worktree")?;
+    let worktree_path = storage.get_worktree_path(&repo_name, &feature_name);
+
+    // Prefer the recorded origin; fall back to the main repository git knows about
+    let origin_path = match storage.get_worktree_origin(&repo_name, &feature_name)? {
+        Some(origin) if Path::new(&origin).is_dir() => PathBuf::from(origin),
+        _ => {
+            let worktree_repo = GitRepo::open(&worktree_path)?;
+            worktree_repo
+                .common_dir()
+                .parent()
+                .context("Could not determine the main repository of this worktree")?
+                .to_path_buf()
+        }
+    };
+
+    let git_repo = GitRepo::open(&origin_path)?;
+    let manager = WorktreeManager::with_storage(&git_repo, storage.clone())?;
+
+    let removal = Removal {
+        manager: &manager,
+        options,
+        provider,
+    };
+    if !removal.confirm(&[(worktree_path.clone(), feature_name.clone())])? {
+        return Ok(());
+    }
+    removal.remove(&worktree_path, &feature_name)?;
+
+    println!("↩ Origin repository: {}", origin_path.display());
+
+    Ok(())
+}
+
+/// Removes several worktrees, continuing past failures, and prints a summary
+fn remove_many(removal: &Removal<'_>, targets: &[String]) -> Result<()> {
+    let mut resolved: Vec<(PathBuf, String)> = Vec::new();
+    let mut failed: Vec<(String, String)> = Vec::new();
+
+    for target in targets {
+        match resolve_target(removal.manager, target) {
+            Ok((path, feature_name)) => {
+                if !resolved.iter().any(|(_, f)| *f == feature_name) {
+                    resolved.push((path, feature_name));
+                }
+            }
+            Err(e) => failed.push((target.clone(), format!("{:#}", e))),
+        }
+    }
+
+    if !resolved.is_empty() && !removal.confirm(&resolved)? {
+        return Ok(());
+    }
+
+    let mut removed = Vec::new();
+    for (worktree_path, feature_name) in resolved {
+        match removal.remove(&worktree_path, &feature_name) {
+            Ok(()) => removed.push(feature_name),
+            Err(e) => {
+                warn!("Could not remove {}: {:#}", feature_name, e);
+                failed.push((feature_name, format!("{:#}", e)));
+            }
+        }
+        println!();
     }
 
     println!(
-        "Removing worktree '{}': {}",
-        feature_name,
-        worktree_path.display()
+        "Summary: removed {} of {} worktree(s)",
+        removed.len(),
+        removed.len() + failed.len()
     );
+    for feature_name in &removed {
+        output::status(format_args!("  ✓ {}", feature_name));
+    }
+    for (target, reason) in &failed {
+        output::status(format_args!("  ✗ {}: {}", target, reason));
+    }
+
+    if !failed.is_empty() {
+        anyhow::bail!("Failed to remove {} worktree(s)", failed.len());
+    }
+
+    Ok(())
+}
 
-    // Read current branch from worktree HEAD before removing it
-    let current_branch = read_worktree_head_branch(&worktree_path);
+/// Everything needed to remove one worktree of the current repository
+struct Removal<'a> {
+    manager: &'a WorktreeManager<&'a GitRepo>,
+    options: &'a RemoveOptions,
+    provider: &'a dyn SelectionProvider,
+}
 
-    // Use the feature name (directory name) as the worktree name for git
-    let worktree_name = worktree_path
-        .file_name()
-        .and_then(|name| name.to_str())
-        .unwrap_or(&feature_name);
+impl Removal<'_> {
+    /// Whether branches are deleted, before asking about unpushed commits
+    fn delete_branch(&self) -> bool {
+        self.options.force_delete_branch
+            || self
+                .options
+                .delete_branch
+                .unwrap_or(self.manager.config().remove.delete_branch)
+    }
 
-    // Remove the filesystem directory first
-    if worktree_path.exists() {
-        fs::remove_dir_all(&worktree_path).context("Failed to remove worktree directory")?;
+    /// Whether `branch` is kept despite [`delete_branch`](Self::delete_branch) because it
+    /// is protected. Only `force_delete_branch` deletes protected branches.
+    fn keeps_protected(&self, branch: &str) -> bool {
+        !self.options.force_delete_branch
+            && self
+                .manager
+                .config()
+                .protected_branches(self.manager.git().default_branch().as_deref())
+                .is_protected(branch)
     }
 
-    git_repo
-        .remove_worktree(worktree_name)
-        .context("Failed to remove worktree from git")?;
+    /// Lists the worktrees about to be removed and asks for confirmation
+    fn confirm(&self, worktrees: &[(PathBuf, String)]) -> Result<bool> {
+        let delete_branch = self.delete_branch();
+        let delete_remote = self
+            .options
+            .delete_remote
+            .unwrap_or(self.manager.config().remove.delete_remote);
+
+        let mut summary = vec!["The following worktree(s) will be removed:".to_string()];
+        for (path, feature_name) in worktrees {
+            let branch_info = match read_worktree_head_branch(path) {
+                Some(branch) if delete_branch && self.keeps_protected(&branch) => {
+                    format!("; keeping protected branch {}", branch)
+                }
+                Some(branch) if delete_branch && delete_remote => {
+                    format!("; deleting branch {} locally and on its remote", branch)
+                }
+                Some(branch) if delete_branch => format!("; deleting branch {}", branch),
+                Some(branch) if delete_remote => {
+                    format!("; deleting branch {} on its remote", branch)
+                }
+                Some(branch) => format!("; keeping branch {}", branch),
+                None => String::new(),
+            };
+            summary.push(format!(
+                "   • {} ({}{})",
+                feature_name,
+                path.display(),
+                branch_info
+            ));
+        }
 
-    // Clean up origin information
-    if let Err(e) = storage.remove_worktree_origin(&repo_name, &feature_name) {
-        println!("⚠ Warning: Failed to clean up origin information: {}", e);
+        let prompt = format!("Remove {} worktree(s)?", worktrees.len());
+        let confirmed = selection::confirm(self.provider, &summary, &prompt)?;
+        if !confirmed {
+            println!("Cancelled — nothing was removed.");
+        }
+        Ok(confirmed)
     }
 
-    // Delete branch only when explicitly requested via --delete-branch
-    if delete_branch {
-        if let Some(branch) = &current_branch {
-            println!("Deleting branch: {}", branch);
-            match git_repo.delete_branch(branch) {
-                Ok(_) => println!("✓ Branch deleted successfully"),
-                Err(e) => println!("⚠ Warning: Failed to delete branch: {}", e),
+    fn remove(&self, worktree_path: &Path, feature_name: &str) -> Result<()> {
+        if !worktree_path.exists() {
+            return Err(WorktreeError::WorktreeMissing {
+                path: worktree_path.to_path_buf(),
             }
-        } else {
-            println!("⚠ Warning: Could not determine branch to delete (detached HEAD or error)");
+            .into());
         }
-    } else if let Some(branch) = &current_branch {
+
         println!(
-            "Branch '{}' preserved (use --delete-branch to remove it)",
-            branch
+            "Removing worktree '{}': {}",
+            feature_name,
+            worktree_path.display()
         );
-    }
 
-    println!("✓ Worktree removed successfully!");
+        // Read current branch from worktree HEAD before removing it
+        let current_branch = read_worktree_head_branch(worktree_path);
+
+        // Decide about the branch before anything is removed, so declining aborts cleanly
+        let mut delete_branch = self.delete_branch();
+        let mut protected = false;
+        if delete_branch && !self.options.force_delete_branch {
+            if let Some(branch) = &current_branch {
+                if self.keeps_protected(branch) {
+                    protected = true;
+                    delete_branch = false;
+                } else {
+                    delete_branch =
+                        confirm_branch_deletion(self.manager.git(), branch, self.provider)?;
+                }
+            }
+        }
 
-    Ok(())
+        // Use the feature name (directory name) as the worktree name for git
+        let worktree_name = worktree_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(feature_name);
+
+        // Archive before anything is deleted; a failed archive aborts the removal
+        if self.options.archive {
+            let archive_path = archive::create_archive(
+                self.manager.storage(),
+                self.manager.repo_name(),
+                feature_name,
+                worktree_path,
+                self.manager.config(),
+            )
+            .context("Failed to archive worktree; nothing was removed")?;
+            println!("📦 Archived to {}", archive_path.display());
+        }
+
+        // Remove the filesystem directory first
+        if worktree_path.exists() {
+            fs::remove_dir_all(worktree_path).context("Failed to remove worktree directory")?;
+        }
+
+        self.manager
+            .git()
+            .remove_worktree(worktree_name)
+            .context("Failed to remove worktree from git")?;
+
+        // Clean up origin information
+        if let Err(e) = self
+            .manager
+            .storage()
+            .remove_worktree_origin(self.manager.repo_name(), feature_name)
+        {
+            warn!("Failed to clean up origin information: {}", e);
+        }
+        if let Err(e) = self
+            .manager
+            .storage()
+            .release_slot(self.manager.repo_name(), feature_name)
+        {
+            warn!("Failed to release worktree slot: {}", e);
+        }
+        if let Err(e) = self
+            .manager
+            .storage()
+            .forget_activity(self.manager.repo_name(), feature_name)
+        {
+            warn!("Failed to clean up activity information: {}", e);
+        }
+        if let Err(e) = self
+            .manager
+            .storage()
+            .forget_labels(self.manager.repo_name(), feature_name)
+        {
+            warn!("Failed to clean up worktree labels: {}", e);
+        }
+        if let Err(e) = self
+            .manager
+            .storage()
+            .forget_note(self.manager.repo_name(), feature_name)
+        {
+            warn!("Failed to clean up worktree note: {}", e);
+        }
+        if let Err(e) = self
+            .manager
+            .storage()
+            .forget_copied_files(self.manager.repo_name(), feature_name)
+        {
+            warn!("Failed to clean up copied file manifest: {}", e);
+        }
+        workspace::refresh_vscode_workspace(
+            self.manager.storage(),
+            self.manager.repo_name(),
+            self.manager.repo_path(),
+        );
+
+        // The remote branch goes first: deleting the local branch drops its upstream config
+        if self
+            .options
+            .delete_remote
+            .unwrap_or(self.manager.config().remove.delete_remote)
+        {
+            match &current_branch {
+                Some(branch) => {
+                    delete_remote_branch(self.manager.git(), self.manager.config(), branch)
+                }
+                None => {
+                    warn!("Could not determine remote branch to delete (detached HEAD or error)")
+                }
+            }
+        }
+
+        // Delete branch only when requested (--delete-branch or the [remove] config)
+        if delete_branch {
+            if let Some(branch) = &current_branch {
+                println!("Deleting branch: {}", branch);
+                match self.manager.git().delete_branch(branch) {
+                    Ok(()) => {
+                        output::status("✓ Branch deleted successfully");
+                        if let Err(e) = self
+                            .manager
+                            .storage()
+                            .forget_managed_branch(self.manager.repo_name(), branch)
+                        {
+                            warn!("Failed to update managed branches: {}", e);
+                        }
+                    }
+                    Err(e) => warn!("Failed to delete branch: {}", e),
+                }
+            } else {
+                warn!("Could not determine branch to delete (detached HEAD or error)");
+            }
+        } else if let Some(branch) = &current_branch {
+            if protected {
+                warn!(
+                    "Not deleting branch '{}': it is protected (use --force-delete-branch to delete it)",
+                    branch
+                );
+            } else {
+                println!(
+                    "Branch '{}' preserved (use --delete-branch to remove it)",
+                    branch
+                );
+            }
+        }
+
+        output::status("✓ Worktree removed successfully!");
+
+        Ok(())
+    }
 }
 
-fn resolve_target(
-    target: &str,
-    storage: &WorktreeStorage,
-    repo_name: &str,
-) -> Result<(PathBuf, String)> {
-    // Match by feature name (directory name) directly
-    let worktree_path = storage.get_worktree_path(repo_name, target);
-    if worktree_path.exists() {
-        return Ok((worktree_path, target.to_string()));
+/// Checks `branch` for commits that exist only locally before it is deleted. Returns
+/// whether to go ahead with the deletion; when there are such commits the user chooses
+/// to push first, delete anyway, or keep the branch.
+fn confirm_branch_deletion(
+    git_repo: &GitRepo,
+    branch: &str,
+    provider: &dyn SelectionProvider,
+) -> Result<bool> {
+    let problem = match git_repo.unpushed_commits(branch) {
+        Ok(0) => return Ok(true),
+        Ok(n) => format!("Branch '{}' has {} unpushed commit(s)", branch, n),
+        Err(e) => format!(
+            "Could not check branch '{}' for unpushed commits ({})",
+            branch, e
+        ),
+    };
+
+    let push_remote = git_repo.push_remote(branch);
+    let push_option = push_remote
+        .as_ref()
+        .map(|remote| format!("Push {} to {}, then delete it", branch, remote));
+    let delete_option = format!("Delete {} anyway (unpushed commits will be lost)", branch);
+    let keep_option = format!("Keep {}", branch);
+
+    let mut options: Vec<String> = push_option.iter().cloned().collect();
+    options.push(delete_option.clone());
+    options.push(keep_option);
+
+    output::status(format_args!("⚠ Warning: {}", problem));
+    let selection = provider
+        .select("What should happen to the branch?", options)
+        .with_context(|| {
+            format!(
+                "{}. Re-run with --force-delete-branch to delete it anyway, or without \
+                 --delete-branch to keep it. Nothing was removed",
+                problem
+            )
+        })?;
+
+    if Some(&selection) == push_option.as_ref() {
+        let pushed = git_repo
+            .push_branch(branch)
+            .with_context(|| format!("Failed to push {}; nothing was removed", branch))?;
+        output::status(format_args!("✓ Pushed {} to {}", branch, pushed));
+        Ok(true)
+    } else {
+        Ok(selection == delete_option)
     }
+}
 
-    // Try partial match against known worktrees
-    let known = storage.list_repo_worktrees(repo_name)?;
-    let matches: Vec<&String> = known.iter().filter(|name| name.contains(target)).collect();
+/// Deletes the remote counterpart of `branch`, refusing protected branches. Failures
+/// are reported as warnings since the worktree is already gone.
+fn delete_remote_branch(git_repo: &GitRepo, config: &WorktreeConfig, branch: &str) {
+    let protected = config.protected_branches(git_repo.default_branch().as_deref());
+    if protected.is_protected(branch) {
+        warn!(
+            "Not deleting remote branch for '{}': it is protected",
+            branch
+        );
+        return;
+    }
 
-    match matches.len() {
-        0 => anyhow::bail!("No worktree found matching '{}'", target),
-        1 => {
-            let feature_name = matches[0].clone();
-            let path = storage.get_worktree_path(repo_name, &feature_name);
-            Ok((path, feature_name))
+    match git_repo.delete_remote_branch(branch) {
+        Ok(Some(remote_branch)) => {
+            output::status(format_args!("✓ Deleted remote branch {}", remote_branch))
         }
-        _ => {
-            eprintln!(
-                "Multiple worktrees match '{}'. Please be more specific:",
-                target
-            );
-            for name in &matches {
-                eprintln!("  {}", name);
+        Ok(None) => println!("ℹ️  No remote branch found for '{}'", branch),
+        Err(e) => warn!("Failed to delete remote branch: {:#}", e),
+    }
+}
+
+fn resolve_target(manager: &WorktreeManager<&GitRepo>, target: &str) -> Result<(PathBuf, String)> {
+    match manager.find_with(target, GlobalConfig::load()?.match_mode) {
+        Ok(info) => Ok((info.path, info.feature_name)),
+        Err(e) => {
+            if let Some(WorktreeError::AmbiguousWorktree { candidates, .. }) =
+                WorktreeError::find(&e)
+            {
+                eprintln!(
+                    "Multiple worktrees match '{}'. Please be more specific:",
+                    target
+                );
+                for name in candidates {
+                    eprintln!("  {}", name);
+                }
             }
-            anyhow::bail!("Ambiguous worktree name '{}'", target);
+            Err(e)
         }
     }
 }
 
-fn list_worktree_completions(storage: &WorktreeStorage, current_repo_only: bool) -> Result<()> {
-    let worktrees = get_available_worktrees(storage, current_repo_only)?;
-
-    for (_, feature_name, _) in worktrees {
+fn list_worktree_completions(current_repo_only: bool) -> Result<()> {
+    for feature_name in completion::feature_names(current_repo_only, None)? {
         println!("{}", feature_name);
     }
 
@@ -165,29 +581,18 @@ fn list_worktree_completions(storage: &WorktreeStorage, current_repo_only: bool)
 fn select_worktree_for_removal(
     storage: &WorktreeStorage,
     current_repo_only: bool,
+    repo: Option<&str>,
     provider: &dyn SelectionProvider,
 ) -> Result<(PathBuf, String)> {
-    let worktrees = get_available_worktrees(storage, current_repo_only)?;
+    let worktrees = jump::get_available_worktrees(storage, current_repo_only, repo)?;
 
     if worktrees.is_empty() {
         anyhow::bail!("No worktrees found");
     }
 
-    // Format: "feature-name (current-branch)  /path"
     let options: Vec<String> = worktrees
         .iter()
-        .map(|(repo, feature_name, path)| {
-            let branch_info = read_worktree_head_branch(path)
-                .map(|b| format!(" ({})", b))
-                .unwrap_or_default();
-            format!(
-                "{}/{}{} ({})",
-                repo,
-                feature_name,
-                branch_info,
-                path.display()
-            )
-        })
+        .map(|(repo, feature_name, path)| jump::picker_option(storage, repo, feature_name, path))
         .collect();
 
     let selection = provider.select("Select worktree to remove:", options.clone())?;
@@ -201,37 +606,87 @@ fn select_worktree_for_removal(
     Ok((path.clone(), feature_name.clone()))
 }
 
-fn get_available_worktrees(
-    storage: &WorktreeStorage,
-    current_repo_only: bool,
-) -> Result<Vec<(String, String, PathBuf)>> {
-    let mut worktrees = Vec::new();
-
-    if current_repo_only {
-        let current_dir = std::env::current_dir()?;
-        if let Ok(git_repo) = GitRepo::open(&current_dir) {
-            let repo_path = git_repo.get_repo_path();
-            let repo_name = WorktreeStorage::get_repo_name(repo_path)?;
-
-            let repo_worktrees = storage.list_repo_worktrees(&repo_name)?;
-            for feature_name in repo_worktrees {
-                let worktree_path = storage.get_worktree_path(&repo_name, &feature_name);
-                if worktree_path.exists() {
-                    worktrees.push((repo_name.clone(), feature_name, worktree_path));
-                }
-            }
-        }
-    } else {
-        let all_worktrees = storage.list_all_worktrees()?;
-        for (repo_name, repo_worktrees) in all_worktrees {
-            for feature_name in repo_worktrees {
-                let worktree_path = storage.get_worktree_path(&repo_name, &feature_name);
-                if worktree_path.exists() {
-                    worktrees.push((repo_name.clone(), feature_name, worktree_path));
-                }
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::selection::MockSelectionProvider;
+    use tempfile::TempDir;
+    use test_support::git;
+
+    /// A repository with an `origin` remote and a local `feature` branch one commit
+    /// ahead of `main`
+    fn setup() -> Result<(TempDir, GitRepo, PathBuf)> {
+        let tmp = TempDir::new()?;
+        let repo = tmp.path().join("repo");
+        let remote = tmp.path().join("remote.git");
+        std::fs::create_dir(&repo)?;
+        git(&repo, &["init", "-b", "main"])?;
+        git(&repo, &["config", "user.name", "Test User"])?;
+        git(&repo, &["config", "user.email", "test@example.com"])?;
+        git(&repo, &["commit", "--allow-empty", "-m", "init"])?;
+        git(tmp.path(), &["init", "--bare", "remote.git"])?;
+        git(
+            &repo,
+            &["remote", "add", "origin", &remote.to_string_lossy()],
+        )?;
+        git(&repo, &["push", "origin", "main"])?;
+        git(&repo, &["checkout", "-b", "feature"])?;
+        git(&repo, &["commit", "--allow-empty", "-m", "work"])?;
+        git(&repo, &["checkout", "main"])?;
+        let git_repo = GitRepo::open(&repo)?;
+        Ok((tmp, git_repo, remote))
+    }
+
+    #[test]
+    fn test_confirm_branch_deletion_pushes_first() -> Result<()> {
+        let (_tmp, git_repo, remote) = setup()?;
+        let provider = MockSelectionProvider::new("Push feature to origin, then delete it");
+
+        assert!(confirm_branch_deletion(&git_repo, "feature", &provider)?);
+        assert!(!git(&remote, &["branch", "--list", "feature"])?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_confirm_branch_deletion_keep_or_delete() -> Result<()> {
+        let (_tmp, git_repo, _remote) = setup()?;
+
+        let keep = MockSelectionProvider::new("Keep feature");
+        assert!(!confirm_branch_deletion(&git_repo, "feature", &keep)?);
+
+        let delete =
+            MockSelectionProvider::new("Delete feature anyway (unpushed commits will be lost)");
+        assert!(confirm_branch_deletion(&git_repo, "feature", &delete)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_removal_confirm_respects_answer() -> Result<()> {
+        let (tmp, git_repo, _remote) = setup()?;
+        let storage = WorktreeStorage::with_root_dir(tmp.path().join("worktrees"))?;
+        let manager = WorktreeManager::with_storage(&git_repo, storage)?;
+        let options = RemoveOptions::default();
+        let worktrees = [(tmp.path().join("worktrees/repo/feat"), "feat".to_string())];
+
+        for (answer, expected) in [("n", false), ("y", true)] {
+            let provider = MockSelectionProvider::new(answer);
+            let removal = Removal {
+                manager: &manager,
+                options: &options,
+                provider: &provider,
+            };
+            assert_eq!(removal.confirm(&worktrees)?, expected);
         }
+        Ok(())
     }
 
-    Ok(worktrees)
+    #[test]
+    fn test_confirm_branch_deletion_skips_prompt_when_pushed() -> Result<()> {
+        let (_tmp, git_repo, _remote) = setup()?;
+        // A provider that fails if asked proves no prompt was shown
+        let provider = MockSelectionProvider::new("not an option");
+
+        assert!(confirm_branch_deletion(&git_repo, "main", &provider)?);
+        Ok(())
+    }
 }
@@ -0,0 +1,107 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use tracing::warn;
+
+use crate::git::GitRepo;
+use crate::output;
+use crate::storage::WorktreeStorage;
+use crate::storage::meta::META_FILE_NAME;
+
+/// Repairs git links and stored origins after the storage root or the main repository
+/// has moved
+///
+/// Rewrites each managed worktree's `.git` file and its `gitdir`/`commondir` pointers in
+/// the main repository, and points `.worktree-origins` entries whose path no longer
+/// exists at the main repository's current location. Worktrees without a
+/// `.worktree-meta.toml` get one.
+///
+/// # Errors
+/// Returns an error if:
+/// - Not run from inside a git repository
+/// - Failed to access the storage system
+pub fn repair_worktrees() -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let git_repo =
+        GitRepo::open(&current_dir).context("Run 'worktree repair' from the main repository")?;
+
+    let main_repo_path = git_repo
+        .common_dir()
+        .parent()
+        .context("Could not determine the main repository directory")?
+        .canonicalize()?;
+
+    let storage = WorktreeStorage::new()?;
+    let repo_name = WorktreeStorage::get_repo_name(&main_repo_path)?;
+
+    println!("🔧 Repairing worktrees for {}", repo_name);
+    println!("   Repository: {}", main_repo_path.display());
+    println!(
+        "   Storage: {}",
+        storage.get_repo_storage_dir(&repo_name).display()
+    );
+    println!();
+
+    let mut repaired_links = 0;
+    let mut features = storage.list_repo_worktrees(&repo_name)?;
+    features.sort();
+
+    for feature_name in &features {
+        let worktree_path = storage.get_worktree_path(&repo_name, feature_name);
+        match git_repo.repair_worktree_links(feature_name, &worktree_path) {
+            Ok(true) => {
+                output::status(format_args!("✓ Repaired git links: {}", feature_name));
+                repaired_links += 1;
+            }
+            Ok(false) => {}
+            Err(e) => warn!("Could not repair {}: {}", feature_name, e),
+        }
+    }
+
+    let updated_origins = repair_origins(&storage, &repo_name, &main_repo_path)?;
+
+    // Worktrees from before per-worktree metadata existed get their file now
+    for feature_name in &features {
+        let worktree_path = storage.get_worktree_path(&repo_name, feature_name);
+        if !worktree_path.join(META_FILE_NAME).exists() {
+            if let Err(e) = storage.write_worktree_meta(&repo_name, feature_name) {
+                warn!("Could not write metadata of {}: {}", feature_name, e);
+            }
+        }
+    }
+
+    println!();
+    if repaired_links == 0 && updated_origins == 0 {
+        println!("✨ All worktree links are intact. Nothing to repair.");
+    } else {
+        println!("✅ Repair complete!");
+        println!("   Repaired {} worktree link(s)", repaired_links);
+        println!("   Updated {} origin entry(ies)", updated_origins);
+    }
+
+    Ok(())
+}
+
+/// Points origin entries whose path no longer exists at the main repository.
+/// Returns how many entries were updated.
+fn repair_origins(
+    storage: &WorktreeStorage,
+    repo_name: &str,
+    main_repo_path: &Path,
+) -> Result<usize> {
+    let new_origin = main_repo_path.to_string_lossy();
+    let mut updated = 0;
+
+    for (feature_name, origin) in storage.list_worktree_origins(repo_name)? {
+        if Path::new(&origin).exists() {
+            continue;
+        }
+        storage.set_worktree_origin(repo_name, &feature_name, &new_origin)?;
+        output::status(format_args!(
+            "✓ Updated origin for {}: {} -> {}",
+            feature_name, origin, new_origin
+        ));
+        updated += 1;
+    }
+
+    Ok(updated)
+}
@@ -0,0 +1,42 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use crate::commands::back::determine_current_worktree;
+use crate::git::GitRepo;
+use crate::storage::WorktreeStorage;
+
+/// Prints the main repository path for the current directory
+///
+/// Inside a managed worktree this is the origin recorded by `create` (the same one
+/// `back` uses); anywhere else it is the main repository git resolves for the
+/// directory.
+///
+/// # Errors
+/// Returns an error if the current directory is neither a managed worktree with a
+/// recorded origin nor inside a git repository
+pub fn print_root() -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let storage = WorktreeStorage::new()?;
+    println!("{}", find_root(&current_dir, &storage)?.display());
+    Ok(())
+}
+
+/// Resolves the main repository for `dir`, preferring the stored origin of a managed
+/// worktree over git's view
+///
+/// # Errors
+/// Returns an error if `dir` is neither a managed worktree with an existing origin nor
+/// inside a git repository
+pub fn find_root(dir: &Path, storage: &WorktreeStorage) -> Result<PathBuf> {
+    if let Ok((repo_name, feature_name)) = determine_current_worktree(dir, storage) {
+        if let Some(origin) = storage.get_worktree_origin(&repo_name, &feature_name)? {
+            let origin = PathBuf::from(origin);
+            if origin.is_dir() {
+                return Ok(origin);
+            }
+        }
+    }
+
+    let git_repo = GitRepo::open(dir).context("Not inside a git repository or managed worktree")?;
+    Ok(git_repo.get_repo_path().to_path_buf())
+}
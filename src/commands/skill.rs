@@ -3,9 +3,12 @@ use clap::Subcommand;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::output;
+
 const EMBEDDED_SKILL: &str = include_str!("../../assets/skill/SKILL.md");
 
 #[derive(Subcommand, Clone)]
+#[non_exhaustive]
 pub enum SkillAction {
     /// Install the worktree-manager agent skill into your coding agent
     Install,
@@ -186,7 +189,7 @@ pub fn skill_status() -> Result<()> {
         println!("✅ Skill is installed and up to date.");
         println!("   Location: {}", skill_file()?.display());
     } else {
-        println!("⚠️  Skill is installed but an update is available.");
+        output::status("⚠️  Skill is installed but an update is available.");
         println!("   Location: {}", skill_file()?.display());
         println!("   Run `worktree skill update` to apply the update.");
     }
@@ -1,78 +1,456 @@
 use anyhow::Result;
+use serde::Serialize;
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
-use crate::git::GitRepo;
-use crate::storage::WorktreeStorage;
+use crate::commands::cleanup::{self, CleanupItem};
+use crate::commands::{list, pr};
+use crate::config::WorktreeConfig;
+use crate::git::{self, GitRepo};
+use crate::output;
+use crate::storage::activity::{Activity, format_age};
+use crate::storage::{WorktreeStorage, read_worktree_head_branch, unix_now};
+use crate::traits::{AheadBehind, GitOperations};
 
-/// Shows the status of all worktrees in the current repository
+/// Everything `worktree status` reports about the current repository
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct StatusReport {
+    /// Repository name (its storage directory)
+    pub repo: String,
+    /// Main repository path
+    pub repo_path: PathBuf,
+    /// Worktrees managed by this tool or registered in git, by feature name
+    pub worktrees: Vec<WorktreeStatus>,
+    /// Inconsistencies between storage, git, and the filesystem
+    pub warnings: Vec<StatusWarning>,
+    /// Days without activity after which a worktree counts as stale, `None` when the
+    /// check is off
+    pub stale_after_days: Option<u64>,
+    /// What `worktree cleanup --merged` would remove, found without changing anything
+    pub cleanup: Vec<CleanupItem>,
+}
+
+/// One worktree in a [`StatusReport`]
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct WorktreeStatus {
+    /// Feature name (directory name under the storage root)
+    pub feature: String,
+    /// Worktree directory
+    pub path: PathBuf,
+    /// Whether the worktree is in this tool's storage
+    pub managed: bool,
+    /// Whether git knows about the worktree
+    pub registered: bool,
+    /// Whether the worktree directory exists
+    pub exists: bool,
+    /// Checked-out branch, `None` when detached or missing
+    pub branch: Option<String>,
+    /// First line of the branch description
+    pub description: Option<String>,
+    /// Whether there are uncommitted changes, when the worktree could be checked
+    pub dirty: Option<bool>,
+    /// Upstream of the branch, such as `origin/feature/auth`
+    pub upstream: Option<String>,
+    /// Commits on the branch that are not on its upstream
+    pub ahead: Option<usize>,
+    /// Commits on the upstream that are not on the branch
+    pub behind: Option<usize>,
+    /// When the worktree was created, in seconds since the Unix epoch
+    pub created: Option<u64>,
+    /// When the worktree was last used, in seconds since the Unix epoch
+    pub last_used: Option<u64>,
+    /// Latest commit, creation, or uncommitted file change, in seconds since the Unix
+    /// epoch
+    pub last_activity: Option<u64>,
+    /// Whether there was no activity for the report's `stale_after_days`
+    pub stale: bool,
+    /// Labels of the worktree
+    pub labels: Vec<String>,
+    /// Note attached to the worktree
+    pub note: Option<String>,
+    /// Most recent pull request of the branch, when asked for
+    pub pull_request: Option<pr::PullRequestStatus>,
+}
+
+/// An inconsistency `status` found
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum StatusWarning {
+    /// Registered in git but not in this tool's storage
+    Unmanaged { feature: String },
+    /// In this tool's storage but not registered in git (`cleanup` prunes it)
+    Unregistered { feature: String },
+    /// Registered in git, but the directory is gone (`cleanup` prunes it)
+    Missing { feature: String },
+}
+
+impl fmt::Display for StatusWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unmanaged { feature } => {
+                write!(f, "{} is registered in git but not managed", feature)
+            }
+            Self::Unregistered { feature } => write!(
+                f,
+                "{} is managed but not registered in git (run `worktree cleanup`)",
+                feature
+            ),
+            Self::Missing { feature } => write!(
+                f,
+                "{} is registered in git but its directory is gone (run `worktree cleanup`)",
+                feature
+            ),
+        }
+    }
+}
+
+/// Options for `worktree status`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatusOptions {
+    /// Show each branch's pull request state (also on with `show-status` in the `[pr]`
+    /// config)
+    pub show_pr: bool,
+    /// Print the [`StatusReport`] as JSON instead of text
+    pub json: bool,
+    /// Keep redrawing the report at this interval until interrupted
+    pub watch: Option<Duration>,
+    /// Days without activity after which a worktree is stale, overriding
+    /// `stale-after-days` in the `[status]` config (0 turns the check off)
+    pub stale_days: Option<u64>,
+}
+
+/// Shows the status of all worktrees in the current repository.
 ///
 /// # Errors
 /// Returns an error if:
 /// - Not in a git repository
 /// - Failed to access storage system
 /// - Git operations fail
-pub fn show_status() -> Result<()> {
+pub fn show_status(options: &StatusOptions) -> Result<()> {
     let current_dir = std::env::current_dir()?;
-    let git_repo = GitRepo::open(&current_dir)?;
-    let repo_path = git_repo.get_repo_path();
+    if let Some(interval) = options.watch {
+        return watch_status(&current_dir, options, interval);
+    }
+
+    let report = collect_status(&current_dir, options)?;
+    if options.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+    Ok(())
+}
+
+/// Clears the terminal and redraws the report every `interval`, or as soon as the
+/// repository's storage directory changes (creating or removing a worktree, or any
+/// metadata update), until interrupted
+fn watch_status(dir: &Path, options: &StatusOptions, interval: Duration) -> Result<()> {
+    const POLL: Duration = Duration::from_millis(250);
 
     let storage = WorktreeStorage::new()?;
-    let repo_name = WorktreeStorage::get_repo_name(repo_path)?;
+    let repo_name = WorktreeStorage::get_repo_name(&git::open_backend(dir)?.get_repo_path())?;
+    let repo_dir = storage.get_repo_storage_dir(&repo_name);
+    let modified = || {
+        std::fs::metadata(&repo_dir)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    };
 
-    println!("Git Worktree Status");
-    println!("{}", "=".repeat(40));
-    println!("Repository: {}", repo_name);
-    println!("Repository path: {}", repo_path.display());
-    println!();
+    loop {
+        let report = collect_status(dir, options)?;
+        let seen = modified();
+        print!("\x1b[2J\x1b[H");
+        println!("Every {}s (Ctrl-C to stop)\n", interval.as_secs());
+        print_report(&report);
+        std::io::stdout().flush()?;
+
+        let started = Instant::now();
+        while started.elapsed() < interval && modified() == seen {
+            std::thread::sleep(POLL);
+        }
+    }
+}
+
+/// Gathers the [`StatusReport`] of the repository containing `dir`. Of `options`, only
+/// `show_pr` and `stale_days` matter.
+///
+/// # Errors
+/// Returns an error if `dir` is not in a git repository, or storage or git cannot be read
+pub fn collect_status(dir: &Path, options: &StatusOptions) -> Result<StatusReport> {
+    let git_repo = git::open_backend(dir)?;
+    let repo_path = git_repo.get_repo_path();
+
+    let storage = WorktreeStorage::new()?;
+    let repo_name = WorktreeStorage::get_repo_name(&repo_path)?;
+    let config = WorktreeConfig::load_from_repo(&repo_path)?;
+    let show_pr = options.show_pr || config.pr.show_status;
+    let stale_after_days = match options.stale_days {
+        Some(0) => None,
+        Some(days) => Some(days),
+        None => config.status.stale_after_days(),
+    };
+    let now = unix_now();
 
     let git_worktrees = git_repo.list_worktrees()?;
     let managed_worktrees = storage.list_repo_worktrees(&repo_name)?;
+    let labels = storage.list_labels(&repo_name)?;
+    let notes = storage.list_notes(&repo_name)?;
+
+    let mut features: Vec<&String> = managed_worktrees.iter().chain(&git_worktrees).collect();
+    features.sort();
+    features.dedup();
+
+    let mut worktrees = Vec::new();
+    let mut warnings = Vec::new();
+    for feature in features {
+        let path = storage.get_worktree_path(&repo_name, feature);
+        let managed = managed_worktrees.contains(feature);
+        let registered = git_worktrees.contains(feature);
+        let exists = path.exists();
+        let feature_name = feature.clone();
+        if !exists {
+            warnings.push(StatusWarning::Missing {
+                feature: feature_name,
+            });
+        } else if !managed {
+            warnings.push(StatusWarning::Unmanaged {
+                feature: feature_name,
+            });
+        } else if !registered {
+            warnings.push(StatusWarning::Unregistered {
+                feature: feature_name,
+            });
+        }
 
-    println!("Git worktrees ({}):", git_worktrees.len());
-    for worktree in &git_worktrees {
-        let worktree_path = storage.get_worktree_path(&repo_name, worktree);
-        let managed = if managed_worktrees.contains(worktree) {
-            "📁"
+        let branch = exists.then(|| read_worktree_head_branch(&path)).flatten();
+        let description = branch
+            .as_deref()
+            .and_then(|branch| git::branch_description(&repo_path, branch))
+            .and_then(|d| d.lines().next().map(str::to_string));
+        let (dirty, upstream, ahead_behind) = if exists && registered {
+            branch_state(git_repo.as_ref(), &path, branch.as_deref())
+        } else {
+            (None, None, None)
+        };
+        let activity = if managed {
+            storage.worktree_activity(&repo_name, feature)
         } else {
-            "⚠"
+            Activity::default()
         };
-        let exists = if worktree_path.exists() { "✓" } else { "✗" };
+        let last_activity =
+            (exists && registered).then(|| activity.last_activity(git_repo.as_ref(), &path));
+        let stale = managed
+            && matches!((stale_after_days, last_activity), (Some(days), Some(time))
+                if now.saturating_sub(time) >= days * 86_400);
+        let pull_request = branch
+            .as_deref()
+            .filter(|_| show_pr && managed)
+            .and_then(|branch| pr::pull_request_status(&storage, &repo_path, &config, branch));
 
-        println!(
-            "  {} {} {} ({})",
+        worktrees.push(WorktreeStatus {
+            feature: feature.clone(),
+            path,
             managed,
+            registered,
             exists,
-            worktree,
-            worktree_path.display()
-        );
+            branch,
+            description,
+            dirty,
+            upstream,
+            ahead: ahead_behind.map(|ab| ab.ahead),
+            behind: ahead_behind.map(|ab| ab.behind),
+            created: activity.created,
+            last_used: activity.last_used,
+            last_activity,
+            stale,
+            labels: labels
+                .get(feature)
+                .map(|labels| labels.iter().cloned().collect())
+                .unwrap_or_default(),
+            note: notes.get(feature).cloned(),
+            pull_request,
+        });
     }
 
+    let cleanup = match GitRepo::open(dir) {
+        Ok(repo) => cleanup::preview(&repo, &storage, &repo_name, dir),
+        Err(_) => Vec::new(),
+    };
+
+    Ok(StatusReport {
+        repo: repo_name,
+        repo_path,
+        worktrees,
+        warnings,
+        stale_after_days,
+        cleanup,
+    })
+}
+
+/// Uncommitted changes, upstream, and ahead/behind counts of a worktree's branch
+fn branch_state(
+    git_repo: &dyn GitOperations,
+    path: &Path,
+    branch: Option<&str>,
+) -> (Option<bool>, Option<String>, Option<AheadBehind>) {
+    let dirty = git_repo.is_dirty(path).ok();
+    let upstream = branch.and_then(|branch| git_repo.upstream(branch).ok().flatten());
+    let ahead_behind = branch.and_then(|branch| git_repo.ahead_behind(branch).ok().flatten());
+    (dirty, upstream, ahead_behind)
+}
+
+fn print_report(report: &StatusReport) {
+    println!("Git Worktree Status");
+    println!("{}", "=".repeat(40));
+    println!("Repository: {}", report.repo);
+    println!("Repository path: {}", report.repo_path.display());
     println!();
-    println!("Managed worktrees ({}):", managed_worktrees.len());
-    for worktree in &managed_worktrees {
-        let worktree_path = storage.get_worktree_path(&repo_name, worktree);
-        let in_git = if git_worktrees.contains(worktree) {
-            "🔗"
-        } else {
-            "⚠"
-        };
-        let exists = if worktree_path.exists() { "✓" } else { "✗" };
 
+    let registered: Vec<&WorktreeStatus> =
+        report.worktrees.iter().filter(|w| w.registered).collect();
+    println!("Git worktrees ({}):", registered.len());
+    for worktree in registered {
         println!(
             "  {} {} {} ({})",
-            in_git,
-            exists,
-            worktree,
-            worktree_path.display()
+            if worktree.managed { "📁" } else { "⚠" },
+            if worktree.exists { "✓" } else { "✗" },
+            worktree.feature,
+            worktree.path.display()
         );
     }
 
+    let managed: Vec<&WorktreeStatus> = report.worktrees.iter().filter(|w| w.managed).collect();
+    println!();
+    println!("Managed worktrees ({}):", managed.len());
+    for worktree in managed {
+        println!(
+            "  {} {} {} ({})",
+            if worktree.registered { "🔗" } else { "⚠" },
+            if worktree.exists { "✓" } else { "✗" },
+            worktree.feature,
+            worktree.path.display()
+        );
+        if let Some(description) = &worktree.description {
+            println!("      {}", description);
+        }
+        print_branch_state(worktree);
+        list::print_activity(&Activity {
+            created: worktree.created,
+            last_used: worktree.last_used,
+        });
+        list::print_note(worktree.note.as_deref());
+        if let Some(status) = &worktree.pull_request {
+            println!("      {}", status);
+        }
+    }
+
+    if !report.warnings.is_empty() {
+        println!();
+        println!("Warnings:");
+        for warning in &report.warnings {
+            output::status(format_args!("  ⚠ {}", warning));
+        }
+    }
+
+    print_stale(report);
+    print_cleanup_preview(&report.cleanup);
+
     println!();
     println!("Legend:");
     println!("  📁 = Managed by this tool");
     println!("  🔗 = Registered in git");
-    println!("  ✓ = Directory exists");
-    println!("  ✗ = Directory missing");
-    println!("  ⚠ = Inconsistent state");
+    output::status("  ✓ = Directory exists");
+    output::status("  ✗ = Directory missing");
+    output::status("  ⚠ = Inconsistent state");
+}
 
-    Ok(())
+/// Prints the stale worktrees with their last activity and a command removing them
+fn print_stale(report: &StatusReport) {
+    let Some(days) = report.stale_after_days else {
+        return;
+    };
+    let stale: Vec<&WorktreeStatus> = report.worktrees.iter().filter(|w| w.stale).collect();
+    if stale.is_empty() {
+        return;
+    }
+
+    println!();
+    println!(
+        "Stale worktrees ({}, no commits or file changes in {}+ days):",
+        stale.len(),
+        days
+    );
+    let width = stale.iter().map(|w| w.feature.len()).max().unwrap_or(0);
+    for worktree in &stale {
+        let age = worktree.last_activity.map_or_else(String::new, |time| {
+            format!("last activity {}", format_age(time))
+        });
+        println!("  {:<width$}  {}", worktree.feature, age, width = width);
+    }
+    let features: Vec<String> = stale.iter().map(|w| shell_word(&w.feature)).collect();
+    println!("  Remove them with: worktree remove {}", features.join(" "));
+}
+
+/// Prints what `cleanup` would remove, with the command that removes it
+fn print_cleanup_preview(items: &[CleanupItem]) {
+    if items.is_empty() {
+        return;
+    }
+    println!();
+    println!("Cleanup preview ({}):", items.len());
+    for item in items {
+        println!("  • {}", item);
+    }
+    let merged = items
+        .iter()
+        .any(|item| matches!(item, CleanupItem::MergedWorktree { .. }));
+    println!(
+        "  Remove them with: worktree cleanup{} (add --dry-run to review first)",
+        if merged { " --merged" } else { "" }
+    );
+}
+
+/// Quotes `value` for a shell command line when it is not a plain word
+fn shell_word(value: &str) -> String {
+    if !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'))
+    {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+/// Prints a worktree's branch with its uncommitted changes and ahead/behind counts,
+/// like `feature/auth (uncommitted changes, 2 ahead, 1 behind origin/feature/auth)`
+fn print_branch_state(worktree: &WorktreeStatus) {
+    let Some(branch) = &worktree.branch else {
+        return;
+    };
+    let mut state = Vec::new();
+    if worktree.dirty == Some(true) {
+        state.push("uncommitted changes".to_string());
+    }
+    if let (Some(ahead), Some(behind), Some(upstream)) =
+        (worktree.ahead, worktree.behind, &worktree.upstream)
+    {
+        state.push(match (ahead, behind) {
+            (0, 0) => format!("up to date with {}", upstream),
+            (ahead, 0) => format!("{} ahead of {}", ahead, upstream),
+            (0, behind) => format!("{} behind {}", behind, upstream),
+            (ahead, behind) => format!("{} ahead, {} behind {}", ahead, behind, upstream),
+        });
+    }
+    if state.is_empty() {
+        println!("      {}", branch);
+    } else {
+        println!("      {} ({})", branch, state.join(", "));
+    }
 }
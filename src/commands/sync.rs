@@ -0,0 +1,99 @@
+use anyhow::Result;
+
+use crate::commands::update::{self, UpdateOptions, UpdateOutcome};
+use crate::git::{self, GitRepo};
+use crate::manager::WorktreeManager;
+use crate::output;
+use crate::storage::WorktreeStorage;
+
+/// Fetches once, then rebases (or merges) every worktree of the current repository onto
+/// its base branch, like `update` for each. Worktrees with uncommitted changes, in the
+/// middle of a rebase or merge, or without a branch are skipped; updates that hit
+/// conflicts are aborted. Prints a line per worktree and a summary.
+///
+/// # Errors
+/// Returns an error if the repository or storage cannot be opened, fetching fails, or
+/// any worktree could not be updated because of conflicts or a failing git command
+pub fn sync_worktrees(options: &UpdateOptions) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let manager =
+        WorktreeManager::with_storage(GitRepo::open(&current_dir)?, WorktreeStorage::new()?)?;
+
+    let worktrees = manager.list()?;
+    if worktrees.is_empty() {
+        println!("No worktrees found for {}", manager.repo_name());
+        return Ok(());
+    }
+
+    if !options.no_fetch {
+        println!("Fetching...");
+        git::fetch_all(manager.repo_path())?;
+    }
+    let strategy = update::strategy(manager.config(), options);
+
+    let mut updated = 0;
+    let mut up_to_date = 0;
+    let mut skipped = 0;
+    let mut failed = 0;
+    for worktree in worktrees {
+        let name = worktree.feature_name;
+        if !worktree.path.exists() {
+            output::status(format_args!("  ⚠ {}: skipped (directory missing)", name));
+            skipped += 1;
+            continue;
+        }
+
+        let result = update::update_target(manager.git(), &worktree.path, None).and_then(
+            |(branch, onto)| {
+                let outcome = update::update_branch(&worktree.path, &onto, strategy, false)?;
+                Ok((branch, onto, outcome))
+            },
+        );
+        match result {
+            Ok((branch, onto, UpdateOutcome::Updated)) => {
+                output::status(format_args!(
+                    "  ✓ {} ({}): updated onto {}",
+                    name, branch, onto
+                ));
+                updated += 1;
+            }
+            Ok((branch, onto, UpdateOutcome::UpToDate)) => {
+                println!("  = {} ({}): up to date with {}", name, branch, onto);
+                up_to_date += 1;
+            }
+            Ok((branch, _, UpdateOutcome::Skipped(reason))) => {
+                output::status(format_args!(
+                    "  ⚠ {} ({}): skipped ({})",
+                    name, branch, reason
+                ));
+                skipped += 1;
+            }
+            Ok((branch, onto, UpdateOutcome::Conflicted(files))) => {
+                output::status(format_args!(
+                    "  ✗ {} ({}): conflicts with {} in {}; left unchanged",
+                    name,
+                    branch,
+                    onto,
+                    files.join(", ")
+                ));
+                failed += 1;
+            }
+            Err(e) => {
+                output::status(format_args!("  ✗ {}: {:#}", name, e));
+                failed += 1;
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "Summary: {} updated, {} up to date, {} skipped, {} failed",
+        updated, up_to_date, skipped, failed
+    );
+
+    if failed > 0 {
+        anyhow::bail!("Failed to update {} worktree(s)", failed);
+    }
+
+    Ok(())
+}
@@ -1,51 +1,207 @@
 use anyhow::{Context, Result};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use tracing::warn;
 
-use crate::commands::create;
-use crate::config::WorktreeConfig;
+use crate::commands::completion;
+use crate::commands::progress::ProgressPrinter;
 use crate::git::GitRepo;
-use crate::storage::WorktreeStorage;
+use crate::manager::WorktreeManager;
+use crate::output;
+use crate::selection::{RealSelectionProvider, SelectionProvider};
+use crate::storage::{WorktreeStorage, read_worktree_head_branch};
 
-/// Synchronizes configuration files between two worktrees
+/// Synchronizes configuration files between two worktrees.
+/// A missing source or target is selected interactively. When `only` is non-empty,
+/// those patterns replace the configured include patterns.
 ///
 /// # Errors
 /// Returns an error if:
 /// - Source or target worktree doesn't exist
 /// - Failed to access storage system
+/// - Interactive selection fails or is cancelled
 /// - Failed to copy configuration files
 /// - Permission issues with file operations
-pub fn sync_config(from: &str, to: &str) -> Result<()> {
-    let current_dir = std::env::current_dir()?;
-    let git_repo = GitRepo::open(&current_dir)?;
-    let repo_path = git_repo.get_repo_path();
-
-    let storage = WorktreeStorage::new()?;
-    let repo_name = WorktreeStorage::get_repo_name(repo_path)?;
+pub fn sync_config(from: Option<&str>, to: Option<&str>, only: &[String]) -> Result<()> {
+    sync_config_with_provider(from, to, only, &RealSelectionProvider)
+}
 
-    let (from_path, _) = resolve_worktree_path(from, &storage, &repo_name)?;
-    let (to_path, _) = resolve_worktree_path(to, &storage, &repo_name)?;
+/// Synchronizes configuration files with a custom selection provider (for testing)
+///
+/// # Errors
+/// Returns an error if:
+/// - Source or target worktree doesn't exist
+/// - Failed to access storage system
+/// - Interactive selection fails or is cancelled
+/// - Failed to copy configuration files
+pub fn sync_config_with_provider(
+    from: Option<&str>,
+    to: Option<&str>,
+    only: &[String],
+    provider: &dyn SelectionProvider,
+) -> Result<()> {
+    sync_config_in(&std::env::current_dir()?, from, to, only, provider)
+}
 
-    if !from_path.exists() {
-        anyhow::bail!("Source worktree does not exist: {}", from_path.display());
-    }
+/// Like [`sync_config_with_provider`], for the repository containing `dir` instead of
+/// the current directory
+///
+/// # Errors
+/// Returns an error if `dir` is not in a git repository, or for the same reasons as
+/// [`sync_config_with_provider`]
+pub fn sync_config_in(
+    dir: &Path,
+    from: Option<&str>,
+    to: Option<&str>,
+    only: &[String],
+    provider: &dyn SelectionProvider,
+) -> Result<()> {
+    let mut manager = WorktreeManager::open(dir)?;
+    let printer = ProgressPrinter::default();
+    manager.on_event(move |event| printer.print(event));
+    let storage = manager.storage();
+    let repo_name = manager.repo_name();
+    let repo_path = manager.repo_path();
 
-    if !to_path.exists() {
-        anyhow::bail!("Target worktree does not exist: {}", to_path.display());
-    }
+    let from_path = match from {
+        Some(from) => resolve_worktree_path(from, storage, repo_name)?.0,
+        None => select_sync_endpoint(
+            storage,
+            repo_name,
+            repo_path,
+            None,
+            "Select source to sync config from:",
+            provider,
+        )?,
+    };
+    let to_path = match to {
+        Some(to) => resolve_worktree_path(to, storage, repo_name)?.0,
+        None => select_sync_endpoint(
+            storage,
+            repo_name,
+            repo_path,
+            Some(&from_path),
+            "Select target to sync config to:",
+            provider,
+        )?,
+    };
 
     println!("Syncing config files:");
     println!("  From: {}", from_path.display());
     println!("  To: {}", to_path.display());
+    if !only.is_empty() {
+        println!("  Only: {}", only.join(", "));
+    }
     println!();
 
-    let config = WorktreeConfig::load_from_repo(repo_path)?;
-    create::copy_config_files(&from_path, &to_path, &config)?;
+    let copied = manager.sync_config(&from_path, &to_path, only)?;
 
-    println!("✓ Config files synced successfully!");
+    if copied == 0 && !only.is_empty() {
+        warn!(
+            "No files matched {} in the source",
+            only.iter()
+                .map(|p| format!("'{}'", p))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    output::status("✓ Config files synced successfully!");
 
     Ok(())
 }
 
+/// Lists worktrees of the current repository for `sync-config` argument completion
+///
+/// Prints nothing when run outside a git repository.
+///
+/// # Errors
+/// Returns an error if storage access fails.
+pub fn list_worktree_completions() -> Result<()> {
+    for feature_name in current_repo_worktree_names()? {
+        println!("{}", feature_name);
+    }
+
+    Ok(())
+}
+
+/// Returns the feature names of the current repository's existing worktrees, or none
+/// outside a git repository
+///
+/// # Errors
+/// Returns an error if storage access fails.
+pub fn current_repo_worktree_names() -> Result<Vec<String>> {
+    let current_dir = std::env::current_dir()?;
+    let key = format!("repo-worktrees {}", current_dir.display());
+    completion::cached(&key, || {
+        let Ok(git_repo) = GitRepo::open(&current_dir) else {
+            return Ok(Vec::new());
+        };
+
+        let storage = WorktreeStorage::new()?;
+        let repo_name = WorktreeStorage::get_repo_name(git_repo.get_repo_path())?;
+
+        Ok(storage
+            .list_repo_worktrees(&repo_name)?
+            .into_iter()
+            .filter(|feature_name| storage.get_worktree_path(&repo_name, feature_name).exists())
+            .collect())
+    })
+}
+
+/// Prompts for a sync source or target among the main repository and its worktrees,
+/// leaving out `exclude` (the already chosen source)
+fn select_sync_endpoint(
+    storage: &WorktreeStorage,
+    repo_name: &str,
+    repo_path: &Path,
+    exclude: Option<&Path>,
+    prompt: &str,
+    provider: &dyn SelectionProvider,
+) -> Result<PathBuf> {
+    let mut candidates = vec![(
+        format!("{} [main repository] ({})", repo_name, repo_path.display()),
+        repo_path.to_path_buf(),
+    )];
+
+    for feature_name in storage.list_repo_worktrees(repo_name)? {
+        let path = storage.get_worktree_path(repo_name, &feature_name);
+        if !path.exists() {
+            continue;
+        }
+        let branch_info = read_worktree_head_branch(&path)
+            .map(|b| format!(" ({})", b))
+            .unwrap_or_default();
+        candidates.push((
+            format!(
+                "{}/{}{} ({})",
+                repo_name,
+                feature_name,
+                branch_info,
+                path.display()
+            ),
+            path,
+        ));
+    }
+
+    if let Some(exclude) = exclude {
+        candidates.retain(|(_, path)| path != exclude);
+    }
+
+    if candidates.is_empty() || (exclude.is_none() && candidates.len() < 2) {
+        anyhow::bail!("No worktrees found to sync config between");
+    }
+
+    let options: Vec<String> = candidates.iter().map(|(label, _)| label.clone()).collect();
+    let selection = provider.select(prompt, options.clone())?;
+
+    let index = options
+        .iter()
+        .position(|o| o == &selection)
+        .ok_or_else(|| anyhow::anyhow!("Selected option not found in list"))?;
+
+    Ok(candidates[index].1.clone())
+}
+
 fn resolve_worktree_path(
     target: &str,
     storage: &WorktreeStorage,
@@ -65,3 +221,71 @@ fn resolve_worktree_path(
     let worktree_path = storage.get_worktree_path(repo_name, target);
     Ok((worktree_path, target.to_string()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::selection::MockSelectionProvider;
+    use tempfile::TempDir;
+
+    fn setup() -> Result<(TempDir, WorktreeStorage, PathBuf)> {
+        let tmp = TempDir::new()?;
+        let storage = WorktreeStorage::with_root_dir(tmp.path().join("worktrees"))?;
+        let repo_path = tmp.path().join("myrepo");
+        std::fs::create_dir_all(&repo_path)?;
+        for feature in ["auth", "payments"] {
+            std::fs::create_dir_all(storage.get_worktree_path("myrepo", feature))?;
+        }
+        Ok((tmp, storage, repo_path))
+    }
+
+    #[test]
+    fn test_select_sync_endpoint_offers_main_repo_and_worktrees() -> Result<()> {
+        let (_tmp, storage, repo_path) = setup()?;
+        let auth_path = storage.get_worktree_path("myrepo", "auth");
+        let provider = MockSelectionProvider::new(format!("myrepo/auth ({})", auth_path.display()));
+
+        let selected =
+            select_sync_endpoint(&storage, "myrepo", &repo_path, None, "Source:", &provider)?;
+        assert_eq!(selected, auth_path);
+
+        let provider = MockSelectionProvider::new(format!(
+            "myrepo [main repository] ({})",
+            repo_path.display()
+        ));
+        let selected =
+            select_sync_endpoint(&storage, "myrepo", &repo_path, None, "Source:", &provider)?;
+        assert_eq!(selected, repo_path);
+        Ok(())
+    }
+
+    #[test]
+    fn test_select_sync_endpoint_excludes_source() -> Result<()> {
+        let (_tmp, storage, repo_path) = setup()?;
+        let auth_path = storage.get_worktree_path("myrepo", "auth");
+        let provider = MockSelectionProvider::new(format!("myrepo/auth ({})", auth_path.display()));
+
+        let result = select_sync_endpoint(
+            &storage,
+            "myrepo",
+            &repo_path,
+            Some(&auth_path),
+            "Target:",
+            &provider,
+        );
+        assert!(result.is_err(), "source should not be offered as a target");
+        Ok(())
+    }
+
+    #[test]
+    fn test_select_sync_endpoint_requires_two_locations() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = WorktreeStorage::with_root_dir(tmp.path().join("worktrees"))?;
+        let provider = MockSelectionProvider::new("anything");
+
+        let result =
+            select_sync_endpoint(&storage, "myrepo", tmp.path(), None, "Source:", &provider);
+        assert!(result.is_err());
+        Ok(())
+    }
+}
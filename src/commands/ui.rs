@@ -0,0 +1,754 @@
+//! `worktree ui`: a terminal interface listing every worktree with its details.
+//!
+//! The interface draws on stderr, so stdout stays free for the path of the worktree to
+//! jump to, as with `jump`. Actions that print or prompt (create, sync-config, opening
+//! an editor) leave the interface, run the regular command, and come back. Removal is
+//! confirmed inside the interface and keeps the worktree's branch.
+
+use anyhow::Result;
+use ratatui::Frame;
+use ratatui::Terminal;
+use ratatui::backend::CrosstermBackend;
+use ratatui::crossterm::cursor::Show;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{
+    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use std::collections::HashMap;
+use std::io::{BufRead, Stderr};
+use std::path::{Path, PathBuf};
+
+use crate::commands::{create, jump, open, sync_config};
+use crate::git;
+use crate::manager::{WorktreeManager, find_origin_repo};
+use crate::selection::{RealSelectionProvider, fuzzy_score};
+use crate::storage::activity::format_age;
+use crate::storage::frecency::sort_by_frecency;
+use crate::storage::{WorktreeStorage, read_worktree_head_branch};
+
+/// Key help shown at the bottom of the interface
+const HELP: &str =
+    "enter jump · o open · c create · d remove · s sync-config · / filter · r refresh · q quit";
+
+/// One worktree in the list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UiEntry {
+    pub repo: String,
+    pub feature: String,
+    pub path: PathBuf,
+    pub branch: Option<String>,
+    pub labels: Vec<String>,
+    pub note: Option<String>,
+    pub created: Option<u64>,
+    pub last_used: Option<u64>,
+}
+
+impl UiEntry {
+    /// The text the filter matches against: `repo/feature branch labels`
+    fn search_text(&self) -> String {
+        format!(
+            "{}/{} {} {}",
+            self.repo,
+            self.feature,
+            self.branch.as_deref().unwrap_or_default(),
+            self.labels.join(" ")
+        )
+    }
+}
+
+/// What the keyboard is currently typing into
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    /// Moving through the list and triggering actions
+    Normal,
+    /// Typing the fuzzy filter
+    Filter,
+    /// Typing the feature name of a new worktree
+    Create(String),
+    /// Asking whether to remove the selected worktree
+    ConfirmRemove,
+}
+
+/// What the event loop should do after a key press
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    None,
+    Quit,
+    Refresh,
+    Jump(PathBuf),
+    Open(PathBuf),
+    Create {
+        repo: Option<String>,
+        feature: String,
+    },
+    Remove {
+        repo: String,
+        feature: String,
+    },
+    SyncConfig {
+        path: PathBuf,
+        feature: String,
+    },
+}
+
+/// State of the interface, independent of the terminal
+#[derive(Debug)]
+pub struct App {
+    entries: Vec<UiEntry>,
+    filter: String,
+    /// Indices into `entries` of the rows shown, best filter match first
+    visible: Vec<usize>,
+    list_state: ListState,
+    mode: Mode,
+    message: Option<String>,
+    /// Branch state lines by worktree path, computed when first selected
+    details: HashMap<PathBuf, String>,
+}
+
+impl App {
+    /// Creates the interface state for `entries`, in the order given
+    #[must_use]
+    pub fn new(entries: Vec<UiEntry>) -> Self {
+        let mut app = Self {
+            entries,
+            filter: String::new(),
+            visible: Vec::new(),
+            list_state: ListState::default(),
+            mode: Mode::Normal,
+            message: None,
+            details: HashMap::new(),
+        };
+        app.apply_filter();
+        app
+    }
+
+    /// The selected worktree, if any row is shown
+    #[must_use]
+    pub fn selected(&self) -> Option<&UiEntry> {
+        let row = self.list_state.selected()?;
+        self.visible.get(row).map(|&index| &self.entries[index])
+    }
+
+    /// The worktrees shown, in display order
+    pub fn visible(&self) -> impl Iterator<Item = &UiEntry> {
+        self.visible.iter().map(|&index| &self.entries[index])
+    }
+
+    /// The current input mode
+    #[must_use]
+    pub fn mode(&self) -> &Mode {
+        &self.mode
+    }
+
+    /// Shows `message` in the status line until the next key press
+    pub fn set_message(&mut self, message: impl Into<String>) {
+        self.message = Some(message.into());
+    }
+
+    /// Replaces the worktrees, keeping the filter and, where possible, the selection
+    pub fn set_entries(&mut self, entries: Vec<UiEntry>) {
+        let selected = self.selected().map(|entry| entry.path.clone());
+        self.entries = entries;
+        self.details.clear();
+        self.apply_filter();
+        if let Some(row) = selected.and_then(|path| self.visible().position(|e| e.path == path)) {
+            self.list_state.select(Some(row));
+        }
+    }
+
+    fn apply_filter(&mut self) {
+        let mut scored: Vec<(i64, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                fuzzy_score(&self.filter, &entry.search_text()).map(|score| (score, index))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        self.visible = scored.into_iter().map(|(_, index)| index).collect();
+        self.list_state
+            .select((!self.visible.is_empty()).then_some(0));
+    }
+
+    fn move_selection(&mut self, offset: isize) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let last = self.visible.len() - 1;
+        let row = self.list_state.selected().unwrap_or(0);
+        self.list_state
+            .select(Some(row.saturating_add_signed(offset).min(last)));
+    }
+
+    /// Updates the state for a key press and returns what the event loop should do
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        self.message = None;
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Action::Quit;
+        }
+        match std::mem::replace(&mut self.mode, Mode::Normal) {
+            Mode::Normal => self.handle_normal_key(key),
+            Mode::Filter => {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        self.filter.push(c);
+                        self.apply_filter();
+                        self.mode = Mode::Filter;
+                    }
+                    KeyCode::Backspace => {
+                        self.filter.pop();
+                        self.apply_filter();
+                        self.mode = Mode::Filter;
+                    }
+                    KeyCode::Esc => {
+                        self.filter.clear();
+                        self.apply_filter();
+                    }
+                    KeyCode::Up | KeyCode::Down => {
+                        self.move_selection(if key.code == KeyCode::Up { -1 } else { 1 });
+                        self.mode = Mode::Filter;
+                    }
+                    KeyCode::Enter => return self.jump_action(),
+                    _ => self.mode = Mode::Filter,
+                }
+                Action::None
+            }
+            Mode::Create(mut name) => match key.code {
+                KeyCode::Char(c) => {
+                    name.push(c);
+                    self.mode = Mode::Create(name);
+                    Action::None
+                }
+                KeyCode::Backspace => {
+                    name.pop();
+                    self.mode = Mode::Create(name);
+                    Action::None
+                }
+                KeyCode::Enter if !name.trim().is_empty() => Action::Create {
+                    repo: self.selected().map(|entry| entry.repo.clone()),
+                    feature: name.trim().to_string(),
+                },
+                KeyCode::Esc | KeyCode::Enter => Action::None,
+                _ => {
+                    self.mode = Mode::Create(name);
+                    Action::None
+                }
+            },
+            Mode::ConfirmRemove => match (key.code, self.selected()) {
+                (KeyCode::Char('y' | 'Y'), Some(entry)) => Action::Remove {
+                    repo: entry.repo.clone(),
+                    feature: entry.feature.clone(),
+                },
+                _ => Action::None,
+            },
+        }
+    }
+
+    fn handle_normal_key(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Char('q') => Action::Quit,
+            KeyCode::Esc if !self.filter.is_empty() => {
+                self.filter.clear();
+                self.apply_filter();
+                Action::None
+            }
+            KeyCode::Esc => Action::Quit,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.move_selection(-1);
+                Action::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.move_selection(1);
+                Action::None
+            }
+            KeyCode::Char('/') => {
+                self.mode = Mode::Filter;
+                Action::None
+            }
+            KeyCode::Char('r') => Action::Refresh,
+            KeyCode::Char('c') => {
+                self.mode = Mode::Create(String::new());
+                Action::None
+            }
+            KeyCode::Enter => self.jump_action(),
+            KeyCode::Char('o') => self
+                .selected()
+                .map_or(Action::None, |entry| Action::Open(entry.path.clone())),
+            KeyCode::Char('d') => {
+                if self.selected().is_some() {
+                    self.mode = Mode::ConfirmRemove;
+                }
+                Action::None
+            }
+            KeyCode::Char('s') => {
+                self.selected()
+                    .map_or(Action::None, |entry| Action::SyncConfig {
+                        path: entry.path.clone(),
+                        feature: entry.feature.clone(),
+                    })
+            }
+            _ => Action::None,
+        }
+    }
+
+    fn jump_action(&self) -> Action {
+        self.selected()
+            .map_or(Action::None, |entry| Action::Jump(entry.path.clone()))
+    }
+
+    /// Draws the interface
+    pub fn render(&mut self, frame: &mut Frame) {
+        let [list_area, details_area, status_area] = Layout::vertical([
+            Constraint::Min(3),
+            Constraint::Length(8),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let items: Vec<ListItem> = self
+            .visible()
+            .map(|entry| {
+                let mut line = format!("{}/{}", entry.repo, entry.feature);
+                if let Some(branch) = &entry.branch {
+                    line.push_str(&format!("  ({})", branch));
+                }
+                if !entry.labels.is_empty() {
+                    line.push_str(&format!("  [{}]", entry.labels.join(", ")));
+                }
+                ListItem::new(line)
+            })
+            .collect();
+        let title = if self.filter.is_empty() && self.mode != Mode::Filter {
+            format!(" Worktrees ({}) ", self.visible.len())
+        } else {
+            format!(" Worktrees ({}) /{} ", self.visible.len(), self.filter)
+        };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("› ");
+        frame.render_stateful_widget(list, list_area, &mut self.list_state);
+
+        self.render_details(frame, details_area);
+
+        let status = match &self.mode {
+            Mode::Create(name) => format!(
+                "New worktree name: {}█  (enter to create, esc to cancel)",
+                name
+            ),
+            Mode::ConfirmRemove => match self.selected() {
+                Some(entry) => format!("Remove {}/{}? (y/N)", entry.repo, entry.feature),
+                None => String::new(),
+            },
+            Mode::Filter => "type to filter · enter to jump · esc to clear".to_string(),
+            Mode::Normal => self.message.clone().unwrap_or_else(|| HELP.to_string()),
+        };
+        frame.render_widget(Paragraph::new(status), status_area);
+    }
+
+    fn render_details(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(entry) = self.selected().cloned() else {
+            let empty = Paragraph::new("No worktrees. Press c to create one.")
+                .block(Block::default().borders(Borders::ALL).title(" Details "));
+            frame.render_widget(empty, area);
+            return;
+        };
+        let state = self
+            .details
+            .entry(entry.path.clone())
+            .or_insert_with(|| branch_state(&entry.path, entry.branch.as_deref()))
+            .clone();
+
+        let mut lines = vec![
+            Line::from(format!("Path:    {}", entry.path.display())),
+            Line::from(format!("Branch:  {}", state)),
+        ];
+        let ages: Vec<String> = [("created", entry.created), ("last used", entry.last_used)]
+            .into_iter()
+            .filter_map(|(label, time)| time.map(|time| format!("{} {}", label, format_age(time))))
+            .collect();
+        if !ages.is_empty() {
+            lines.push(Line::from(format!("Age:     {}", ages.join(", "))));
+        }
+        if let Some(note) = &entry.note {
+            lines.extend(note.lines().enumerate().map(|(i, line)| {
+                Line::from(format!(
+                    "{}{}",
+                    if i == 0 { "Note:    " } else { "         " },
+                    line
+                ))
+            }));
+        }
+        let details = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::ALL).title(" Details "));
+        frame.render_widget(details, area);
+    }
+}
+
+/// Branch with its uncommitted changes and ahead/behind counts, like
+/// `feature/auth (uncommitted changes, 2 ahead)`
+fn branch_state(path: &Path, branch: Option<&str>) -> String {
+    let Some(branch) = branch else {
+        return "(detached)".to_string();
+    };
+    let Ok(backend) = git::open_backend(path) else {
+        return branch.to_string();
+    };
+    let mut state = Vec::new();
+    if backend.is_dirty(path).unwrap_or(false) {
+        state.push("uncommitted changes".to_string());
+    }
+    if let Ok(Some(ab)) = backend.ahead_behind(branch) {
+        match (ab.ahead, ab.behind) {
+            (0, 0) => state.push("up to date".to_string()),
+            (ahead, behind) => {
+                if ahead > 0 {
+                    state.push(format!("{} ahead", ahead));
+                }
+                if behind > 0 {
+                    state.push(format!("{} behind", behind));
+                }
+            }
+        }
+    }
+    if state.is_empty() {
+        branch.to_string()
+    } else {
+        format!("{} ({})", branch, state.join(", "))
+    }
+}
+
+/// Every existing managed worktree, most frecently visited first
+///
+/// # Errors
+/// Returns an error if the storage directory cannot be read
+pub fn load_entries(storage: &WorktreeStorage) -> Result<Vec<UiEntry>> {
+    let mut entries = Vec::new();
+    for (repo, features) in storage.list_all_worktrees()? {
+        let labels = storage.list_labels(&repo)?;
+        let notes = storage.list_notes(&repo)?;
+        for feature in features {
+            let path = storage.get_worktree_path(&repo, &feature);
+            if !path.exists() {
+                continue;
+            }
+            let activity = storage.worktree_activity(&repo, &feature);
+            entries.push(UiEntry {
+                branch: read_worktree_head_branch(&path),
+                labels: labels
+                    .get(&feature)
+                    .map(|labels| labels.iter().cloned().collect())
+                    .unwrap_or_default(),
+                note: notes.get(&feature).cloned(),
+                created: activity.created,
+                last_used: activity.last_used,
+                repo: repo.clone(),
+                feature,
+                path,
+            });
+        }
+    }
+    sort_by_frecency(&mut entries, &storage.visits()?, |entry| {
+        (entry.repo.as_str(), entry.feature.as_str())
+    });
+    Ok(entries)
+}
+
+/// Runs the interface until the user quits or picks a worktree to jump to. The chosen
+/// path is written to `path_file` if given (the shell integration reads it to `cd`),
+/// else printed on stdout.
+///
+/// # Errors
+/// Returns an error if there is no terminal, storage cannot be read, or drawing fails.
+pub fn run_ui(path_file: Option<&Path>) -> Result<()> {
+    use std::io::IsTerminal;
+    anyhow::ensure!(
+        std::io::stderr().is_terminal() && std::io::stdin().is_terminal(),
+        "worktree ui needs an interactive terminal"
+    );
+
+    let storage = WorktreeStorage::new()?;
+    let mut app = App::new(load_entries(&storage)?);
+    let mut terminal = UiSession::enter()?;
+
+    let jump_to = loop {
+        terminal.draw(|frame| app.render(frame))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match app.handle_key(key) {
+            Action::None => {}
+            Action::Quit => break None,
+            Action::Jump(path) => break Some(path),
+            Action::Refresh => {
+                app.set_entries(load_entries(&storage)?);
+                app.set_message("Refreshed");
+            }
+            Action::Open(path) => {
+                suspended(&mut terminal, false, || open::open_in_editor(&path))?;
+            }
+            Action::Create { repo, feature } => {
+                suspended(&mut terminal, true, || {
+                    create_in(&storage, repo.as_deref(), &feature)
+                })?;
+                app.set_entries(load_entries(&storage)?);
+            }
+            Action::Remove { repo, feature } => {
+                let message = match remove_in(&storage, &repo, &feature) {
+                    Ok(()) => format!("Removed {}/{}", repo, feature),
+                    Err(e) => format!("Could not remove {}/{}: {:#}", repo, feature, e),
+                };
+                app.set_entries(load_entries(&storage)?);
+                app.set_message(message);
+            }
+            Action::SyncConfig { path, feature } => {
+                suspended(&mut terminal, true, || {
+                    sync_config::sync_config_in(
+                        &path,
+                        Some(&feature),
+                        None,
+                        &[],
+                        &RealSelectionProvider,
+                    )
+                })?;
+            }
+        }
+    };
+    terminal.leave()?;
+
+    if let Some(path) = jump_to {
+        jump::record_jump(&storage, &path);
+        match path_file {
+            Some(path_file) => std::fs::write(path_file, path.display().to_string())?,
+            None => println!("{}", path.display()),
+        }
+    }
+    Ok(())
+}
+
+/// Creates a worktree in `repo` (or the repository of the current directory), asking
+/// for its branch like `worktree create <feature>` does
+fn create_in(storage: &WorktreeStorage, repo: Option<&str>, feature: &str) -> Result<()> {
+    let dir = match repo.and_then(|repo| find_origin_repo(storage, repo)) {
+        Some(origin) => origin.get_repo_path().to_path_buf(),
+        None => std::env::current_dir()?,
+    };
+    create::interactive_create_in(&dir, feature, &create::CreateOptions::default())?;
+    Ok(())
+}
+
+/// Removes a worktree of `repo` that the user confirmed in the interface. The branch
+/// is kept, so nothing is lost that `worktree create` cannot bring back.
+fn remove_in(storage: &WorktreeStorage, repo: &str, feature: &str) -> Result<()> {
+    let git_repo = find_origin_repo(storage, repo)
+        .ok_or_else(|| anyhow::anyhow!("Could not locate the main repository of '{}'", repo))?;
+    WorktreeManager::with_storage(&git_repo, storage.clone())?.remove(feature, false)?;
+    Ok(())
+}
+
+type UiTerminal = Terminal<CrosstermBackend<Stderr>>;
+
+/// The terminal in raw mode on the alternate screen. Dropping it, including on an early
+/// return or a panic, puts the terminal back the way the shell expects it.
+struct UiSession {
+    terminal: UiTerminal,
+    active: bool,
+}
+
+impl UiSession {
+    fn enter() -> Result<Self> {
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let _ = restore_terminal();
+            hook(info);
+        }));
+
+        let mut session = Self {
+            terminal: Terminal::new(CrosstermBackend::new(std::io::stderr()))?,
+            active: false,
+        };
+        session.resume()?;
+        Ok(session)
+    }
+
+    fn resume(&mut self) -> Result<()> {
+        self.active = true;
+        enable_raw_mode()?;
+        execute!(std::io::stderr(), EnterAlternateScreen)?;
+        self.terminal.clear()?;
+        Ok(())
+    }
+
+    fn leave(&mut self) -> Result<()> {
+        self.active = false;
+        restore_terminal()
+    }
+}
+
+impl std::ops::Deref for UiSession {
+    type Target = UiTerminal;
+
+    fn deref(&self) -> &UiTerminal {
+        &self.terminal
+    }
+}
+
+impl std::ops::DerefMut for UiSession {
+    fn deref_mut(&mut self) -> &mut UiTerminal {
+        &mut self.terminal
+    }
+}
+
+impl Drop for UiSession {
+    fn drop(&mut self) {
+        if self.active {
+            let _ = restore_terminal();
+        }
+    }
+}
+
+fn restore_terminal() -> Result<()> {
+    disable_raw_mode()?;
+    execute!(std::io::stderr(), LeaveAlternateScreen, Show)?;
+    Ok(())
+}
+
+/// Leaves the interface to run `action` on the normal screen, then comes back. With
+/// `pause`, waits for Enter first so its output can be read.
+fn suspended(
+    terminal: &mut UiSession,
+    pause: bool,
+    action: impl FnOnce() -> Result<()>,
+) -> Result<()> {
+    terminal.leave()?;
+    let result = action();
+    if let Err(e) = &result {
+        eprintln!("Error: {:#}", e);
+    }
+    if pause || result.is_err() {
+        eprintln!("\nPress Enter to return to worktree ui");
+        let mut line = String::new();
+        std::io::stdin().lock().read_line(&mut line)?;
+    }
+    terminal.resume()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn entry(repo: &str, feature: &str, labels: &[&str]) -> UiEntry {
+        UiEntry {
+            repo: repo.to_string(),
+            feature: feature.to_string(),
+            path: PathBuf::from(format!("/nonexistent/{}/{}", repo, feature)),
+            branch: Some(format!("feature/{}", feature)),
+            labels: labels.iter().map(ToString::to_string).collect(),
+            note: None,
+            created: None,
+            last_used: None,
+        }
+    }
+
+    fn render_to_string(app: &mut App, width: u16, height: u16) -> String {
+        let mut terminal =
+            Terminal::new(ratatui::backend::TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn press(app: &mut App, code: KeyCode) -> Action {
+        app.handle_key(KeyEvent::from(code))
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            press(app, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn test_filter_and_jump() {
+        let mut app = App::new(vec![
+            entry("api", "auth", &[]),
+            entry("api", "billing", &["review"]),
+            entry("web", "search", &[]),
+        ]);
+        press(&mut app, KeyCode::Char('/'));
+        type_text(&mut app, "review");
+        let names: Vec<&str> = app.visible().map(|e| e.feature.as_str()).collect();
+        assert_eq!(names, ["billing"]);
+
+        assert_eq!(
+            press(&mut app, KeyCode::Enter),
+            Action::Jump(PathBuf::from("/nonexistent/api/billing"))
+        );
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.visible().count(), 3);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.selected().map(|e| e.feature.as_str()), Some("search"));
+    }
+
+    #[test]
+    fn test_create_and_remove_actions() {
+        let mut app = App::new(vec![entry("api", "auth", &[])]);
+        press(&mut app, KeyCode::Char('c'));
+        type_text(&mut app, "payments");
+        assert_eq!(
+            press(&mut app, KeyCode::Enter),
+            Action::Create {
+                repo: Some("api".to_string()),
+                feature: "payments".to_string(),
+            }
+        );
+
+        press(&mut app, KeyCode::Char('d'));
+        assert_eq!(app.mode(), &Mode::ConfirmRemove);
+        assert_eq!(press(&mut app, KeyCode::Char('n')), Action::None);
+        press(&mut app, KeyCode::Char('d'));
+        assert_eq!(
+            press(&mut app, KeyCode::Char('y')),
+            Action::Remove {
+                repo: "api".to_string(),
+                feature: "auth".to_string(),
+            }
+        );
+        assert_eq!(press(&mut app, KeyCode::Char('q')), Action::Quit);
+    }
+
+    #[test]
+    fn test_render_lists_worktrees_and_details() {
+        let mut first = entry("api", "auth", &["review"]);
+        first.note = Some("Waiting on API review".to_string());
+        let mut app = App::new(vec![first, entry("api", "billing", &[])]);
+        let screen = render_to_string(&mut app, 80, 16);
+        assert!(screen.contains("Worktrees (2)"));
+        assert!(screen.contains("› api/auth  (feature/auth)  [review]"));
+        assert!(screen.contains("Note:    Waiting on API review"));
+        assert!(screen.contains("enter jump"));
+    }
+}
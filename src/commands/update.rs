@@ -0,0 +1,253 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use crate::commands::back;
+use crate::config::{UpdateStrategy, WorktreeConfig};
+use crate::git::{self, GitRepo, conflicted_files, git_output, run_git};
+use crate::manager::WorktreeManager;
+use crate::output;
+use crate::storage::{WorktreeStorage, read_worktree_head_branch};
+
+/// Options for `worktree update`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpdateOptions {
+    /// Rebase or merge. `None` uses `strategy` from the `[update]` config section.
+    pub strategy: Option<UpdateStrategy>,
+    /// Update onto this reference instead of the branch's recorded base or the default
+    /// branch
+    pub onto: Option<String>,
+    /// Don't fetch from the remotes first
+    pub no_fetch: bool,
+}
+
+/// What happened to one worktree's branch
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum UpdateOutcome {
+    /// The branch already contained the base branch
+    UpToDate,
+    /// The branch was rebased onto or merged with the base branch
+    Updated,
+    /// Rebasing or merging stopped on conflicts in these files and was aborted, leaving
+    /// the branch as it was
+    Conflicted(Vec<String>),
+    /// The worktree was not touched, for the given reason
+    Skipped(String),
+}
+
+/// Fetches and rebases (or merges) a worktree's branch onto its base branch: the branch
+/// it was created from with `create --from`, or else the default branch. Uncommitted
+/// changes are stashed first and restored afterwards. Without a target, updates the
+/// worktree containing the current directory.
+///
+/// # Errors
+/// Returns an error if the worktree is not found, its HEAD is detached, no base branch
+/// can be determined, a git command fails, or the update stops on conflicts
+pub fn update_worktree(target: Option<&str>, options: &UpdateOptions) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let manager =
+        WorktreeManager::with_storage(GitRepo::open(&current_dir)?, WorktreeStorage::new()?)?;
+    let path = match target {
+        Some(target) => manager.find(target)?.path,
+        None => {
+            let (_, feature_name) =
+                back::determine_current_worktree(&current_dir, manager.storage())?;
+            manager.worktree_path(&feature_name)
+        }
+    };
+
+    if !options.no_fetch {
+        println!("Fetching...");
+        git::fetch_all(manager.repo_path())?;
+    }
+
+    let (branch, onto) = update_target(manager.git(), &path, options.onto.as_deref())?;
+    let strategy = strategy(manager.config(), options);
+    println!(
+        "{} {} onto {}...",
+        match strategy {
+            UpdateStrategy::Merge => "Merging",
+            _ => "Rebasing",
+        },
+        branch,
+        onto
+    );
+
+    match update_branch(&path, &onto, strategy, true)? {
+        UpdateOutcome::UpToDate => {
+            output::status(format_args!("✓ {} is up to date with {}", branch, onto))
+        }
+        UpdateOutcome::Updated => {
+            output::status(format_args!("✓ Updated {} onto {}", branch, onto))
+        }
+        UpdateOutcome::Conflicted(files) => {
+            output::status_err(format_args!("✗ Conflicts with {} in:", onto));
+            for file in &files {
+                eprintln!("   {}", file);
+            }
+            eprintln!(
+                "The update was aborted and {} is unchanged. To resolve the conflicts yourself, run `git {} {}` in {}",
+                branch,
+                strategy_command(strategy),
+                onto,
+                path.display()
+            );
+            anyhow::bail!("Updating {} stopped on conflicts", branch);
+        }
+        UpdateOutcome::Skipped(reason) => {
+            anyhow::bail!("Not updating {}: {}", path.display(), reason)
+        }
+    }
+
+    Ok(())
+}
+
+/// The strategy from the options, or else from the `[update]` config section
+#[must_use]
+pub fn strategy(config: &WorktreeConfig, options: &UpdateOptions) -> UpdateStrategy {
+    options.strategy.unwrap_or(config.update.strategy)
+}
+
+/// The branch checked out in the worktree and the reference to update it onto: `onto`,
+/// else the branch's recorded base, else the default branch. A local branch is replaced
+/// by its `origin` counterpart when there is one, so freshly fetched commits are used.
+///
+/// # Errors
+/// Returns an error if the worktree's HEAD is detached or no base branch is found
+pub fn update_target(
+    git_repo: &GitRepo,
+    worktree_path: &Path,
+    onto: Option<&str>,
+) -> Result<(String, String)> {
+    let branch = read_worktree_head_branch(worktree_path).ok_or_else(|| {
+        anyhow::anyhow!("No branch is checked out in {}", worktree_path.display())
+    })?;
+
+    let base = onto
+        .map(str::to_string)
+        .or_else(|| git::branch_base(git_repo.get_repo_path(), &branch))
+        .or_else(|| git_repo.integration_branch())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Could not determine the base branch of {}; pass --onto",
+                branch
+            )
+        })?;
+
+    let remote_base = format!("origin/{}", base);
+    let onto = if git_repo.branch_exists(&base)?
+        && git_repo.list_remote_branches()?.contains(&remote_base)
+    {
+        remote_base
+    } else {
+        base
+    };
+
+    Ok((branch, onto))
+}
+
+/// Rebases the worktree's branch onto `onto`, or merges `onto` into it. Conflicts abort
+/// the rebase or merge. With `autostash`, uncommitted changes are stashed first and
+/// restored afterwards; without it, a worktree with uncommitted changes is skipped.
+/// Worktrees in the middle of a rebase or merge are skipped either way.
+///
+/// # Errors
+/// Returns an error if a git command fails for reasons other than conflicts
+pub fn update_branch(
+    worktree_path: &Path,
+    onto: &str,
+    strategy: UpdateStrategy,
+    autostash: bool,
+) -> Result<UpdateOutcome> {
+    if let Some(operation) = operation_in_progress(worktree_path)? {
+        return Ok(UpdateOutcome::Skipped(format!(
+            "a {} is in progress",
+            operation
+        )));
+    }
+
+    let dirty = !run_git(worktree_path, ["status", "--porcelain"])?
+        .trim()
+        .is_empty();
+    if dirty && !autostash {
+        return Ok(UpdateOutcome::Skipped("uncommitted changes".to_string()));
+    }
+    if dirty {
+        run_git(
+            worktree_path,
+            [
+                "stash",
+                "push",
+                "--include-untracked",
+                "--message",
+                "worktree update",
+            ],
+        )?;
+    }
+
+    let before = run_git(worktree_path, ["rev-parse", "HEAD"])?;
+    let args: &[&str] = match strategy {
+        UpdateStrategy::Merge => &["merge", "--no-edit", onto],
+        _ => &["rebase", onto],
+    };
+    let output = git_output(worktree_path, args)?;
+    let outcome = if output.status.success() {
+        if run_git(worktree_path, ["rev-parse", "HEAD"])? == before {
+            UpdateOutcome::UpToDate
+        } else {
+            UpdateOutcome::Updated
+        }
+    } else {
+        let conflicts = conflicted_files(worktree_path)?;
+        if operation_in_progress(worktree_path)?.is_none() {
+            // Nothing to abort: git refused to start, e.g. because `onto` is unknown
+            restore_stash(worktree_path, dirty);
+            anyhow::bail!(
+                "git {} failed: {}",
+                strategy_command(strategy),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        run_git(worktree_path, [strategy_command(strategy), "--abort"])?;
+        UpdateOutcome::Conflicted(conflicts)
+    };
+
+    restore_stash(worktree_path, dirty);
+    Ok(outcome)
+}
+
+fn strategy_command(strategy: UpdateStrategy) -> &'static str {
+    match strategy {
+        UpdateStrategy::Merge => "merge",
+        _ => "rebase",
+    }
+}
+
+/// Pops the stash made by [`update_branch`]. A pop that conflicts keeps the stash.
+fn restore_stash(worktree_path: &Path, stashed: bool) {
+    if !stashed {
+        return;
+    }
+    if let Err(e) = run_git(worktree_path, ["stash", "pop"]) {
+        warn!(
+            "Could not restore your uncommitted changes in {}: {}\n  They are kept in `git stash list`.",
+            worktree_path.display(),
+            e
+        );
+    }
+}
+
+/// The rebase or merge the worktree is in the middle of, if any
+fn operation_in_progress(worktree_path: &Path) -> Result<Option<&'static str>> {
+    let git_dir = PathBuf::from(run_git(worktree_path, ["rev-parse", "--absolute-git-dir"])?);
+    Ok(
+        if git_dir.join("rebase-merge").exists() || git_dir.join("rebase-apply").exists() {
+            Some("rebase")
+        } else if git_dir.join("MERGE_HEAD").exists() {
+            Some("merge")
+        } else {
+            None
+        },
+    )
+}
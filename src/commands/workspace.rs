@@ -0,0 +1,132 @@
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use serde_json::{Map, Value, json};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use crate::git::GitRepo;
+use crate::output;
+use crate::storage::{WorktreeStorage, read_worktree_head_branch};
+
+#[derive(Subcommand, Clone)]
+#[non_exhaustive]
+pub enum WorkspaceAction {
+    /// Write a VS Code multi-root workspace with the main repository and its worktrees
+    Vscode,
+}
+
+/// Dispatches the `worktree workspace` subcommand.
+///
+/// # Errors
+/// Returns an error if the current directory is not a git repository or the workspace
+/// file cannot be written.
+pub fn run_workspace_command(action: &WorkspaceAction) -> Result<()> {
+    match action {
+        WorkspaceAction::Vscode => generate_vscode_workspace(),
+    }
+}
+
+/// Writes the VS Code workspace for the current repository and prints its path
+///
+/// # Errors
+/// Returns an error if the current directory is not a git repository or the workspace
+/// file cannot be written.
+pub fn generate_vscode_workspace() -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let git_repo = GitRepo::open(&current_dir)?;
+    let storage = WorktreeStorage::new()?;
+    let repo_name = WorktreeStorage::get_repo_name(git_repo.get_repo_path())?;
+
+    let path = write_vscode_workspace(&storage, &repo_name, git_repo.get_repo_path())?;
+    output::status(format_args!(
+        "✓ Wrote VS Code workspace: {}",
+        path.display()
+    ));
+    println!("  Open it with: code {}", path.display());
+    Ok(())
+}
+
+/// Where the VS Code workspace of `repo_name` lives: next to its worktrees in storage
+#[must_use]
+pub fn vscode_workspace_path(storage: &WorktreeStorage, repo_name: &str) -> PathBuf {
+    storage
+        .get_repo_storage_dir(repo_name)
+        .join(format!("{}.code-workspace", repo_name))
+}
+
+/// Writes the workspace file listing `main_repo` and every managed worktree of
+/// `repo_name`, each named after its branch. Settings and other keys already in the
+/// file are kept; only `folders` is replaced.
+///
+/// # Errors
+/// Returns an error if storage cannot be read, an existing workspace file is not plain
+/// JSON (VS Code allows comments, which cannot be preserved), or the file cannot be
+/// written.
+pub fn write_vscode_workspace(
+    storage: &WorktreeStorage,
+    repo_name: &str,
+    main_repo: &Path,
+) -> Result<PathBuf> {
+    let path = vscode_workspace_path(storage, repo_name);
+
+    let mut workspace = if path.exists() {
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        match serde_json::from_str::<Value>(&content) {
+            Ok(Value::Object(map)) => map,
+            _ => anyhow::bail!(
+                "{} is not plain JSON (comments are not supported); fix or delete it and try again",
+                path.display()
+            ),
+        }
+    } else {
+        Map::new()
+    };
+    workspace.insert(
+        "folders".to_string(),
+        Value::Array(workspace_folders(storage, repo_name, main_repo)?),
+    );
+
+    std::fs::create_dir_all(storage.get_repo_storage_dir(repo_name))?;
+    let content = serde_json::to_string_pretty(&Value::Object(workspace))?;
+    std::fs::write(&path, format!("{}\n", content))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Regenerates the workspace of `repo_name` if one was generated before, so it follows
+/// worktrees as they are created and removed. Failures only warn.
+pub fn refresh_vscode_workspace(storage: &WorktreeStorage, repo_name: &str, main_repo: &Path) {
+    if !vscode_workspace_path(storage, repo_name).exists() {
+        return;
+    }
+    if let Err(e) = write_vscode_workspace(storage, repo_name, main_repo) {
+        warn!("Failed to update the VS Code workspace: {}", e);
+    }
+}
+
+fn workspace_folders(
+    storage: &WorktreeStorage,
+    repo_name: &str,
+    main_repo: &Path,
+) -> Result<Vec<Value>> {
+    let mut folders = vec![json!({
+        "name": repo_name,
+        "path": main_repo.to_string_lossy(),
+    })];
+
+    let mut features = storage.list_repo_worktrees(repo_name)?;
+    features.sort();
+    for feature_name in features {
+        let path = storage.get_worktree_path(repo_name, &feature_name);
+        if !path.exists() {
+            continue;
+        }
+        let name = read_worktree_head_branch(&path).unwrap_or(feature_name);
+        folders.push(json!({
+            "name": name,
+            "path": path.to_string_lossy(),
+        }));
+    }
+    Ok(folders)
+}
@@ -0,0 +1,67 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::config::ZellijMode;
+use crate::output;
+
+/// Environment variable Zellij sets inside its sessions
+pub const ZELLIJ_ENV: &str = "ZELLIJ";
+
+/// Returns true when running inside a Zellij session
+#[must_use]
+pub fn in_session() -> bool {
+    std::env::var_os(ZELLIJ_ENV).is_some()
+}
+
+/// Opens `path` in Zellij as a new tab or pane of the current session, or as a new
+/// background session, named `name`
+///
+/// # Errors
+/// Returns an error if a tab or pane is requested outside a Zellij session, or if the
+/// `zellij` command cannot be run or fails.
+pub fn open(path: &Path, name: &str, mode: ZellijMode) -> Result<()> {
+    let path_arg = path.to_string_lossy();
+    let args: Vec<&str> = match mode {
+        ZellijMode::Tab | ZellijMode::Pane if !in_session() => {
+            anyhow::bail!(
+                "Not inside a Zellij session (${} is not set).\n\
+                Run this from Zellij, or set `mode = \"session\"` under [zellij] in the global config.",
+                ZELLIJ_ENV
+            );
+        }
+        ZellijMode::Tab => vec!["action", "new-tab", "--cwd", &path_arg, "--name", name],
+        ZellijMode::Pane => vec!["action", "new-pane", "--cwd", &path_arg],
+        ZellijMode::Session => vec![
+            "attach",
+            "--create-background",
+            name,
+            "options",
+            "--default-cwd",
+            &path_arg,
+        ],
+    };
+
+    let status = Command::new("zellij")
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::inherit())
+        .status()
+        .context("Failed to run zellij. Is it installed and on PATH?")?;
+    if !status.success() {
+        anyhow::bail!(
+            "zellij {} failed with exit code {}",
+            args.join(" "),
+            status.code().unwrap_or(-1)
+        );
+    }
+
+    if mode == ZellijMode::Session {
+        output::status_err(format_args!(
+            "✓ Started Zellij session '{}'. Attach with: zellij attach {}",
+            name, name
+        ));
+    }
+    Ok(())
+}
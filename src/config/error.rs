@@ -0,0 +1,24 @@
+use std::path::PathBuf;
+
+/// Result type for configuration loading
+pub type Result<T, E = ConfigError> = std::result::Result<T, E>;
+
+/// Errors from loading `.worktree-config.toml` or the global config file
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// The config file exists but cannot be read
+    #[error("Failed to read config file: {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    /// The config file cannot be written
+    #[error("Failed to write config file: {}", path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
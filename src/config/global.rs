@@ -0,0 +1,289 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tracing::warn;
+
+use super::{ConfigError, OpenTarget, Result};
+
+/// Environment variable pointing at the global config file, or at a directory of config
+/// files, overriding the default locations
+pub const GLOBAL_CONFIG_ENV: &str = "WORKTREE_CONFIG";
+
+/// File name of the global config, also inside a [`config_override`] directory
+pub const GLOBAL_CONFIG_FILE_NAME: &str = "config.toml";
+
+static CONFIG_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Points config loading at `path` (from `--config`), taking precedence over
+/// `$WORKTREE_CONFIG`. Only the first call has an effect.
+pub fn set_config_override(path: PathBuf) {
+    let _ = CONFIG_OVERRIDE.set(path);
+}
+
+/// The config file or directory set by [`set_config_override`] or `$WORKTREE_CONFIG`.
+/// A file replaces the global config; a directory holds a `config.toml` that does, and
+/// optionally a `.worktree-config.toml` that replaces every repository's own.
+#[must_use]
+pub fn config_override() -> Option<PathBuf> {
+    CONFIG_OVERRIDE.get().cloned().or_else(|| {
+        std::env::var_os(GLOBAL_CONFIG_ENV)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    })
+}
+
+/// User-wide settings that apply to every repository, read from
+/// `$XDG_CONFIG_HOME/worktree/config.toml` (default `~/.config/worktree/config.toml`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct GlobalConfig {
+    /// Editor command for `worktree open` when the repository config sets none
+    #[serde(default)]
+    pub editor: Option<String>,
+    /// Named ways to open a worktree (`[open.<name>]`) for `worktree open --with`;
+    /// a repository's targets override these
+    #[serde(default)]
+    pub open: BTreeMap<String, OpenTarget>,
+    /// Opening worktrees in Zellij
+    #[serde(default)]
+    pub zellij: ZellijConfig,
+    /// How worktree targets match feature names (`match`)
+    #[serde(default, rename = "match")]
+    pub match_mode: MatchMode,
+    /// Storage root to use when `WORKTREE_STORAGE_ROOT` is unset (`storage-root`), as
+    /// recorded by `worktree relocate`
+    #[serde(default, rename = "storage-root")]
+    pub storage_root: Option<PathBuf>,
+    /// `branch-prefix` for repositories whose config sets none
+    #[serde(default, rename = "branch-prefix")]
+    pub branch_prefix: Option<String>,
+}
+
+/// How a target that is not an exact feature name or checked-out branch matches
+/// feature names in `jump`, `remove`, and the other commands taking a worktree
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum MatchMode {
+    /// Only exact feature names and branches
+    Exact,
+    /// Also feature names starting with the target
+    Prefix,
+    /// Also feature names containing the target, when none starts with it
+    #[default]
+    Fuzzy,
+}
+
+impl MatchMode {
+    /// Keeps the best partial matches of `target` among `candidates`: the feature names
+    /// starting with it, else (with [`MatchMode::Fuzzy`]) those containing it. Exact
+    /// matches are up to the caller.
+    #[must_use]
+    pub fn best_matches<T>(
+        self,
+        target: &str,
+        candidates: Vec<T>,
+        name: impl Fn(&T) -> &str,
+    ) -> Vec<T> {
+        let rank = |candidate: &T| {
+            let name = name(candidate);
+            if self != MatchMode::Exact && name.starts_with(target) {
+                Some(1)
+            } else if self == MatchMode::Fuzzy && name.contains(target) {
+                Some(0)
+            } else {
+                None
+            }
+        };
+        let Some(best) = candidates.iter().filter_map(rank).max() else {
+            return Vec::new();
+        };
+        candidates
+            .into_iter()
+            .filter(|candidate| rank(candidate) == Some(best))
+            .collect()
+    }
+}
+
+/// How `jump` opens worktrees in Zellij (`[zellij]`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ZellijConfig {
+    /// Open in Zellij whenever `jump` runs inside a Zellij session, as if `--zellij`
+    /// were passed
+    #[serde(default)]
+    pub auto: bool,
+    /// Where the worktree opens
+    #[serde(default)]
+    pub mode: ZellijMode,
+}
+
+/// Where a worktree opened in Zellij goes
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum ZellijMode {
+    /// A new tab in the current session, named after the worktree
+    #[default]
+    Tab,
+    /// A new pane in the current tab
+    Pane,
+    /// A new background session named after the worktree
+    Session,
+}
+
+impl GlobalConfig {
+    /// Location of the global config file: the [`config_override`] (its `config.toml`
+    /// if it is a directory), else `$XDG_CONFIG_HOME/worktree/config.toml`, else
+    /// `~/.config/worktree/config.toml`
+    #[must_use]
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = config_override() {
+            return Some(if path.is_dir() {
+                path.join(GLOBAL_CONFIG_FILE_NAME)
+            } else {
+                path
+            });
+        }
+        std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+            .map(|config_dir| config_dir.join("worktree").join(GLOBAL_CONFIG_FILE_NAME))
+    }
+
+    /// Loads the global config, using defaults when the file does not exist
+    ///
+    /// # Errors
+    /// Only returns an error if the file exists but cannot be read. Invalid TOML is
+    /// reported as a warning and the defaults are used.
+    pub fn load() -> Result<Self> {
+        let Some(config_path) = Self::path().filter(|path| path.exists()) else {
+            return Ok(Self::default());
+        };
+
+        let content = fs::read_to_string(&config_path).map_err(|source| ConfigError::Read {
+            path: config_path.clone(),
+            source,
+        })?;
+
+        match toml::from_str::<GlobalConfig>(&content) {
+            Ok(config) => Ok(config),
+            Err(e) => {
+                warn!(
+                    "Invalid TOML syntax in {}:\n  {}\n  Using default global configuration.",
+                    config_path.display(),
+                    e
+                );
+                Ok(Self::default())
+            }
+        }
+    }
+}
+
+/// Sets the top-level `key` of the global config file to the string `value`, replacing
+/// an existing line for it or adding one at the top, so the rest of the file (comments
+/// included) stays as it was. Returns the file's path.
+///
+/// # Errors
+/// Returns an error if no config location can be determined, or the file cannot be read
+/// or written
+pub fn set_global_value(key: &str, value: &str) -> Result<PathBuf> {
+    let path = GlobalConfig::path().ok_or_else(|| ConfigError::Write {
+        path: PathBuf::from("config.toml"),
+        source: std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "no home directory or XDG_CONFIG_HOME",
+        ),
+    })?;
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(source) => return Err(ConfigError::Read { path, source }),
+    };
+    let line = format!("{} = {}", key, toml::Value::String(value.to_string()));
+    let content = replace_top_level_key(&content, key, &line);
+
+    let write_error = |source| ConfigError::Write {
+        path: path.clone(),
+        source,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(write_error)?;
+    }
+    fs::write(&path, content).map_err(write_error)?;
+    Ok(path)
+}
+
+/// Replaces the line assigning `key` before the first table header with `line`, or
+/// puts `line` first
+fn replace_top_level_key(content: &str, key: &str, line: &str) -> String {
+    let mut lines: Vec<&str> = content.lines().collect();
+    let top_level = lines
+        .iter()
+        .position(|l| l.trim_start().starts_with('['))
+        .unwrap_or(lines.len());
+    let existing = lines[..top_level].iter().position(|l| {
+        l.split_once('=')
+            .is_some_and(|(name, _)| name.trim().trim_matches('"') == key)
+    });
+    match existing {
+        Some(index) => lines[index] = line,
+        None => lines.insert(0, line),
+    }
+    let mut content = lines.join("\n");
+    content.push('\n');
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_zellij_section() -> anyhow::Result<()> {
+        let config: GlobalConfig = toml::from_str("[zellij]\nauto = true\nmode = \"session\"\n")?;
+        assert!(config.zellij.auto);
+        assert_eq!(config.zellij.mode, ZellijMode::Session);
+
+        let config: GlobalConfig = toml::from_str("")?;
+        assert_eq!(config.zellij, ZellijConfig::default());
+        Ok(())
+    }
+
+    #[test]
+    fn test_match_mode_prefers_prefix_over_substring() -> anyhow::Result<()> {
+        let names = vec!["oauth-legacy", "auth-v2", "authz"];
+        let matches =
+            |mode: MatchMode, target: &str| mode.best_matches(target, names.clone(), |n| n);
+
+        assert_eq!(matches(MatchMode::Fuzzy, "auth"), ["auth-v2", "authz"]);
+        assert_eq!(matches(MatchMode::Fuzzy, "legacy"), ["oauth-legacy"]);
+        assert_eq!(matches(MatchMode::Prefix, "legacy"), Vec::<&str>::new());
+        assert_eq!(matches(MatchMode::Prefix, "auth-"), ["auth-v2"]);
+        assert!(matches(MatchMode::Exact, "auth").is_empty());
+
+        let config: GlobalConfig = toml::from_str("match = \"prefix\"\n")?;
+        assert_eq!(config.match_mode, MatchMode::Prefix);
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_top_level_key_keeps_the_rest() {
+        let line = "storage-root = \"/new\"";
+        assert_eq!(
+            replace_top_level_key("# mine\neditor = \"vim\"\n", "storage-root", line),
+            "storage-root = \"/new\"\n# mine\neditor = \"vim\"\n"
+        );
+        assert_eq!(
+            replace_top_level_key(
+                "storage-root = \"/old\"\n[zellij]\nstorage-root = 1\n",
+                "storage-root",
+                line
+            ),
+            "storage-root = \"/new\"\n[zellij]\nstorage-root = 1\n"
+        );
+    }
+}
@@ -5,15 +5,57 @@
 //! - Additive merging with sensible defaults
 //! - Symlink patterns for long-lived shared files
 //! - Post-create hooks for setup automation
+//! - Protected branch patterns that branch-deleting operations must skip
+//! - Retention limits enforced by `autoclean`
+//! - When `status` considers a worktree stale
+//! - Defaults for `remove` flags
+//! - The git backend used for repository operations
+//! - Whether worktrees live under the storage root or next to the repository
+//! - The editor `worktree open` launches, and named `[open]` targets for `open --with`
+//! - A generated direnv `.envrc` for new worktrees
+//! - Per-worktree names for devcontainer configurations
+//! - A per-worktree Docker Compose project name
+//! - Build caches shared between worktrees
+//! - How heavyweight dependency directories such as `node_modules` are set up
+//! - mise/asdf toolchain setup
+//! - Git LFS checkout in new worktrees
+//! - Git hooks in new worktrees
+//!
+//! User-wide settings that are not tied to a repository (such as Zellij integration)
+//! live in a separate global config file, see [`GlobalConfig`].
+
+mod error;
+mod global;
 
-use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+pub use error::{ConfigError, Result};
+pub use global::{
+    GLOBAL_CONFIG_ENV, GLOBAL_CONFIG_FILE_NAME, GlobalConfig, MatchMode, ZellijConfig, ZellijMode,
+    config_override, set_config_override, set_global_value,
+};
+
+/// Placeholder replaced by the login name in `branch-prefix`
+pub const USER_PLACEHOLDER: &str = "{user}";
+
+/// File name of a repository's config, at its root or in a [`config_override`] directory
+pub const REPO_CONFIG_FILE_NAME: &str = ".worktree-config.toml";
 
 /// Main configuration structure for worktree file copying.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct WorktreeConfig {
+    /// Branch patterns (exact names or globs) that are never deleted
+    #[serde(rename = "protected-branches", default)]
+    pub protected_branches: Option<Vec<String>>,
+    /// Prepended to the feature name to form the branch when `create` is given no branch
+    /// (`cf/`, or `{user}/` for the login name). Overrides the global config.
+    #[serde(rename = "branch-prefix", default)]
+    pub branch_prefix: Option<String>,
     /// File copying pattern configuration
     #[serde(rename = "copy-patterns", default)]
     pub copy_patterns: CopyPatterns,
@@ -23,10 +65,81 @@ pub struct WorktreeConfig {
     /// Post-create hook configuration
     #[serde(rename = "on-create", default)]
     pub on_create: OnCreate,
+    /// Limits on how many worktrees to keep and for how long
+    #[serde(default)]
+    pub retention: Retention,
+    /// Defaults for `worktree status`
+    #[serde(default)]
+    pub status: StatusDefaults,
+    /// Defaults for `worktree create`
+    #[serde(default)]
+    pub create: CreateDefaults,
+    /// Defaults for `worktree remove`
+    #[serde(default)]
+    pub remove: RemoveDefaults,
+    /// Defaults for `worktree update`
+    #[serde(default)]
+    pub update: UpdateDefaults,
+    /// Defaults for `worktree finish`
+    #[serde(default)]
+    pub finish: FinishDefaults,
+    /// Defaults for `worktree pr create`
+    #[serde(default)]
+    pub pr: PullRequestDefaults,
+    /// Defaults for `worktree jump` and `worktree path`
+    #[serde(default)]
+    pub jump: JumpDefaults,
+    /// Per-worktree `.envrc` generation for direnv
+    #[serde(default)]
+    pub direnv: Direnv,
+    /// Per-worktree names for `.devcontainer/` configurations
+    #[serde(default)]
+    pub devcontainer: Devcontainer,
+    /// Per-worktree Docker Compose project name
+    #[serde(default)]
+    pub compose: Compose,
+    /// Build caches shared between the repository's worktrees
+    #[serde(rename = "build-cache", default)]
+    pub build_cache: BuildCache,
+    /// How ignored dependency directories (`node_modules`) are set up in new worktrees
+    #[serde(rename = "dependency-dirs", default)]
+    pub dependency_dirs: DependencyDirs,
+    /// mise/asdf tool version setup in new worktrees
+    #[serde(default)]
+    pub toolchain: Toolchain,
+    /// Git LFS handling in new worktrees
+    #[serde(default)]
+    pub lfs: Lfs,
+    /// Git hook setup in new worktrees
+    #[serde(rename = "git-hooks", default)]
+    pub git_hooks: GitHooks,
+    /// `branch.<name>.description` for branches made by `create`
+    #[serde(rename = "branch-description", default)]
+    pub branch_description: BranchDescription,
+    /// Whether new worktrees copy the main repository's git config into their own
+    /// `config.worktree` (default true). When false, `extensions.worktreeConfig` is left
+    /// alone.
+    #[serde(rename = "inherit-git-config", default)]
+    pub inherit_git_config: Option<bool>,
+    /// Which implementation runs git operations
+    #[serde(rename = "git-backend", default)]
+    pub git_backend: GitBackend,
+    /// Where new worktrees are created
+    #[serde(default)]
+    pub layout: Layout,
+    /// Editor command for `worktree open`: a program (`code`, `zed`, `nvim`) given the
+    /// worktree path, or a template containing `{path}`. Overrides the global config.
+    #[serde(default)]
+    pub editor: Option<String>,
+    /// Named ways to open a worktree (`[open.<name>]`), used by `worktree open --with`.
+    /// Override targets of the same name in the global config.
+    #[serde(default)]
+    pub open: BTreeMap<String, OpenTarget>,
 }
 
 /// File copying pattern configuration with flexible merging behavior.
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[non_exhaustive]
 pub struct CopyPatterns {
     /// Patterns to include in file copying (glob patterns)
     #[serde(default)]
@@ -38,7 +151,8 @@ pub struct CopyPatterns {
 
 /// Symlink pattern configuration. Matching paths are symlinked to the origin repo
 /// instead of copied.
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[non_exhaustive]
 pub struct SymlinkPatterns {
     /// Patterns to symlink (glob patterns or exact paths)
     #[serde(default)]
@@ -47,22 +161,494 @@ pub struct SymlinkPatterns {
 
 /// Post-create hook configuration. Commands run sequentially in the worktree directory
 /// after all files are copied and symlinked.
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[non_exhaustive]
 pub struct OnCreate {
     /// Shell command strings to execute after worktree creation
     #[serde(default)]
     pub commands: Option<Vec<String>>,
 }
 
+/// Retention policy enforced by `worktree autoclean`. Unset limits are not enforced.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Retention {
+    /// Keep at most this many worktrees per repository (most recently active first)
+    #[serde(rename = "max-worktrees", default)]
+    pub max_worktrees: Option<usize>,
+    /// Remove worktrees with no activity for more than this many days
+    #[serde(rename = "max-age-days", default)]
+    pub max_age_days: Option<u64>,
+}
+
+impl Retention {
+    /// Returns true if at least one limit is configured
+    #[must_use]
+    pub fn is_configured(&self) -> bool {
+        self.max_worktrees.is_some() || self.max_age_days.is_some()
+    }
+}
+
+/// Defaults for `worktree status`
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StatusDefaults {
+    /// Flag worktrees with no commits or file changes for this many days as stale
+    /// (default 30; 0 turns the check off)
+    #[serde(rename = "stale-after-days", default)]
+    pub stale_after_days: Option<u64>,
+}
+
+impl StatusDefaults {
+    /// Days without activity after which a worktree is stale, `None` when turned off
+    #[must_use]
+    pub fn stale_after_days(&self) -> Option<u64> {
+        match self.stale_after_days {
+            Some(0) => None,
+            Some(days) => Some(days),
+            None => Some(30),
+        }
+    }
+}
+
+/// Defaults for `worktree create` flags
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CreateDefaults {
+    /// Fetch from the remotes before creating, as if `--fetch` were passed
+    #[serde(default)]
+    pub fetch: bool,
+}
+
+/// Defaults for `worktree remove` flags
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RemoveDefaults {
+    /// Also delete the worktree's branch, as if `--delete-branch` were passed
+    #[serde(rename = "delete-branch", default)]
+    pub delete_branch: bool,
+    /// Also delete the branch on its remote, as if `--delete-remote` were passed
+    #[serde(rename = "delete-remote", default)]
+    pub delete_remote: bool,
+}
+
+/// Defaults for `worktree update`
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UpdateDefaults {
+    /// How a branch is brought up to date with its base branch
+    #[serde(default)]
+    pub strategy: UpdateStrategy,
+}
+
+/// How `update` brings a branch up to date with its base branch
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum UpdateStrategy {
+    /// Rebase the branch onto the base branch
+    #[default]
+    Rebase,
+    /// Merge the base branch into the branch
+    Merge,
+}
+
+/// Defaults for `worktree finish`
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FinishDefaults {
+    /// How a worktree's branch is merged into the default branch
+    #[serde(default)]
+    pub strategy: FinishStrategy,
+}
+
+/// How `finish` merges a worktree's branch into the default branch
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum FinishStrategy {
+    /// `git merge`, fast-forwarding when possible
+    #[default]
+    Merge,
+    /// `git merge --squash`, committed as one commit
+    Squash,
+    /// `git merge --ff-only`: fail unless the default branch can be fast-forwarded
+    FfOnly,
+}
+
+/// Defaults for `worktree jump` and `worktree path`
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct JumpDefaults {
+    /// How a target that is not an exact name is matched. `None` uses `match` from the
+    /// global config.
+    #[serde(rename = "match", default)]
+    pub match_mode: Option<MatchMode>,
+}
+
+/// Defaults for `worktree pr create`
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PullRequestDefaults {
+    /// The forge CLI that opens the pull request. `None` picks `glab` for remotes on a
+    /// GitLab host and `gh` otherwise.
+    #[serde(default)]
+    pub tool: Option<ForgeTool>,
+    /// Open pull requests as drafts
+    #[serde(default)]
+    pub draft: bool,
+    /// Show each branch's pull request state in `list` and `status`, like `--pr`
+    #[serde(rename = "show-status", default)]
+    pub show_status: bool,
+}
+
+/// The command-line client of a code forge
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum ForgeTool {
+    /// GitHub CLI
+    Gh,
+    /// GitLab CLI (merge requests)
+    Glab,
+}
+
+/// Writes an `.envrc` with per-worktree variables into each new worktree (`[direnv]`)
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Direnv {
+    /// Generate the `.envrc` when a worktree is created
+    #[serde(default)]
+    pub enabled: bool,
+    /// Template file, relative to the repository root, rendered instead of the generated
+    /// variables. `{name}`, `{branch}`, `{path}`, `{repo}`, and `{origin}` are replaced.
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Run `direnv allow` on the new `.envrc`
+    #[serde(default)]
+    pub allow: bool,
+}
+
+/// Copies and adapts `.devcontainer/` configurations in new worktrees so the
+/// containers of two worktrees do not collide (`[devcontainer]`)
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Devcontainer {
+    /// Adapt devcontainer configurations when a worktree is created
+    #[serde(default)]
+    pub adapt: bool,
+    /// Template for the configuration's display `name` (default `{repo} ({branch})`)
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Template for the container name passed to `docker run` (default `{repo}-{name}`)
+    #[serde(rename = "container-name", default)]
+    pub container_name: Option<String>,
+}
+
+/// Gives each new worktree its own Docker Compose project name (`[compose]`), so the
+/// stacks of several worktrees can run side by side
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Compose {
+    /// Set the project name when a worktree is created
+    #[serde(default)]
+    pub enabled: bool,
+    /// Template for the project name (default `{repo}-{name}`)
+    #[serde(rename = "project-name", default)]
+    pub project_name: Option<String>,
+    /// Where the project name is written
+    #[serde(default)]
+    pub target: ComposeTarget,
+}
+
+/// Where the Compose project name is written
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum ComposeTarget {
+    /// `COMPOSE_PROJECT_NAME` in the worktree's `.env`
+    #[default]
+    Env,
+    /// The top-level `name` in `compose.override.yaml`
+    Override,
+}
+
+/// Shares build caches between worktrees (`[build-cache]`), so new worktrees do not
+/// start with a cold build
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BuildCache {
+    /// Shared cache directory: absolute, `~/`-relative, or relative to the repository
+    /// root (default `<storage root>/<repo>/.build-cache`)
+    #[serde(default)]
+    pub dir: Option<String>,
+    /// Worktree paths (such as `target` or `.gradle`) symlinked to the same path in the
+    /// shared directory
+    #[serde(default)]
+    pub link: Option<Vec<String>>,
+    /// Files written into new worktrees, keyed by path. `{cache}` in the content is
+    /// replaced by the shared directory, e.g. a `.cargo/config.toml` setting `target-dir`.
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+}
+
+impl BuildCache {
+    /// Returns true if anything is linked or written
+    #[must_use]
+    pub fn is_configured(&self) -> bool {
+        self.link.as_ref().is_some_and(|link| !link.is_empty()) || !self.files.is_empty()
+    }
+}
+
+/// How heavyweight ignored directories such as `node_modules` get into new worktrees
+/// (`[dependency-dirs]`). Copy patterns exclude them, since copying them is slow.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DependencyDirs {
+    /// What to do at create time
+    #[serde(default)]
+    pub strategy: DependencyStrategy,
+    /// Directories (relative paths or globs such as `packages/*/node_modules`) handled
+    /// by `hardlink-clone` (default `["node_modules"]`)
+    #[serde(default)]
+    pub paths: Option<Vec<String>>,
+    /// Shell command run in the new worktree by the `command` strategy, e.g.
+    /// `pnpm install --prefer-offline`
+    #[serde(default)]
+    pub command: Option<String>,
+}
+
+impl DependencyDirs {
+    /// The configured paths, or `node_modules`
+    #[must_use]
+    pub fn paths(&self) -> Vec<String> {
+        self.paths
+            .clone()
+            .unwrap_or_else(|| vec!["node_modules".to_string()])
+    }
+}
+
+/// Strategy for dependency directories in new worktrees
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum DependencyStrategy {
+    /// Leave them out; install them yourself
+    #[default]
+    Skip,
+    /// Recreate them from the main repository with hard links to its files
+    HardlinkClone,
+    /// Run `command` in the new worktree
+    Command,
+}
+
+/// Sets up mise or asdf tool versions in new worktrees (`[toolchain]`)
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Toolchain {
+    /// Copy untracked `mise.toml`/`.tool-versions` files into new worktrees and trust
+    /// mise configurations
+    #[serde(default)]
+    pub enabled: bool,
+    /// Also install the tool versions (`mise install`, or `asdf install` without mise)
+    #[serde(default)]
+    pub install: bool,
+}
+
+/// Git LFS handling in new worktrees (`[lfs]`)
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Lfs {
+    /// What to do when the worktree uses LFS
+    #[serde(default)]
+    pub mode: LfsMode,
+}
+
+/// What `create` does in worktrees whose `.gitattributes` use the LFS filter
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum LfsMode {
+    /// Run `git lfs pull` when git-lfs is installed, otherwise warn
+    #[default]
+    Pull,
+    /// Only warn that the worktree contains LFS pointer files
+    Warn,
+    /// Do nothing
+    Off,
+}
+
+/// Descriptions written to new branches (`[branch-description]`)
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BranchDescription {
+    /// Description template, with the same placeholders as the `[direnv]` template
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Ask for a description when `create` makes a new branch without `--description`
+    #[serde(default)]
+    pub prompt: bool,
+}
+
+/// Makes the repository's git hooks work in new worktrees (`[git-hooks]`)
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GitHooks {
+    /// Set the worktree's `core.hooksPath` to the main repository's hooks directory
+    #[serde(default)]
+    pub share: bool,
+    /// Hook installer run in the new worktree, e.g. `npx husky` or `lefthook install`
+    #[serde(default)]
+    pub install: Option<String>,
+}
+
+/// A named way to open a worktree (`[open.<name>]`), such as an editor, file manager,
+/// or terminal
+///
+/// Each command is a program given the worktree path, or a template containing
+/// `{path}`. The platform keys let one shared config work on every OS.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct OpenTarget {
+    /// Command used when no platform-specific command applies
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Command on macOS
+    #[serde(default)]
+    pub macos: Option<String>,
+    /// Command on Linux
+    #[serde(default)]
+    pub linux: Option<String>,
+    /// Command on Windows
+    #[serde(default)]
+    pub windows: Option<String>,
+}
+
+impl OpenTarget {
+    /// The command for the platform this binary runs on, falling back to `command`
+    #[must_use]
+    pub fn platform_command(&self) -> Option<&str> {
+        let platform = match std::env::consts::OS {
+            "macos" => self.macos.as_deref(),
+            "linux" => self.linux.as_deref(),
+            "windows" => self.windows.as_deref(),
+            _ => None,
+        };
+        platform.or(self.command.as_deref())
+    }
+}
+
+/// Where a repository's worktrees are created (`layout`)
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Layout {
+    /// In the repository's directory under the storage root (`<storage root>/<repo>/`)
+    #[default]
+    Central,
+    /// In `<repo>-worktrees/` next to the main repository
+    Sibling,
+}
+
+/// Implementation used for git operations (`git-backend`)
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum GitBackend {
+    /// gitoxide when built with the `gix` feature, otherwise libgit2
+    #[default]
+    Auto,
+    /// libgit2 through the `git2` crate
+    Libgit2,
+    /// gitoxide; requires the `gix` feature
+    Gix,
+    /// The system `git` binary, for setups libgit2 does not handle (credential
+    /// helpers, fsmonitor, partial clones)
+    Cli,
+}
+
+impl GitBackend {
+    /// Reads just the `git-backend` setting from the repository's config file. Falls
+    /// back to [`GitBackend::Auto`] silently if the file is missing or invalid; the full
+    /// config load reports such problems.
+    #[must_use]
+    pub fn from_repo(repo_path: &Path) -> Self {
+        let Ok(content) = fs::read_to_string(WorktreeConfig::path(repo_path)) else {
+            return Self::default();
+        };
+        toml::from_str::<toml::Table>(&content)
+            .ok()
+            .and_then(|table| table.get("git-backend").cloned())
+            .and_then(|value| value.try_into().ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Branches that are always protected, in addition to configured patterns and the
+/// repository's detected default branch
+const BUILTIN_PROTECTED_BRANCHES: &[&str] = &["main", "master"];
+
+/// Set of branch patterns that must never be deleted
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProtectedBranches {
+    patterns: Vec<String>,
+}
+
+impl ProtectedBranches {
+    /// Returns true if `branch` matches any protected name or glob
+    #[must_use]
+    pub fn is_protected(&self, branch: &str) -> bool {
+        self.patterns.iter().any(|pattern| {
+            pattern == branch || glob::Pattern::new(pattern).is_ok_and(|glob| glob.matches(branch))
+        })
+    }
+
+    /// The protected names and globs, in the order they were added
+    #[must_use]
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    fn add(&mut self, pattern: &str) {
+        if !self.patterns.iter().any(|p| p == pattern) {
+            self.patterns.push(pattern.to_string());
+        }
+    }
+}
+
 impl Default for WorktreeConfig {
     fn default() -> Self {
         Self {
+            protected_branches: None,
+            branch_prefix: None,
             copy_patterns: CopyPatterns {
                 include: Some(Self::default_include_patterns()),
                 exclude: Some(Self::default_exclude_patterns()),
             },
             symlink_patterns: SymlinkPatterns { include: None },
             on_create: OnCreate { commands: None },
+            retention: Retention::default(),
+            status: StatusDefaults::default(),
+            create: CreateDefaults::default(),
+            remove: RemoveDefaults::default(),
+            update: UpdateDefaults::default(),
+            finish: FinishDefaults::default(),
+            pr: PullRequestDefaults::default(),
+            jump: JumpDefaults::default(),
+            direnv: Direnv::default(),
+            devcontainer: Devcontainer::default(),
+            compose: Compose::default(),
+            build_cache: BuildCache::default(),
+            dependency_dirs: DependencyDirs::default(),
+            toolchain: Toolchain::default(),
+            lfs: Lfs::default(),
+            git_hooks: GitHooks::default(),
+            branch_description: BranchDescription::default(),
+            inherit_git_config: None,
+            git_backend: GitBackend::default(),
+            layout: Layout::default(),
+            editor: None,
+            open: BTreeMap::new(),
         }
     }
 }
@@ -89,20 +675,38 @@ impl WorktreeConfig {
         ]
     }
 
+    /// Location of the repository's config file: `.worktree-config.toml` in a
+    /// [`config_override`] directory if there is one, else at the repository root
+    #[must_use]
+    pub fn path(repo_path: &Path) -> PathBuf {
+        config_override()
+            .filter(|path| path.is_dir())
+            .map(|dir| dir.join(REPO_CONFIG_FILE_NAME))
+            .filter(|path| path.exists())
+            .unwrap_or_else(|| repo_path.join(REPO_CONFIG_FILE_NAME))
+    }
+
     /// Loads worktree configuration from a repository with robust error handling.
     ///
     /// # Errors
     /// Only returns an error if the file system operation fails (e.g., permission denied).
     /// TOML parsing errors are handled gracefully with warnings and fallback to defaults.
     pub fn load_from_repo(repo_path: &Path) -> Result<Self> {
-        let config_path = repo_path.join(".worktree-config.toml");
+        let config_path = Self::path(repo_path);
 
         if !config_path.exists() {
+            debug!(
+                "No {}; using the default configuration",
+                config_path.display()
+            );
             return Ok(Self::default());
         }
+        debug!("Loading {}", config_path.display());
 
-        let content = fs::read_to_string(&config_path)
-            .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
+        let content = fs::read_to_string(&config_path).map_err(|source| ConfigError::Read {
+            path: config_path.clone(),
+            source,
+        })?;
 
         // Handle empty/blank files
         if content.trim().is_empty() {
@@ -113,14 +717,76 @@ impl WorktreeConfig {
         match toml::from_str::<WorktreeConfig>(&content) {
             Ok(config) => Ok(config.merged_with_defaults()),
             Err(e) => {
-                eprintln!("Warning: Invalid TOML syntax in .worktree-config.toml:");
-                eprintln!("  {}", e);
-                eprintln!("  Using default configuration. Please fix the syntax and try again.");
+                warn!(
+                    "Invalid TOML syntax in .worktree-config.toml:\n  {}\n  Using default configuration. Please fix the syntax and try again.",
+                    e
+                );
                 Ok(Self::default())
             }
         }
     }
 
+    /// The configured `branch-prefix` with `{user}` replaced by the login name (`$USER`).
+    /// `None` when no prefix is set, or when it needs a login name and there is none.
+    #[must_use]
+    pub fn branch_prefix(&self) -> Option<String> {
+        let template = self.branch_prefix.as_deref()?;
+        if !template.contains(USER_PLACEHOLDER) {
+            return Some(template.to_string());
+        }
+        let user = ["USER", "USERNAME"]
+            .into_iter()
+            .filter_map(std::env::var_os)
+            .find(|user| !user.is_empty());
+        match user {
+            Some(user) => Some(template.replace(USER_PLACEHOLDER, &user.to_string_lossy())),
+            None => {
+                warn!(
+                    "branch-prefix {:?} needs $USER, which is not set; not prefixing",
+                    template
+                );
+                None
+            }
+        }
+    }
+
+    /// Builds the protected branch set: `main`, `master`, the repository's default branch
+    /// (if known), and the configured `protected-branches` patterns
+    #[must_use]
+    pub fn protected_branches(&self, default_branch: Option<&str>) -> ProtectedBranches {
+        let mut protected = ProtectedBranches::default();
+        for branch in BUILTIN_PROTECTED_BRANCHES {
+            protected.add(branch);
+        }
+        if let Some(default_branch) = default_branch {
+            protected.add(default_branch);
+        }
+        for pattern in self.protected_branches.iter().flatten() {
+            protected.add(pattern);
+        }
+        protected
+    }
+
+    /// Whether new worktrees inherit the main repository's git config
+    /// (`inherit-git-config`, default true)
+    #[must_use]
+    pub fn inherits_git_config(&self) -> bool {
+        self.inherit_git_config.unwrap_or(true)
+    }
+
+    /// Restricts copying to the given include patterns, keeping the configured excludes
+    /// and symlink patterns. Used by `sync-config --only`.
+    #[must_use]
+    pub fn restricted_to(self, include_patterns: Vec<String>) -> Self {
+        Self {
+            copy_patterns: CopyPatterns {
+                include: Some(include_patterns),
+                exclude: self.copy_patterns.exclude,
+            },
+            ..self
+        }
+    }
+
     /// Merges user configuration with defaults.
     #[must_use]
     pub fn merged_with_defaults(self) -> Self {
@@ -144,12 +810,36 @@ impl WorktreeConfig {
         }
 
         Self {
+            protected_branches: self.protected_branches,
+            branch_prefix: self.branch_prefix,
             copy_patterns: CopyPatterns {
                 include: Some(merged_includes),
                 exclude: Some(merged_excludes),
             },
             symlink_patterns: self.symlink_patterns,
             on_create: self.on_create,
+            retention: self.retention,
+            status: self.status,
+            create: self.create,
+            remove: self.remove,
+            update: self.update,
+            finish: self.finish,
+            pr: self.pr,
+            jump: self.jump,
+            direnv: self.direnv,
+            devcontainer: self.devcontainer,
+            compose: self.compose,
+            build_cache: self.build_cache,
+            dependency_dirs: self.dependency_dirs,
+            toolchain: self.toolchain,
+            lfs: self.lfs,
+            git_hooks: self.git_hooks,
+            branch_description: self.branch_description,
+            inherit_git_config: self.inherit_git_config,
+            git_backend: self.git_backend,
+            layout: self.layout,
+            editor: self.editor,
+            open: self.open,
         }
     }
 }
@@ -0,0 +1,146 @@
+//! Failure modes that callers may want to tell apart.
+//!
+//! The [`git`](crate::git), [`storage`](crate::storage), and [`config`](crate::config)
+//! modules return their own error types ([`GitError`], [`StorageError`],
+//! [`ConfigError`]). Commands return [`anyhow::Result`] and add [`WorktreeError`] for
+//! command-level failures; any of these can be recovered from the error chain by
+//! downcasting. The CLI maps them to distinct exit codes with [`exit_code`] so shell
+//! scripts can branch on them.
+
+use std::path::PathBuf;
+
+use crate::config::ConfigError;
+use crate::git::GitError;
+use crate::storage::StorageError;
+
+/// Exit code for errors without a dedicated code
+pub const EXIT_FAILURE: u8 = 1;
+
+/// Exit code clap uses for invalid command-line usage
+pub const EXIT_USAGE: u8 = 2;
+
+/// Command-level failures
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum WorktreeError {
+    /// A worktree already exists where a new one would go
+    #[error("Worktree '{feature}' already exists at: {}", path.display())]
+    WorktreeExists { feature: String, path: PathBuf },
+    /// The worktree directory does not exist
+    #[error("Worktree path does not exist: {}", path.display())]
+    WorktreeMissing { path: PathBuf },
+    /// No worktree matches the given name
+    #[error("No worktree found matching '{target}'")]
+    NoMatchingWorktree { target: String },
+    /// More than one worktree matches the given name; `candidates` lists the matches
+    #[error("Ambiguous worktree name '{target}'")]
+    AmbiguousWorktree {
+        target: String,
+        candidates: Vec<String>,
+    },
+    /// The worktree has uncommitted changes that the operation would lose
+    #[error("Worktree has uncommitted changes: {}", path.display())]
+    DirtyWorktree { path: PathBuf },
+}
+
+impl WorktreeError {
+    /// Finds the first `WorktreeError` in an error's chain, including errors that were
+    /// wrapped with context
+    #[must_use]
+    pub fn find(error: &anyhow::Error) -> Option<&WorktreeError> {
+        error.chain().find_map(|cause| cause.downcast_ref())
+    }
+}
+
+/// The process exit code for an error: the code of the first cause in its chain that
+/// has one, or [`EXIT_FAILURE`]
+///
+/// | Code | Error                                                  |
+/// | ---- | ------------------------------------------------------ |
+/// | 3    | [`GitError::NotARepo`]                                 |
+/// | 4    | [`WorktreeError::WorktreeMissing`], [`WorktreeError::NoMatchingWorktree`] |
+/// | 5    | [`WorktreeError::AmbiguousWorktree`]                   |
+/// | 6    | [`WorktreeError::WorktreeExists`]                      |
+/// | 7    | [`GitError::BranchMissing`]                            |
+/// | 8    | [`WorktreeError::DirtyWorktree`]                       |
+/// | 9    | [`StorageError::Locked`]                               |
+/// | 10   | [`StorageError::Corrupt`]                              |
+/// | 11   | [`ConfigError`]                                        |
+#[must_use]
+pub fn exit_code(error: &anyhow::Error) -> u8 {
+    error
+        .chain()
+        .find_map(cause_exit_code)
+        .unwrap_or(EXIT_FAILURE)
+}
+
+fn cause_exit_code(cause: &(dyn std::error::Error + 'static)) -> Option<u8> {
+    if let Some(error) = cause.downcast_ref::<WorktreeError>() {
+        return Some(match error {
+            WorktreeError::WorktreeMissing { .. } | WorktreeError::NoMatchingWorktree { .. } => 4,
+            WorktreeError::AmbiguousWorktree { .. } => 5,
+            WorktreeError::WorktreeExists { .. } => 6,
+            WorktreeError::DirtyWorktree { .. } => 8,
+        });
+    }
+    if let Some(error) = cause.downcast_ref::<GitError>() {
+        return match error {
+            GitError::NotARepo { .. } => Some(3),
+            GitError::BranchMissing { .. } => Some(7),
+            _ => None,
+        };
+    }
+    if let Some(error) = cause.downcast_ref::<StorageError>() {
+        return match error {
+            StorageError::Locked { .. } => Some(9),
+            StorageError::Corrupt { .. } => Some(10),
+            _ => None,
+        };
+    }
+    cause.downcast_ref::<ConfigError>().map(|_| 11)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code_found_through_context() {
+        let error = anyhow::Error::new(WorktreeError::NoMatchingWorktree {
+            target: "feat".to_string(),
+        })
+        .context("Failed to jump");
+
+        assert_eq!(exit_code(&error), 4);
+        assert!(matches!(
+            WorktreeError::find(&error),
+            Some(WorktreeError::NoMatchingWorktree { .. })
+        ));
+    }
+
+    #[test]
+    fn test_exit_code_for_module_errors() {
+        let not_a_repo = anyhow::Error::new(GitError::NotARepo {
+            path: PathBuf::from("/tmp"),
+        });
+        assert_eq!(exit_code(&not_a_repo), 3);
+
+        let locked = anyhow::Error::new(StorageError::Locked {
+            holder: "PID 1".to_string(),
+            waited_secs: 10,
+        })
+        .context("Failed to store origin");
+        assert_eq!(exit_code(&locked), 9);
+
+        let other_git = anyhow::Error::new(GitError::PushRejected {
+            remote: "origin".to_string(),
+            reason: "non-fast-forward".to_string(),
+        });
+        assert_eq!(exit_code(&other_git), EXIT_FAILURE);
+    }
+
+    #[test]
+    fn test_exit_code_defaults_to_failure() {
+        assert_eq!(exit_code(&anyhow::anyhow!("something else")), EXIT_FAILURE);
+    }
+}
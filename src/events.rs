@@ -0,0 +1,195 @@
+//! Machine-readable progress events (`--json-events`).
+//!
+//! With events enabled, `create` and `sync-config` write one JSON object per line to
+//! stderr as they work, and log output (warnings included) becomes `warning`, `error`,
+//! and `log` events instead of text. Every object carries its kind in `"event"`:
+//!
+//! ```text
+//! {"event":"step-started","step":"copy-config"}
+//! {"event":"file-copied","path":".env"}
+//! {"event":"worktree-created","feature":"auth","branch":"feature/auth","path":"/home/me/.worktrees/app/auth"}
+//! ```
+//!
+//! Stdout keeps the human-readable output, so a frontend can show it as is.
+
+use serde::Serialize;
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turns event output on or off (from `--json-events`)
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether events are being written
+#[must_use]
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// One progress event
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum Event<'a> {
+    /// A step of the operation began: `create-worktree`, `inherit-config`,
+    /// `symlink-config`, `copy-config`, `set-up`, `git-hooks`, or `on-create-hooks`.
+    /// Symlinks and post-create hooks are only reported when some are configured.
+    StepStarted {
+        /// Name of the step
+        step: &'a str,
+    },
+    /// `create` made a new branch, from `from` when given
+    BranchCreated {
+        /// The new branch
+        branch: &'a str,
+        /// Starting point of the branch
+        from: Option<&'a str>,
+    },
+    /// A config file was copied, relative to the worktree root
+    FileCopied {
+        /// Copied file
+        path: &'a Path,
+    },
+    /// A config directory was copied, relative to the worktree root
+    DirectoryCopied {
+        /// Copied directory
+        path: &'a Path,
+    },
+    /// A symlink to the main repository was created, relative to the worktree root
+    Symlinked {
+        /// The symlink
+        path: &'a Path,
+        /// What it points to
+        target: &'a Path,
+    },
+    /// Copying config files began, with the number of files and bytes selected
+    CopyStarted {
+        /// Files to copy, counting each file inside copied directories
+        files: u64,
+        /// Their total size
+        bytes: u64,
+    },
+    /// One file of a config copy was written, for progress display. Not written with
+    /// `--json-events`.
+    #[serde(skip)]
+    BytesCopied {
+        /// Size of the file
+        bytes: u64,
+    },
+    /// Copying config files finished
+    CopyFinished {
+        /// Files copied, counting each file inside copied directories
+        files: u64,
+        /// Their total size
+        bytes: u64,
+    },
+    /// A step started an external command: `git lfs pull` (`lfs`), a mise or asdf
+    /// command (`toolchain`), the dependency install command (`dependency-dirs`), the
+    /// hook installer (`git-hooks`), `direnv allow` (`direnv`), or a post-create hook
+    /// (`on-create-hooks`)
+    CommandStarted {
+        /// Step running the command
+        step: &'a str,
+        /// The command line
+        command: &'a str,
+    },
+    /// A command from [`Event::CommandStarted`] succeeded; failures are warnings
+    CommandFinished {
+        /// Step running the command
+        step: &'a str,
+        /// The command line
+        command: &'a str,
+    },
+    /// A step generated a file in the worktree, relative to the worktree root:
+    /// `direnv` wrote `.envrc`, `devcontainer` adapted a configuration, or
+    /// `build-cache` wrote a `[build-cache.files]` entry
+    FileWritten {
+        /// Step that wrote the file
+        step: &'a str,
+        /// The file
+        path: &'a Path,
+    },
+    /// The Compose project name was written to `file` (`[compose]`)
+    ComposeProjectNamed {
+        /// The project name
+        name: &'a str,
+        /// `.env` or the override file
+        file: &'a str,
+    },
+    /// The shared build cache is set up in `dir` (`[build-cache]`)
+    BuildCacheStarted {
+        /// The cache directory
+        dir: &'a Path,
+    },
+    /// A path was linked to the shared build cache, relative to the worktree root
+    CacheLinked {
+        /// The symlink
+        path: &'a Path,
+        /// Its directory in the cache
+        target: &'a Path,
+    },
+    /// A dependency directory was cloned from the main repository with hard links
+    /// (`[dependency-dirs]`), relative to the worktree root
+    DirectoryCloned {
+        /// The cloned directory
+        path: &'a Path,
+        /// Number of files in it
+        files: usize,
+    },
+    /// The worktree uses the main repository's hooks from `dir` (`[git-hooks] share`)
+    HooksShared {
+        /// The hooks directory
+        dir: &'a Path,
+    },
+    /// A warning; the operation goes on
+    Warning {
+        /// The warning text
+        message: &'a str,
+    },
+    /// The operation failed
+    Error {
+        /// The error with its causes
+        message: &'a str,
+    },
+    /// Other log output, shown with `-v` or `WORKTREE_LOG`
+    Log {
+        /// `info`, `debug`, or `trace`
+        level: &'a str,
+        /// Module that logged it
+        target: &'a str,
+        /// The log text
+        message: &'a str,
+    },
+    /// `create` finished
+    WorktreeCreated {
+        /// Feature name of the worktree
+        feature: &'a str,
+        /// Checked-out branch
+        branch: &'a str,
+        /// Worktree directory
+        path: &'a Path,
+    },
+    /// `sync-config` finished
+    ConfigSynced {
+        /// Source directory
+        from: &'a Path,
+        /// Target directory
+        to: &'a Path,
+        /// Number of files and directories copied
+        files: usize,
+    },
+}
+
+/// Writes `event` to stderr as one JSON line, if events are enabled
+pub fn emit(event: &Event<'_>) {
+    if !enabled() {
+        return;
+    }
+    if let Ok(line) = serde_json::to_string(event) {
+        let _ = writeln!(std::io::stderr().lock(), "{}", line);
+    }
+}
@@ -0,0 +1,471 @@
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use tracing::{trace, warn};
+
+use super::error::{GitError, Result, io_error};
+use super::{is_include_directive, resolve_include_path, should_inherit_config_key};
+use crate::traits::{AheadBehind, CommitInfo, GitOperations};
+
+/// [`GitOperations`] that runs the system `git` binary for everything, so credential
+/// helpers, fsmonitor, partial clones, and other features libgit2 does not support
+/// behave exactly as they do on the command line. Selected with `git-backend = "cli"`.
+pub struct CliGitRepo {
+    common_dir: PathBuf,
+    /// The main repository; git runs here even when opened from a linked worktree
+    repo_path: PathBuf,
+}
+
+impl CliGitRepo {
+    /// Opens the git repository containing `path`. From inside a linked worktree this
+    /// opens the main repository the worktree belongs to.
+    ///
+    /// # Errors
+    /// Returns an error if `git` cannot be run or `path` is not inside a git repository
+    pub fn open(path: &Path) -> Result<Self> {
+        let output = git_output(path, ["rev-parse", "--git-common-dir"])?;
+        if !output.status.success() {
+            return Err(GitError::NotARepo {
+                path: path.to_path_buf(),
+            });
+        }
+
+        // Relative to the directory git ran in
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let common_dir = path.join(stdout.trim());
+        let common_dir = common_dir.canonicalize().unwrap_or(common_dir);
+        // A bare repository (`worktree clone`) is its own main repository; otherwise the
+        // main working directory is the parent of the shared `.git` directory
+        let repo_path = if run_git(&common_dir, ["rev-parse", "--is-bare-repository"])? == "true" {
+            common_dir.clone()
+        } else {
+            common_dir
+                .parent()
+                .map_or_else(|| common_dir.clone(), Path::to_path_buf)
+        };
+        Ok(Self {
+            common_dir,
+            repo_path,
+        })
+    }
+
+    /// Runs git in the repository and returns its trimmed stdout
+    fn git<I, S>(&self, args: I) -> Result<String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        run_git(&self.repo_path, args)
+    }
+
+    /// Runs git in the repository and reports whether it exited successfully
+    fn git_succeeds<I, S>(&self, args: I) -> Result<bool>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        Ok(git_output(&self.repo_path, args)?.status.success())
+    }
+
+    /// Short names of the refs under `prefix` (e.g. `main`, `origin/main`, `v1.0`)
+    fn ref_names(&self, prefix: &str) -> Result<Vec<String>> {
+        Ok(self
+            .git(["for-each-ref", "--format=%(refname:lstrip=2)", prefix])?
+            .lines()
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn ensure_branch(&self, branch: &str) -> Result<()> {
+        if self.branch_exists(branch)? {
+            Ok(())
+        } else {
+            Err(GitError::BranchMissing {
+                branch: branch.to_string(),
+            })
+        }
+    }
+
+    /// The branch feature branches are merged into: the target of `origin/HEAD`, or
+    /// else a local `main` or `master`
+    fn integration_branch(&self) -> Result<Option<String>> {
+        if let Ok(target) = self.git(["symbolic-ref", "--short", "refs/remotes/origin/HEAD"]) {
+            if let Some(branch) = target.strip_prefix("origin/") {
+                return Ok(Some(branch.to_string()));
+            }
+        }
+        for branch in ["main", "master"] {
+            if self.branch_exists(branch)? {
+                return Ok(Some(branch.to_string()));
+            }
+        }
+        Ok(None)
+    }
+
+    fn count(&self, range: &str) -> Result<usize> {
+        let count = self.git(["rev-list", "--count", range])?;
+        Ok(count.parse().unwrap_or_default())
+    }
+}
+
+impl GitOperations for CliGitRepo {
+    fn get_repo_path(&self) -> PathBuf {
+        self.repo_path.clone()
+    }
+
+    fn branch_exists(&self, branch_name: &str) -> Result<bool> {
+        self.git_succeeds([
+            "show-ref",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{}", branch_name),
+        ])
+    }
+
+    fn create_worktree(
+        &self,
+        branch_name: &str,
+        worktree_path: &Path,
+        create_branch: bool,
+    ) -> Result<()> {
+        self.create_worktree_from(branch_name, worktree_path, create_branch, None)
+    }
+
+    fn create_worktree_from(
+        &self,
+        branch_name: &str,
+        worktree_path: &Path,
+        create_branch: bool,
+        from_ref: Option<&str>,
+    ) -> Result<()> {
+        let path = worktree_path.as_os_str();
+        if create_branch {
+            let mut args = vec![
+                OsStr::new("worktree"),
+                OsStr::new("add"),
+                OsStr::new("-b"),
+                OsStr::new(branch_name),
+                path,
+            ];
+            args.extend(from_ref.map(OsStr::new));
+            self.git(args)?;
+        } else {
+            self.ensure_branch(branch_name)?;
+            self.git([
+                OsStr::new("worktree"),
+                OsStr::new("add"),
+                path,
+                OsStr::new(branch_name),
+            ])?;
+        }
+        Ok(())
+    }
+
+    fn remove_worktree(&self, worktree_name: &str) -> Result<()> {
+        // Like libgit2's prune: drop git's administrative directory, leaving any
+        // working directory alone
+        let admin_dir = self.common_dir.join("worktrees").join(worktree_name);
+        if !admin_dir.is_dir() {
+            return Err(GitError::WorktreeNotRegistered {
+                name: worktree_name.to_string(),
+                admin_dir,
+            });
+        }
+        std::fs::remove_dir_all(&admin_dir)
+            .map_err(io_error("Failed to remove worktree metadata", &admin_dir))
+    }
+
+    fn list_worktrees(&self) -> Result<Vec<String>> {
+        let worktrees_dir = self.common_dir.join("worktrees");
+        let Ok(entries) = std::fs::read_dir(&worktrees_dir) else {
+            return Ok(Vec::new());
+        };
+
+        let mut names = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(io_error("Failed to list worktrees", &worktrees_dir))?;
+            if let Some(name) = entry.file_name().to_str() {
+                names.push(name.to_string());
+            }
+        }
+        Ok(names)
+    }
+
+    fn delete_branch(&self, branch_name: &str) -> Result<()> {
+        self.ensure_branch(branch_name)?;
+        self.git(["branch", "-D", branch_name])?;
+        Ok(())
+    }
+
+    fn inherit_config(&self, worktree_path: &Path) -> Result<()> {
+        self.git(["config", "extensions.worktreeConfig", "true"])?;
+
+        // NUL-separated pairs of "file:<origin>" and "key\nvalue", without the values
+        // of included files; later entries override earlier ones
+        let listing = self.git(["config", "--list", "-z", "--no-includes", "--show-origin"])?;
+        let mut entries = std::collections::HashMap::new();
+        let mut includes = Vec::new();
+        let mut fields = listing.split('\0').filter(|e| !e.is_empty());
+        while let (Some(origin), Some(entry)) = (fields.next(), fields.next()) {
+            let (key, value) = entry.split_once('\n').unwrap_or((entry, "true"));
+            if is_include_directive(key) {
+                // Relative origins are relative to the directory git ran in
+                let config_file = origin
+                    .strip_prefix("file:")
+                    .map(|file| self.repo_path.join(file));
+                let include = (
+                    key.to_string(),
+                    resolve_include_path(value, config_file.as_deref()),
+                );
+                if !includes.contains(&include) {
+                    includes.push(include);
+                }
+            } else if should_inherit_config_key(key) {
+                entries.insert(key.to_string(), value.to_string());
+            }
+        }
+
+        for (key, value) in entries {
+            if let Err(e) = run_git(worktree_path, ["config", "--worktree", &key, &value]) {
+                warn!("Failed to set config {}: {}", key, e);
+            }
+        }
+        // After the values, so included values override them as in the original files
+        for (key, path) in includes {
+            let result = run_git(
+                worktree_path,
+                [
+                    "config",
+                    "--worktree",
+                    "--fixed-value",
+                    "--replace-all",
+                    &key,
+                    &path,
+                    &path,
+                ],
+            );
+            if let Err(e) = result {
+                warn!("Failed to set config {}: {}", key, e);
+            }
+        }
+        Ok(())
+    }
+
+    fn list_local_branches(&self) -> Result<Vec<String>> {
+        self.ref_names("refs/heads/")
+    }
+
+    fn list_remote_branches(&self) -> Result<Vec<String>> {
+        self.ref_names("refs/remotes/")
+    }
+
+    fn list_tags(&self) -> Result<Vec<String>> {
+        self.ref_names("refs/tags/")
+    }
+
+    fn is_dirty(&self, worktree_path: &Path) -> Result<bool> {
+        let status = run_git(
+            worktree_path,
+            ["status", "--porcelain", "--untracked-files=all"],
+        )?;
+        Ok(!status.is_empty())
+    }
+
+    fn changed_files(&self, worktree_path: &Path) -> Result<Vec<PathBuf>> {
+        let status = run_git(
+            worktree_path,
+            ["status", "--porcelain", "-z", "--untracked-files=all"],
+        )?;
+        // Entries are `XY <path>`, NUL-terminated; renames and copies are followed by
+        // the original path as a separate entry
+        let mut files = Vec::new();
+        let mut entries = status.split('\0').filter(|entry| !entry.is_empty());
+        while let Some(entry) = entries.next() {
+            let Some(path) = entry.get(3..) else {
+                continue;
+            };
+            files.push(PathBuf::from(path));
+            if entry.starts_with(['R', 'C']) {
+                entries.next();
+            }
+        }
+        Ok(files)
+    }
+
+    fn upstream(&self, branch: &str) -> Result<Option<String>> {
+        self.ensure_branch(branch)?;
+        Ok(self
+            .git([
+                "rev-parse",
+                "--abbrev-ref",
+                "--symbolic-full-name",
+                &format!("{}@{{upstream}}", branch),
+            ])
+            .ok())
+    }
+
+    fn ahead_behind(&self, branch: &str) -> Result<Option<AheadBehind>> {
+        if self.upstream(branch)?.is_none() {
+            return Ok(None);
+        }
+        let counts = self.git([
+            "rev-list",
+            "--left-right",
+            "--count",
+            &format!("{0}...{0}@{{upstream}}", branch),
+        ])?;
+        let mut counts = counts
+            .split_whitespace()
+            .map(|n| n.parse().unwrap_or_default());
+        Ok(Some(AheadBehind {
+            ahead: counts.next().unwrap_or_default(),
+            behind: counts.next().unwrap_or_default(),
+        }))
+    }
+
+    fn unpushed_commits(&self, branch: &str) -> Result<usize> {
+        if self.upstream(branch)?.is_some() {
+            return self.count(&format!("{0}@{{upstream}}..{0}", branch));
+        }
+
+        let default_branch = self
+            .integration_branch()?
+            .ok_or_else(|| GitError::NoBaseBranch {
+                branch: branch.to_string(),
+            })?;
+        let remote_default = format!("refs/remotes/origin/{}", default_branch);
+        let base = if self.git_succeeds(["rev-parse", "--verify", "--quiet", &remote_default])? {
+            remote_default
+        } else {
+            default_branch
+        };
+        self.count(&format!("{}..{}", base, branch))
+    }
+
+    fn is_merged_into(&self, branch: &str, target: &str) -> Result<bool> {
+        self.ensure_branch(branch)?;
+        let tip = self.git(["rev-parse", &format!("refs/heads/{}", branch)])?;
+
+        // A branch still at the commit it was created from has no commits of its own
+        let reflog = self
+            .git([
+                "reflog",
+                "show",
+                "--format=%H",
+                &format!("refs/heads/{}", branch),
+            ])
+            .unwrap_or_default();
+        if reflog.lines().last() == Some(tip.as_str()) {
+            return Ok(false);
+        }
+
+        self.git_succeeds(["merge-base", "--is-ancestor", &tip, target])
+    }
+
+    fn last_commit(&self, worktree_path: &Path) -> Result<CommitInfo> {
+        let log = run_git(
+            worktree_path,
+            ["log", "-1", "--format=%H%x00%s%x00%an%x00%ct"],
+        )?;
+        let mut fields = log.split('\0');
+        let mut next = || fields.next().unwrap_or_default().to_string();
+        Ok(CommitInfo {
+            id: next(),
+            summary: next(),
+            author: next(),
+            time: next().parse().unwrap_or_default(),
+        })
+    }
+}
+
+/// Runs git in `dir` and returns its output, whether or not it succeeded
+///
+/// # Errors
+/// Returns an error if git cannot be run
+pub fn git_output<I, S>(dir: &Path, args: I) -> Result<Output>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let args: Vec<S> = args.into_iter().collect();
+    trace!(
+        "git -C {} {}",
+        dir.display(),
+        args.iter()
+            .map(|arg| arg.as_ref().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(io_error("Failed to run git in", dir))
+}
+
+/// Runs git in `dir` and returns its stdout, failing with its stderr if it exits with
+/// an error
+///
+/// # Errors
+/// Returns an error if git cannot be run or fails
+pub fn run_git<I, S>(dir: &Path, args: I) -> Result<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let args: Vec<S> = args.into_iter().collect();
+    let output = git_output(dir, &args)?;
+    if !output.status.success() {
+        return Err(GitError::Command {
+            args: args
+                .iter()
+                .map(|a| a.as_ref().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(" "),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_end()
+        .to_string())
+}
+
+/// Files with unresolved conflicts in the worktree at `dir`
+///
+/// # Errors
+/// Returns an error if git fails
+pub fn conflicted_files(dir: &Path) -> Result<Vec<String>> {
+    Ok(run_git(dir, ["diff", "--name-only", "--diff-filter=U"])?
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
+/// Fetches every remote of the repository at `repo_path`, pruning deleted branches.
+/// Repositories without remotes are left alone.
+///
+/// # Errors
+/// Returns an error carrying git's stderr if `git fetch` fails
+pub fn fetch_all(repo_path: &Path) -> Result<()> {
+    if run_git(repo_path, ["remote"])?.is_empty() {
+        return Ok(());
+    }
+    run_git(repo_path, ["fetch", "--all", "--prune", "--quiet"]).map(drop)
+}
+
+/// Whether the Git LFS extension is installed, so `git lfs` commands run in `dir`
+#[must_use]
+pub fn lfs_installed(dir: &Path) -> bool {
+    git_output(dir, ["lfs", "version"]).is_ok_and(|output| output.status.success())
+}
+
+/// Downloads the LFS files of the worktree at `dir` and replaces its pointer files with
+/// them (`git lfs pull`)
+///
+/// # Errors
+/// Returns an error carrying git's stderr if git cannot be run or the pull fails
+pub fn lfs_pull(dir: &Path) -> Result<()> {
+    run_git(dir, ["lfs", "pull"]).map(drop)
+}
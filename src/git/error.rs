@@ -0,0 +1,119 @@
+use std::path::{Path, PathBuf};
+
+/// Result type for git operations
+pub type Result<T, E = GitError> = std::result::Result<T, E>;
+
+/// Errors from git operations
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum GitError {
+    /// The path is not inside a git repository
+    #[error("Not a git repository: {}", path.display())]
+    NotARepo { path: PathBuf },
+    /// A local branch does not exist
+    #[error("Branch '{branch}' does not exist")]
+    BranchMissing { branch: String },
+    /// A revision (branch, tag, commit) cannot be resolved
+    #[error("Failed to resolve reference '{reference}'")]
+    ReferenceNotFound {
+        reference: String,
+        #[source]
+        source: git2::Error,
+    },
+    /// A revision resolves to something other than a commit
+    #[error("Reference '{reference}' does not point to a commit")]
+    NotACommit {
+        reference: String,
+        #[source]
+        source: git2::Error,
+    },
+    /// The branch has no upstream and the repository has no default branch
+    #[error("No upstream or default branch to compare '{branch}' against")]
+    NoBaseBranch { branch: String },
+    /// Git has no administrative directory for the worktree
+    #[error("Git has no worktree named '{name}' ({} is missing)", admin_dir.display())]
+    WorktreeNotRegistered { name: String, admin_dir: PathBuf },
+    /// The remote is not configured
+    #[error("Failed to find remote '{remote}'")]
+    RemoteMissing {
+        remote: String,
+        #[source]
+        source: git2::Error,
+    },
+    /// Cloning the remote repository failed (unreachable, authentication, ...)
+    #[error("Failed to clone '{url}'")]
+    CloneFailed {
+        url: String,
+        #[source]
+        source: git2::Error,
+    },
+    /// The push could not be performed (network, authentication, ...)
+    #[error("Failed to push to remote '{remote}'")]
+    PushFailed {
+        remote: String,
+        #[source]
+        source: git2::Error,
+    },
+    /// The remote refused the pushed ref update
+    #[error("Remote '{remote}' rejected the push: {reason}")]
+    PushRejected { remote: String, reason: String },
+    /// A force push with lease found the remote branch moved since it was last fetched
+    #[error("{remote}/{branch} changed since it was last fetched; fetch and review it first")]
+    StaleLease { remote: String, branch: String },
+    /// A filesystem operation on `path` failed
+    #[error("{action}: {}", path.display())]
+    Io {
+        action: &'static str,
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    /// A libgit2 call failed during the described step
+    #[error("{action}")]
+    Operation {
+        action: &'static str,
+        #[source]
+        source: git2::Error,
+    },
+    /// The `git` binary exited with an error
+    #[error("`git {args}` failed: {stderr}")]
+    Command { args: String, stderr: String },
+    /// A gitoxide operation failed
+    #[cfg(feature = "gix")]
+    #[error("{action}")]
+    Gix {
+        action: &'static str,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// Any other libgit2 failure
+    #[error(transparent)]
+    Git(#[from] git2::Error),
+}
+
+/// Builds a `map_err` adapter that records which filesystem operation failed on `path`
+pub fn io_error(action: &'static str, path: &Path) -> impl FnOnce(std::io::Error) -> GitError {
+    let path = path.to_path_buf();
+    move |source| GitError::Io {
+        action,
+        path,
+        source,
+    }
+}
+
+/// Builds a `map_err` adapter that records which step a libgit2 error came from
+pub fn operation(action: &'static str) -> impl FnOnce(git2::Error) -> GitError {
+    move |source| GitError::Operation { action, source }
+}
+
+/// Builds a `map_err` adapter that records which gitoxide operation failed
+#[cfg(feature = "gix")]
+pub fn gix_error<E>(action: &'static str) -> impl FnOnce(E) -> GitError
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    move |source| GitError::Gix {
+        action,
+        source: Box::new(source),
+    }
+}
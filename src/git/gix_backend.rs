@@ -0,0 +1,267 @@
+use gix::bstr::ByteSlice;
+use gix::remote::Direction;
+use gix::status::UntrackedFiles;
+use std::path::{Path, PathBuf};
+
+use super::GitRepo;
+use super::error::{GitError, Result, gix_error};
+use crate::traits::{AheadBehind, CommitInfo, GitOperations};
+
+/// [`GitOperations`] backed by gitoxide for read-heavy queries (ref listing, status,
+/// upstream and ahead/behind), which are much faster than libgit2 in large
+/// repositories. Operations that write (creating and removing worktrees, deleting
+/// branches, inheriting config) and the merge and unpushed-commit checks are delegated
+/// to libgit2.
+pub struct GixRepo {
+    repo: gix::Repository,
+    git2: GitRepo,
+}
+
+impl GixRepo {
+    /// Opens the git repository containing `path`
+    ///
+    /// # Errors
+    /// Returns an error if `path` is not inside a git repository
+    pub fn open(path: &Path) -> Result<Self> {
+        let git2 = GitRepo::open(path)?;
+        let repo =
+            gix::open(git2.get_repo_path()).map_err(gix_error("Failed to open repository"))?;
+        Ok(Self { repo, git2 })
+    }
+
+    /// Short names (e.g. `main`, `origin/main`, `v1.0`) of the references under `prefix`
+    fn reference_names(&self, prefix: &str) -> Result<Vec<String>> {
+        let platform = self
+            .repo
+            .references()
+            .map_err(gix_error("Failed to read references"))?;
+        let references = platform
+            .prefixed(prefix)
+            .map_err(gix_error("Failed to read references"))?;
+
+        let mut names = Vec::new();
+        for reference in references {
+            let reference = reference.map_err(|source| GitError::Gix {
+                action: "Failed to read reference",
+                source,
+            })?;
+            names.push(reference.name().shorten().to_string());
+        }
+        Ok(names)
+    }
+
+    /// Full name of the existing remote-tracking ref that local `branch` tracks
+    fn upstream_ref(&self, branch: &str) -> Result<Option<gix::refs::FullName>> {
+        if !self.branch_exists(branch)? {
+            return Err(GitError::BranchMissing {
+                branch: branch.to_string(),
+            });
+        }
+        let local: gix::refs::FullName = format!("refs/heads/{}", branch)
+            .try_into()
+            .map_err(gix_error("Invalid branch name"))?;
+
+        let Some(tracking) = self
+            .repo
+            .branch_remote_tracking_ref_name(local.as_ref(), Direction::Fetch)
+        else {
+            return Ok(None);
+        };
+        let tracking = tracking
+            .map_err(gix_error("Failed to read upstream configuration"))?
+            .into_owned();
+
+        let exists = self
+            .repo
+            .try_find_reference(tracking.as_ref())
+            .map_err(gix_error("Failed to read upstream"))?
+            .is_some();
+        Ok(exists.then_some(tracking))
+    }
+
+    fn peel_to_id(&self, name: &str) -> Result<gix::ObjectId> {
+        let mut reference = self
+            .repo
+            .find_reference(name)
+            .map_err(gix_error("Failed to find reference"))?;
+        Ok(reference
+            .peel_to_id()
+            .map_err(gix_error("Failed to resolve reference"))?
+            .detach())
+    }
+
+    /// Commits reachable from `tip` but not from `hidden`
+    fn count_only_in(&self, tip: gix::ObjectId, hidden: gix::ObjectId) -> Result<usize> {
+        let walk = self
+            .repo
+            .rev_walk([tip])
+            .with_hidden([hidden])
+            .all()
+            .map_err(gix_error("Failed to walk history"))?;
+
+        let mut count = 0;
+        for info in walk {
+            info.map_err(gix_error("Failed to walk history"))?;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+impl GitOperations for GixRepo {
+    fn get_repo_path(&self) -> PathBuf {
+        self.git2.get_repo_path().to_path_buf()
+    }
+
+    fn branch_exists(&self, branch_name: &str) -> Result<bool> {
+        Ok(self
+            .repo
+            .try_find_reference(format!("refs/heads/{}", branch_name).as_str())
+            .map_err(gix_error("Failed to look up branch"))?
+            .is_some())
+    }
+
+    fn create_worktree(
+        &self,
+        branch_name: &str,
+        worktree_path: &Path,
+        create_branch: bool,
+    ) -> Result<()> {
+        self.git2
+            .create_worktree(branch_name, worktree_path, create_branch)
+    }
+
+    fn create_worktree_from(
+        &self,
+        branch_name: &str,
+        worktree_path: &Path,
+        create_branch: bool,
+        from_ref: Option<&str>,
+    ) -> Result<()> {
+        self.git2
+            .create_worktree_from(branch_name, worktree_path, create_branch, from_ref)
+    }
+
+    fn remove_worktree(&self, worktree_name: &str) -> Result<()> {
+        self.git2.remove_worktree(worktree_name)
+    }
+
+    fn list_worktrees(&self) -> Result<Vec<String>> {
+        let worktrees = self
+            .repo
+            .worktrees()
+            .map_err(gix_error("Failed to list worktrees"))?;
+        Ok(worktrees
+            .iter()
+            .map(|proxy| proxy.id().to_string())
+            .collect())
+    }
+
+    fn delete_branch(&self, branch_name: &str) -> Result<()> {
+        self.git2.delete_branch(branch_name)
+    }
+
+    fn inherit_config(&self, worktree_path: &Path) -> Result<()> {
+        self.git2.inherit_config(worktree_path)
+    }
+
+    fn list_local_branches(&self) -> Result<Vec<String>> {
+        self.reference_names("refs/heads/")
+    }
+
+    fn list_remote_branches(&self) -> Result<Vec<String>> {
+        self.reference_names("refs/remotes/")
+    }
+
+    fn list_tags(&self) -> Result<Vec<String>> {
+        self.reference_names("refs/tags/")
+    }
+
+    fn is_dirty(&self, worktree_path: &Path) -> Result<bool> {
+        let repo = gix::open(worktree_path).map_err(gix_error("Failed to open worktree"))?;
+        let mut changes = repo
+            .status(gix::progress::Discard)
+            .map_err(gix_error("Failed to read status"))?
+            .untracked_files(UntrackedFiles::Files)
+            .into_iter(Vec::new())
+            .map_err(gix_error("Failed to read status"))?;
+
+        match changes.next() {
+            Some(change) => {
+                change.map_err(gix_error("Failed to read status"))?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn changed_files(&self, worktree_path: &Path) -> Result<Vec<PathBuf>> {
+        let repo = gix::open(worktree_path).map_err(gix_error("Failed to open worktree"))?;
+        let changes = repo
+            .status(gix::progress::Discard)
+            .map_err(gix_error("Failed to read status"))?
+            .untracked_files(UntrackedFiles::Files)
+            .into_iter(Vec::new())
+            .map_err(gix_error("Failed to read status"))?;
+
+        let mut files = Vec::new();
+        for change in changes {
+            let change = change.map_err(gix_error("Failed to read status"))?;
+            let path = PathBuf::from(change.location().to_str_lossy().as_ref());
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+        Ok(files)
+    }
+
+    fn upstream(&self, branch: &str) -> Result<Option<String>> {
+        Ok(self
+            .upstream_ref(branch)?
+            .map(|tracking| tracking.as_ref().shorten().to_string()))
+    }
+
+    fn ahead_behind(&self, branch: &str) -> Result<Option<AheadBehind>> {
+        let Some(tracking) = self.upstream_ref(branch)? else {
+            return Ok(None);
+        };
+        let local = self.peel_to_id(&format!("refs/heads/{}", branch))?;
+        let upstream = self.peel_to_id(&tracking.to_string())?;
+
+        Ok(Some(AheadBehind {
+            ahead: self.count_only_in(local, upstream)?,
+            behind: self.count_only_in(upstream, local)?,
+        }))
+    }
+
+    fn unpushed_commits(&self, branch: &str) -> Result<usize> {
+        self.git2.unpushed_commits(branch)
+    }
+
+    fn is_merged_into(&self, branch: &str, target: &str) -> Result<bool> {
+        self.git2.is_merged_into(branch, target)
+    }
+
+    fn last_commit(&self, worktree_path: &Path) -> Result<CommitInfo> {
+        let repo = gix::open(worktree_path).map_err(gix_error("Failed to open worktree"))?;
+        let commit = repo
+            .head_commit()
+            .map_err(gix_error("Failed to read HEAD commit"))?;
+        let message = commit
+            .message()
+            .map_err(gix_error("Failed to read HEAD commit"))?;
+        let author = commit
+            .author()
+            .map_err(gix_error("Failed to read HEAD commit"))?;
+
+        Ok(CommitInfo {
+            id: commit.id.to_string(),
+            summary: message.summary().to_str_lossy().into_owned(),
+            author: author.name.to_str_lossy().into_owned(),
+            time: commit
+                .time()
+                .map_err(gix_error("Failed to read HEAD commit"))?
+                .seconds,
+        })
+    }
+}
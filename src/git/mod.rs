@@ -1,29 +1,504 @@
-use anyhow::{Context, Result};
+mod cli;
+mod error;
+#[cfg(feature = "gix")]
+mod gix_backend;
+
 use git2::{BranchType, Repository};
 use std::collections::HashMap;
+use std::fs;
 use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+use crate::config::GitBackend;
+use crate::traits::{AheadBehind, CommitInfo, GitOperations};
+pub use cli::{
+    CliGitRepo, conflicted_files, fetch_all, git_output, lfs_installed, lfs_pull, run_git,
+};
+pub use error::{GitError, Result};
+use error::{io_error, operation};
+#[cfg(feature = "gix")]
+pub use gix_backend::GixRepo;
 
-use crate::traits::GitOperations;
+/// Opens the repository containing `path` with the backend chosen by `git-backend` in
+/// its `.worktree-config.toml`. The default picks `GixRepo` when built with the `gix`
+/// feature and [`GitRepo`] otherwise; `"cli"` picks [`CliGitRepo`].
+///
+/// # Errors
+/// Returns an error if `path` is not inside a git repository
+pub fn open_backend(path: &Path) -> Result<Box<dyn GitOperations>> {
+    let repo = GitRepo::open(path)?;
+    let backend = GitBackend::from_repo(repo.get_repo_path());
+    debug!(
+        "git-backend for {}: {:?}",
+        repo.get_repo_path().display(),
+        backend
+    );
+    match backend {
+        GitBackend::Cli => Ok(Box::new(CliGitRepo::open(path)?)),
+        #[cfg(feature = "gix")]
+        GitBackend::Auto | GitBackend::Gix => Ok(Box::new(GixRepo::open(path)?)),
+        #[cfg(not(feature = "gix"))]
+        GitBackend::Gix => {
+            warn!("git-backend = \"gix\" needs worktree built with --features gix; using libgit2");
+            Ok(Box::new(repo))
+        }
+        _ => Ok(Box::new(repo)),
+    }
+}
+
+/// The description of `branch` (`branch.<name>.description`), if it has a non-empty one
+#[must_use]
+pub fn branch_description(repo_path: &Path, branch: &str) -> Option<String> {
+    let repo = Repository::open(repo_path).ok()?;
+    let config = repo.config().ok()?;
+    config
+        .get_string(&format!("branch.{}.description", branch))
+        .ok()
+        .filter(|description| !description.trim().is_empty())
+}
+
+/// Sets the description of `branch`, like `git branch --edit-description`
+///
+/// # Errors
+/// Returns an error if the repository cannot be opened or its config cannot be written
+pub fn set_branch_description(repo_path: &Path, branch: &str, description: &str) -> Result<()> {
+    let repo = Repository::open(repo_path).map_err(operation("Failed to open repository"))?;
+    repo.config()
+        .map_err(operation("Failed to get repository config"))?
+        .set_str(&format!("branch.{}.description", branch), description)
+        .map_err(operation("Failed to set branch description"))
+}
+
+/// Makes local `branch` track the remote-tracking branch `upstream` (such as
+/// `origin/feature/auth`), like `git branch --set-upstream-to`
+///
+/// # Errors
+/// Returns an error if the repository or either branch cannot be found
+pub fn set_branch_upstream(repo_path: &Path, branch: &str, upstream: &str) -> Result<()> {
+    let repo = Repository::open(repo_path).map_err(operation("Failed to open repository"))?;
+    repo.find_branch(branch, BranchType::Local)
+        .map_err(operation("Failed to find branch"))?
+        .set_upstream(Some(upstream))
+        .map_err(operation("Failed to set upstream"))
+}
+
+/// The branch `branch` was started from by `worktree create --from`
+/// (`branch.<name>.worktree-base`), if one was recorded
+#[must_use]
+pub fn branch_base(repo_path: &Path, branch: &str) -> Option<String> {
+    let repo = Repository::open(repo_path).ok()?;
+    let config = repo.config().ok()?;
+    config
+        .get_string(&format!("branch.{}.worktree-base", branch))
+        .ok()
+}
+
+/// Records the branch `branch` was started from, for `worktree update`
+///
+/// # Errors
+/// Returns an error if the repository cannot be opened or its config cannot be written
+pub fn set_branch_base(repo_path: &Path, branch: &str, base: &str) -> Result<()> {
+    let repo = Repository::open(repo_path).map_err(operation("Failed to open repository"))?;
+    repo.config()
+        .map_err(operation("Failed to get repository config"))?
+        .set_str(&format!("branch.{}.worktree-base", branch), base)
+        .map_err(operation("Failed to record base branch"))
+}
+
+/// Points the worktree at the main repository's hooks by setting `core.hooksPath` in
+/// the worktree's own config, and returns the hooks directory
+///
+/// A relative `core.hooksPath` (such as husky's `.husky/_`) is resolved against the
+/// main repository, since the generated hooks usually exist only there. Without one,
+/// the repository's shared `hooks` directory is used. Like [`GitRepo::inherit_config`],
+/// this enables `extensions.worktreeConfig` in the main repository.
+///
+/// # Errors
+/// Returns an error if either repository cannot be opened or a config value cannot be
+/// read or written
+pub fn share_hooks(repo_path: &Path, worktree_path: &Path) -> Result<PathBuf> {
+    let repo = Repository::open(repo_path).map_err(operation("Failed to open repository"))?;
+    let mut main_config = repo
+        .config()
+        .map_err(operation("Failed to get repository config"))?;
+    let hooks_dir = match main_config.get_path("core.hooksPath") {
+        Ok(path) if path.is_absolute() => path,
+        Ok(path) => repo.workdir().unwrap_or_else(|| repo.path()).join(path),
+        Err(_) => repo.commondir().join("hooks"),
+    };
+    main_config
+        .set_bool("extensions.worktreeConfig", true)
+        .map_err(operation("Failed to enable worktree config extension"))?;
+
+    // Write the worktree's own config file directly: setting the value through the
+    // worktree repository's config would land in the shared repository config
+    let worktree_repo =
+        Repository::open(worktree_path).map_err(operation("Failed to open worktree repository"))?;
+    git2::Config::open(&worktree_repo.path().join("config.worktree"))
+        .map_err(operation("Failed to open worktree config"))?
+        .set_str("core.hooksPath", &hooks_dir.to_string_lossy())
+        .map_err(operation("Failed to set core.hooksPath"))?;
+    Ok(hooks_dir)
+}
+
+/// Callbacks for network operations. Credentials come from the SSH agent or the git
+/// credential helper configured for `repo` (the global config when there is no repository
+/// yet); attempts are capped so a rejected credential fails instead of being retried forever.
+fn remote_callbacks(repo: Option<&Repository>) -> git2::RemoteCallbacks<'_> {
+    let mut attempts = 0;
+    let mut callbacks = git2::RemoteCallbacks::new();
+    callbacks.credentials(move |url, username, allowed| {
+        attempts += 1;
+        if attempts > 3 {
+            return Err(git2::Error::from_str("authentication failed"));
+        }
+        if allowed.contains(git2::CredentialType::SSH_KEY) {
+            git2::Cred::ssh_key_from_agent(username.unwrap_or("git"))
+        } else if allowed.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
+            let config = repo.map_or_else(git2::Config::open_default, Repository::config)?;
+            git2::Cred::credential_helper(&config, url, username)
+        } else {
+            git2::Cred::default()
+        }
+    });
+    callbacks
+}
+
+/// The result of [`GitRepo::publish_branch`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PublishedBranch {
+    /// The remote-tracking name pushed to, e.g. `origin/feature`
+    pub remote_branch: String,
+    /// Whether the branch had no upstream and now tracks `remote_branch`
+    pub upstream_set: bool,
+}
 
 pub struct GitRepo {
     repo: Repository,
+    repo_path: PathBuf,
 }
 
 impl GitRepo {
     /// Opens a git repository at the specified path
     ///
+    /// From inside a linked worktree this opens the main repository the worktree belongs
+    /// to, so commands behave as if run from the origin repository.
+    ///
     /// # Errors
     /// Returns an error if:
     /// - The path is not a valid git repository
     /// - Failed to access the repository
     pub fn open(path: &Path) -> Result<Self> {
-        let repo = Repository::discover(path).context("Failed to find git repository")?;
-        Ok(Self { repo })
+        let repo = Repository::discover(path).map_err(|e| {
+            if e.code() == git2::ErrorCode::NotFound {
+                GitError::NotARepo {
+                    path: path.to_path_buf(),
+                }
+            } else {
+                GitError::Operation {
+                    action: "Failed to find git repository",
+                    source: e,
+                }
+            }
+        })?;
+        // A worktree whose main repository is gone (e.g. moved) still opens on its own
+        let repo = if repo.is_worktree() {
+            Repository::open(repo.commondir()).unwrap_or(repo)
+        } else {
+            repo
+        };
+        Ok(Self::from_repository(repo))
+    }
+
+    fn from_repository(repo: Repository) -> Self {
+        // Bare repositories (`worktree clone`) have no working directory
+        let repo_path = repo
+            .workdir()
+            .unwrap_or_else(|| repo.path())
+            .components()
+            .collect();
+        Self { repo, repo_path }
     }
 
+    /// Returns the main repository's path: its working directory, or the git directory
+    /// itself for a bare repository
     #[must_use]
     pub fn get_repo_path(&self) -> &Path {
-        self.repo.workdir().unwrap_or_else(|| self.repo.path())
+        &self.repo_path
+    }
+
+    /// Clones `url` as a bare repository into `path`
+    ///
+    /// # Errors
+    /// Returns an error if the clone fails (unreachable remote, rejected credentials, or
+    /// `path` already exists and is not empty)
+    pub fn clone_bare(url: &str, path: &Path) -> Result<Self> {
+        let mut fetch_options = git2::FetchOptions::new();
+        fetch_options.remote_callbacks(remote_callbacks(None));
+        let repo = git2::build::RepoBuilder::new()
+            .bare(true)
+            .fetch_options(fetch_options)
+            .clone(url, path)
+            .map_err(|source| GitError::CloneFailed {
+                url: url.to_string(),
+                source,
+            })?;
+        Ok(Self::from_repository(repo))
+    }
+
+    /// Returns the branch HEAD points at, even if it has no commits yet
+    ///
+    /// # Errors
+    /// Returns an error if HEAD is detached or cannot be read
+    pub fn head_branch(&self) -> Result<String> {
+        let head = self.repo.find_reference("HEAD")?;
+        head.symbolic_target()
+            .and_then(|target| target.strip_prefix("refs/heads/"))
+            .map(str::to_string)
+            .ok_or_else(|| git2::Error::from_str("HEAD does not point at a branch").into())
+    }
+
+    /// Returns the shared `.git` directory of the main repository, even when opened
+    /// from inside a linked worktree
+    #[must_use]
+    pub fn common_dir(&self) -> &Path {
+        self.repo.commondir()
+    }
+
+    /// Rewrites the links between a linked worktree and its administrative directory
+    /// (`.git/worktrees/<name>`) so they point at the current locations. Needed after the
+    /// main repository or the worktree itself has been moved.
+    ///
+    /// Returns true if any link had to be rewritten.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - No administrative directory exists for `worktree_name`
+    /// - The worktree directory does not exist
+    /// - Failed to read or write the link files
+    pub fn repair_worktree_links(&self, worktree_name: &str, worktree_path: &Path) -> Result<bool> {
+        let admin_dir = self.common_dir().join("worktrees").join(worktree_name);
+        if !admin_dir.is_dir() {
+            return Err(GitError::WorktreeNotRegistered {
+                name: worktree_name.to_string(),
+                admin_dir,
+            });
+        }
+        let admin_dir = admin_dir
+            .canonicalize()
+            .map_err(io_error("Failed to resolve", &admin_dir))?;
+        let worktree_path = worktree_path
+            .canonicalize()
+            .map_err(io_error("Worktree directory not found", worktree_path))?;
+        let dot_git = worktree_path.join(".git");
+        let mut repaired = false;
+
+        // <worktree>/.git -> "gitdir: <admin dir>"
+        let current = fs::read_to_string(&dot_git).unwrap_or_default();
+        let points_to = current.trim().strip_prefix("gitdir:").map(str::trim);
+        if !points_to.is_some_and(|p| same_path(Path::new(p), &admin_dir)) {
+            fs::write(&dot_git, format!("gitdir: {}\n", admin_dir.display()))
+                .map_err(io_error("Failed to write", &dot_git))?;
+            repaired = true;
+        }
+
+        // <admin dir>/gitdir -> "<worktree>/.git"
+        let gitdir_file = admin_dir.join("gitdir");
+        let current = fs::read_to_string(&gitdir_file).unwrap_or_default();
+        if !same_path(Path::new(current.trim()), &dot_git) {
+            fs::write(&gitdir_file, format!("{}\n", dot_git.display()))
+                .map_err(io_error("Failed to write", &gitdir_file))?;
+            repaired = true;
+        }
+
+        // <admin dir>/commondir is normally relative; an absolute path goes stale on moves
+        let commondir_file = admin_dir.join("commondir");
+        let current = fs::read_to_string(&commondir_file).unwrap_or_default();
+        let commondir = Path::new(current.trim());
+        if commondir.is_absolute() && !same_path(commondir, self.common_dir()) {
+            fs::write(&commondir_file, "../..\n")
+                .map_err(io_error("Failed to write", &commondir_file))?;
+            repaired = true;
+        }
+
+        Ok(repaired)
+    }
+
+    /// Returns the repository's default branch as recorded by `origin/HEAD`, if any
+    #[must_use]
+    pub fn default_branch(&self) -> Option<String> {
+        let reference = self.repo.find_reference("refs/remotes/origin/HEAD").ok()?;
+        reference
+            .symbolic_target()?
+            .strip_prefix("refs/remotes/origin/")
+            .map(str::to_string)
+    }
+
+    /// Returns the branch that feature branches are merged into: the target of
+    /// `origin/HEAD`, or else a local `main` or `master`
+    #[must_use]
+    pub fn integration_branch(&self) -> Option<String> {
+        self.default_branch().or_else(|| {
+            ["main", "master"]
+                .into_iter()
+                .find(|b| self.branch_exists(b).unwrap_or(false))
+                .map(str::to_string)
+        })
+    }
+
+    /// Returns true if local `branch` has been merged into `target` (any revision, e.g.
+    /// `main` or `refs/remotes/origin/main`).
+    ///
+    /// A branch that still points at the commit it was created from (per its reflog) has
+    /// no commits of its own yet and is not considered merged, even though `target` may
+    /// have moved past it.
+    ///
+    /// # Errors
+    /// Returns an error if either revision cannot be resolved
+    pub fn is_merged_into(&self, branch: &str, target: &str) -> Result<bool> {
+        let branch_commit = self
+            .repo
+            .find_branch(branch, BranchType::Local)?
+            .get()
+            .peel_to_commit()?
+            .id();
+        let target_commit = self.resolve_reference(target)?.id();
+
+        if !self.has_own_commits(branch, branch_commit) {
+            return Ok(false);
+        }
+        Ok(branch_commit == target_commit
+            || self
+                .repo
+                .graph_descendant_of(target_commit, branch_commit)?)
+    }
+
+    /// Returns false if the branch still points at the commit it was created from.
+    /// Without a reflog this cannot be known, and the branch is assumed to have commits.
+    fn has_own_commits(&self, branch: &str, tip: git2::Oid) -> bool {
+        let Ok(reflog) = self.repo.reflog(&format!("refs/heads/{}", branch)) else {
+            return true;
+        };
+        // Entries are newest first; the last one records the branch's creation
+        match reflog.get(reflog.len().saturating_sub(1)) {
+            Some(created) => created.id_new() != tip,
+            None => true,
+        }
+    }
+
+    /// Counts commits on local `branch` that exist only locally: commits ahead of its
+    /// upstream, or, without an upstream, commits not on the default branch
+    /// (`origin/<default>` if present, else the local default branch)
+    ///
+    /// # Errors
+    /// Returns an error if the branch does not exist, or it has no upstream and no
+    /// default branch can be found to compare against
+    pub fn unpushed_commits(&self, branch: &str) -> Result<usize> {
+        let local = self.repo.find_branch(branch, BranchType::Local)?;
+        let local_commit = local.get().peel_to_commit()?.id();
+
+        let base_commit = if let Ok(upstream) = local.upstream() {
+            upstream.get().peel_to_commit()?.id()
+        } else {
+            let default_branch =
+                self.integration_branch()
+                    .ok_or_else(|| GitError::NoBaseBranch {
+                        branch: branch.to_string(),
+                    })?;
+            self.resolve_reference(&format!("refs/remotes/origin/{}", default_branch))
+                .or_else(|_| self.resolve_reference(&default_branch))?
+                .id()
+        };
+
+        let (ahead, _behind) = self.repo.graph_ahead_behind(local_commit, base_commit)?;
+        Ok(ahead)
+    }
+
+    /// Returns the upstream of local `branch` as a short name (e.g. `origin/feature`), or
+    /// `None` if it has no upstream
+    ///
+    /// # Errors
+    /// Returns an error if the branch does not exist
+    pub fn upstream(&self, branch: &str) -> Result<Option<String>> {
+        let local = self.repo.find_branch(branch, BranchType::Local)?;
+        let Ok(upstream) = local.upstream() else {
+            return Ok(None);
+        };
+        Ok(upstream.name()?.map(str::to_string))
+    }
+
+    /// Counts the commits local `branch` is ahead of and behind its upstream, or
+    /// returns `None` if it has no upstream
+    ///
+    /// # Errors
+    /// Returns an error if the branch does not exist or git operations fail
+    pub fn ahead_behind(&self, branch: &str) -> Result<Option<AheadBehind>> {
+        let local = self.repo.find_branch(branch, BranchType::Local)?;
+        let Ok(upstream) = local.upstream() else {
+            return Ok(None);
+        };
+        let (ahead, behind) = self.repo.graph_ahead_behind(
+            local.get().peel_to_commit()?.id(),
+            upstream.get().peel_to_commit()?.id(),
+        )?;
+        Ok(Some(AheadBehind { ahead, behind }))
+    }
+
+    /// Returns the HEAD commit of the worktree at `worktree_path`
+    ///
+    /// # Errors
+    /// Returns an error if the worktree cannot be opened or has no HEAD commit
+    pub fn last_commit(worktree_path: &Path) -> Result<CommitInfo> {
+        let repo = Repository::open(worktree_path)?;
+        let commit = repo.head()?.peel_to_commit()?;
+        Ok(CommitInfo {
+            id: commit.id().to_string(),
+            summary: commit.summary().unwrap_or_default().to_string(),
+            author: commit.author().name().unwrap_or_default().to_string(),
+            time: commit.time().seconds(),
+        })
+    }
+
+    /// Returns the commit time (seconds since the Unix epoch) of HEAD in the worktree
+    /// at `worktree_path`
+    ///
+    /// # Errors
+    /// Returns an error if the worktree cannot be opened or has no HEAD commit
+    pub fn head_commit_time(worktree_path: &Path) -> Result<i64> {
+        let repo = Repository::open(worktree_path)?;
+        let commit = repo.head()?.peel_to_commit()?;
+        Ok(commit.time().seconds())
+    }
+
+    /// Returns true if the worktree at `worktree_path` has uncommitted changes,
+    /// including untracked files (ignored files do not count)
+    ///
+    /// # Errors
+    /// Returns an error if the worktree cannot be opened or its status read
+    pub fn has_uncommitted_changes(worktree_path: &Path) -> Result<bool> {
+        let repo = Repository::open(worktree_path)?;
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(true).include_ignored(false);
+        Ok(!repo.statuses(Some(&mut opts))?.is_empty())
+    }
+
+    /// Lists the files of the worktree at `worktree_path` with uncommitted changes,
+    /// including untracked files, relative to the worktree root
+    ///
+    /// # Errors
+    /// Returns an error if the worktree cannot be opened or its status read
+    pub fn changed_files(worktree_path: &Path) -> Result<Vec<PathBuf>> {
+        let repo = Repository::open(worktree_path)?;
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .include_ignored(false);
+        Ok(repo
+            .statuses(Some(&mut opts))?
+            .iter()
+            .filter_map(|entry| entry.path().map(PathBuf::from))
+            .collect())
     }
 
     /// Checks if a branch exists in the repository
@@ -84,7 +559,15 @@ impl GitRepo {
         let branch = self
             .repo
             .find_branch(branch_name, BranchType::Local)
-            .with_context(|| format!("Failed to find branch '{}'", branch_name))?;
+            .map_err(|e| {
+                if e.code() == git2::ErrorCode::NotFound {
+                    GitError::BranchMissing {
+                        branch: branch_name.to_string(),
+                    }
+                } else {
+                    e.into()
+                }
+            })?;
 
         // Use the directory name as the worktree name to avoid filesystem conflicts
         let worktree_name = worktree_path
@@ -110,12 +593,17 @@ impl GitRepo {
     /// - The reference cannot be resolved to a commit
     /// - Git operations fail
     pub fn resolve_reference(&self, reference: &str) -> Result<git2::Commit<'_>> {
-        let obj = self
-            .repo
-            .revparse_single(reference)
-            .with_context(|| format!("Failed to resolve reference '{}'", reference))?;
-        obj.peel_to_commit()
-            .with_context(|| format!("Reference '{}' does not point to a commit", reference))
+        let obj =
+            self.repo
+                .revparse_single(reference)
+                .map_err(|source| GitError::ReferenceNotFound {
+                    reference: reference.to_string(),
+                    source,
+                })?;
+        obj.peel_to_commit().map_err(|source| GitError::NotACommit {
+            reference: reference.to_string(),
+            source,
+        })
     }
 
     /// Removes a worktree from the repository
@@ -175,6 +663,218 @@ impl GitRepo {
         Ok(())
     }
 
+    /// Deletes the remote counterpart of local `branch` (see [`Self::remote_counterpart`]).
+    /// Returns the deleted remote-tracking name (e.g. `origin/feature`), or `None` if no
+    /// remote branch is known. Must be called before the local branch is deleted, since
+    /// deleting it also drops its upstream configuration.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The remote cannot be found or reached
+    /// - Authentication fails
+    /// - The remote rejects the deletion
+    pub fn delete_remote_branch(&self, branch: &str) -> Result<Option<String>> {
+        let (remote_name, remote_ref) = self.remote_counterpart(branch);
+        let remote_branch = remote_ref
+            .strip_prefix("refs/heads/")
+            .unwrap_or(&remote_ref);
+        let tracking_ref = format!("refs/remotes/{}/{}", remote_name, remote_branch);
+        let Ok(mut tracking) = self.repo.find_reference(&tracking_ref) else {
+            return Ok(None);
+        };
+
+        self.push(&remote_name, &format!(":{}", remote_ref))?;
+
+        // libgit2 may leave the remote-tracking ref behind
+        if tracking.delete().is_err() && self.repo.find_reference(&tracking_ref).is_ok() {
+            warn!("Failed to delete {}", tracking_ref);
+        }
+
+        Ok(Some(format!("{}/{}", remote_name, remote_branch)))
+    }
+
+    /// Pushes local `branch` to its remote counterpart (see
+    /// [`Self::remote_counterpart`]). Returns the remote-tracking name, e.g.
+    /// `origin/feature`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The remote cannot be found or reached
+    /// - Authentication fails
+    /// - The remote rejects the push (e.g. not a fast-forward)
+    pub fn push_branch(&self, branch: &str) -> Result<String> {
+        let (remote_name, remote_ref) = self.remote_counterpart(branch);
+        self.push(
+            &remote_name,
+            &format!("refs/heads/{}:{}", branch, remote_ref),
+        )?;
+
+        let remote_branch = remote_ref
+            .strip_prefix("refs/heads/")
+            .unwrap_or(&remote_ref);
+        Ok(format!("{}/{}", remote_name, remote_branch))
+    }
+
+    /// Pushes local `branch` like [`Self::push_branch`] and sets its upstream when it has
+    /// none, so later pushes and `@{upstream}` find the remote branch. With
+    /// `force_with_lease`, the push may rewrite the remote branch, but only if it still
+    /// points where its remote-tracking ref says (like `git push --force-with-lease`).
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The branch does not exist
+    /// - The remote cannot be found or reached, or authentication fails
+    /// - The remote branch moved since it was last fetched (with `force_with_lease`)
+    /// - The remote rejects the push, or the upstream cannot be recorded
+    pub fn publish_branch(&self, branch: &str, force_with_lease: bool) -> Result<PublishedBranch> {
+        let local = self
+            .repo
+            .find_branch(branch, BranchType::Local)
+            .map_err(|_| GitError::BranchMissing {
+                branch: branch.to_string(),
+            })?;
+        let local_ref = format!("refs/heads/{}", branch);
+        let upstream_set = self.repo.branch_upstream_remote(&local_ref).is_err();
+        let (remote_name, remote_ref) = self.remote_counterpart(branch);
+        let remote_branch = remote_ref
+            .strip_prefix("refs/heads/")
+            .unwrap_or(&remote_ref)
+            .to_string();
+        let tracking_ref = format!("refs/remotes/{}/{}", remote_name, remote_branch);
+
+        let refspec = format!("refs/heads/{}:{}", branch, remote_ref);
+        let refspec = if force_with_lease {
+            let expected = self
+                .repo
+                .find_reference(&tracking_ref)
+                .ok()
+                .and_then(|reference| reference.target());
+            if self.remote_target(&remote_name, &remote_ref)? != expected {
+                return Err(GitError::StaleLease {
+                    remote: remote_name,
+                    branch: remote_branch,
+                });
+            }
+            format!("+{}", refspec)
+        } else {
+            refspec
+        };
+        self.push(&remote_name, &refspec)?;
+
+        if let Some(oid) = local.get().target() {
+            self.repo
+                .reference(&tracking_ref, oid, true, "push")
+                .map_err(operation("Failed to update the remote-tracking branch"))?;
+        }
+        if upstream_set {
+            let mut config = self
+                .repo
+                .config()
+                .map_err(operation("Failed to open repository config"))?;
+            config
+                .set_str(&format!("branch.{}.remote", branch), &remote_name)
+                .and_then(|()| config.set_str(&format!("branch.{}.merge", branch), &remote_ref))
+                .map_err(operation("Failed to set the upstream branch"))?;
+        }
+
+        Ok(PublishedBranch {
+            remote_branch: format!("{}/{}", remote_name, remote_branch),
+            upstream_set,
+        })
+    }
+
+    /// Where `remote_ref` points on `remote_name` right now, asking the remote
+    fn remote_target(&self, remote_name: &str, remote_ref: &str) -> Result<Option<git2::Oid>> {
+        let mut remote =
+            self.repo
+                .find_remote(remote_name)
+                .map_err(|source| GitError::RemoteMissing {
+                    remote: remote_name.to_string(),
+                    source,
+                })?;
+        let failed = |source| GitError::PushFailed {
+            remote: remote_name.to_string(),
+            source,
+        };
+        let connection = remote
+            .connect_auth(
+                git2::Direction::Push,
+                Some(remote_callbacks(Some(&self.repo))),
+                None,
+            )
+            .map_err(failed)?;
+        let heads = connection.list().map_err(failed)?;
+        Ok(heads
+            .iter()
+            .find(|head| head.name() == remote_ref)
+            .map(git2::RemoteHead::oid))
+    }
+
+    /// Returns the remote that [`Self::push_branch`] would push `branch` to, if that
+    /// remote exists
+    #[must_use]
+    pub fn push_remote(&self, branch: &str) -> Option<String> {
+        let (remote_name, _) = self.remote_counterpart(branch);
+        self.repo.find_remote(&remote_name).ok()?;
+        Some(remote_name)
+    }
+
+    /// The remote name and remote ref corresponding to local `branch`: its configured
+    /// upstream, else the same-named branch on `origin`
+    fn remote_counterpart(&self, branch: &str) -> (String, String) {
+        let local_ref = format!("refs/heads/{}", branch);
+        let remote_name = self
+            .repo
+            .branch_upstream_remote(&local_ref)
+            .ok()
+            .and_then(|buf| buf.as_str().map(str::to_string))
+            .unwrap_or_else(|| "origin".to_string());
+        let remote_ref = self
+            .repo
+            .branch_upstream_merge(&local_ref)
+            .ok()
+            .and_then(|buf| buf.as_str().map(str::to_string))
+            .unwrap_or(local_ref);
+        (remote_name, remote_ref)
+    }
+
+    /// Pushes a single refspec to `remote_name`, failing if the remote rejects it
+    fn push(&self, remote_name: &str, refspec: &str) -> Result<()> {
+        let mut remote =
+            self.repo
+                .find_remote(remote_name)
+                .map_err(|source| GitError::RemoteMissing {
+                    remote: remote_name.to_string(),
+                    source,
+                })?;
+
+        let mut rejection = None;
+        {
+            let mut callbacks = remote_callbacks(Some(&self.repo));
+            callbacks.push_update_reference(|_, status| {
+                rejection = status.map(str::to_string);
+                Ok(())
+            });
+            let mut push_options = git2::PushOptions::new();
+            push_options.remote_callbacks(callbacks);
+
+            remote
+                .push(&[refspec], Some(&mut push_options))
+                .map_err(|source| GitError::PushFailed {
+                    remote: remote_name.to_string(),
+                    source,
+                })?;
+        }
+        if let Some(reason) = rejection {
+            return Err(GitError::PushRejected {
+                remote: remote_name.to_string(),
+                reason,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Lists all local branches in the repository
     ///
     /// # Errors
@@ -226,7 +926,13 @@ impl GitRepo {
         Ok(tag_names)
     }
 
-    /// Enables worktree-specific configuration and copies parent repo's effective config
+    /// Enables worktree-specific configuration and copies parent repo's config into the
+    /// worktree's `config.worktree`
+    ///
+    /// Values that come from `include` and `includeIf` files are not copied. The include
+    /// directives are copied instead, after the values, so git evaluates their conditions
+    /// (such as `gitdir:` or `onbranch:`) for the worktree rather than baking in what
+    /// applied to the parent repository.
     ///
     /// # Errors
     /// Returns an error if:
@@ -238,102 +944,141 @@ impl GitRepo {
         let mut main_config = self
             .repo
             .config()
-            .context("Failed to get repository config")?;
+            .map_err(operation("Failed to get repository config"))?;
         main_config
             .set_bool("extensions.worktreeConfig", true)
-            .context("Failed to enable worktree config extension")?;
+            .map_err(operation("Failed to enable worktree config extension"))?;
 
-        // Open the worktree repository to set its config
-        let worktree_repo =
-            Repository::open(worktree_path).context("Failed to open worktree repository")?;
+        // Open the worktree repository to find its config file
+        let worktree_repo = Repository::open(worktree_path)
+            .map_err(operation("Failed to open worktree repository"))?;
 
-        // Get the effective config from the parent repository (includes conditional includes)
-        let parent_config = self
-            .get_effective_config()
-            .context("Failed to read parent repository config")?;
+        let InheritableConfig { values, includes } = self.get_inheritable_config()?;
 
-        // Set worktree-specific configuration
-        let mut worktree_config = worktree_repo
-            .config()
-            .context("Failed to get worktree config")?;
+        // Write the worktree's own config file directly: setting values through the
+        // worktree repository's config would land in the shared repository config
+        let mut worktree_config = git2::Config::open(&worktree_repo.path().join("config.worktree"))
+            .map_err(operation("Failed to open worktree config"))?;
 
         // Copy relevant configuration keys to the worktree
-        for (key, config_value) in parent_config {
-            if should_inherit_config_key(&key) {
-                match config_value {
-                    ConfigValue::String(s) => {
-                        if let Err(e) = worktree_config.set_str(&key, &s) {
-                            eprintln!("Warning: Failed to set config {}: {}", key, e);
-                        }
-                    }
-                    ConfigValue::Bool(b) => {
-                        if let Err(e) = worktree_config.set_bool(&key, b) {
-                            eprintln!("Warning: Failed to set config {}: {}", key, e);
-                        }
-                    }
-                    ConfigValue::Int(i) => {
-                        if let Err(e) = worktree_config.set_i64(&key, i) {
-                            eprintln!("Warning: Failed to set config {}: {}", key, e);
-                        }
-                    }
-                }
+        for (key, value) in values {
+            if let Err(e) = worktree_config.set_str(&key, &value) {
+                warn!("Failed to set config {}: {}", key, e);
+            }
+        }
+
+        for (key, path) in includes {
+            let existing = format!("^{}$", escape_regex(&path));
+            if let Err(e) = worktree_config.set_multivar(&key, &existing, &path) {
+                warn!("Failed to set config {}: {}", key, e);
             }
         }
 
         Ok(())
     }
 
-    /// Reads the effective configuration from the parent repository
-    fn get_effective_config(&self) -> Result<HashMap<String, ConfigValue>> {
+    /// Reads the parent repository's inheritable config values, leaving out values from
+    /// included files, and its include directives with their paths made absolute
+    fn get_inheritable_config(&self) -> Result<InheritableConfig> {
         let mut config = self
             .repo
             .config()
-            .context("Failed to get repository config")?;
+            .map_err(operation("Failed to get repository config"))?;
 
-        let mut config_map = HashMap::new();
+        let mut values = HashMap::new();
+        let mut includes = Vec::new();
 
-        // Get a snapshot of the current config which includes all effective values
+        // Entries come in precedence order, so later values override earlier ones
         let snapshot = config
             .snapshot()
-            .context("Failed to create config snapshot")?;
+            .map_err(operation("Failed to create config snapshot"))?;
 
         let mut entries = snapshot
             .entries(None)
-            .context("Failed to get config entries")?;
+            .map_err(operation("Failed to get config entries"))?;
 
         while let Some(entry_result) = entries.next() {
-            if let Ok(entry) = entry_result {
-                if let Some(name) = entry.name() {
-                    let key = name.to_string();
-
-                    if let Some(value_str) = entry.value() {
-                        // Try to determine the type and parse accordingly
-                        let config_value = if let Ok(bool_val) = config.get_bool(&key) {
-                            ConfigValue::Bool(bool_val)
-                        } else if let Ok(int_val) = config.get_i64(&key) {
-                            ConfigValue::Int(int_val)
-                        } else {
-                            ConfigValue::String(value_str.to_string())
-                        };
-
-                        config_map.insert(key, config_value);
-                    }
+            let Ok(entry) = entry_result else {
+                continue;
+            };
+            if entry.include_depth() > 0 {
+                continue;
+            }
+            let (Some(key), Some(value)) = (entry.name(), entry.value()) else {
+                continue;
+            };
+
+            if is_include_directive(key) {
+                let config_file = match entry.level() {
+                    git2::ConfigLevel::Local => Some(self.repo.commondir().join("config")),
+                    git2::ConfigLevel::Global => git2::Config::find_global().ok(),
+                    git2::ConfigLevel::XDG => git2::Config::find_xdg().ok(),
+                    git2::ConfigLevel::System => git2::Config::find_system().ok(),
+                    _ => None,
+                };
+                let include = (
+                    key.to_string(),
+                    resolve_include_path(value, config_file.as_deref()),
+                );
+                if !includes.contains(&include) {
+                    includes.push(include);
                 }
+            } else if should_inherit_config_key(key) {
+                values.insert(key.to_string(), value.to_string());
             }
         }
 
-        Ok(config_map)
+        Ok(InheritableConfig { values, includes })
+    }
+}
+
+/// Parent repository config to copy into a worktree
+struct InheritableConfig {
+    values: HashMap<String, String>,
+    /// Include directives as `(key, absolute path)`, in their original order
+    includes: Vec<(String, String)>,
+}
+
+/// Returns true for `include.path` and `includeIf.<condition>.path` keys
+fn is_include_directive(key: &str) -> bool {
+    key == "include.path" || (key.starts_with("includeif.") && key.ends_with(".path"))
+}
+
+/// Makes a relative include path absolute. Git resolves it against the directory of
+/// the config file containing the directive, which is not where the worktree's config
+/// lives.
+fn resolve_include_path(path: &str, config_file: Option<&Path>) -> String {
+    if path.starts_with('~') || Path::new(path).is_absolute() {
+        return path.to_string();
+    }
+    match config_file.and_then(Path::parent) {
+        Some(dir) => dir.join(path).to_string_lossy().into_owned(),
+        None => path.to_string(),
     }
 }
 
-#[derive(Debug, Clone)]
-enum ConfigValue {
-    String(String),
-    Bool(bool),
-    Int(i64),
+/// Escapes regular expression metacharacters for libgit2's multivar matching
+fn escape_regex(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if "\\.^$|?*+()[]{}".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
 }
 
 /// Determines which configuration keys should be inherited by worktrees
+/// Compares two paths after resolving symlinks, falling back to a literal comparison
+/// for paths that do not exist
+fn same_path(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
 fn should_inherit_config_key(key: &str) -> bool {
     // Don't inherit keys that are specific to the main repository
     const EXCLUDED_KEYS: &[&str] = &[
@@ -451,4 +1196,32 @@ impl GitOperations for GitRepo {
     fn list_tags(&self) -> Result<Vec<String>> {
         self.list_tags()
     }
+
+    fn is_dirty(&self, worktree_path: &Path) -> Result<bool> {
+        GitRepo::has_uncommitted_changes(worktree_path)
+    }
+
+    fn changed_files(&self, worktree_path: &Path) -> Result<Vec<PathBuf>> {
+        GitRepo::changed_files(worktree_path)
+    }
+
+    fn upstream(&self, branch: &str) -> Result<Option<String>> {
+        self.upstream(branch)
+    }
+
+    fn ahead_behind(&self, branch: &str) -> Result<Option<AheadBehind>> {
+        self.ahead_behind(branch)
+    }
+
+    fn unpushed_commits(&self, branch: &str) -> Result<usize> {
+        self.unpushed_commits(branch)
+    }
+
+    fn is_merged_into(&self, branch: &str, target: &str) -> Result<bool> {
+        self.is_merged_into(branch, target)
+    }
+
+    fn last_commit(&self, worktree_path: &Path) -> Result<CommitInfo> {
+        GitRepo::last_commit(worktree_path)
+    }
 }
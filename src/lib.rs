@@ -5,7 +5,7 @@
 //!
 //! ## Features
 //!
-//! - **Centralized Storage** - Organizes worktrees in `~/.worktrees/<repo-name>/<branch-name>/`
+//! - **Centralized Storage** - Organizes worktrees in `<storage root>/<repo-name>/<feature-name>/`
 //! - **Smart Config Management** - Automatically copies gitignored config files to new worktrees
 //! - **Branch Synchronization** - Keeps worktrees and git branches in sync
 //! - **Comprehensive Status** - Shows detailed worktree and branch status
@@ -30,18 +30,46 @@
 //!
 //! ## Module Structure
 //!
+//! - [`manager`] - [`WorktreeManager`], the high-level API for listing, creating, and removing worktrees
 //! - [`commands`] - Individual command implementations (create, list, remove, status, etc.)
-//! - [`storage`] - Manages worktree storage in `~/.worktrees/` with branch name sanitization
+//! - [`provision`] - Sets up new worktrees: config files, hooks, and the `[direnv]`, `[build-cache]`, and other integrations
+//! - [`storage`] - Resolves the storage root ([`WorktreeStorage::new`]) and manages the worktrees and metadata under it
 //! - [`config`] - Handles `.worktree-config.toml` files for customizing file copy patterns
+//! - [`events`] - NDJSON progress events for `--json-events`
 //! - [`git`] - Git operations wrapper using git2 crate
+//! - [`error`] - [`WorktreeError`] command failures and the CLI exit codes for all error types
+//! - [`logging`] - The `tracing` subscriber behind `-v`, `-q`, and `WORKTREE_LOG`
+//! - [`output`] - Colored `✓`/`✗`/`⚠` status lines and the `--color` setting
 //! - [`selection`] - Abstracts interactive selection prompts for testability
-//! - [`traits`] - Defines GitOperations trait for testability and abstraction
+//! - [`traits`] - Defines the GitOperations and StorageOperations traits for testability and abstraction
+//! - [`prelude`] - Re-exports of the key types for library consumers
+//!
+//! ## Library Usage
+//!
+//! Start with [`WorktreeManager`]. The key types are re-exported at the crate root and in [`prelude`]. Fallible functions
+//! in [`git`], [`storage`], and [`config`] return typed errors ([`GitError`],
+//! [`StorageError`], [`ConfigError`]) that can be matched on. Commands return
+//! [`anyhow::Result`]; downcast the error (or use [`WorktreeError::find`]) to check for a
+//! specific failure.
 
 pub mod commands;
 pub mod config;
+pub mod error;
+pub mod events;
 pub mod git;
+pub mod logging;
+pub mod manager;
+pub mod output;
+pub mod prelude;
+pub mod provision;
 pub mod selection;
 pub mod storage;
 pub mod traits;
 
-pub use anyhow::Result;
+pub use config::{ConfigError, WorktreeConfig};
+pub use error::WorktreeError;
+pub use git::{GitError, GitRepo};
+pub use manager::{WorktreeInfo, WorktreeManager};
+pub use selection::{GitRefOption, RealSelectionProvider, SelectionProvider};
+pub use storage::{MemoryStorage, StorageError, WorktreeStorage};
+pub use traits::{AheadBehind, CommitInfo, GitOperations, StorageOperations};
@@ -0,0 +1,105 @@
+//! Diagnostic output through `tracing`.
+//!
+//! Commands print their results with `println!`; warnings and diagnostics go through
+//! `tracing` macros and land on stderr. [`init`] installs the subscriber the CLI uses:
+//! warnings by default, less with `-q`, more with each `-v`, or any filter in
+//! `WORKTREE_LOG` (such as `debug` or `worktree::git=trace`). Library users who install
+//! no subscriber see no diagnostics.
+
+use std::fmt;
+
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::events;
+use crate::output::{ERROR, WARNING};
+
+/// Environment variable holding a filter that overrides `-v` and `-q`
+pub const LOG_ENV: &str = "WORKTREE_LOG";
+
+/// Installs the stderr subscriber for the CLI. `verbose` counts `-v` flags; `quiet`
+/// shows errors only. A valid `WORKTREE_LOG` filter takes precedence over both.
+pub fn init(verbose: u8, quiet: bool) {
+    let level = match (quiet, verbose) {
+        (true, _) => Level::ERROR,
+        (false, 0) => Level::WARN,
+        (false, 1) => Level::INFO,
+        (false, 2) => Level::DEBUG,
+        (false, _) => Level::TRACE,
+    };
+
+    let mut invalid_filter = None;
+    let filter = match std::env::var(LOG_ENV) {
+        Ok(directives) if !directives.trim().is_empty() => EnvFilter::try_new(&directives)
+            .unwrap_or_else(|e| {
+                invalid_filter = Some((directives, e));
+                EnvFilter::new(level.as_str())
+            }),
+        _ => EnvFilter::new(level.as_str()),
+    };
+
+    // Fails only when a subscriber is already installed, which is fine
+    let _ = tracing_subscriber::fmt()
+        .with_writer(anstream::stderr)
+        .with_env_filter(filter)
+        .event_format(Format)
+        .try_init();
+
+    if let Some((directives, e)) = invalid_filter {
+        tracing::warn!("Ignoring invalid {}={:?}: {}", LOG_ENV, directives, e);
+    }
+}
+
+/// Formats events like the rest of the CLI output: colored `⚠ Warning: ...` and
+/// `✗ Error: ...`, plain info lines, and `debug`/`trace` lines tagged with their module.
+/// The writer strips the colors when `--color` turns them off. With `--json-events`,
+/// events are written as [`events::Event`]s instead.
+struct Format;
+
+impl<S, N> FormatEvent<S, N> for Format
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let metadata = event.metadata();
+        if events::enabled() {
+            let mut message = String::new();
+            ctx.field_format()
+                .format_fields(Writer::new(&mut message), event)?;
+            let level = metadata.level().as_str().to_lowercase();
+            events::emit(&match *metadata.level() {
+                Level::ERROR => events::Event::Error { message: &message },
+                Level::WARN => events::Event::Warning { message: &message },
+                _ => events::Event::Log {
+                    level: &level,
+                    target: metadata.target(),
+                    message: &message,
+                },
+            });
+            return Ok(());
+        }
+
+        match *metadata.level() {
+            Level::ERROR => write!(writer, "{ERROR}✗ Error:{ERROR:#} ")?,
+            Level::WARN => write!(writer, "{WARNING}⚠ Warning:{WARNING:#} ")?,
+            Level::INFO => {}
+            level => write!(
+                writer,
+                "{} {}: ",
+                level.as_str().to_lowercase(),
+                metadata.target()
+            )?,
+        }
+        ctx.field_format().format_fields(writer.by_ref(), event)?;
+        writeln!(writer)
+    }
+}
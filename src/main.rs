@@ -1,16 +1,62 @@
-use clap::{CommandFactory, Parser, Subcommand, ValueHint};
-use worktree::Result;
-use worktree::commands::init::Shell;
+use anyhow::Result;
+use clap::{ArgAction, CommandFactory, Parser, Subcommand, ValueHint};
+use clap_complete::{ArgValueCandidates, CompleteEnv};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+use worktree::commands::du::DuSort;
+use worktree::commands::init::{CompletionTarget, Shell};
+use worktree::commands::label::LabelAction;
+use worktree::commands::list::ListSort;
+use worktree::commands::pr::PrAction;
 use worktree::commands::skill::SkillAction;
+use worktree::commands::workspace::{self, WorkspaceAction};
 use worktree::commands::{
-    back, cleanup, create, init, jump, list, remove, skill, status, sync_config,
+    archive, autoclean, back, carapace, cleanup, clone, completion, create, du, finish, info, init,
+    jump, label, list, lock_status, manpages, migrate, note, open, path, pr, prompt, push,
+    relocate, remove, repair, root, skill, status, sync, sync_config, ui, update,
 };
+use worktree::config::{self, FinishStrategy, UpdateStrategy};
+use worktree::error;
+use worktree::events;
+use worktree::logging;
+use worktree::output::{self, ColorChoice};
+use worktree::selection;
+use worktree::storage::labels::LabelFilter;
+use worktree::storage::lock;
 
 #[derive(Parser)]
 #[command(name = "worktree")]
 #[command(about = "A CLI tool for managing git worktrees with enhanced features")]
 #[command(version)]
 pub struct Cli {
+    /// Seconds to wait for the storage metadata lock before giving up
+    #[arg(
+        long,
+        global = true,
+        value_name = "SECS",
+        env = "WORKTREE_LOCK_TIMEOUT"
+    )]
+    lock_timeout: Option<u64>,
+    /// Skip confirmation prompts for destructive commands (remove, cleanup, autoclean)
+    #[arg(short, long, global = true)]
+    yes: bool,
+    /// Show more diagnostics on stderr: -v info, -vv debug, -vvv trace. `list -v` also shows notes
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    verbose: u8,
+    /// Only report errors on stderr; `create -q` also hides its progress output on stdout
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    /// When to color output; `auto` colors terminals unless NO_COLOR is set
+    #[arg(long, global = true, value_enum, value_name = "WHEN", default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+    /// Write progress events of create and sync-config to stderr as NDJSON, for frontends
+    #[arg(long, global = true)]
+    json_events: bool,
+    /// Global config file to use, or a directory holding `config.toml` and optionally a
+    /// `.worktree-config.toml` that replaces each repository's
+    #[arg(long, global = true, value_name = "PATH", value_hint = ValueHint::AnyPath)]
+    config: Option<PathBuf>,
     #[command(subcommand)]
     command: Commands,
 }
@@ -26,7 +72,7 @@ enum Commands {
         #[arg(value_hint = ValueHint::Other)]
         branch: Option<String>,
         /// Starting point for new branch (branch, commit, tag)
-        #[arg(long)]
+        #[arg(long, add = ArgValueCandidates::new(completion::git_refs))]
         from: Option<String>,
         /// Launch interactive selection for --from reference
         #[arg(long)]
@@ -34,6 +80,35 @@ enum Commands {
         /// List available git references for completion (internal use)
         #[arg(long, hide = true)]
         list_from_completions: bool,
+        /// Open the new worktree in the configured editor
+        #[arg(long)]
+        open: bool,
+        /// Don't copy the parent repository's git config into the worktree (leaves
+        /// `extensions.worktreeConfig` untouched)
+        #[arg(long)]
+        no_inherit_config: bool,
+        /// Describe the branch (stored as `branch.<name>.description`, shown by `list`
+        /// and `status`)
+        #[arg(long, value_hint = ValueHint::Other)]
+        description: Option<String>,
+        /// Print the new worktree's path on stdout when done; with -q it is the only output
+        #[arg(long)]
+        print_path: bool,
+        /// Fetch from the remotes first (default: `fetch` in the [create] config)
+        #[arg(long, conflicts_with = "no_fetch")]
+        fetch: bool,
+        /// Don't fetch even if the [create] config says to
+        #[arg(long)]
+        no_fetch: bool,
+    },
+    /// Clone a repository bare into storage, with every branch checked out as a worktree
+    Clone {
+        /// URL or path of the repository to clone
+        #[arg(value_hint = ValueHint::Url)]
+        url: String,
+        /// Repository name in storage (default: derived from the URL)
+        #[arg(long, value_hint = ValueHint::Other)]
+        name: Option<String>,
     },
     /// List all worktrees
     #[command(visible_alias = "ls")]
@@ -41,35 +116,97 @@ enum Commands {
         /// Show worktrees for current repo only
         #[arg(long)]
         current: bool,
+        /// Show each branch's pull request state (asks gh or glab; cached for a few minutes)
+        #[arg(long)]
+        pr: bool,
+        /// Order worktrees within each repository
+        #[arg(long, value_enum, default_value_t = ListSort::Name)]
+        sort: ListSort,
+        /// Reverse the order
+        #[arg(long)]
+        reverse: bool,
+        /// Only show worktrees with this label, or without it when prefixed with `!` (repeatable)
+        #[arg(long = "label", value_name = "LABEL")]
+        labels: Vec<LabelFilter>,
+        /// Print one line per worktree from a template, e.g. '{repo}\t{branch}\t{path}'.
+        /// Placeholders: {repo} {feature} {branch} {path} {dirty} {ahead} {behind} {age} {labels} {note}
+        #[arg(long, value_name = "TEMPLATE", conflicts_with_all = ["verbose", "pr"])]
+        format: Option<String>,
     },
     /// Remove a worktree
     Remove {
-        /// Feature name or path to remove. If not provided, opens interactive selection.
-        #[arg(value_hint = ValueHint::Other)]
-        target: Option<String>,
-        /// Also delete the branch checked out in this worktree
-        #[arg(long)]
+        /// Feature names or paths to remove. If not provided, opens interactive selection.
+        #[arg(value_hint = ValueHint::Other, add = ArgValueCandidates::new(completion::worktree_names))]
+        targets: Vec<String>,
+        /// Also delete the branch checked out in this worktree (default: `delete-branch` in
+        /// the [remove] config)
+        #[arg(long, conflicts_with = "keep_branch")]
         delete_branch: bool,
+        /// Keep the branch even if the [remove] config says to delete it
+        #[arg(long, conflicts_with = "force_delete_branch")]
+        keep_branch: bool,
+        /// Delete the branch even if it has unpushed commits or is protected (implies
+        /// --delete-branch)
+        #[arg(long)]
+        force_delete_branch: bool,
         /// Launch interactive selection mode
         #[arg(long)]
         interactive: bool,
         /// List available worktrees for completion (internal use)
         #[arg(long, hide = true)]
         list_completions: bool,
-        /// Show worktrees for current repo only
-        #[arg(long)]
+        /// Remove the worktree you are in (with --interactive: only offer this repo's worktrees)
+        #[arg(long, conflicts_with = "targets")]
         current: bool,
+        /// Archive the worktree's files before removing it (see `restore`)
+        #[arg(long)]
+        archive: bool,
+        /// Also delete the branch on its remote (default: `delete-remote` in the [remove] config)
+        #[arg(long, conflicts_with = "keep_remote")]
+        delete_remote: bool,
+        /// Keep the remote branch even if the [remove] config says to delete it
+        #[arg(long)]
+        keep_remote: bool,
+        /// Remove worktrees of this repository in storage (same as `repo/name` targets)
+        #[arg(long, value_name = "NAME", conflicts_with = "current", add = ArgValueCandidates::new(completion::repo_names))]
+        repo: Option<String>,
+    },
+    /// Show worktree status, or with a worktree, everything known about it (like `info`)
+    Status {
+        /// Feature name, branch, or `repo/name` of a worktree to show in detail
+        #[arg(value_hint = ValueHint::Other, add = ArgValueCandidates::new(completion::worktree_names), conflicts_with_all = ["pr", "watch", "stale_days"])]
+        target: Option<String>,
+        /// Show each branch's pull request state (asks gh or glab; cached for a few minutes)
+        #[arg(long)]
+        pr: bool,
+        /// Print the report as JSON (for dashboards and editor extensions)
+        #[arg(long)]
+        json: bool,
+        /// Keep the report on screen, redrawing it when worktrees change and every --interval
+        #[arg(long, conflicts_with = "json")]
+        watch: bool,
+        /// Seconds between redraws with --watch
+        #[arg(long, value_name = "SECS", default_value_t = 2, requires = "watch")]
+        interval: u64,
+        /// Flag worktrees with no commits or file changes for this many days as stale
+        /// (default: `stale-after-days` in the `[status]` config, or 30; 0 turns it off)
+        #[arg(long, value_name = "DAYS")]
+        stale_days: Option<u64>,
     },
-    /// Show worktree status
-    Status,
     /// Sync config files between worktrees
     SyncConfig {
-        /// Source branch or path
-        #[arg(value_hint = ValueHint::Other)]
-        from: String,
-        /// Target branch or path
-        #[arg(value_hint = ValueHint::Other)]
-        to: String,
+        /// Source feature name or path. If not provided, opens interactive selection.
+        #[arg(value_hint = ValueHint::Other, add = ArgValueCandidates::new(completion::current_repo_worktree_names))]
+        from: Option<String>,
+        /// Target feature name or path. If not provided, opens interactive selection.
+        #[arg(value_hint = ValueHint::Other, add = ArgValueCandidates::new(completion::current_repo_worktree_names))]
+        to: Option<String>,
+        /// Only sync files matching this pattern instead of the configured set (repeatable)
+        #[arg(long, value_name = "PATTERN")]
+        only: Vec<String>,
+        /// List available worktrees for completion (internal use)
+        #[arg(long, hide = true)]
+        list_completions: bool,
     },
     /// Generate shell integration for directory navigation
     Init {
@@ -77,17 +214,17 @@ enum Commands {
         #[arg(value_enum)]
         shell: Shell,
     },
-    /// Generate shell completions
+    /// Generate shell completions (already included in `init`) or a carapace spec
     Completions {
-        /// Shell to generate completions for
+        /// Shell or completion framework to generate completions for
         #[arg(value_enum)]
-        shell: Shell,
+        shell: CompletionTarget,
     },
     /// Jump to a worktree directory
     #[command(visible_alias = "switch")]
     Jump {
-        /// Target worktree (feature name). If not provided, opens interactive selection.
-        #[arg(value_hint = ValueHint::Other)]
+        /// Target worktree (feature name), or `-` for the previous location. If not provided, opens interactive selection.
+        #[arg(value_hint = ValueHint::Other, add = ArgValueCandidates::new(completion::worktree_names))]
         target: Option<String>,
         /// Launch interactive selection mode
         #[arg(long)]
@@ -98,21 +235,280 @@ enum Commands {
         /// Current repo only
         #[arg(long)]
         current: bool,
+        /// Jump to the previously visited location (same as `-`)
+        #[arg(long, conflicts_with_all = ["target", "interactive"])]
+        last: bool,
+        /// Only consider worktrees of this repository (same as a `repo/name` target)
+        #[arg(long, value_name = "NAME", conflicts_with = "current", add = ArgValueCandidates::new(completion::repo_names))]
+        repo: Option<String>,
+        /// Open the worktree in Zellij (tab, pane, or session per the global config) instead of changing directory
+        #[arg(long)]
+        zellij: bool,
+        /// Change directory even if the global config opens worktrees in Zellij
+        #[arg(long, conflicts_with = "zellij")]
+        no_zellij: bool,
+        /// Only consider worktrees with this label, or without it when prefixed with `!` (repeatable)
+        #[arg(long = "label", value_name = "LABEL")]
+        labels: Vec<LabelFilter>,
+        /// If no worktree matches but the target is a local or remote branch, create its worktree without asking
+        #[arg(long, conflicts_with_all = ["interactive", "last"])]
+        create: bool,
     },
     /// Clean up orphaned branches and worktree references
-    Cleanup,
+    Cleanup {
+        /// Show what would be removed without removing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Choose which orphaned items to remove
+        #[arg(short, long, conflicts_with = "dry_run")]
+        interactive: bool,
+        /// Clean up every repository in storage, not just the current one
+        #[arg(long)]
+        all: bool,
+        /// Also remove clean worktrees (and their branches) merged into the default branch
+        #[arg(long)]
+        merged: bool,
+    },
+    /// Remove the oldest or stalest worktrees according to the [retention] config
+    Autoclean {
+        /// Show what would be removed without removing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show disk usage of worktrees per repository
+    Du {
+        /// Order repositories and worktrees by name or by size (largest first)
+        #[arg(long, value_enum, default_value_t = DuSort::Name)]
+        sort: DuSort,
+    },
+    /// Fetch and rebase (or merge) a worktree's branch onto its base branch
+    Update {
+        /// Feature name or branch of the worktree (default: the worktree you are in)
+        #[arg(value_hint = ValueHint::Other, add = ArgValueCandidates::new(completion::current_repo_worktree_names))]
+        target: Option<String>,
+        /// Merge the base branch instead of rebasing (default: `strategy` in the [update] config)
+        #[arg(long, conflicts_with = "rebase")]
+        merge: bool,
+        /// Rebase even if the [update] config says to merge
+        #[arg(long)]
+        rebase: bool,
+        /// Update onto this branch instead of the recorded base or default branch
+        #[arg(long, value_name = "REF", add = ArgValueCandidates::new(completion::git_refs))]
+        onto: Option<String>,
+        /// Don't fetch from the remotes first
+        #[arg(long)]
+        no_fetch: bool,
+    },
+    /// Fetch once, then rebase (or merge) every worktree of this repository onto its base branch
+    Sync {
+        /// Merge base branches instead of rebasing (default: `strategy` in the [update] config)
+        #[arg(long, conflicts_with = "rebase")]
+        merge: bool,
+        /// Rebase even if the [update] config says to merge
+        #[arg(long)]
+        rebase: bool,
+        /// Don't fetch from the remotes first
+        #[arg(long)]
+        no_fetch: bool,
+    },
+    /// Merge a worktree's branch into the default branch, then remove the worktree and branch
+    Finish {
+        /// Feature name or branch of the worktree (default: the worktree you are in)
+        #[arg(value_hint = ValueHint::Other, add = ArgValueCandidates::new(completion::current_repo_worktree_names))]
+        target: Option<String>,
+        /// Squash the branch into one commit (default: `strategy` in the [finish] config)
+        #[arg(long, conflicts_with_all = ["ff_only", "merge"])]
+        squash: bool,
+        /// Only fast-forward the default branch
+        #[arg(long, conflicts_with = "merge")]
+        ff_only: bool,
+        /// Merge even if the [finish] config says otherwise
+        #[arg(long)]
+        merge: bool,
+        /// Merge into this branch instead of the default branch
+        #[arg(long, value_name = "BRANCH", add = ArgValueCandidates::new(completion::git_refs))]
+        into: Option<String>,
+        /// Keep the branch after removing the worktree
+        #[arg(long)]
+        keep_branch: bool,
+    },
+    /// Push a worktree's branch to its remote, setting the upstream if missing
+    Push {
+        /// Feature name, branch, or `repo/name` of the worktree (default: the worktree you are in)
+        #[arg(value_hint = ValueHint::Other, add = ArgValueCandidates::new(completion::worktree_names))]
+        target: Option<String>,
+        /// Overwrite the remote branch, unless someone pushed to it since it was last fetched
+        #[arg(long)]
+        force_with_lease: bool,
+    },
+    /// Archive a worktree's files into the storage directory without removing it
+    Archive {
+        /// Feature name of the worktree to archive
+        #[arg(value_hint = ValueHint::Other, add = ArgValueCandidates::new(completion::current_repo_worktree_names))]
+        feature: String,
+    },
+    /// Recreate an archived worktree. Lists archives when no feature is given.
+    Restore {
+        /// Feature name of the archived worktree
+        #[arg(value_hint = ValueHint::Other)]
+        feature: Option<String>,
+    },
     /// Navigate back to the original repository
     Back,
+    /// Open a worktree in the configured editor, or with a named `[open]` target
+    #[command(visible_alias = "code")]
+    Open {
+        /// Target worktree (feature name or `repo/name`). If not provided, opens interactive selection.
+        #[arg(value_hint = ValueHint::Other, add = ArgValueCandidates::new(completion::worktree_names))]
+        target: Option<String>,
+        /// Only consider worktrees of this repository (same as a `repo/name` target)
+        #[arg(long, value_name = "NAME", add = ArgValueCandidates::new(completion::repo_names))]
+        repo: Option<String>,
+        /// Open with this `[open.<name>]` target from the config instead of the editor
+        #[arg(long, value_name = "NAME", add = ArgValueCandidates::new(completion::open_targets))]
+        with: Option<String>,
+    },
+    /// Print the main repository path (from the main repository or any managed worktree)
+    Root,
+    /// Print a worktree's path, matched like `jump`, without prompting or side effects
+    Path {
+        /// Target worktree (feature name, branch, unique partial name, or `repo/name`)
+        #[arg(value_hint = ValueHint::Other, add = ArgValueCandidates::new(completion::worktree_names))]
+        target: String,
+        /// Current repo only
+        #[arg(long)]
+        current: bool,
+        /// Only consider worktrees of this repository (same as a `repo/name` target)
+        #[arg(long, value_name = "NAME", conflicts_with = "current", add = ArgValueCandidates::new(completion::repo_names))]
+        repo: Option<String>,
+    },
+    /// Print compact location info for shell prompts (nothing outside a repository)
+    Prompt {
+        /// Output template with {repo}, {feature}, {branch}, {kind}, {marker}, and {dirty}
+        #[arg(long, default_value = prompt::DEFAULT_FORMAT)]
+        format: String,
+        /// Also check for uncommitted changes ({dirty} shows `*`), at the cost of a status scan
+        #[arg(long)]
+        dirty: bool,
+    },
+    /// Fix worktree git links and origins after the storage root or main repository moved
+    Repair,
+    /// Move the storage root, with every worktree, to a new location
+    Relocate {
+        /// New storage root; must not exist yet or be an empty directory
+        #[arg(value_hint = ValueHint::DirPath)]
+        new_root: PathBuf,
+    },
+    /// Upgrade the storage root to the current format
+    Migrate {
+        /// Show which migrations would run without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show who holds the storage metadata lock (debugging aid)
+    LockStatus {
+        /// Remove the lock if its holder is no longer running
+        #[arg(long = "break")]
+        break_lock: bool,
+        /// With --break, remove the lock even if its holder is still running
+        #[arg(long, requires = "break_lock")]
+        force: bool,
+    },
     /// Manage the worktree-manager agent skill
     Skill {
         #[command(subcommand)]
         action: SkillAction,
     },
+    /// Open pull requests for worktrees
+    Pr {
+        #[command(subcommand)]
+        action: PrAction,
+    },
+    /// Attach a note to a worktree, or print its note
+    Note {
+        /// Feature name, branch, or `repo/name` of the worktree
+        #[arg(value_hint = ValueHint::Other, add = ArgValueCandidates::new(completion::worktree_names))]
+        target: String,
+        /// The note; replaces any earlier one. Omit to print the current note.
+        message: Option<String>,
+        /// Remove the note
+        #[arg(long, conflicts_with = "message")]
+        clear: bool,
+    },
+    /// Show everything known about a worktree: branch, base, ages, uncommitted files,
+    /// labels, note, copied files, and origin
+    Info {
+        /// Feature name, branch, or `repo/name` of the worktree (default: the current one)
+        #[arg(value_hint = ValueHint::Other, add = ArgValueCandidates::new(completion::worktree_names))]
+        target: Option<String>,
+        /// Print the details as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Browse worktrees in a terminal interface and jump, open, create, or remove them
+    Ui {
+        /// Write the path to jump to into this file instead of stdout (used by the shell
+        /// integration)
+        #[arg(long, value_name = "FILE", hide = true)]
+        path_file: Option<PathBuf>,
+    },
+    /// Label worktrees to group and filter them (`list --label`, `jump --label`)
+    Label {
+        #[command(subcommand)]
+        action: LabelAction,
+    },
+    /// Generate editor workspaces spanning the repository and its worktrees
+    Workspace {
+        #[command(subcommand)]
+        action: WorkspaceAction,
+    },
+    /// Write man pages for all commands to a directory (for packagers)
+    #[command(hide = true)]
+    Manpages {
+        /// Directory to write the pages to (created if missing)
+        #[arg(value_hint = ValueHint::DirPath)]
+        out_dir: PathBuf,
+    },
 }
 
-fn main() -> Result<()> {
+fn main() -> ExitCode {
+    // Answers `COMPLETE=<shell> worktree-bin -- <words>` from the shell integration and exits
+    CompleteEnv::with_factory(Cli::command)
+        .var(init::COMPLETE_ENV)
+        .complete();
+
     let cli = Cli::parse();
 
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            if events::enabled() {
+                events::emit(&events::Event::Error {
+                    message: &format!("{:#}", e),
+                });
+            } else {
+                let style = output::ERROR;
+                anstream::eprintln!("{style}Error:{style:#} {:?}", e);
+            }
+            ExitCode::from(error::exit_code(&e))
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    if let Some(secs) = cli.lock_timeout {
+        lock::set_lock_timeout(Duration::from_secs(secs));
+    }
+    if let Some(path) = cli.config {
+        config::set_config_override(path);
+    }
+    selection::set_assume_yes(cli.yes);
+    output::set_color_choice(cli.color);
+    events::set_enabled(cli.json_events);
+    logging::init(cli.verbose, cli.quiet);
+    let verbose = cli.verbose;
+    let quiet = cli.quiet;
+
     match cli.command {
         Commands::Create {
             feature_name,
@@ -120,29 +516,57 @@ fn main() -> Result<()> {
             from,
             interactive_from,
             list_from_completions,
+            open,
+            no_inherit_config,
+            description,
+            print_path,
+            fetch,
+            no_fetch,
         } => {
             if list_from_completions {
                 create::list_git_ref_completions()?;
                 return Ok(());
             }
 
-            match (feature_name, branch, from, interactive_from) {
+            let options = create::CreateOptions {
+                no_inherit_config,
+                description,
+                fetch: match (fetch, no_fetch) {
+                    (true, _) => Some(true),
+                    (_, true) => Some(false),
+                    _ => None,
+                },
+                output: if quiet {
+                    output::Target::Discard
+                } else {
+                    output::Target::Stdout
+                },
+            };
+            let path = match (feature_name, branch, from, interactive_from) {
                 // No args — full interactive workflow
-                (None, None, None, false) => {
-                    create::interactive_create_workflow()?;
-                }
+                (None, None, None, false) => create::interactive_create_workflow(&options)?,
                 // Feature name provided, wants interactive --from selection
                 (Some(feat), branch_arg, None, true) => {
-                    create::interactive_from_selection(&feat, branch_arg.as_deref())?;
-                }
-                // Feature name provided, no branch — prompt for branch interactively
-                (Some(feat), None, _from_ref, false) => {
-                    create::interactive_create_with_feature(&feat)?;
+                    create::interactive_from_selection(&feat, branch_arg.as_deref(), &options)?
                 }
+                // Feature name provided, no branch — use the prefixed feature name when a
+                // branch prefix is configured, else prompt for branch interactively
+                (Some(feat), None, from_ref, false) => match create::prefixed_branch(&feat)? {
+                    Some(branch_arg) => create::create_worktree(
+                        &feat,
+                        Some(&branch_arg),
+                        from_ref.as_deref(),
+                        &options,
+                    )?,
+                    None => create::interactive_create_with_feature(&feat, &options)?,
+                },
                 // Both feature name and branch provided
-                (Some(feat), Some(branch_arg), from_ref, false) => {
-                    create::create_worktree(&feat, Some(&branch_arg), from_ref.as_deref())?;
-                }
+                (Some(feat), Some(branch_arg), from_ref, false) => create::create_worktree(
+                    &feat,
+                    Some(&branch_arg),
+                    from_ref.as_deref(),
+                    &options,
+                )?,
                 // Invalid: --from without feature name
                 (None, _, Some(_), _) => {
                     anyhow::bail!(
@@ -157,7 +581,7 @@ fn main() -> Result<()> {
                 }
                 // Feature + branch + from + interactive_from: use from ref
                 (Some(feat), Some(branch_arg), Some(from_ref), true) => {
-                    create::create_worktree(&feat, Some(&branch_arg), Some(&from_ref))?;
+                    create::create_worktree(&feat, Some(&branch_arg), Some(&from_ref), &options)?
                 }
                 // Catch-all: invalid combinations
                 _ => {
@@ -165,31 +589,102 @@ fn main() -> Result<()> {
                         "Invalid argument combination. Run 'worktree create --help' for usage."
                     );
                 }
+            };
+            if print_path {
+                println!("{}", path.display());
             }
+            if open {
+                open::open_in_editor(&path)?;
+            }
+        }
+        Commands::Clone { url, name } => {
+            clone::clone_repository(&url, name.as_deref())?;
         }
-        Commands::List { current } => {
-            list::list_worktrees(current)?;
+        Commands::List {
+            current,
+            pr,
+            sort,
+            reverse,
+            labels,
+            format,
+        } => {
+            list::list_worktrees(&list::ListOptions {
+                current_repo_only: current,
+                show_pr: pr,
+                sort,
+                reverse,
+                labels,
+                verbose: verbose > 0,
+                format,
+            })?;
         }
         Commands::Remove {
-            target,
+            targets,
             delete_branch,
+            keep_branch,
+            force_delete_branch,
             interactive,
             list_completions,
             current,
+            archive,
+            delete_remote,
+            keep_remote,
+            repo,
         } => {
-            remove::remove_worktree(
-                target.as_deref(),
-                delete_branch,
+            let options = remove::RemoveOptions {
+                delete_branch: match (delete_branch, keep_branch) {
+                    (true, _) => Some(true),
+                    (_, true) => Some(false),
+                    _ => None,
+                },
+                force_delete_branch,
+                delete_remote: match (delete_remote, keep_remote) {
+                    (true, _) => Some(true),
+                    (_, true) => Some(false),
+                    _ => None,
+                },
                 interactive,
                 list_completions,
                 current,
-            )?;
+                archive,
+                repo,
+            };
+            remove::remove_worktree(&targets, &options)?;
         }
-        Commands::Status => {
-            status::show_status()?;
+        Commands::Status {
+            json,
+            target: Some(target),
+            ..
+        } => {
+            info::show_info(Some(&target), json)?;
         }
-        Commands::SyncConfig { from, to } => {
-            sync_config::sync_config(&from, &to)?;
+        Commands::Status {
+            pr,
+            json,
+            watch,
+            interval,
+            stale_days,
+            target: None,
+        } => {
+            status::show_status(&status::StatusOptions {
+                show_pr: pr,
+                json,
+                watch: watch.then(|| Duration::from_secs(interval.max(1))),
+                stale_days,
+            })?;
+        }
+        Commands::SyncConfig {
+            from,
+            to,
+            only,
+            list_completions,
+        } => {
+            if list_completions {
+                sync_config::list_worktree_completions()?;
+                return Ok(());
+            }
+
+            sync_config::sync_config(from.as_deref(), to.as_deref(), &only)?;
         }
         Commands::Init { shell } => {
             init::generate_shell_integration(shell);
@@ -199,23 +694,189 @@ fn main() -> Result<()> {
             interactive,
             list_completions,
             current,
+            last,
+            repo,
+            zellij,
+            no_zellij,
+            labels,
+            create,
         } => {
-            jump::jump_worktree(target.as_deref(), interactive, list_completions, current)?;
+            let target = if last {
+                Some(jump::PREVIOUS_TARGET)
+            } else {
+                target.as_deref()
+            };
+            jump::jump_worktree(
+                target,
+                &jump::JumpOptions {
+                    interactive,
+                    list_completions,
+                    current_repo_only: current,
+                    repo,
+                    zellij: match (zellij, no_zellij) {
+                        (true, _) => Some(true),
+                        (_, true) => Some(false),
+                        _ => None,
+                    },
+                    labels,
+                    create,
+                },
+            )?;
         }
-        Commands::Completions { shell } => {
-            let mut cmd = Cli::command();
-            init::generate_completions(shell, &mut cmd);
+        Commands::Completions { shell } => match shell.shell() {
+            Some(shell) => init::generate_completions(shell)?,
+            None => print!("{}", carapace::spec(&Cli::command())),
+        },
+        Commands::Cleanup {
+            dry_run,
+            interactive,
+            all,
+            merged,
+        } => {
+            cleanup::cleanup_worktrees(dry_run, interactive, all, merged)?;
         }
-        Commands::Cleanup => {
-            cleanup::cleanup_worktrees()?;
+        Commands::Autoclean { dry_run } => {
+            autoclean::autoclean(dry_run)?;
+        }
+        Commands::Du { sort } => {
+            du::show_disk_usage(sort)?;
         }
         Commands::Back => {
             back::back_to_origin()?;
         }
+        Commands::Manpages { out_dir } => {
+            manpages::generate_manpages(Cli::command(), &out_dir)?;
+        }
+        Commands::Open { target, repo, with } => {
+            open::open_worktree(target.as_deref(), repo.as_deref(), with.as_deref())?;
+        }
+        Commands::Root => {
+            root::print_root()?;
+        }
+        Commands::Path {
+            target,
+            current,
+            repo,
+        } => {
+            path::print_path(&target, current, repo.as_deref())?;
+        }
+        Commands::Prompt { format, dirty } => {
+            prompt::print_prompt(&format, dirty);
+        }
+        Commands::Update {
+            target,
+            merge,
+            rebase,
+            onto,
+            no_fetch,
+        } => {
+            update::update_worktree(
+                target.as_deref(),
+                &update::UpdateOptions {
+                    strategy: update_strategy(merge, rebase),
+                    onto,
+                    no_fetch,
+                },
+            )?;
+        }
+        Commands::Sync {
+            merge,
+            rebase,
+            no_fetch,
+        } => {
+            sync::sync_worktrees(&update::UpdateOptions {
+                strategy: update_strategy(merge, rebase),
+                onto: None,
+                no_fetch,
+            })?;
+        }
+        Commands::Finish {
+            target,
+            squash,
+            ff_only,
+            merge,
+            into,
+            keep_branch,
+        } => {
+            let strategy = if squash {
+                Some(FinishStrategy::Squash)
+            } else if ff_only {
+                Some(FinishStrategy::FfOnly)
+            } else if merge {
+                Some(FinishStrategy::Merge)
+            } else {
+                None
+            };
+            finish::finish_worktree(
+                target.as_deref(),
+                &finish::FinishOptions {
+                    strategy,
+                    into,
+                    keep_branch,
+                },
+            )?;
+        }
+        Commands::Archive { feature } => {
+            archive::archive_worktree(&feature)?;
+        }
+        Commands::Restore { feature } => {
+            archive::restore_worktree(feature.as_deref())?;
+        }
+        Commands::Repair => {
+            repair::repair_worktrees()?;
+        }
+        Commands::Relocate { new_root } => {
+            relocate::relocate_storage(&new_root)?;
+        }
+        Commands::Migrate { dry_run } => {
+            migrate::migrate_storage(dry_run)?;
+        }
+        Commands::LockStatus { break_lock, force } => {
+            lock_status::show_lock_status(break_lock, force)?;
+        }
         Commands::Skill { action } => {
             skill::run_skill_command(&action)?;
         }
+        Commands::Push {
+            target,
+            force_with_lease,
+        } => {
+            push::push_worktree(target.as_deref(), force_with_lease)?;
+        }
+        Commands::Pr { action } => {
+            pr::run_pr_command(&action)?;
+        }
+        Commands::Note {
+            target,
+            message,
+            clear,
+        } => {
+            note::note_worktree(&target, message.as_deref(), clear)?;
+        }
+        Commands::Info { target, json } => {
+            info::show_info(target.as_deref(), json)?;
+        }
+        Commands::Ui { path_file } => {
+            ui::run_ui(path_file.as_deref())?;
+        }
+        Commands::Label { action } => {
+            label::run_label_command(&action)?;
+        }
+        Commands::Workspace { action } => {
+            workspace::run_workspace_command(&action)?;
+        }
     }
 
     Ok(())
 }
+
+/// The update strategy chosen by `--merge`/`--rebase`, if either was given
+fn update_strategy(merge: bool, rebase: bool) -> Option<UpdateStrategy> {
+    if merge {
+        Some(UpdateStrategy::Merge)
+    } else if rebase {
+        Some(UpdateStrategy::Rebase)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,788 @@
+//! High-level API for the worktrees of one repository.
+//!
+//! [`WorktreeManager`] bundles the git repository, storage, and configuration that every
+//! operation needs, so editor plugins and other Rust tools can list, create, remove, and
+//! sync worktrees without running `worktree-bin`. The CLI commands delegate to it and add
+//! prompts and progress output on top.
+//!
+//! ```no_run
+//! use worktree::WorktreeManager;
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let manager = WorktreeManager::open(std::path::Path::new("."))?;
+//! let created = manager.create("auth", Some("feature/auth"), None)?;
+//! for worktree in manager.list()? {
+//!     println!("{} ({:?})", worktree.feature_name, worktree.branch);
+//! }
+//! manager.remove(&created.info.feature_name, false)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use crate::config::{Layout, MatchMode, WorktreeConfig};
+use crate::error::WorktreeError;
+use crate::events::Event;
+use crate::git::{self, GitError, GitRepo};
+use crate::output::Target;
+use crate::provision::template::TemplateContext;
+use crate::provision::{
+    self, build_cache, compose, config_files, dependency_dirs, devcontainer, direnv, git_hooks,
+    hooks, lfs, toolchain,
+};
+use crate::storage::layout::sibling_worktree_dir;
+use crate::storage::{WorktreeStorage, read_worktree_head_branch};
+use crate::traits::{GitOperations, StorageOperations};
+
+/// A managed worktree of the repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WorktreeInfo {
+    /// Feature name, which is also the worktree's directory name
+    pub feature_name: String,
+    pub path: PathBuf,
+    /// Branch checked out in the worktree; `None` if HEAD is detached or the worktree
+    /// directory is missing
+    pub branch: Option<String>,
+}
+
+impl WorktreeInfo {
+    /// Returns true if the worktree directory exists
+    #[must_use]
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+}
+
+/// A worktree that [`WorktreeManager::plan_create`] has checked can be created
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CreatePlan {
+    pub feature_name: String,
+    /// Branch the worktree will check out
+    pub branch: String,
+    pub path: PathBuf,
+    /// True if the branch does not exist yet and will be created
+    pub create_branch: bool,
+    /// Written to `branch.<name>.description`. `None` uses the `[branch-description]`
+    /// template when the branch is new.
+    pub description: Option<String>,
+}
+
+/// Result of [`WorktreeManager::create`]
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CreatedWorktree {
+    pub info: WorktreeInfo,
+    /// True if the branch did not exist and was created
+    pub created_branch: bool,
+    /// False if inheriting the parent repository's git config was turned off
+    /// (`inherit-git-config = false`)
+    pub inherited_config: bool,
+    /// Why the parent repository's git config could not be inherited, if it could not.
+    /// The worktree is usable regardless.
+    pub inherit_config_error: Option<GitError>,
+}
+
+/// Result of [`WorktreeManager::remove`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RemovedWorktree {
+    /// Branch that was checked out in the worktree, if any
+    pub branch: Option<String>,
+    /// True if that branch was deleted
+    pub branch_deleted: bool,
+}
+
+/// Entry point for managing the worktrees of one repository
+pub struct WorktreeManager<G = GitRepo, S = WorktreeStorage> {
+    git: G,
+    storage: S,
+    repo_path: PathBuf,
+    repo_name: String,
+    config: WorktreeConfig,
+    observer: Box<dyn Fn(&Event<'_>)>,
+    command_stdout: Target,
+}
+
+impl WorktreeManager {
+    /// Opens the repository containing `path`, using the default storage root
+    ///
+    /// # Errors
+    /// Returns an error if `path` is not inside a git repository, the storage root
+    /// cannot be resolved, or the repository's config file cannot be read
+    pub fn open(path: &Path) -> Result<Self> {
+        Self::with_storage(GitRepo::open(path)?, WorktreeStorage::new()?)
+    }
+}
+
+/// Opens the main repository of `repo_name` in storage through the origin paths
+/// recorded for its worktrees. Returns `None` if no origin leads back to it.
+pub fn find_origin_repo<S: StorageOperations + ?Sized>(
+    storage: &S,
+    repo_name: &str,
+) -> Option<GitRepo> {
+    let origins = storage.list_worktree_origins(repo_name).ok()?;
+
+    origins.into_iter().find_map(|(_, origin)| {
+        let git_repo = GitRepo::open(Path::new(&origin)).ok()?;
+        let name = WorktreeStorage::get_repo_name(git_repo.get_repo_path()).ok()?;
+        (name == repo_name).then_some(git_repo)
+    })
+}
+
+impl<G: GitOperations, S: StorageOperations> WorktreeManager<G, S> {
+    /// Creates a manager from an already opened repository and storage
+    ///
+    /// # Errors
+    /// Returns an error if the repository name cannot be determined or its config file
+    /// cannot be read
+    pub fn with_storage(git: G, storage: S) -> Result<Self> {
+        let repo_path = git.get_repo_path();
+        let repo_name = WorktreeStorage::get_repo_name(&repo_path)?;
+        let config = WorktreeConfig::load_from_repo(&repo_path)?;
+        Ok(Self {
+            git,
+            storage,
+            repo_path,
+            repo_name,
+            config,
+            observer: Box::new(|_| {}),
+            command_stdout: Target::Stdout,
+        })
+    }
+
+    /// Calls `observer` with the progress [`Event`]s of [`create`](Self::create) and
+    /// [`sync_config`](Self::sync_config), replacing any previous observer. The manager
+    /// prints nothing itself; without an observer the events are dropped.
+    pub fn on_event(&mut self, observer: impl Fn(&Event<'_>) + 'static) {
+        self.observer = Box::new(observer);
+    }
+
+    /// Where the commands [`create`](Self::create) runs (hooks, installers, and tool
+    /// commands) write their stdout; the process's stdout by default
+    pub fn set_command_stdout(&mut self, target: Target) {
+        self.command_stdout = target;
+    }
+
+    /// The underlying repository
+    #[must_use]
+    pub fn git(&self) -> &G {
+        &self.git
+    }
+
+    #[must_use]
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// The repository's `.worktree-config.toml` merged with defaults
+    #[must_use]
+    pub fn config(&self) -> &WorktreeConfig {
+        &self.config
+    }
+
+    /// Mutable access to the configuration, for overriding it with command-line flags
+    /// before creating worktrees
+    pub fn config_mut(&mut self) -> &mut WorktreeConfig {
+        &mut self.config
+    }
+
+    /// Working directory of the main repository
+    #[must_use]
+    pub fn repo_path(&self) -> &Path {
+        &self.repo_path
+    }
+
+    /// Name of the repository's directory under the storage root
+    #[must_use]
+    pub fn repo_name(&self) -> &str {
+        &self.repo_name
+    }
+
+    /// Where the worktree for `feature_name` lives (whether or not it exists)
+    #[must_use]
+    pub fn worktree_path(&self, feature_name: &str) -> PathBuf {
+        self.storage
+            .get_worktree_path(&self.repo_name, feature_name)
+    }
+
+    /// Lists the repository's managed worktrees
+    ///
+    /// # Errors
+    /// Returns an error if the storage directory cannot be read
+    pub fn list(&self) -> Result<Vec<WorktreeInfo>> {
+        Ok(self
+            .storage
+            .list_repo_worktrees(&self.repo_name)?
+            .into_iter()
+            .map(|feature_name| self.info(feature_name))
+            .collect())
+    }
+
+    /// Finds a worktree by exact feature name, then by the branch checked out in it, or
+    /// else by a unique prefix or substring of a feature name
+    ///
+    /// # Errors
+    /// Returns [`WorktreeError::NoMatchingWorktree`] or
+    /// [`WorktreeError::AmbiguousWorktree`] if no single worktree matches, or an error
+    /// if the storage directory cannot be read
+    pub fn find(&self, target: &str) -> Result<WorktreeInfo> {
+        self.find_with(target, MatchMode::default())
+    }
+
+    /// Like [`find`](Self::find), with `mode` deciding which partial feature names
+    /// match: prefixes win over substrings, and [`MatchMode::Exact`] allows neither
+    ///
+    /// # Errors
+    /// Returns [`WorktreeError::NoMatchingWorktree`] or
+    /// [`WorktreeError::AmbiguousWorktree`] if no single worktree matches, or an error
+    /// if the storage directory cannot be read
+    pub fn find_with(&self, target: &str, mode: MatchMode) -> Result<WorktreeInfo> {
+        if self.storage.is_managed(&self.repo_name, target) {
+            return Ok(self.info(target.to_string()));
+        }
+
+        let worktrees = self.storage.list_repo_worktrees(&self.repo_name)?;
+        if let Some(name) = worktrees.iter().find(|name| {
+            read_worktree_head_branch(&self.worktree_path(name)).as_deref() == Some(target)
+        }) {
+            return Ok(self.info(name.clone()));
+        }
+
+        let mut matches = mode.best_matches(target, worktrees, String::as_str);
+
+        match matches.len() {
+            0 => Err(WorktreeError::NoMatchingWorktree {
+                target: target.to_string(),
+            }
+            .into()),
+            1 => Ok(self.info(matches.remove(0))),
+            _ => {
+                matches.sort();
+                Err(WorktreeError::AmbiguousWorktree {
+                    target: target.to_string(),
+                    candidates: matches,
+                }
+                .into())
+            }
+        }
+    }
+
+    /// The repository path recorded when the worktree was created, if any
+    ///
+    /// # Errors
+    /// Returns an error if the origin metadata cannot be read
+    pub fn origin(&self, feature_name: &str) -> Result<Option<PathBuf>> {
+        Ok(self
+            .storage
+            .get_worktree_origin(&self.repo_name, feature_name)?
+            .map(PathBuf::from))
+    }
+
+    /// A feature name for a worktree of `branch` that no worktree of the repository
+    /// uses yet: the branch's last segment (`auth` for `feature/auth`), else the whole
+    /// branch with `/` turned into `-`, else that plus the first free numeric suffix
+    /// (`feature-auth-2`). Characters feature names cannot contain become `-`, so
+    /// distinct branches such as `feature/auth` and `bugfix/auth` never share a directory.
+    #[must_use]
+    pub fn feature_name_for_branch(&self, branch: &str) -> String {
+        let sanitize = |name: &str| -> String {
+            name.chars()
+                .map(|c| {
+                    if matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') {
+                        '-'
+                    } else {
+                        c
+                    }
+                })
+                .collect()
+        };
+        let is_free = |name: &str| {
+            WorktreeStorage::validate_feature_name(name).is_ok()
+                && !self.worktree_path(name).exists()
+        };
+
+        let flat = sanitize(branch);
+        let short = sanitize(branch.rsplit('/').next().unwrap_or(branch));
+        if is_free(&short) {
+            return short;
+        }
+        if is_free(&flat) {
+            return flat;
+        }
+        let mut suffix = 2;
+        loop {
+            let name = format!("{}-{}", flat, suffix);
+            if is_free(&name) {
+                return name;
+            }
+            suffix += 1;
+        }
+    }
+
+    /// The branch `create` uses for `feature_name` when given none: the feature name
+    /// behind the configured `branch-prefix`, if any
+    #[must_use]
+    pub fn default_branch_for_feature(&self, feature_name: &str) -> String {
+        format!(
+            "{}{}",
+            self.config.branch_prefix().unwrap_or_default(),
+            feature_name
+        )
+    }
+
+    /// Checks that a worktree for `feature_name` on `branch` (default: the feature name
+    /// behind the configured `branch-prefix`) can be created, and works out where it goes
+    /// and whether the branch is new
+    ///
+    /// # Errors
+    /// Returns an error if the feature name is invalid, the worktree already exists
+    /// ([`WorktreeError::WorktreeExists`], with a free name to use instead when it is
+    /// the worktree of another branch), or the branch lookup fails
+    pub fn plan_create(&self, feature_name: &str, branch: Option<&str>) -> Result<CreatePlan> {
+        WorktreeStorage::validate_feature_name(feature_name)?;
+        self.apply_layout()?;
+
+        let branch = branch.map_or_else(
+            || self.default_branch_for_feature(feature_name),
+            str::to_string,
+        );
+        let path = self.worktree_path(feature_name);
+
+        if path.exists() {
+            let taken_by = read_worktree_head_branch(&path).filter(|other| *other != branch);
+            let error = anyhow::Error::new(WorktreeError::WorktreeExists {
+                feature: feature_name.to_string(),
+                path,
+            });
+            return Err(match taken_by {
+                Some(other) => error.context(format!(
+                    "Feature name '{}' is taken by branch '{}'. Pick another one for '{}', \
+                    such as: worktree create {} {}",
+                    feature_name,
+                    other,
+                    branch,
+                    self.feature_name_for_branch(&branch),
+                    branch
+                )),
+                None => error,
+            });
+        }
+
+        let create_branch = !self.git.branch_exists(&branch)?;
+
+        Ok(CreatePlan {
+            feature_name: feature_name.to_string(),
+            branch,
+            path,
+            create_branch,
+            description: None,
+        })
+    }
+
+    /// Points the repository's worktree directory at the one `layout` asks for. Existing
+    /// worktrees are not moved, so while there are any the directory stays as it is.
+    fn apply_layout(&self) -> Result<()> {
+        // A bare clone made by `worktree clone` already sits in storage
+        let in_storage = self.repo_path.starts_with(self.storage.get_root_dir());
+        let wanted = match self.config.layout {
+            Layout::Sibling if !in_storage => {
+                Some(sibling_worktree_dir(&self.repo_path, &self.repo_name))
+            }
+            Layout::Sibling | Layout::Central => None,
+        };
+        let wanted_dir = wanted
+            .clone()
+            .unwrap_or_else(|| self.storage.get_repo_storage_dir(&self.repo_name));
+        let current_dir = self.storage.worktree_dir(&self.repo_name);
+        if current_dir == wanted_dir {
+            return Ok(());
+        }
+        if !self
+            .storage
+            .list_repo_worktrees(&self.repo_name)?
+            .is_empty()
+        {
+            warn!(
+                "layout = \"{}\" takes effect once the worktrees in {} are removed",
+                if wanted.is_some() {
+                    "sibling"
+                } else {
+                    "central"
+                },
+                current_dir.display()
+            );
+            return Ok(());
+        }
+        self.storage
+            .set_worktree_dir(&self.repo_name, wanted.as_deref())
+            .context("Failed to record the worktree directory")?;
+        Ok(())
+    }
+
+    /// Creates a worktree for `feature_name` on `branch` (default: the feature name),
+    /// creating the branch from `from` (default: HEAD) if it does not exist. Config
+    /// files are symlinked and copied from the main repository, the origin is recorded,
+    /// and the `[on-create]` hooks are run.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The feature name is invalid
+    /// - The worktree already exists ([`WorktreeError::WorktreeExists`])
+    /// - Git operations fail
+    /// - Config files cannot be linked or copied
+    pub fn create(
+        &self,
+        feature_name: &str,
+        branch: Option<&str>,
+        from: Option<&str>,
+    ) -> Result<CreatedWorktree> {
+        let plan = self.plan_create(feature_name, branch)?;
+        self.create_planned(plan, from)
+    }
+
+    /// Carries out a plan from [`plan_create`](Self::plan_create)
+    ///
+    /// # Errors
+    /// Returns an error if git operations fail or config files cannot be linked or
+    /// copied
+    pub fn create_planned(&self, plan: CreatePlan, from: Option<&str>) -> Result<CreatedWorktree> {
+        let CreatePlan {
+            feature_name,
+            branch,
+            path,
+            create_branch,
+            description,
+        } = plan;
+        let report: provision::Report<'_> = &*self.observer;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create parent directory: {}", parent.display())
+            })?;
+        }
+
+        report(&Event::StepStarted {
+            step: "create-worktree",
+        });
+        self.git
+            .create_worktree_from(&branch, &path, create_branch, from)?;
+        if create_branch {
+            self.storage
+                .record_managed_branch(&self.repo_name, &branch)?;
+            report(&Event::BranchCreated {
+                branch: &branch,
+                from,
+            });
+        }
+
+        let inherited_config = self.config.inherits_git_config();
+        let inherit_config_error = if inherited_config {
+            report(&Event::StepStarted {
+                step: "inherit-config",
+            });
+            self.git.inherit_config(&path).err()
+        } else {
+            None
+        };
+        lfs::check_out_lfs_files(self.config.lfs.mode, &path, report);
+
+        // Symlinks first: they take precedence over copies
+        config_files::create_symlinks(&self.repo_path, &path, &self.config, report)?;
+        let copied = config_files::copy_config_files(&self.repo_path, &path, &self.config, report)?;
+        self.storage
+            .record_copied_files(&self.repo_name, &feature_name, &copied)?;
+        let slot = self.storage.allocate_slot(&self.repo_name, &feature_name)?;
+        self.storage
+            .record_created(&self.repo_name, &feature_name)?;
+        let context = TemplateContext {
+            name: &feature_name,
+            branch: &branch,
+            path: &path,
+            repo: &self.repo_name,
+            origin: &self.repo_path,
+            slot,
+        };
+        let description = description
+            .or_else(|| {
+                let template = self.config.branch_description.template.as_deref()?;
+                create_branch.then(|| context.render(template))
+            })
+            .filter(|description| !description.trim().is_empty());
+        if let Some(description) = &description {
+            git::set_branch_description(&self.repo_path, &branch, description)?;
+        }
+        if let Some(base) = from.filter(|_| create_branch) {
+            if self.is_branch(base)? {
+                git::set_branch_base(&self.repo_path, &branch, base)?;
+            }
+        }
+        report(&Event::StepStarted { step: "set-up" });
+        direnv::write_envrc(&self.config.direnv, &context, self.command_stdout, report)?;
+        devcontainer::adapt_devcontainers(&self.config.devcontainer, &context, report)?;
+        compose::write_project_name(&self.config.compose, &context, report)?;
+        build_cache::set_up_build_cache(
+            &self.config.build_cache,
+            &context,
+            &self
+                .storage
+                .get_repo_storage_dir(&self.repo_name)
+                .join(build_cache::BUILD_CACHE_DIR),
+            report,
+        )?;
+        let variables = context.variables();
+        toolchain::set_up_toolchain(
+            &self.config.toolchain,
+            &self.repo_path,
+            &path,
+            self.command_stdout,
+            report,
+        )?;
+        dependency_dirs::set_up_dependency_dirs(
+            &self.config.dependency_dirs,
+            &self.repo_path,
+            &path,
+            &variables,
+            self.command_stdout,
+            report,
+        )?;
+
+        provision::store_origin_info(
+            &self.storage,
+            &self.repo_name,
+            &feature_name,
+            &self.repo_path,
+        )?;
+        if let Err(e) = self
+            .storage
+            .write_worktree_meta(&self.repo_name, &feature_name)
+        {
+            warn!("Failed to write worktree metadata: {}", e);
+        }
+
+        report(&Event::StepStarted { step: "git-hooks" });
+        git_hooks::set_up_git_hooks(
+            &self.config.git_hooks,
+            &self.repo_path,
+            &path,
+            &variables,
+            self.command_stdout,
+            report,
+        );
+        hooks::run_on_create_hooks(&path, &self.config, &variables, self.command_stdout, report)?;
+        report(&Event::WorktreeCreated {
+            feature: &feature_name,
+            branch: &branch,
+            path: &path,
+        });
+
+        Ok(CreatedWorktree {
+            info: WorktreeInfo {
+                feature_name,
+                path,
+                branch: Some(branch),
+            },
+            created_branch: create_branch,
+            inherited_config,
+            inherit_config_error,
+        })
+    }
+
+    /// Removes a worktree's directory, its git registration, and its origin entry, and
+    /// optionally the branch it had checked out. Nothing is asked or archived; see
+    /// `commands::remove` for the interactive safety checks.
+    ///
+    /// # Errors
+    /// Returns an error if the worktree does not exist or any removal step fails
+    pub fn remove(&self, feature_name: &str, delete_branch: bool) -> Result<RemovedWorktree> {
+        let worktree_path = self.worktree_path(feature_name);
+        if !worktree_path.exists() {
+            return Err(WorktreeError::WorktreeMissing {
+                path: worktree_path,
+            }
+            .into());
+        }
+
+        let branch = read_worktree_head_branch(&worktree_path);
+
+        std::fs::remove_dir_all(&worktree_path).context("Failed to remove worktree directory")?;
+        self.git
+            .remove_worktree(feature_name)
+            .context("Failed to remove worktree from git")?;
+        self.storage
+            .remove_worktree_origin(&self.repo_name, feature_name)?;
+        self.storage.release_slot(&self.repo_name, feature_name)?;
+        self.storage
+            .forget_activity(&self.repo_name, feature_name)?;
+        self.storage.forget_labels(&self.repo_name, feature_name)?;
+        self.storage.forget_note(&self.repo_name, feature_name)?;
+        self.storage
+            .forget_copied_files(&self.repo_name, feature_name)?;
+
+        let mut branch_deleted = false;
+        if let (true, Some(branch)) = (delete_branch, &branch) {
+            self.git.delete_branch(branch)?;
+            self.storage
+                .forget_managed_branch(&self.repo_name, branch)?;
+            branch_deleted = true;
+        }
+
+        Ok(RemovedWorktree {
+            branch,
+            branch_deleted,
+        })
+    }
+
+    /// Copies config files from `from` to `to` (worktree or repository directories)
+    /// using the configured patterns, or only `only` if it is non-empty. Returns the
+    /// number of files and directories copied.
+    ///
+    /// # Errors
+    /// Returns an error if either directory is missing or copying fails
+    pub fn sync_config(&self, from: &Path, to: &Path, only: &[String]) -> Result<usize> {
+        if !from.exists() {
+            return Err(WorktreeError::WorktreeMissing {
+                path: from.to_path_buf(),
+            })
+            .context("Source worktree does not exist");
+        }
+        if !to.exists() {
+            return Err(WorktreeError::WorktreeMissing {
+                path: to.to_path_buf(),
+            })
+            .context("Target worktree does not exist");
+        }
+
+        let report: provision::Report<'_> = &*self.observer;
+        let copied = if only.is_empty() {
+            config_files::copy_config_files(from, to, &self.config, report)?
+        } else {
+            let config = self.config.clone().restricted_to(only.to_vec());
+            config_files::copy_config_files(from, to, &config, report)?
+        };
+        report(&Event::ConfigSynced {
+            from,
+            to,
+            files: copied.len(),
+        });
+        Ok(copied.len())
+    }
+
+    /// True if `name` is a local branch or a remote-tracking branch such as `origin/main`
+    fn is_branch(&self, name: &str) -> Result<bool> {
+        Ok(self.git.branch_exists(name)?
+            || self.git.list_remote_branches()?.iter().any(|b| b == name))
+    }
+
+    fn info(&self, feature_name: String) -> WorktreeInfo {
+        let path = self.worktree_path(&feature_name);
+        WorktreeInfo {
+            branch: read_worktree_head_branch(&path),
+            feature_name,
+            path,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+    use tempfile::TempDir;
+    use test_support::git;
+
+    fn setup() -> Result<(TempDir, WorktreeManager)> {
+        let tmp = TempDir::new()?;
+        let repo = tmp.path().join("repo");
+        std::fs::create_dir(&repo)?;
+        git(&repo, &["init", "-b", "main"])?;
+        git(&repo, &["config", "user.name", "Test User"])?;
+        git(&repo, &["config", "user.email", "test@example.com"])?;
+        git(&repo, &["commit", "--allow-empty", "-m", "init"])?;
+
+        let storage = WorktreeStorage::with_root_dir(tmp.path().join("worktrees"))?;
+        let manager = WorktreeManager::with_storage(GitRepo::open(&repo)?, storage)?;
+        Ok((tmp, manager))
+    }
+
+    #[test]
+    fn test_create_find_and_remove() -> Result<()> {
+        let (_tmp, manager) = setup()?;
+
+        let created = manager.create("auth", Some("feature/auth"), None)?;
+        assert!(created.created_branch);
+        assert!(created.info.exists());
+        assert_eq!(manager.list()?, vec![created.info.clone()]);
+        assert_eq!(manager.find("au")?, created.info);
+        assert_eq!(
+            manager.origin("auth")?.as_deref(),
+            Some(manager.repo_path())
+        );
+
+        let removed = manager.remove("auth", true)?;
+        assert_eq!(removed.branch.as_deref(), Some("feature/auth"));
+        assert!(removed.branch_deleted);
+        assert!(!created.info.exists());
+        assert!(!manager.git().branch_exists("feature/auth")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_feature_names_of_distinct_branches_never_collide() -> Result<()> {
+        let (_tmp, manager) = setup()?;
+        assert_eq!(manager.feature_name_for_branch("feature/a-b"), "a-b");
+        manager.create("a-b", Some("feature/a-b"), None)?;
+
+        // The short name is taken, so the whole branch is used
+        assert_eq!(manager.feature_name_for_branch("bugfix/a-b"), "bugfix-a-b");
+        manager.create("feature-a-b", Some("x"), None)?;
+        manager.create("b", Some("y"), None)?;
+        assert_eq!(
+            manager.feature_name_for_branch("feature/a/b"),
+            "feature-a-b-2"
+        );
+        manager.create("feature-a-b-2", Some("z"), None)?;
+        assert_eq!(
+            manager.feature_name_for_branch("feature/a/b"),
+            "feature-a-b-3"
+        );
+
+        // Reusing a taken name for another branch says which one to use instead
+        let Err(error) = manager.create("a-b", Some("bugfix/a-b"), None) else {
+            anyhow::bail!("created a second worktree named a-b");
+        };
+        assert!(matches!(
+            WorktreeError::find(&error),
+            Some(WorktreeError::WorktreeExists { .. })
+        ));
+        assert!(format!("{}", error).contains("worktree create bugfix-a-b bugfix/a-b"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_reports_ambiguous_candidates() -> Result<()> {
+        let (tmp, manager) = setup()?;
+        let storage = MemoryStorage::new(tmp.path().join("worktrees"));
+        storage.add_worktree("repo", "api-v1");
+        storage.add_worktree("repo", "api-v2");
+        storage.add_worktree("other", "api-v3");
+        let manager = WorktreeManager::with_storage(manager.git, storage)?;
+
+        assert_eq!(manager.find("api-v1")?.feature_name, "api-v1");
+        let Err(error) = manager.find("api") else {
+            anyhow::bail!("expected an ambiguous match");
+        };
+        assert_eq!(
+            WorktreeError::find(&error),
+            Some(&WorktreeError::AmbiguousWorktree {
+                target: "api".to_string(),
+                candidates: vec!["api-v1".to_string(), "api-v2".to_string()],
+            })
+        );
+        Ok(())
+    }
+}
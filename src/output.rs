@@ -0,0 +1,146 @@
+//! Colored status lines.
+//!
+//! Commands mark outcome lines with `✓` (success), `✗` (failure), or `⚠` (warning).
+//! [`status`] and [`status_err`] print such a line with its marker colored, and
+//! `Warning:`/`Error:` labels colored with it; [`Target`] does the same for output that
+//! may go to stderr or nowhere instead. Color follows `--color`
+//! ([`set_color_choice`]): `auto` colors only terminals and honors `NO_COLOR` and
+//! `CLICOLOR_FORCE`; `always` and `never` force it on or off.
+
+use anstyle::{AnsiColor, Style};
+use clap::ValueEnum;
+use std::fmt::Display;
+use std::os::fd::AsFd;
+use std::process::Stdio;
+
+/// Style of `✓` markers
+pub const SUCCESS: Style = AnsiColor::Green.on_default().bold();
+/// Style of `⚠` markers and `Warning:` labels
+pub const WARNING: Style = AnsiColor::Yellow.on_default().bold();
+/// Style of `✗` markers and `Error:` labels
+pub const ERROR: Style = AnsiColor::Red.on_default().bold();
+
+/// When to color output (`--color`)
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ColorChoice {
+    /// Color terminals unless `NO_COLOR` is set
+    #[default]
+    Auto,
+    /// Always color, even when piped or with `NO_COLOR`
+    Always,
+    /// Never color
+    Never,
+}
+
+/// Applies `--color` to everything printed through [`status`], [`status_err`], and the
+/// log output
+pub fn set_color_choice(choice: ColorChoice) {
+    let choice = match choice {
+        ColorChoice::Auto => anstream::ColorChoice::Auto,
+        ColorChoice::Always => anstream::ColorChoice::Always,
+        ColorChoice::Never => anstream::ColorChoice::Never,
+    };
+    choice.write_global();
+}
+
+/// Prints a status line to stdout, coloring its leading marker
+pub fn status(line: impl Display) {
+    anstream::println!("{}", paint(&line.to_string()));
+}
+
+/// Prints a status line to stderr, coloring its leading marker
+pub fn status_err(line: impl Display) {
+    anstream::eprintln!("{}", paint(&line.to_string()));
+}
+
+/// Where a command's progress output goes
+///
+/// Stdout normally; stderr when stdout carries the command's result, such as the path
+/// `jump --create` prints for the shell function; nowhere for `create --quiet`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Target {
+    /// Print to stdout
+    #[default]
+    Stdout,
+    /// Print to stderr
+    Stderr,
+    /// Print nothing
+    Discard,
+}
+
+impl Target {
+    /// Prints `line` as is
+    pub fn line(self, line: impl Display) {
+        match self {
+            Self::Stdout => anstream::println!("{}", line),
+            Self::Stderr => anstream::eprintln!("{}", line),
+            Self::Discard => {}
+        }
+    }
+
+    /// Prints a status line, coloring its leading marker
+    pub fn status(self, line: impl Display) {
+        self.line(paint(&line.to_string()));
+    }
+
+    /// Where a child process should write its stdout to follow this target
+    #[must_use]
+    pub fn stdio(self) -> Stdio {
+        match self {
+            Self::Stdout => Stdio::inherit(),
+            Self::Stderr => std::io::stderr()
+                .as_fd()
+                .try_clone_to_owned()
+                .map_or_else(|_| Stdio::inherit(), Stdio::from),
+            Self::Discard => Stdio::null(),
+        }
+    }
+}
+
+/// Colors the marker after the line's indentation, plus a `Warning:` or `Error:` label
+/// right after it. Lines without a marker are returned unchanged; the escape codes are
+/// stripped on output when color is off.
+fn paint(line: &str) -> String {
+    let body = line.trim_start();
+    let indent = &line[..line.len() - body.len()];
+    let mut chars = body.chars();
+    let style = match chars.next() {
+        Some('✓') => SUCCESS,
+        Some('✗') => ERROR,
+        Some('⚠') => WARNING,
+        _ => return line.to_string(),
+    };
+    let rest = chars.as_str();
+    let label_len = ["Warning:", "Error:"]
+        .iter()
+        .find(|label| rest.trim_start().starts_with(*label))
+        .map_or(0, |label| {
+            rest.len() - rest.trim_start().len() + label.len()
+        });
+    let marker = &body[..body.len() - rest.len()];
+    format!(
+        "{indent}{style}{marker}{}{style:#}{}",
+        &rest[..label_len],
+        &rest[label_len..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paint_colors_marker_and_label() {
+        assert_eq!(
+            paint("  ✓ Done: make"),
+            format!("  {SUCCESS}✓{SUCCESS:#} Done: make")
+        );
+        assert_eq!(
+            paint("⚠ Warning: disk full"),
+            format!("{WARNING}⚠ Warning:{WARNING:#} disk full")
+        );
+        assert_eq!(paint("Copying files..."), "Copying files...");
+    }
+}
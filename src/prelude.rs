@@ -0,0 +1,26 @@
+//! Convenience re-exports for embedding `worktree` as a library.
+//!
+//! ```no_run
+//! use worktree::prelude::*;
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let storage = WorktreeStorage::new()?;
+//! let repo = GitRepo::open(std::path::Path::new("."))?;
+//! let repo_name = WorktreeStorage::get_repo_name(repo.get_repo_path())?;
+//! let config = WorktreeConfig::load_from_repo(repo.get_repo_path())?;
+//! # let _ = (storage, repo_name, config);
+//! # Ok(())
+//! # }
+//! ```
+
+pub use crate::config::{
+    ConfigError, CopyPatterns, OnCreate, ProtectedBranches, SymlinkPatterns, WorktreeConfig,
+};
+pub use crate::error::WorktreeError;
+pub use crate::git::{GitError, GitRepo};
+pub use crate::manager::{
+    CreatePlan, CreatedWorktree, RemovedWorktree, WorktreeInfo, WorktreeManager,
+};
+pub use crate::selection::{GitRefOption, RealSelectionProvider, SelectionProvider};
+pub use crate::storage::{MemoryStorage, StorageError, WorktreeStorage};
+pub use crate::traits::{AheadBehind, CommitInfo, GitOperations, StorageOperations};
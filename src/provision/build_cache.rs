@@ -0,0 +1,178 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use crate::config::BuildCache;
+use crate::events::Event;
+use crate::provision::Report;
+use crate::provision::template::TemplateContext;
+
+/// Shared cache directory inside the repository's storage directory, used when
+/// `[build-cache] dir` is not set
+pub const BUILD_CACHE_DIR: &str = ".build-cache";
+
+/// Placeholder replaced by the shared cache directory in `[build-cache.files]`
+pub const CACHE_PLACEHOLDER: &str = "{cache}";
+
+/// Links the configured paths of a new worktree to the shared cache and writes the
+/// configured files
+///
+/// `default_dir` is used when `[build-cache] dir` is not set. Paths that already exist
+/// in the worktree (for example committed files) are left alone with a warning.
+///
+/// # Errors
+/// Returns an error if the cache directory, a symlink, or a file cannot be created.
+pub fn set_up_build_cache(
+    config: &BuildCache,
+    context: &TemplateContext<'_>,
+    default_dir: &Path,
+    report: Report<'_>,
+) -> Result<()> {
+    if !config.is_configured() {
+        return Ok(());
+    }
+
+    let cache_dir = config.dir.as_deref().map_or_else(
+        || default_dir.to_path_buf(),
+        |dir| resolve_dir(dir, context.origin),
+    );
+    fs::create_dir_all(&cache_dir).with_context(|| {
+        format!(
+            "Failed to create build cache directory {}",
+            cache_dir.display()
+        )
+    })?;
+    report(&Event::BuildCacheStarted { dir: &cache_dir });
+
+    for entry in config.link.as_deref().unwrap_or_default() {
+        let entry = entry.trim_end_matches('/');
+        let link = context.path.join(entry);
+        if link.symlink_metadata().is_ok() {
+            warn!(
+                "{} already exists in the worktree; not linking it to the build cache",
+                entry
+            );
+            continue;
+        }
+        let target = cache_dir.join(entry);
+        fs::create_dir_all(&target)
+            .with_context(|| format!("Failed to create {}", target.display()))?;
+        if let Some(parent) = link.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        std::os::unix::fs::symlink(&target, &link).with_context(|| {
+            format!(
+                "Failed to create symlink {} -> {}",
+                link.display(),
+                target.display()
+            )
+        })?;
+        report(&Event::CacheLinked {
+            path: Path::new(entry),
+            target: &target,
+        });
+    }
+
+    let cache = cache_dir.display().to_string();
+    for (file, template) in &config.files {
+        let path = context.path.join(file);
+        if path.symlink_metadata().is_ok() {
+            warn!(
+                "{} already exists in the worktree; not overwriting it",
+                file
+            );
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = context.render(&template.replace(CACHE_PLACEHOLDER, &cache));
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+        report(&Event::FileWritten {
+            step: "build-cache",
+            path: Path::new(file),
+        });
+    }
+    Ok(())
+}
+
+/// Points a worktree's build cache links and `[build-cache.files]` at `new_root` after
+/// the storage root moved there from `old_root`
+///
+/// Links into the old root are recreated with the same path under the new one, and
+/// occurrences of the old root in the configured files are replaced, since `{cache}`
+/// and `{path}` render to absolute paths. Caches outside the storage root are left
+/// alone. Returns the number of links and files updated.
+///
+/// # Errors
+/// Returns an error if a link cannot be recreated or a file cannot be rewritten.
+pub fn relocate_build_cache(
+    config: &BuildCache,
+    worktree_path: &Path,
+    old_root: &Path,
+    new_root: &Path,
+) -> Result<usize> {
+    let mut updated = 0;
+    for entry in config.link.as_deref().unwrap_or_default() {
+        let link = worktree_path.join(entry.trim_end_matches('/'));
+        let Ok(target) = fs::read_link(&link) else {
+            continue;
+        };
+        let Ok(rest) = target.strip_prefix(old_root) else {
+            continue;
+        };
+        fs::remove_file(&link)
+            .with_context(|| format!("Failed to replace symlink {}", link.display()))?;
+        std::os::unix::fs::symlink(new_root.join(rest), &link)
+            .with_context(|| format!("Failed to recreate symlink {}", link.display()))?;
+        updated += 1;
+    }
+
+    let old = old_root.display().to_string();
+    let new = new_root.display().to_string();
+    for file in config.files.keys() {
+        let path = worktree_path.join(file);
+        if path.is_symlink() {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if content.contains(&old) {
+            fs::write(&path, content.replace(&old, &new))
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            updated += 1;
+        }
+    }
+    Ok(updated)
+}
+
+/// Resolves the configured cache directory: `~/` is the home directory and relative
+/// paths are relative to the repository root
+fn resolve_dir(dir: &str, repo_root: &Path) -> PathBuf {
+    if let Some(rest) = dir.strip_prefix("~/") {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(rest);
+        }
+    }
+    repo_root.join(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_dir() {
+        let root = Path::new("/src/app");
+        assert_eq!(resolve_dir("/cache", root), PathBuf::from("/cache"));
+        assert_eq!(
+            resolve_dir("../cache", root),
+            PathBuf::from("/src/app/../cache")
+        );
+        if let Some(home) = dirs::home_dir() {
+            assert_eq!(resolve_dir("~/.cache/app", root), home.join(".cache/app"));
+        }
+    }
+}
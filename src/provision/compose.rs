@@ -0,0 +1,147 @@
+use anyhow::{Context, Result};
+use std::fs;
+
+use crate::config::{Compose, ComposeTarget};
+use crate::events::Event;
+use crate::provision::Report;
+use crate::provision::template::TemplateContext;
+
+/// Default template for the project name
+pub const DEFAULT_PROJECT_NAME: &str = "{repo}-{name}";
+
+/// Variable Docker Compose reads the project name from
+pub const PROJECT_NAME_VAR: &str = "COMPOSE_PROJECT_NAME";
+
+/// Environment file Docker Compose loads from the project directory
+pub const ENV_FILE: &str = ".env";
+
+/// Override file Docker Compose merges automatically
+pub const OVERRIDE_FILE: &str = "compose.override.yaml";
+
+/// Writes the worktree's Compose project name when `[compose]` is enabled
+///
+/// A `.env` or override file that is a symlink to the main repository is replaced by a
+/// copy first, so the main repository keeps its own name.
+///
+/// # Errors
+/// Returns an error if the file cannot be read or written.
+pub fn write_project_name(
+    config: &Compose,
+    context: &TemplateContext<'_>,
+    report: Report<'_>,
+) -> Result<()> {
+    if !config.enabled {
+        return Ok(());
+    }
+
+    let project_name = project_name(
+        &context.render(
+            config
+                .project_name
+                .as_deref()
+                .unwrap_or(DEFAULT_PROJECT_NAME),
+        ),
+    );
+    let (file, update): (&str, fn(&str, &str) -> String) = match config.target {
+        ComposeTarget::Env => (ENV_FILE, set_env_var),
+        ComposeTarget::Override => (OVERRIDE_FILE, set_override_name),
+    };
+
+    let path = context.path.join(file);
+    let existing = if path.exists() {
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?
+    } else {
+        String::new()
+    };
+    if path.is_symlink() {
+        fs::remove_file(&path)
+            .with_context(|| format!("Failed to replace symlink {}", path.display()))?;
+    }
+    fs::write(&path, update(&existing, &project_name))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    report(&Event::ComposeProjectNamed {
+        name: &project_name,
+        file,
+    });
+    Ok(())
+}
+
+/// Compose project names allow lowercase letters, digits, `-`, and `_`, starting with a
+/// letter or digit; other characters (such as `/` from branch names) become `-`
+#[must_use]
+pub fn project_name(name: &str) -> String {
+    let sanitized: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '-') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    sanitized
+        .trim_start_matches(|c: char| !c.is_ascii_alphanumeric())
+        .to_string()
+}
+
+/// Sets `COMPOSE_PROJECT_NAME` in `.env` content, replacing an existing assignment
+fn set_env_var(content: &str, project_name: &str) -> String {
+    let assignment = format!("{}={}", PROJECT_NAME_VAR, project_name);
+    replace_or_prepend(content, &assignment, |line| {
+        line.trim_start()
+            .trim_start_matches("export ")
+            .starts_with(&format!("{}=", PROJECT_NAME_VAR))
+    })
+}
+
+/// Sets the top-level `name` in override file content, replacing an existing one
+fn set_override_name(content: &str, project_name: &str) -> String {
+    replace_or_prepend(content, &format!("name: {}", project_name), |line| {
+        line.starts_with("name:")
+    })
+}
+
+/// Replaces the first line matching `is_setting` with `setting`, or puts `setting`
+/// first when no line matches
+fn replace_or_prepend(content: &str, setting: &str, is_setting: impl Fn(&str) -> bool) -> String {
+    let mut replaced = false;
+    let mut lines: Vec<&str> = content
+        .lines()
+        .map(|line| {
+            if !replaced && is_setting(line) {
+                replaced = true;
+                setting
+            } else {
+                line
+            }
+        })
+        .collect();
+    if !replaced {
+        lines.insert(0, setting);
+    }
+    format!("{}\n", lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_env_var_and_override_name() {
+        assert_eq!(
+            set_env_var("DB=x\nCOMPOSE_PROJECT_NAME=app\n", "app-auth"),
+            "DB=x\nCOMPOSE_PROJECT_NAME=app-auth\n"
+        );
+        assert_eq!(
+            set_env_var("", "app-auth"),
+            "COMPOSE_PROJECT_NAME=app-auth\n"
+        );
+        assert_eq!(
+            set_override_name("services:\n  web:\n    name: web\n", "app-auth"),
+            "name: app-auth\nservices:\n  web:\n    name: web\n"
+        );
+        assert_eq!(project_name("My_App-feature/Auth"), "my_app-feature-auth");
+    }
+}
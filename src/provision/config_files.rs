@@ -0,0 +1,446 @@
+//! Config files a new worktree gets from the main repository: `[symlink-patterns]`
+//! become symlinks to the main repository, and `[copy-patterns]` are copied.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+use crate::config::WorktreeConfig;
+use crate::events::Event;
+use crate::provision::Report;
+
+/// Creates symlinks in the worktree for patterns listed in `[symlink-patterns]`.
+/// Symlinks point to the absolute path in the origin repo.
+///
+/// # Errors
+/// Returns an error if symlink creation fails for reasons other than missing origin path.
+pub fn create_symlinks(
+    source_path: &Path,
+    target_path: &Path,
+    config: &WorktreeConfig,
+    report: Report<'_>,
+) -> Result<()> {
+    let patterns = match config.symlink_patterns.include.as_deref() {
+        Some(p) if !p.is_empty() => p,
+        _ => return Ok(()),
+    };
+
+    report(&Event::StepStarted {
+        step: "symlink-config",
+    });
+
+    for pattern in patterns {
+        if let Some(matches) = find_matching_files(source_path, pattern)? {
+            for source_file in matches {
+                let relative_path = source_file.strip_prefix(source_path)?;
+                let target_link = target_path.join(relative_path);
+
+                // Canonicalize the origin path for the symlink target
+                let canonical_source = source_file.canonicalize().with_context(|| {
+                    format!(
+                        "Failed to canonicalize symlink source: {}",
+                        source_file.display()
+                    )
+                })?;
+
+                // Create parent dir if needed
+                if let Some(parent) = target_link.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+
+                // Skip if already exists (e.g. already copied)
+                if target_link.exists() || target_link.symlink_metadata().is_ok() {
+                    continue;
+                }
+
+                std::os::unix::fs::symlink(&canonical_source, &target_link).with_context(|| {
+                    format!(
+                        "Failed to create symlink {} -> {}",
+                        target_link.display(),
+                        canonical_source.display()
+                    )
+                })?;
+
+                report(&Event::Symlinked {
+                    path: relative_path,
+                    target: &canonical_source,
+                });
+            }
+        } else {
+            warn!(
+                "Symlink pattern '{}' did not match any files in origin repo — skipping",
+                pattern
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Copies configuration files from source to target based on config patterns,
+/// skipping any paths that are covered by symlink patterns. Returns the files and
+/// directories copied, relative to the target.
+///
+/// # Errors
+/// Returns an error if file operations fail.
+pub fn copy_config_files(
+    source_path: &Path,
+    target_path: &Path,
+    config: &WorktreeConfig,
+    report: Report<'_>,
+) -> Result<Vec<PathBuf>> {
+    report(&Event::StepStarted {
+        step: "copy-config",
+    });
+
+    let sources = config_files_to_copy(source_path, target_path, config)?;
+    let (files, bytes) = sources
+        .iter()
+        .map(|source| tally(source))
+        .fold((0, 0), |(files, bytes), (f, b)| (files + f, bytes + b));
+    report(&Event::CopyStarted { files, bytes });
+    let mut copied = Vec::new();
+    let mut files = 0;
+    let mut bytes = 0;
+    let mut on_file = |size: u64| {
+        files += 1;
+        bytes += size;
+        report(&Event::BytesCopied { bytes: size });
+    };
+
+    for source_file in sources {
+        let relative_path = source_file.strip_prefix(source_path)?;
+        let target_file = target_path.join(relative_path);
+
+        if let Some(parent) = target_file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        if source_file.is_file() {
+            let size = std::fs::copy(&source_file, &target_file)
+                .with_context(|| format!("Failed to copy {}", relative_path.display()))?;
+            on_file(size);
+            report(&Event::FileCopied {
+                path: relative_path,
+            });
+            copied.push(relative_path.to_path_buf());
+        } else if source_file.is_dir() {
+            copy_dir_tracked(&source_file, &target_file, &mut on_file)?;
+            report(&Event::DirectoryCopied {
+                path: relative_path,
+            });
+            copied.push(relative_path.to_path_buf());
+        }
+    }
+
+    report(&Event::CopyFinished { files, bytes });
+    Ok(copied)
+}
+
+/// The files and directories under `source_path` that the copy patterns select,
+/// without excluded paths, paths covered by symlink patterns, and paths already
+/// symlinked in `target_path`
+fn config_files_to_copy(
+    source_path: &Path,
+    target_path: &Path,
+    config: &WorktreeConfig,
+) -> Result<Vec<PathBuf>> {
+    let symlink_patterns = config.symlink_patterns.include.as_deref().unwrap_or(&[]);
+    let mut sources = Vec::new();
+
+    for pattern in config.copy_patterns.include.as_deref().unwrap_or_default() {
+        let matches = find_matching_files(source_path, pattern)?;
+        debug!(
+            "Copy pattern '{}' matched {} path(s)",
+            pattern,
+            matches.as_ref().map_or(0, Vec::len)
+        );
+        for source_file in matches.unwrap_or_default() {
+            if should_exclude_file(
+                &source_file,
+                config.copy_patterns.exclude.as_deref().unwrap_or_default(),
+            )? {
+                debug!("Skipping {}: excluded", source_file.display());
+                continue;
+            }
+
+            // Skip if already covered by a symlink pattern
+            if is_covered_by_symlink_pattern(&source_file, source_path, symlink_patterns) {
+                debug!("Skipping {}: symlinked instead", source_file.display());
+                continue;
+            }
+
+            // Skip if a symlink already exists at the target (defer to create_symlinks)
+            let target_file = target_path.join(source_file.strip_prefix(source_path)?);
+            if target_file
+                .symlink_metadata()
+                .is_ok_and(|m| m.file_type().is_symlink())
+            {
+                continue;
+            }
+
+            sources.push(source_file);
+        }
+    }
+
+    Ok(sources)
+}
+
+/// Number of files under `path` (1 for a file) and their total size in bytes
+fn tally(path: &Path) -> (u64, u64) {
+    if path.is_file() {
+        return (1, path.metadata().map_or(0, |m| m.len()));
+    }
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return (0, 0);
+    };
+    entries
+        .flatten()
+        .map(|entry| tally(&entry.path()))
+        .fold((0, 0), |(files, bytes), (f, b)| (files + f, bytes + b))
+}
+
+/// Checks if a file path is covered by any symlink pattern
+fn is_covered_by_symlink_pattern(
+    file_path: &Path,
+    base_path: &Path,
+    symlink_patterns: &[String],
+) -> bool {
+    if symlink_patterns.is_empty() {
+        return false;
+    }
+
+    let Ok(relative) = file_path.strip_prefix(base_path) else {
+        return false;
+    };
+
+    let rel_str = relative.to_string_lossy();
+
+    for pattern in symlink_patterns {
+        // Check if the relative path starts with the pattern (prefix match for dirs)
+        let normalized_pattern = pattern.trim_end_matches('/');
+        if rel_str == *pattern
+            || rel_str == normalized_pattern
+            || rel_str.starts_with(&format!("{}/", normalized_pattern))
+        {
+            return true;
+        }
+
+        // Glob match
+        if pattern.contains('*') {
+            if let Ok(p) = glob::Pattern::new(pattern) {
+                if p.matches(&rel_str) {
+                    return true;
+                }
+            }
+        }
+    }
+
+    false
+}
+
+/// Paths under `base_path` matching `pattern` (a glob, or a plain relative path that
+/// must exist), or `None` when nothing matches
+///
+/// # Errors
+/// Returns an error if the glob pattern is invalid or a match cannot be read.
+pub fn find_matching_files(
+    base_path: &Path,
+    pattern: &str,
+) -> Result<Option<Vec<std::path::PathBuf>>> {
+    let mut matches = Vec::new();
+
+    if pattern.contains('*') {
+        for entry in glob::glob(&base_path.join(pattern).to_string_lossy())? {
+            matches.push(entry?);
+        }
+    } else {
+        let path = base_path.join(pattern);
+        if path.exists() {
+            matches.push(path);
+        }
+    }
+
+    if matches.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(matches))
+    }
+}
+
+/// Returns true if `file_path` matches any exclude pattern: globs match the whole
+/// path, plain patterns match as substrings
+///
+/// # Errors
+/// Returns an error if a glob pattern is invalid
+pub fn should_exclude_file(file_path: &Path, exclude_patterns: &[String]) -> Result<bool> {
+    let file_str = file_path.to_string_lossy();
+
+    for pattern in exclude_patterns {
+        if pattern.contains('*') {
+            if glob::Pattern::new(pattern)?.matches(&file_str) {
+                return Ok(true);
+            }
+        } else if file_str.contains(pattern.as_str()) {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Copies the directory `source` into `target`, creating it and any subdirectories
+///
+/// # Errors
+/// Returns an error if a directory cannot be read or a file cannot be copied.
+pub fn copy_dir_recursive(source: &Path, target: &Path) -> Result<()> {
+    copy_dir_tracked(source, target, &mut |_| {})
+}
+
+/// [`copy_dir_recursive`], calling `on_file` with the size of each copied file
+fn copy_dir_tracked(source: &Path, target: &Path, on_file: &mut dyn FnMut(u64)) -> Result<()> {
+    std::fs::create_dir_all(target)?;
+
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        let source_path = entry.path();
+        let target_path = target.join(entry.file_name());
+
+        if source_path.is_dir() {
+            copy_dir_tracked(&source_path, &target_path, on_file)?;
+        } else {
+            on_file(std::fs::copy(&source_path, &target_path)?);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::config::{OnCreate, SymlinkPatterns};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn make_config_with_symlinks(patterns: Vec<String>) -> WorktreeConfig {
+        WorktreeConfig {
+            copy_patterns: crate::config::CopyPatterns {
+                include: Some(vec![]),
+                exclude: Some(vec![]),
+            },
+            symlink_patterns: SymlinkPatterns {
+                include: Some(patterns),
+            },
+            on_create: OnCreate { commands: None },
+            ..WorktreeConfig::default()
+        }
+    }
+
+    // ── create_symlinks ──────────────────────────────────────────────────────
+
+    #[test]
+    fn test_create_symlinks_creates_symlink_for_matching_path() {
+        let tmp = TempDir::new().unwrap();
+        let origin = tmp.path().join("origin");
+        let worktree = tmp.path().join("worktree");
+        fs::create_dir_all(&origin).unwrap();
+        fs::create_dir_all(&worktree).unwrap();
+
+        // Create a file in the origin that will be symlinked
+        fs::write(origin.join("shared-data.txt"), "content").unwrap();
+
+        let config = make_config_with_symlinks(vec!["shared-data.txt".to_string()]);
+        create_symlinks(&origin, &worktree, &config, &|_| {}).unwrap();
+
+        let link = worktree.join("shared-data.txt");
+        assert!(link.symlink_metadata().is_ok(), "symlink should exist");
+        assert!(
+            link.symlink_metadata().unwrap().file_type().is_symlink(),
+            "should be a symlink not a copy"
+        );
+        assert_eq!(fs::read_to_string(&link).unwrap(), "content");
+    }
+
+    #[test]
+    fn test_create_symlinks_skips_missing_origin_path_with_no_error() {
+        let tmp = TempDir::new().unwrap();
+        let origin = tmp.path().join("origin");
+        let worktree = tmp.path().join("worktree");
+        fs::create_dir_all(&origin).unwrap();
+        fs::create_dir_all(&worktree).unwrap();
+
+        // Pattern matches nothing in origin — should not error, should not create anything
+        let config = make_config_with_symlinks(vec!["does-not-exist.txt".to_string()]);
+        let result = create_symlinks(&origin, &worktree, &config, &|_| {});
+
+        assert!(
+            result.is_ok(),
+            "missing origin path should not cause an error"
+        );
+        assert!(
+            !worktree.join("does-not-exist.txt").exists(),
+            "no symlink should be created for missing path"
+        );
+    }
+
+    #[test]
+    fn test_create_symlinks_takes_precedence_over_copy() {
+        let tmp = TempDir::new().unwrap();
+        let origin = tmp.path().join("origin");
+        let worktree = tmp.path().join("worktree");
+        fs::create_dir_all(&origin).unwrap();
+        fs::create_dir_all(&worktree).unwrap();
+
+        fs::write(origin.join(".env"), "ORIGIN=1").unwrap();
+
+        // Config: symlink .env AND copy .env* — symlink should take precedence
+        let config = WorktreeConfig {
+            copy_patterns: crate::config::CopyPatterns {
+                include: Some(vec![".env*".to_string()]),
+                exclude: Some(vec![]),
+            },
+            symlink_patterns: SymlinkPatterns {
+                include: Some(vec![".env".to_string()]),
+            },
+            on_create: OnCreate { commands: None },
+            ..WorktreeConfig::default()
+        };
+
+        // First create symlinks (as in create_worktree_internal)
+        create_symlinks(&origin, &worktree, &config, &|_| {}).unwrap();
+        // Then copy (should skip .env because it's already symlinked)
+        copy_config_files(&origin, &worktree, &config, &|_| {}).unwrap();
+
+        let target = worktree.join(".env");
+        assert!(target.symlink_metadata().is_ok(), ".env should exist");
+        assert!(
+            target.symlink_metadata().unwrap().file_type().is_symlink(),
+            ".env should be a symlink, not a copy"
+        );
+    }
+
+    // ── copy_dir_tracked ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_copy_dir_tracked_reports_each_file() {
+        let tmp = TempDir::new().unwrap();
+        let source = tmp.path().join("cache");
+        fs::create_dir_all(source.join("nested")).unwrap();
+        fs::write(source.join("a.bin"), "1234").unwrap();
+        fs::write(source.join("nested/b.bin"), "56").unwrap();
+
+        let mut sizes = Vec::new();
+        copy_dir_tracked(&source, &tmp.path().join("copy"), &mut |size| {
+            sizes.push(size)
+        })
+        .unwrap();
+        sizes.sort_unstable();
+
+        assert_eq!(sizes, [2, 4]);
+        assert_eq!(tally(&source), (2, 6));
+        assert!(tmp.path().join("copy/nested/b.bin").exists());
+    }
+}
@@ -0,0 +1,158 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use std::process::Stdio;
+use tracing::warn;
+
+use crate::config::{DependencyDirs, DependencyStrategy};
+use crate::events::Event;
+use crate::output::Target;
+use crate::provision::Report;
+use crate::provision::config_files;
+use crate::provision::template::run_shell;
+
+/// Sets up the dependency directories of a new worktree with the configured strategy.
+/// The install command gets `env` (the `WORKTREE_*` variables), like post-create hooks,
+/// and writes its stdout to `stdout`.
+///
+/// # Errors
+/// Returns an error if a `hardlink-clone` fails part-way. A failing install command is
+/// only a warning, like a failing post-create hook.
+pub fn set_up_dependency_dirs(
+    config: &DependencyDirs,
+    origin: &Path,
+    worktree_path: &Path,
+    env: &[(&str, String)],
+    stdout: Target,
+    report: Report<'_>,
+) -> Result<()> {
+    match config.strategy {
+        DependencyStrategy::Skip => Ok(()),
+        DependencyStrategy::HardlinkClone => {
+            hardlink_clone_all(config, origin, worktree_path, report)
+        }
+        DependencyStrategy::Command => {
+            run_install_command(
+                config.command.as_deref(),
+                worktree_path,
+                env,
+                stdout,
+                report,
+            );
+            Ok(())
+        }
+    }
+}
+
+fn hardlink_clone_all(
+    config: &DependencyDirs,
+    origin: &Path,
+    worktree_path: &Path,
+    report: Report<'_>,
+) -> Result<()> {
+    for pattern in config.paths() {
+        let Some(sources) = config_files::find_matching_files(origin, &pattern)? else {
+            continue;
+        };
+        for source in sources.into_iter().filter(|source| source.is_dir()) {
+            let relative = source.strip_prefix(origin)?;
+            let target = worktree_path.join(relative);
+            if target.symlink_metadata().is_ok() {
+                continue;
+            }
+            let files = hardlink_clone(&source, &target)
+                .with_context(|| format!("Failed to clone {}", relative.display()))?;
+            report(&Event::DirectoryCloned {
+                path: relative,
+                files,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Recreates the directory tree `source` at `target`, hard-linking files (copying them
+/// when the two are on different file systems) and recreating symlinks as they are.
+/// Returns the number of files.
+///
+/// # Errors
+/// Returns an error if a directory, link, or file cannot be created.
+pub fn hardlink_clone(source: &Path, target: &Path) -> Result<usize> {
+    fs::create_dir_all(target)?;
+    let mut files = 0;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let source_path = entry.path();
+        let target_path = target.join(entry.file_name());
+        let file_type = entry.file_type()?;
+
+        if file_type.is_symlink() {
+            std::os::unix::fs::symlink(fs::read_link(&source_path)?, &target_path)?;
+        } else if file_type.is_dir() {
+            files += hardlink_clone(&source_path, &target_path)?;
+        } else {
+            if fs::hard_link(&source_path, &target_path).is_err() {
+                fs::copy(&source_path, &target_path)?;
+            }
+            files += 1;
+        }
+    }
+    Ok(files)
+}
+
+fn run_install_command(
+    command: Option<&str>,
+    worktree_path: &Path,
+    env: &[(&str, String)],
+    stdout: Target,
+    report: Report<'_>,
+) {
+    let Some(command) = command.filter(|command| !command.trim().is_empty()) else {
+        warn!("[dependency-dirs] strategy is \"command\" but no command is configured");
+        return;
+    };
+
+    report(&Event::CommandStarted {
+        step: "dependency-dirs",
+        command,
+    });
+    match run_shell(command, worktree_path, env, Stdio::null(), stdout.stdio()) {
+        Ok(()) => report(&Event::CommandFinished {
+            step: "dependency-dirs",
+            command,
+        }),
+        Err(e) => warn!("Dependency command failed: {:#}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::MetadataExt;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_hardlink_clone_links_files_and_keeps_symlinks() -> anyhow::Result<()> {
+        let tmp = TempDir::new()?;
+        let source = tmp.path().join("origin").join("node_modules");
+        fs::create_dir_all(source.join("lodash"))?;
+        fs::create_dir_all(source.join(".bin"))?;
+        fs::write(
+            source.join("lodash").join("index.js"),
+            "module.exports = {}",
+        )?;
+        std::os::unix::fs::symlink("../lodash/index.js", source.join(".bin").join("lodash"))?;
+
+        let target = tmp.path().join("worktree").join("node_modules");
+        assert_eq!(hardlink_clone(&source, &target)?, 1);
+
+        let original = fs::metadata(source.join("lodash").join("index.js"))?;
+        let cloned = fs::metadata(target.join("lodash").join("index.js"))?;
+        assert_eq!(original.ino(), cloned.ino());
+        assert_eq!(
+            fs::read_link(target.join(".bin").join("lodash"))?,
+            Path::new("../lodash/index.js")
+        );
+        Ok(())
+    }
+}
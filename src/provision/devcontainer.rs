@@ -0,0 +1,277 @@
+//! Devcontainer adaptation for new worktrees.
+//!
+//! Two worktrees of a repository share its `.devcontainer/` configuration, so their
+//! containers get the same display name and, with a fixed `--name` in `runArgs`, the
+//! same container name. With `[devcontainer] adapt = true`, each new worktree gets a
+//! copy whose names are rendered per worktree and whose containers carry a label with
+//! the branch. Compose-based configurations keep their names; the compose project name
+//! separates those.
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use std::fs;
+use std::iter::Peekable;
+use std::path::{Path, PathBuf};
+use std::str::Chars;
+use tracing::warn;
+
+use crate::config::Devcontainer;
+use crate::events::Event;
+use crate::provision::Report;
+use crate::provision::config_files;
+use crate::provision::template::TemplateContext;
+
+/// Directory holding devcontainer configurations
+pub const DEVCONTAINER_DIR: &str = ".devcontainer";
+
+/// Default template for the configuration's display name
+pub const DEFAULT_NAME: &str = "{repo} ({branch})";
+
+/// Default template for the container name
+pub const DEFAULT_CONTAINER_NAME: &str = "{repo}-{name}";
+
+/// Label added to containers, holding the worktree's branch
+pub const BRANCH_LABEL: &str = "dev.worktree.branch";
+
+/// Copies `.devcontainer/` from the main repository when the worktree has none (it is
+/// untracked there) and adapts every configuration in the worktree
+///
+/// Configurations that cannot be parsed are skipped with a warning. Comments in
+/// adapted files are not preserved.
+///
+/// # Errors
+/// Returns an error if the directory cannot be copied or an adapted file cannot be
+/// written.
+pub fn adapt_devcontainers(
+    config: &Devcontainer,
+    context: &TemplateContext<'_>,
+    report: Report<'_>,
+) -> Result<()> {
+    if !config.adapt {
+        return Ok(());
+    }
+
+    let origin_dir = context.origin.join(DEVCONTAINER_DIR);
+    let worktree_dir = context.path.join(DEVCONTAINER_DIR);
+    if origin_dir.is_dir() && !worktree_dir.exists() {
+        config_files::copy_dir_recursive(&origin_dir, &worktree_dir)
+            .with_context(|| format!("Failed to copy {}", origin_dir.display()))?;
+    }
+
+    let name = context.render(config.name.as_deref().unwrap_or(DEFAULT_NAME));
+    let container_name = container_name(
+        &context.render(
+            config
+                .container_name
+                .as_deref()
+                .unwrap_or(DEFAULT_CONTAINER_NAME),
+        ),
+    );
+
+    for path in config_files(context.path) {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let Ok(Value::Object(mut devcontainer)) = serde_json::from_str(&strip_jsonc(&content))
+        else {
+            warn!(
+                "Skipping {}: not a valid devcontainer configuration",
+                path.display()
+            );
+            continue;
+        };
+        adapt(&mut devcontainer, &name, &container_name, context.branch);
+        let adapted = serde_json::to_string_pretty(&Value::Object(devcontainer))?;
+        fs::write(&path, format!("{}\n", adapted))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        report(&Event::FileWritten {
+            step: "devcontainer",
+            path: path.strip_prefix(context.path).unwrap_or(&path),
+        });
+    }
+    Ok(())
+}
+
+/// Sets the display name and, unless Docker Compose runs the container, a unique
+/// container name and the branch label in `runArgs`
+pub fn adapt(
+    devcontainer: &mut Map<String, Value>,
+    name: &str,
+    container_name: &str,
+    branch: &str,
+) {
+    devcontainer.insert("name".to_string(), Value::String(name.to_string()));
+    if devcontainer.contains_key("dockerComposeFile") {
+        return;
+    }
+
+    let mut run_args: Vec<Value> = Vec::new();
+    let existing = devcontainer
+        .get("runArgs")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let mut args = existing.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            Some("--name") => {
+                args.next();
+            }
+            Some(value) if value.starts_with("--name=") => {}
+            Some(value) if value.starts_with(&format!("--label={}=", BRANCH_LABEL)) => {}
+            _ => run_args.push(arg),
+        }
+    }
+    run_args.push(Value::String(format!("--name={}", container_name)));
+    run_args.push(Value::String(format!(
+        "--label={}={}",
+        BRANCH_LABEL, branch
+    )));
+    devcontainer.insert("runArgs".to_string(), Value::Array(run_args));
+}
+
+/// Devcontainer configurations in `dir`: `.devcontainer.json`,
+/// `.devcontainer/devcontainer.json`, and `.devcontainer/<name>/devcontainer.json`
+fn config_files(dir: &Path) -> Vec<PathBuf> {
+    let devcontainer_dir = dir.join(DEVCONTAINER_DIR);
+    let mut files = vec![
+        dir.join(".devcontainer.json"),
+        devcontainer_dir.join("devcontainer.json"),
+    ];
+    if let Ok(entries) = fs::read_dir(&devcontainer_dir) {
+        let mut nested: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_dir())
+            .map(|path| path.join("devcontainer.json"))
+            .collect();
+        nested.sort();
+        files.extend(nested);
+    }
+    files.retain(|path| path.is_file());
+    files
+}
+
+/// Docker container names allow `[a-zA-Z0-9][a-zA-Z0-9_.-]*`; other characters
+/// (such as `/` from branch names) become `-`
+fn container_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    sanitized
+        .trim_start_matches(|c: char| !c.is_ascii_alphanumeric())
+        .to_string()
+}
+
+/// Removes the comments and trailing commas devcontainer.json allows (JSON with
+/// comments), leaving plain JSON
+fn strip_jsonc(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+            }
+            (',', _) => {
+                if !matches!(next_token(chars.clone()), Some('}' | ']')) {
+                    out.push(c);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// First character after whitespace and comments
+fn next_token(mut chars: Peekable<Chars<'_>>) -> Option<char> {
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            (c, _) if c.is_whitespace() => {}
+            ('/', Some('/')) => {
+                chars.find(|&next| next == '\n');
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                chars.find(|&next| {
+                    let end = previous == '*' && next == '/';
+                    previous = next;
+                    end
+                });
+            }
+            _ => return Some(c),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_jsonc() {
+        let content =
+            "{\n  // comment\n  \"url\": \"http://x\", /* block */\n  \"a\": [1, 2,],\n}\n";
+        let value: Value = serde_json::from_str(&strip_jsonc(content)).unwrap_or(Value::Null);
+        assert_eq!(value["url"], "http://x");
+        assert_eq!(value["a"], serde_json::json!([1, 2]));
+    }
+
+    #[test]
+    fn test_adapt_replaces_names_and_keeps_other_args() {
+        let mut devcontainer = serde_json::json!({
+            "name": "App",
+            "runArgs": ["--name", "app", "--cap-add=SYS_PTRACE"],
+        });
+        if let Value::Object(map) = &mut devcontainer {
+            adapt(map, "app (feature/auth)", "app-auth", "feature/auth");
+        }
+        assert_eq!(devcontainer["name"], "app (feature/auth)");
+        assert_eq!(
+            devcontainer["runArgs"],
+            serde_json::json!([
+                "--cap-add=SYS_PTRACE",
+                "--name=app-auth",
+                "--label=dev.worktree.branch=feature/auth"
+            ])
+        );
+        assert_eq!(container_name("app-feature/auth"), "app-feature-auth");
+    }
+}
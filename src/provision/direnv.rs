@@ -0,0 +1,158 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tracing::warn;
+
+use crate::config::Direnv;
+use crate::events::Event;
+use crate::output::Target;
+use crate::provision::Report;
+use crate::provision::template::TemplateContext;
+
+/// File direnv loads from a directory
+pub const ENVRC: &str = ".envrc";
+
+/// Writes the worktree's `.envrc` when `[direnv]` is enabled, and runs `direnv allow`
+/// if configured, with its stdout going to `stdout`
+///
+/// With a template, the rendered template becomes the `.envrc`. Otherwise the worktree
+/// variables are exported ahead of any `.envrc` copied from the main repository; a
+/// symlinked one is replaced by a file that sources its target, so the main
+/// repository's file is never modified.
+///
+/// # Errors
+/// Returns an error if the template cannot be read or the `.envrc` cannot be written.
+/// A failing `direnv allow` is only a warning.
+pub fn write_envrc(
+    config: &Direnv,
+    context: &TemplateContext<'_>,
+    stdout: Target,
+    report: Report<'_>,
+) -> Result<()> {
+    if !config.enabled {
+        return Ok(());
+    }
+
+    let envrc_path = context.path.join(ENVRC);
+    let content = match &config.template {
+        Some(template) => {
+            let template_path = context.origin.join(template);
+            let template = fs::read_to_string(&template_path).with_context(|| {
+                format!("Failed to read direnv template {}", template_path.display())
+            })?;
+            context.render(&template)
+        }
+        None => generated_envrc(context, &envrc_path)?,
+    };
+
+    if envrc_path.is_symlink() {
+        fs::remove_file(&envrc_path)
+            .with_context(|| format!("Failed to replace symlink {}", envrc_path.display()))?;
+    }
+    fs::write(&envrc_path, content)
+        .with_context(|| format!("Failed to write {}", envrc_path.display()))?;
+    report(&Event::FileWritten {
+        step: "direnv",
+        path: Path::new(ENVRC),
+    });
+
+    if config.allow {
+        allow(context.path, stdout, report);
+    }
+    Ok(())
+}
+
+/// Exports of the worktree variables followed by the existing `.envrc`, if any
+fn generated_envrc(context: &TemplateContext<'_>, envrc_path: &Path) -> Result<String> {
+    let mut content = format!(
+        "# Generated by worktree for the '{}' worktree\n",
+        context.name
+    );
+    for (name, value) in context.variables() {
+        content.push_str(&format!("export {}={}\n", name, shell_quote(&value)));
+    }
+
+    if envrc_path.is_symlink() {
+        let target = fs::canonicalize(envrc_path).unwrap_or_else(|_| envrc_path.to_path_buf());
+        content.push_str(&format!(
+            "\nsource_env {}\n",
+            shell_quote(&target.display().to_string())
+        ));
+    } else if envrc_path.exists() {
+        let existing = fs::read_to_string(envrc_path)
+            .with_context(|| format!("Failed to read {}", envrc_path.display()))?;
+        content.push('\n');
+        content.push_str(&existing);
+    }
+    Ok(content)
+}
+
+/// Runs `direnv allow` in `path`, warning if direnv is missing or fails
+fn allow(path: &Path, stdout: Target, report: Report<'_>) {
+    report(&Event::CommandStarted {
+        step: "direnv",
+        command: "direnv allow",
+    });
+    let status = Command::new("direnv")
+        .arg("allow")
+        .current_dir(path)
+        .stdin(Stdio::null())
+        .stdout(stdout.stdio())
+        .status();
+    match status {
+        Ok(status) if status.success() => report(&Event::CommandFinished {
+            step: "direnv",
+            command: "direnv allow",
+        }),
+        Ok(status) => warn!(
+            "`direnv allow` failed with exit code {}",
+            status.code().unwrap_or(-1)
+        ),
+        Err(e) => warn!("Failed to run `direnv allow`: {}", e),
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn context<'a>(path: &'a Path, origin: &'a Path) -> TemplateContext<'a> {
+        TemplateContext {
+            name: "auth",
+            branch: "feature/auth",
+            path,
+            repo: "app",
+            origin,
+            slot: 1,
+        }
+    }
+
+    #[test]
+    fn test_generated_envrc_keeps_copied_content() -> anyhow::Result<()> {
+        let tmp = TempDir::new()?;
+        fs::write(tmp.path().join(ENVRC), "dotenv\n")?;
+        let config = Direnv {
+            enabled: true,
+            ..Direnv::default()
+        };
+
+        write_envrc(
+            &config,
+            &context(tmp.path(), Path::new("/src/app")),
+            Target::Stdout,
+            &|_| {},
+        )?;
+
+        let content = fs::read_to_string(tmp.path().join(ENVRC))?;
+        assert!(content.contains("export WORKTREE_BRANCH='feature/auth'\n"));
+        assert!(content.contains("export WORKTREE_ORIGIN='/src/app'\n"));
+        assert!(content.ends_with("\ndotenv\n"));
+        Ok(())
+    }
+}
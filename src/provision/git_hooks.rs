@@ -0,0 +1,48 @@
+use std::path::Path;
+use std::process::Stdio;
+use tracing::warn;
+
+use crate::config::GitHooks;
+use crate::events::Event;
+use crate::git;
+use crate::output::Target;
+use crate::provision::Report;
+use crate::provision::template::run_shell;
+
+/// Shares the main repository's hooks with a new worktree and runs the configured hook
+/// installer in it, with `env` set for the installer and its stdout going to `stdout`.
+/// Failures are only warnings, like failing post-create hooks.
+pub fn set_up_git_hooks(
+    config: &GitHooks,
+    origin: &Path,
+    worktree_path: &Path,
+    env: &[(&str, String)],
+    stdout: Target,
+    report: Report<'_>,
+) {
+    if config.share {
+        match git::share_hooks(origin, worktree_path) {
+            Ok(hooks_dir) => report(&Event::HooksShared { dir: &hooks_dir }),
+            Err(e) => warn!("Failed to share git hooks: {}", e),
+        }
+    }
+
+    let Some(install) = config
+        .install
+        .as_deref()
+        .filter(|cmd| !cmd.trim().is_empty())
+    else {
+        return;
+    };
+    report(&Event::CommandStarted {
+        step: "git-hooks",
+        command: install,
+    });
+    match run_shell(install, worktree_path, env, Stdio::null(), stdout.stdio()) {
+        Ok(()) => report(&Event::CommandFinished {
+            step: "git-hooks",
+            command: install,
+        }),
+        Err(e) => warn!("Hook installer failed: {:#}", e),
+    }
+}
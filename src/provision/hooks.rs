@@ -0,0 +1,172 @@
+//! `[on-create]` hooks, run in a new worktree once it is set up.
+
+use anyhow::Result;
+use std::path::Path;
+use std::process::Stdio;
+use tracing::warn;
+
+use crate::config::WorktreeConfig;
+use crate::events::Event;
+use crate::output::Target;
+use crate::provision::Report;
+use crate::provision::template::run_shell;
+
+/// Runs post-create hooks defined in `[on-create] commands`, with `env` (such as the
+/// `WORKTREE_*` variables) set for them and their stdout going to `stdout`.
+/// On first failure, remaining commands are skipped and a warning is logged.
+/// The worktree remains created regardless.
+///
+/// # Errors
+/// Never returns Err — hook failures are warnings, not errors.
+#[allow(clippy::unnecessary_wraps)]
+pub fn run_on_create_hooks(
+    worktree_path: &Path,
+    config: &WorktreeConfig,
+    env: &[(&str, String)],
+    stdout: Target,
+    report: Report<'_>,
+) -> Result<()> {
+    let commands = match config.on_create.commands.as_deref() {
+        Some(c) if !c.is_empty() => c,
+        _ => return Ok(()),
+    };
+
+    report(&Event::StepStarted {
+        step: "on-create-hooks",
+    });
+
+    for cmd_str in commands {
+        report(&Event::CommandStarted {
+            step: "on-create-hooks",
+            command: cmd_str,
+        });
+
+        match run_shell(cmd_str, worktree_path, env, Stdio::null(), stdout.stdio()) {
+            Ok(()) => report(&Event::CommandFinished {
+                step: "on-create-hooks",
+                command: cmd_str,
+            }),
+            Err(e) => {
+                warn!(
+                    "Hook command failed: {:#}\n  Remaining post-create commands skipped.",
+                    e
+                );
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use crate::config::{OnCreate, SymlinkPatterns};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn make_config_with_hooks(commands: Vec<String>) -> WorktreeConfig {
+        WorktreeConfig {
+            copy_patterns: crate::config::CopyPatterns {
+                include: Some(vec![]),
+                exclude: Some(vec![]),
+            },
+            symlink_patterns: SymlinkPatterns { include: None },
+            on_create: OnCreate {
+                commands: Some(commands),
+            },
+            ..WorktreeConfig::default()
+        }
+    }
+
+    // ── run_on_create_hooks ──────────────────────────────────────────────────
+
+    #[test]
+    fn test_run_on_create_hooks_runs_commands_in_order() {
+        let tmp = TempDir::new().unwrap();
+        let worktree = tmp.path().join("worktree");
+        fs::create_dir_all(&worktree).unwrap();
+
+        // Commands write to a file in sequence to verify ordering
+        let marker = worktree.join("order.txt");
+        let config = make_config_with_hooks(vec![
+            format!("sh -c 'echo first >> {}'", marker.display()),
+            format!("sh -c 'echo second >> {}'", marker.display()),
+            format!("sh -c 'echo third >> {}'", marker.display()),
+        ]);
+
+        run_on_create_hooks(&worktree, &config, &[], Target::Stdout, &|_| {}).unwrap();
+
+        let content = fs::read_to_string(&marker).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines, vec!["first", "second", "third"]);
+    }
+
+    #[test]
+    fn test_run_on_create_hooks_stops_on_first_failure() {
+        let tmp = TempDir::new().unwrap();
+        let worktree = tmp.path().join("worktree");
+        fs::create_dir_all(&worktree).unwrap();
+
+        let marker = worktree.join("ran.txt");
+        let config = make_config_with_hooks(vec![
+            format!("sh -c 'echo before-fail >> {}'", marker.display()),
+            "sh -c 'exit 1'".to_string(), // fails here
+            format!("sh -c 'echo after-fail >> {}'", marker.display()),
+        ]);
+
+        // Should succeed (hook failure is non-fatal to the create operation)
+        let result = run_on_create_hooks(&worktree, &config, &[], Target::Stdout, &|_| {});
+        assert!(result.is_ok(), "hook failure should not propagate as Err");
+
+        let content = fs::read_to_string(&marker).unwrap_or_default();
+        let lines: Vec<&str> = content.lines().collect();
+        // First command ran, third command did not
+        assert!(
+            lines.contains(&"before-fail"),
+            "command before failure should have run"
+        );
+        assert!(
+            !lines.contains(&"after-fail"),
+            "command after failure should NOT have run"
+        );
+    }
+
+    #[test]
+    fn test_run_on_create_hooks_worktree_intact_after_failure() {
+        let tmp = TempDir::new().unwrap();
+        let worktree = tmp.path().join("worktree");
+        fs::create_dir_all(&worktree).unwrap();
+
+        // Put a file in the worktree before hooks run
+        fs::write(worktree.join("important.txt"), "do not delete").unwrap();
+
+        let config = make_config_with_hooks(vec!["sh -c 'exit 42'".to_string()]);
+
+        run_on_create_hooks(&worktree, &config, &[], Target::Stdout, &|_| {}).unwrap();
+
+        // Worktree directory and its contents must still exist
+        assert!(
+            worktree.exists(),
+            "worktree directory should still exist after hook failure"
+        );
+        assert!(
+            worktree.join("important.txt").exists(),
+            "worktree contents should be intact after hook failure"
+        );
+    }
+
+    #[test]
+    fn test_run_on_create_hooks_no_commands_is_noop() {
+        let tmp = TempDir::new().unwrap();
+        let worktree = tmp.path().join("worktree");
+        fs::create_dir_all(&worktree).unwrap();
+
+        let config = WorktreeConfig::default();
+        let result = run_on_create_hooks(&worktree, &config, &[], Target::Stdout, &|_| {});
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,48 @@
+use std::path::Path;
+use tracing::warn;
+
+use crate::config::LfsMode;
+use crate::events::Event;
+use crate::git;
+use crate::provision::Report;
+
+/// Returns true if the worktree's `.gitattributes` routes files through the LFS filter
+#[must_use]
+pub fn uses_lfs(worktree_path: &Path) -> bool {
+    std::fs::read_to_string(worktree_path.join(".gitattributes"))
+        .is_ok_and(|attributes| attributes.contains("filter=lfs"))
+}
+
+/// Replaces LFS pointer files in a new worktree with their content
+///
+/// Worktrees are checked out without running the LFS filter, so LFS-tracked files start
+/// as pointer files. With [`LfsMode::Pull`] this runs `git lfs pull` when git-lfs is
+/// installed; otherwise, and with [`LfsMode::Warn`], it warns how to fix the worktree.
+/// Failures are only warnings.
+pub fn check_out_lfs_files(mode: LfsMode, worktree_path: &Path, report: Report<'_>) {
+    if mode == LfsMode::Off || !uses_lfs(worktree_path) {
+        return;
+    }
+
+    if mode == LfsMode::Pull && git::lfs_installed(worktree_path) {
+        report(&Event::CommandStarted {
+            step: "lfs",
+            command: "git lfs pull",
+        });
+        match git::lfs_pull(worktree_path) {
+            Ok(()) => {
+                report(&Event::CommandFinished {
+                    step: "lfs",
+                    command: "git lfs pull",
+                });
+                return;
+            }
+            Err(e) => warn!("{}", e),
+        }
+    }
+
+    warn!(
+        "This repository uses Git LFS; LFS files in the new worktree are pointer files.\n  Install git-lfs if needed, then run `git lfs pull` in {}",
+        worktree_path.display()
+    );
+}
@@ -0,0 +1,57 @@
+//! Setting up new worktrees.
+//!
+//! [`WorktreeManager::create`](crate::WorktreeManager::create) runs these steps after
+//! checking out a worktree: config files are symlinked and copied from the main
+//! repository, the configured integrations (`[direnv]`, `[devcontainer]`, `[compose]`,
+//! `[build-cache]`, `[toolchain]`, `[dependency-dirs]`, `[git-hooks]`) are applied, and
+//! the `[on-create]` hooks are run. Nothing here prints: each step reports its progress
+//! as [`Event`]s to a callback, and warnings go to the log.
+
+pub mod build_cache;
+pub mod compose;
+pub mod config_files;
+pub mod dependency_dirs;
+pub mod devcontainer;
+pub mod direnv;
+pub mod git_hooks;
+pub mod hooks;
+pub mod lfs;
+pub mod template;
+pub mod toolchain;
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::events::Event;
+use crate::traits::StorageOperations;
+
+/// Receives the progress [`Event`]s of a setup step
+pub type Report<'r> = &'r dyn Fn(&Event<'_>);
+
+/// Stores the origin repository path in storage metadata for back navigation
+///
+/// # Errors
+/// Returns an error if storing origin information fails.
+pub fn store_origin_info(
+    storage: &dyn StorageOperations,
+    repo_name: &str,
+    feature_name: &str,
+    repo_path: &Path,
+) -> Result<()> {
+    let canonical_repo_path = repo_path.canonicalize().with_context(|| {
+        format!(
+            "Failed to canonicalize repository path: {}",
+            repo_path.display()
+        )
+    })?;
+
+    storage
+        .store_worktree_origin(
+            repo_name,
+            feature_name,
+            &canonical_repo_path.to_string_lossy(),
+        )
+        .context("Failed to store worktree origin information")?;
+
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// What generated files know about a new worktree, filled into templates as `{name}`,
+/// `{branch}`, `{path}`, `{repo}`, `{origin}`, and `{slot}`
+#[derive(Debug, Clone, Copy)]
+pub struct TemplateContext<'a> {
+    /// Feature name of the worktree
+    pub name: &'a str,
+    /// Branch checked out in the worktree
+    pub branch: &'a str,
+    /// Worktree directory
+    pub path: &'a Path,
+    /// Repository name
+    pub repo: &'a str,
+    /// Main repository the worktree belongs to
+    pub origin: &'a Path,
+    /// Slot number of the worktree, for deriving ports and other per-worktree resources
+    pub slot: u32,
+}
+
+impl TemplateContext<'_> {
+    /// The values as `WORKTREE_*` environment variables
+    #[must_use]
+    pub fn variables(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("WORKTREE_NAME", self.name.to_string()),
+            ("WORKTREE_BRANCH", self.branch.to_string()),
+            ("WORKTREE_PATH", self.path.display().to_string()),
+            ("WORKTREE_REPO", self.repo.to_string()),
+            ("WORKTREE_ORIGIN", self.origin.display().to_string()),
+            ("WORKTREE_SLOT", self.slot.to_string()),
+        ]
+    }
+
+    /// Replaces `{name}`, `{branch}`, `{path}`, `{repo}`, `{origin}`, and `{slot}` in
+    /// `template`. `{slot+N}` is the slot plus `N`, e.g. `{slot+3000}` for a port.
+    #[must_use]
+    pub fn render(&self, template: &str) -> String {
+        let rendered = template
+            .replace("{name}", self.name)
+            .replace("{branch}", self.branch)
+            .replace("{path}", &self.path.display().to_string())
+            .replace("{repo}", self.repo)
+            .replace("{origin}", &self.origin.display().to_string())
+            .replace("{slot}", &self.slot.to_string());
+        self.render_slot_offsets(&rendered)
+    }
+
+    /// Replaces each `{slot+N}` with the slot plus `N`
+    fn render_slot_offsets(&self, template: &str) -> String {
+        const PREFIX: &str = "{slot+";
+        let mut out = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find(PREFIX) {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + PREFIX.len()..];
+            let offset = after
+                .split_once('}')
+                .and_then(|(offset, tail)| Some((offset.parse::<u32>().ok()?, tail)));
+            match offset {
+                Some((offset, tail)) => {
+                    out.push_str(&self.slot.saturating_add(offset).to_string());
+                    rest = tail;
+                }
+                None => {
+                    out.push_str(PREFIX);
+                    rest = after;
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// Runs `command` with `sh -c` in `cwd`, with `env` (such as
+/// [`TemplateContext::variables`]) added to its environment. `stdin` is
+/// [`Stdio::null`] for hooks and installers, so they cannot wait on a prompt, and
+/// [`Stdio::inherit`] for interactive programs such as editors. The command writes its
+/// stdout to `stdout`.
+///
+/// # Errors
+/// Returns an error if the shell cannot be started or `command` exits unsuccessfully
+pub fn run_shell(
+    command: &str,
+    cwd: &Path,
+    env: &[(&str, String)],
+    stdin: Stdio,
+    stdout: Stdio,
+) -> Result<()> {
+    let status = Command::new("sh")
+        .args(["-c", command])
+        .current_dir(cwd)
+        .envs(env.iter().map(|(name, value)| (name, value)))
+        .stdin(stdin)
+        .stdout(stdout)
+        .status()
+        .with_context(|| format!("Failed to run '{}'", command))?;
+    anyhow::ensure!(
+        status.success(),
+        "'{}' failed with exit code {}",
+        command,
+        status.code().unwrap_or(-1)
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_placeholders() {
+        let context = TemplateContext {
+            name: "auth",
+            branch: "feature/auth",
+            path: Path::new("/wt/app/auth"),
+            repo: "app",
+            origin: Path::new("/src/app"),
+            slot: 2,
+        };
+        assert_eq!(
+            context.render("export NAME={name}@{branch} # {repo} {path} {origin}"),
+            "export NAME=auth@feature/auth # app /wt/app/auth /src/app"
+        );
+        assert_eq!(
+            context.render("PORT={slot+3000} DB=app_{slot} {slot+x}"),
+            "PORT=3002 DB=app_2 {slot+x}"
+        );
+    }
+}
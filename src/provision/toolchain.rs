@@ -0,0 +1,121 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tracing::warn;
+
+use crate::config::Toolchain;
+use crate::events::Event;
+use crate::output::Target;
+use crate::provision::Report;
+
+/// mise configuration files, which mise only reads once trusted
+pub const MISE_FILES: &[&str] = &[
+    "mise.toml",
+    ".mise.toml",
+    "mise.local.toml",
+    ".mise.local.toml",
+];
+
+/// asdf's tool version file, which mise also reads
+pub const TOOL_VERSIONS: &str = ".tool-versions";
+
+/// Copies the tool version files of the main repository into a new worktree where they
+/// are missing, then trusts and optionally installs them
+///
+/// mise is used when it is installed; otherwise `.tool-versions` is installed with
+/// asdf. The tool commands write their stdout to `stdout`; failing ones are only
+/// warnings.
+///
+/// # Errors
+/// Returns an error if a tool version file cannot be copied.
+pub fn set_up_toolchain(
+    config: &Toolchain,
+    origin: &Path,
+    worktree_path: &Path,
+    stdout: Target,
+    report: Report<'_>,
+) -> Result<()> {
+    if !config.enabled {
+        return Ok(());
+    }
+
+    let mut found = Vec::new();
+    for file in MISE_FILES.iter().chain(std::iter::once(&TOOL_VERSIONS)) {
+        let source = origin.join(file);
+        let target = worktree_path.join(file);
+        if source.is_file() && !target.exists() {
+            fs::copy(&source, &target).with_context(|| format!("Failed to copy {}", file))?;
+            report(&Event::FileCopied {
+                path: Path::new(file),
+            });
+        }
+        if target.is_file() {
+            found.push(*file);
+        }
+    }
+    if found.is_empty() {
+        return Ok(());
+    }
+
+    if is_installed("mise") {
+        if found.iter().any(|file| MISE_FILES.contains(file)) {
+            run_tool(worktree_path, "mise", &["trust"], stdout, report);
+        }
+        if config.install {
+            run_tool(worktree_path, "mise", &["install"], stdout, report);
+        }
+    } else if found.contains(&TOOL_VERSIONS) && is_installed("asdf") {
+        if config.install {
+            run_tool(worktree_path, "asdf", &["install"], stdout, report);
+        }
+    } else {
+        warn!(
+            "Found {} but neither mise nor asdf is installed",
+            found.join(", ")
+        );
+    }
+    Ok(())
+}
+
+fn is_installed(program: &str) -> bool {
+    Command::new(program)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok()
+}
+
+/// Runs a tool command in the worktree, warning if it fails
+fn run_tool(
+    worktree_path: &Path,
+    program: &str,
+    args: &[&str],
+    stdout: Target,
+    report: Report<'_>,
+) {
+    let command = format!("{} {}", program, args.join(" "));
+    report(&Event::CommandStarted {
+        step: "toolchain",
+        command: &command,
+    });
+    let status = Command::new(program)
+        .args(args)
+        .current_dir(worktree_path)
+        .stdin(Stdio::null())
+        .stdout(stdout.stdio())
+        .status();
+    match status {
+        Ok(status) if status.success() => report(&Event::CommandFinished {
+            step: "toolchain",
+            command: &command,
+        }),
+        Ok(status) => warn!(
+            "`{}` failed with exit code {}",
+            command,
+            status.code().unwrap_or(-1)
+        ),
+        Err(e) => warn!("Failed to run `{}`: {}", command, e),
+    }
+}
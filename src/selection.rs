@@ -1,16 +1,43 @@
 use anyhow::Result;
-use inquire::{Select, Text, validator::Validation};
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use inquire::validator::{ErrorMessage, Validation};
+use inquire::{Confirm, InquireError, MultiSelect, Select, Text};
+use std::collections::VecDeque;
 use std::error::Error;
 use std::fmt;
+use std::io::IsTerminal;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
 
 use crate::git::GitRepo;
 
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+/// Makes [`confirm`] answer yes without prompting (from `--yes`)
+pub fn set_assume_yes(assume_yes: bool) {
+    ASSUME_YES.store(assume_yes, Ordering::Relaxed);
+}
+
+/// Asks the user to confirm a destructive action described by `summary` (one line per
+/// affected item), unless `--yes` was given. Returns false if the user declines.
+///
+/// # Errors
+/// Returns an error if the prompt fails or the user cancels it
+pub fn confirm(provider: &dyn SelectionProvider, summary: &[String], prompt: &str) -> Result<bool> {
+    if ASSUME_YES.load(Ordering::Relaxed) {
+        return Ok(true);
+    }
+    provider.confirm(summary, prompt)
+}
+
 /// Type alias for validation functions
 pub type ValidatorFn = fn(&str) -> Result<Validation, Box<dyn Error + Send + Sync>>;
 
 /// Represents a git reference option with visual grouping
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum GitRefOption {
     /// A selectable git reference
     Reference { name: String, display: String },
@@ -48,26 +75,98 @@ pub trait SelectionProvider {
     /// Returns an error if the selection process fails or user cancels
     fn select_grouped(&self, prompt: &str, options: Vec<GitRefOption>) -> Result<String>;
 
+    /// Present a multi-selection menu and return the chosen options (possibly none).
+    /// With `all_selected`, every option starts out checked.
+    ///
+    /// # Errors
+    /// Returns an error if the selection process fails or user cancels
+    fn select_many(
+        &self,
+        prompt: &str,
+        options: Vec<String>,
+        all_selected: bool,
+    ) -> Result<Vec<String>>;
+
     /// Get text input from the user with validation
     ///
     /// # Errors
     /// Returns an error if the input process fails or user cancels
     fn get_text_input(&self, prompt: &str, validator: Option<ValidatorFn>) -> Result<String>;
+
+    /// Show a summary of what is about to happen and ask a yes/no question (default
+    /// no). Prefer the [`confirm`] helper, which honors `--yes`.
+    ///
+    /// # Errors
+    /// Returns an error if the prompt fails or user cancels
+    fn confirm(&self, summary: &[String], prompt: &str) -> Result<bool>;
+}
+
+/// Help line shown under the interactive lists
+const FILTER_HELP: &str = "type to filter, ↑↓ to move, enter to select";
+
+/// Scores `option` against the filter typed so far with skim-style fuzzy matching
+/// (`wtfa` matches `worktree/feature-auth`). Higher is better; `None` hides the option.
+/// An empty filter keeps every option in its original order.
+#[must_use]
+pub fn fuzzy_score(filter: &str, option: &str) -> Option<i64> {
+    if filter.is_empty() {
+        return Some(0);
+    }
+    SkimMatcherV2::default()
+        .smart_case()
+        .fuzzy_match(option, filter)
+}
+
+fn fuzzy_scorer(filter: &str, _option: &String, value: &str, _index: usize) -> Option<i64> {
+    fuzzy_score(filter, value)
 }
 
-/// Real implementation using inquire::Select for production use
+/// Real implementation using inquire prompts for production use. Lists are filtered
+/// as you type, with fuzzy matching, and sorted by how well each option matches.
+///
+/// When `WORKTREE_SELECT` is set, every prompt is answered by the process-wide
+/// [`ScriptedSelectionProvider`] instead, so scripts and CI can drive interactive
+/// commands.
 pub struct RealSelectionProvider;
 
+/// The scripted provider for this process, if `WORKTREE_SELECT` is set
+fn scripted_provider() -> Option<&'static ScriptedSelectionProvider> {
+    static SCRIPTED: OnceLock<Option<ScriptedSelectionProvider>> = OnceLock::new();
+    SCRIPTED
+        .get_or_init(ScriptedSelectionProvider::from_env)
+        .as_ref()
+}
+
+/// Adds a hint about scripting to inquire's "not a terminal" failure
+fn prompt_error(e: InquireError) -> anyhow::Error {
+    if matches!(e, InquireError::NotTTY) {
+        anyhow::Error::new(e).context(format!(
+            "No terminal for the interactive prompt; set {} to answer it from a script",
+            SELECT_ENV
+        ))
+    } else {
+        e.into()
+    }
+}
+
 impl SelectionProvider for RealSelectionProvider {
     fn select(&self, prompt: &str, options: Vec<String>) -> Result<String> {
+        if let Some(scripted) = scripted_provider() {
+            return scripted.select(prompt, options);
+        }
         let selection = Select::new(prompt, options)
-            .with_page_size(10)
-            .with_vim_mode(true)
-            .prompt()?;
+            .with_page_size(15)
+            .with_scorer(&fuzzy_scorer)
+            .with_help_message(FILTER_HELP)
+            .prompt()
+            .map_err(prompt_error)?;
         Ok(selection)
     }
 
     fn select_grouped(&self, prompt: &str, options: Vec<GitRefOption>) -> Result<String> {
+        if let Some(scripted) = scripted_provider() {
+            return scripted.select_grouped(prompt, options);
+        }
         // Parse options into groups
         let mut groups: Vec<(String, Vec<String>)> = Vec::new();
         let mut current_group_name = String::new();
@@ -122,42 +221,345 @@ impl SelectionProvider for RealSelectionProvider {
         anyhow::bail!("Selected group not found")
     }
 
+    fn select_many(
+        &self,
+        prompt: &str,
+        options: Vec<String>,
+        all_selected: bool,
+    ) -> Result<Vec<String>> {
+        if let Some(scripted) = scripted_provider() {
+            return scripted.select_many(prompt, options, all_selected);
+        }
+        let mut multi_select = MultiSelect::new(prompt, options)
+            .with_page_size(15)
+            .with_scorer(&fuzzy_scorer)
+            .with_help_message("type to filter, space to toggle, enter to confirm");
+        if all_selected {
+            multi_select = multi_select.with_all_selected_by_default();
+        }
+        multi_select.prompt().map_err(prompt_error)
+    }
+
     fn get_text_input(&self, prompt: &str, validator: Option<ValidatorFn>) -> Result<String> {
+        if let Some(scripted) = scripted_provider() {
+            return scripted.get_text_input(prompt, validator);
+        }
         let mut text_prompt = Text::new(prompt);
 
         if let Some(validation_fn) = validator {
             text_prompt = text_prompt.with_validator(validation_fn);
         }
 
-        let result = text_prompt.prompt()?;
+        let result = text_prompt.prompt().map_err(prompt_error)?;
         Ok(result)
     }
+
+    /// Without a terminal there is nobody to ask, so scripts proceed as before
+    fn confirm(&self, summary: &[String], prompt: &str) -> Result<bool> {
+        if let Some(scripted) = scripted_provider() {
+            return scripted.confirm(summary, prompt);
+        }
+        if !std::io::stdin().is_terminal() {
+            return Ok(true);
+        }
+        for line in summary {
+            println!("{}", line);
+        }
+        Confirm::new(prompt)
+            .with_default(false)
+            .prompt()
+            .map_err(prompt_error)
+    }
+}
+
+/// Environment variable holding scripted answers for interactive prompts
+pub const SELECT_ENV: &str = "WORKTREE_SELECT";
+
+/// Answers prompts from a queue of scripted answers instead of a terminal, for scripts
+/// and CI.
+///
+/// Built from `WORKTREE_SELECT`: answers are separated by newlines or `;`, one per
+/// prompt, in order. `WORKTREE_SELECT=-` reads one line from stdin per prompt instead.
+///
+/// - Selections take the option equal to the answer, else the only option containing
+///   it (`feature/x` picks `repo/x (feature/x) (/path)`)
+/// - Multi-selections take a comma-separated list, `*` for all, or an empty answer for
+///   none
+/// - Confirmations accept `y`/`yes`/`n`/`no`, and say yes once the answers run out
+pub struct ScriptedSelectionProvider {
+    answers: Mutex<VecDeque<String>>,
+    read_stdin: bool,
+}
+
+impl ScriptedSelectionProvider {
+    /// Answers prompts with `answers`, in order
+    pub fn new<I, S>(answers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            answers: Mutex::new(answers.into_iter().map(Into::into).collect()),
+            read_stdin: false,
+        }
+    }
+
+    /// Answers prompts with lines read from stdin
+    #[must_use]
+    pub fn from_stdin() -> Self {
+        Self {
+            answers: Mutex::new(VecDeque::new()),
+            read_stdin: true,
+        }
+    }
+
+    /// Builds the provider from `WORKTREE_SELECT`, or `None` if it is not set
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let value = std::env::var(SELECT_ENV).ok()?;
+        if value.trim() == "-" {
+            return Some(Self::from_stdin());
+        }
+        Some(Self::new(value.split(['\n', ';']).map(str::trim)))
+    }
+
+    fn next_answer(&self) -> Option<String> {
+        let queued = self
+            .answers
+            .lock()
+            .map_or(None, |mut answers| answers.pop_front());
+        if queued.is_some() || !self.read_stdin {
+            return queued;
+        }
+        let mut line = String::new();
+        match std::io::stdin().read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line.trim_end_matches(['\r', '\n']).to_string()),
+        }
+    }
+
+    fn require_answer(&self, prompt: &str) -> Result<String> {
+        self.next_answer().ok_or_else(|| {
+            anyhow::anyhow!(
+                "No scripted answer left for prompt '{}' (set {})",
+                prompt,
+                SELECT_ENV
+            )
+        })
+    }
+}
+
+/// Picks the option equal to `answer`, else the only option containing it
+fn match_option(prompt: &str, answer: &str, options: &[String]) -> Result<String> {
+    if let Some(exact) = options.iter().find(|option| *option == answer) {
+        return Ok(exact.clone());
+    }
+    let matches: Vec<&String> = options
+        .iter()
+        .filter(|option| option.contains(answer))
+        .collect();
+    match matches.as_slice() {
+        [only] => Ok((*only).clone()),
+        [] => anyhow::bail!(
+            "Scripted answer '{}' matches none of the options for '{}':\n  {}",
+            answer,
+            prompt,
+            options.join("\n  ")
+        ),
+        _ => anyhow::bail!(
+            "Scripted answer '{}' matches several options for '{}':\n  {}",
+            answer,
+            prompt,
+            matches
+                .iter()
+                .map(|option| option.as_str())
+                .collect::<Vec<_>>()
+                .join("\n  ")
+        ),
+    }
+}
+
+impl SelectionProvider for ScriptedSelectionProvider {
+    fn select(&self, prompt: &str, options: Vec<String>) -> Result<String> {
+        let answer = self.require_answer(prompt)?;
+        match_option(prompt, &answer, &options)
+    }
+
+    fn select_grouped(&self, prompt: &str, options: Vec<GitRefOption>) -> Result<String> {
+        let names: Vec<String> = options
+            .into_iter()
+            .filter_map(|option| match option {
+                GitRefOption::Reference { name, .. } => Some(name),
+                GitRefOption::Separator(_) => None,
+            })
+            .collect();
+        self.select(prompt, names)
+    }
+
+    fn select_many(
+        &self,
+        prompt: &str,
+        options: Vec<String>,
+        _all_selected: bool,
+    ) -> Result<Vec<String>> {
+        let answer = self.require_answer(prompt)?;
+        match answer.trim() {
+            "*" => Ok(options),
+            "" => Ok(Vec::new()),
+            list => list
+                .split(',')
+                .map(|item| match_option(prompt, item.trim(), &options))
+                .collect(),
+        }
+    }
+
+    fn get_text_input(&self, prompt: &str, validator: Option<ValidatorFn>) -> Result<String> {
+        let answer = self.require_answer(prompt)?;
+        if let Some(validate) = validator {
+            if let Validation::Invalid(message) =
+                validate(&answer).map_err(|e| anyhow::anyhow!(e))?
+            {
+                let reason = match message {
+                    ErrorMessage::Custom(reason) => reason,
+                    ErrorMessage::Default => "rejected by validation".to_string(),
+                };
+                anyhow::bail!("Scripted answer '{}' is invalid: {}", answer, reason);
+            }
+        }
+        Ok(answer)
+    }
+
+    fn confirm(&self, summary: &[String], prompt: &str) -> Result<bool> {
+        for line in summary {
+            println!("{}", line);
+        }
+        match self.next_answer() {
+            None => Ok(true),
+            Some(answer) => match answer.to_lowercase().as_str() {
+                "y" | "yes" => Ok(true),
+                "n" | "no" => Ok(false),
+                other => anyhow::bail!(
+                    "Scripted answer '{}' for '{}' is not yes or no",
+                    other,
+                    prompt
+                ),
+            },
+        }
+    }
 }
 
-/// Mock implementation for testing that returns a predetermined value
+/// Mock implementation for testing that returns a predetermined value.
+///
+/// For `select_many`, the response holds one chosen option per line (empty for none).
+/// For `confirm`, a response of `y` or `yes` (any case) confirms.
+///
+/// To test flows with several prompts, build a scripted mock instead: each prompt must
+/// match the next expected one and gets its own response.
+///
+/// ```
+/// # use worktree::selection::{MockSelectionProvider, SelectionProvider};
+/// let provider = MockSelectionProvider::scripted()
+///     .expect("Feature name:", "auth")
+///     .expect("Proceed?", "y");
+/// assert_eq!(provider.get_text_input("Feature name:", None)?, "auth");
+/// assert!(provider.confirm(&[], "Proceed?")?);
+/// provider.verify_consumed()?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
 pub struct MockSelectionProvider {
     pub response: String,
+    script: Option<Mutex<VecDeque<(String, String)>>>,
 }
 
 impl MockSelectionProvider {
     pub fn new(response: impl Into<String>) -> Self {
         Self {
             response: response.into(),
+            script: None,
+        }
+    }
+
+    /// Creates a mock that answers a sequence of expected prompts, added with
+    /// [`expect`](Self::expect)
+    #[must_use]
+    pub fn scripted() -> Self {
+        Self {
+            response: String::new(),
+            script: Some(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /// Expects `prompt` as the next prompt and answers it with `response`
+    #[must_use]
+    pub fn expect(mut self, prompt: impl Into<String>, response: impl Into<String>) -> Self {
+        let entry = (prompt.into(), response.into());
+        match &mut self.script {
+            Some(script) => {
+                if let Ok(script) = script.get_mut() {
+                    script.push_back(entry);
+                }
+            }
+            None => self.script = Some(Mutex::new(VecDeque::from([entry]))),
+        }
+        self
+    }
+
+    /// Checks that every scripted prompt was asked
+    ///
+    /// # Errors
+    /// Returns an error listing the expected prompts that were never asked
+    pub fn verify_consumed(&self) -> Result<()> {
+        let Some(script) = &self.script else {
+            return Ok(());
+        };
+        let script = script
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Mock script lock poisoned"))?;
+        if !script.is_empty() {
+            anyhow::bail!(
+                "Scripted prompts never asked: {}",
+                script
+                    .iter()
+                    .map(|(prompt, _)| format!("'{}'", prompt))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    /// The response for `prompt`: the next scripted one, or the fixed response
+    fn response_for(&self, prompt: &str) -> Result<String> {
+        let Some(script) = &self.script else {
+            return Ok(self.response.clone());
+        };
+        let next = script
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Mock script lock poisoned"))?
+            .pop_front();
+        match next {
+            Some((expected, response)) if expected == prompt => Ok(response),
+            Some((expected, _)) => {
+                anyhow::bail!("Expected prompt '{}' but got '{}'", expected, prompt)
+            }
+            None => anyhow::bail!("Unexpected prompt '{}': no scripted responses left", prompt),
         }
     }
 }
 
 impl SelectionProvider for MockSelectionProvider {
-    fn select(&self, _prompt: &str, options: Vec<String>) -> Result<String> {
+    fn select(&self, prompt: &str, options: Vec<String>) -> Result<String> {
+        let response = self.response_for(prompt)?;
         // Validate that the response is actually in the options
-        if options.contains(&self.response) {
-            Ok(self.response.clone())
+        if options.contains(&response) {
+            Ok(response)
         } else {
-            anyhow::bail!("Mock response '{}' not found in options", self.response)
+            anyhow::bail!("Mock response '{}' not found in options", response)
         }
     }
 
-    fn select_grouped(&self, _prompt: &str, options: Vec<GitRefOption>) -> Result<String> {
+    fn select_grouped(&self, prompt: &str, options: Vec<GitRefOption>) -> Result<String> {
+        let response = self.response_for(prompt)?;
         // Extract only the selectable reference names from the grouped options
         let selectable_values: Vec<String> = options
             .into_iter()
@@ -168,19 +570,38 @@ impl SelectionProvider for MockSelectionProvider {
             .collect();
 
         // Validate that the response is actually in the selectable options
-        if selectable_values.contains(&self.response) {
-            Ok(self.response.clone())
+        if selectable_values.contains(&response) {
+            Ok(response)
         } else {
-            anyhow::bail!(
-                "Mock response '{}' not found in grouped options",
-                self.response
-            )
+            anyhow::bail!("Mock response '{}' not found in grouped options", response)
         }
     }
 
-    fn get_text_input(&self, _prompt: &str, _validator: Option<ValidatorFn>) -> Result<String> {
+    fn select_many(
+        &self,
+        prompt: &str,
+        options: Vec<String>,
+        _all_selected: bool,
+    ) -> Result<Vec<String>> {
+        let chosen: Vec<String> = self
+            .response_for(prompt)?
+            .lines()
+            .map(str::to_string)
+            .collect();
+        if let Some(missing) = chosen.iter().find(|c| !options.contains(c)) {
+            anyhow::bail!("Mock response '{}' not found in options", missing);
+        }
+        Ok(chosen)
+    }
+
+    fn get_text_input(&self, prompt: &str, _validator: Option<ValidatorFn>) -> Result<String> {
         // For testing, return a predetermined response
-        Ok(self.response.clone())
+        self.response_for(prompt)
+    }
+
+    fn confirm(&self, _summary: &[String], prompt: &str) -> Result<bool> {
+        let response = self.response_for(prompt)?;
+        Ok(matches!(response.to_lowercase().as_str(), "y" | "yes"))
     }
 }
 
@@ -301,6 +722,100 @@ pub fn extract_reference_from_selection(selection: &str) -> Result<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fuzzy_score_matches_subsequences() {
+        assert!(fuzzy_score("wtfa", "worktree/feature-auth").is_some());
+        assert!(fuzzy_score("auth", "api/payments").is_none());
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_closer_matches() {
+        let exact = fuzzy_score("auth", "api/auth");
+        let scattered = fuzzy_score("auth", "api/haulmstxh");
+        assert!(exact > scattered, "{exact:?} should beat {scattered:?}");
+    }
+
+    #[test]
+    fn test_scripted_provider_answers_in_order() -> Result<()> {
+        let options = vec![
+            "repo/auth (feature/auth)".to_string(),
+            "repo/billing (feature/billing)".to_string(),
+        ];
+        let provider = ScriptedSelectionProvider::new(["feature/billing", "repo/auth", "no"]);
+
+        assert_eq!(
+            provider.select("Pick", options.clone())?,
+            "repo/billing (feature/billing)"
+        );
+        assert_eq!(
+            provider.select_many("Pick", options, false)?,
+            vec!["repo/auth (feature/auth)".to_string()]
+        );
+        assert!(!provider.confirm(&[], "Proceed?")?);
+        // Out of answers: confirmations proceed, selections fail
+        assert!(provider.confirm(&[], "Proceed?")?);
+        assert!(provider.select("Pick", vec!["a".to_string()]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_scripted_provider_rejects_ambiguous_and_unknown_answers() {
+        let options = vec!["feature/a".to_string(), "feature/b".to_string()];
+        let provider = ScriptedSelectionProvider::new(["feature", "other"]);
+
+        assert!(provider.select("Pick", options.clone()).is_err());
+        assert!(provider.select("Pick", options).is_err());
+    }
+
+    #[test]
+    fn test_scripted_provider_validates_text_input() {
+        let reject_spaces: ValidatorFn = |input| {
+            Ok(if input.contains(' ') {
+                Validation::Invalid("no spaces".into())
+            } else {
+                Validation::Valid
+            })
+        };
+        let provider = ScriptedSelectionProvider::new(["has space", "ok"]);
+
+        assert!(
+            provider
+                .get_text_input("Name", Some(reject_spaces))
+                .is_err()
+        );
+        assert!(matches!(
+            provider.get_text_input("Name", Some(reject_spaces)),
+            Ok(ref s) if s == "ok"
+        ));
+    }
+
+    #[test]
+    fn test_scripted_mock_answers_prompts_in_order() -> Result<()> {
+        let provider = MockSelectionProvider::scripted()
+            .expect("Choose a category:", "Tags")
+            .expect("Choose from Tags:", "v1.0");
+
+        assert_eq!(
+            provider.select("Choose a category:", vec!["Tags".to_string()])?,
+            "Tags"
+        );
+        assert!(provider.verify_consumed().is_err());
+        assert_eq!(
+            provider.select("Choose from Tags:", vec!["v1.0".to_string()])?,
+            "v1.0"
+        );
+        provider.verify_consumed()?;
+        assert!(provider.select("Extra:", vec!["v1.0".to_string()]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_scripted_mock_rejects_unexpected_prompt() {
+        let provider = MockSelectionProvider::scripted().expect("Feature name:", "auth");
+        assert!(provider.get_text_input("Branch name:", None).is_err());
+    }
+
     #[test]
     fn test_mock_selection_provider_valid_response() {
         let options = vec!["option1".to_string(), "option2".to_string()];
@@ -319,6 +834,31 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_mock_selection_provider_select_many() {
+        let options = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+
+        let provider = MockSelectionProvider::new("a\nc");
+        let result = provider.select_many("Test prompt", options.clone(), true);
+        assert!(matches!(result, Ok(ref v) if v == &["a", "c"]));
+
+        let provider = MockSelectionProvider::new("");
+        let result = provider.select_many("Test prompt", options.clone(), true);
+        assert!(matches!(result, Ok(ref v) if v.is_empty()));
+
+        let provider = MockSelectionProvider::new("a\nz");
+        assert!(provider.select_many("Test prompt", options, true).is_err());
+    }
+
+    #[test]
+    fn test_mock_selection_provider_confirm() -> Result<()> {
+        assert!(MockSelectionProvider::new("y").confirm(&[], "Proceed?")?);
+        assert!(MockSelectionProvider::new("Yes").confirm(&[], "Proceed?")?);
+        assert!(!MockSelectionProvider::new("n").confirm(&[], "Proceed?")?);
+        assert!(!MockSelectionProvider::new("").confirm(&[], "Proceed?")?);
+        Ok(())
+    }
+
     #[test]
     fn test_extract_path_from_selection() {
         let selection = "repo/branch (/some/path)";
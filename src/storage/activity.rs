@@ -0,0 +1,298 @@
+//! Creation and last-use times of worktrees.
+//!
+//! `.worktree-activity` in the repository's storage directory holds one
+//! `<feature> <created> <last used>` line per worktree, in seconds since the Unix epoch.
+//! `create` records both times and `jump` bumps the last use; removing a worktree drops
+//! its line. Creation times missing from it come from the worktree's `.worktree-meta.toml`
+//! (see [`super::meta`]). Worktrees created before either file existed fall back to the
+//! filesystem: the directory's birth time, and the last change to the worktree's git
+//! index (commits, staging, checkouts) counts as use too.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::error::{Result, io_error};
+use super::{WorktreeStorage, unix_now};
+use crate::traits::GitOperations;
+
+/// Name of the activity file in a repository's storage directory
+pub const ACTIVITY_FILE_NAME: &str = ".worktree-activity";
+
+/// When a worktree was created and last used, in seconds since the Unix epoch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Activity {
+    /// When the worktree was created, if known
+    pub created: Option<u64>,
+    /// When the worktree was last jumped to or changed, if known
+    pub last_used: Option<u64>,
+}
+
+impl Activity {
+    /// When the worktree at `path` was last worked on: the latest of its last use, its
+    /// creation, its HEAD commit, and the newest modification of a file with uncommitted
+    /// changes. Unknown or unreadable parts count as never. Retention and stale checks
+    /// go by this.
+    #[must_use]
+    pub fn last_activity(&self, git: &dyn GitOperations, path: &Path) -> u64 {
+        let commit_time = git
+            .last_commit(path)
+            .ok()
+            .and_then(|commit| u64::try_from(commit.time).ok())
+            .unwrap_or(0);
+        let changed = git
+            .changed_files(path)
+            .unwrap_or_default()
+            .iter()
+            .map(|file| modified(&path.join(file)).unwrap_or(0))
+            .max()
+            .unwrap_or(0);
+
+        [
+            self.last_used.unwrap_or(0),
+            self.created.unwrap_or(0),
+            modified(&path.join(".git")).unwrap_or(0),
+            commit_time,
+            changed,
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or(0)
+    }
+}
+
+/// Recorded activity of a repository's worktrees, keyed by feature name
+pub type ActivityMap = BTreeMap<String, Activity>;
+
+impl WorktreeStorage {
+    /// Records that a worktree was just created, which also counts as using it
+    ///
+    /// # Errors
+    /// Returns an error if the storage lock cannot be acquired or the activity file
+    /// cannot be read or written
+    pub fn record_created(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        let now = unix_now();
+        self.update_activity(repo_name, feature_name, |activity| {
+            activity.created = Some(now);
+            activity.last_used = Some(now);
+        })
+    }
+
+    /// Records that a worktree was just used (e.g. jumped to)
+    ///
+    /// # Errors
+    /// Returns an error if the storage lock cannot be acquired or the activity file
+    /// cannot be read or written
+    pub fn record_use(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        let now = unix_now();
+        self.update_activity(repo_name, feature_name, |activity| {
+            activity.last_used = Some(now);
+        })
+    }
+
+    /// Returns the recorded activity of a repository's worktrees
+    ///
+    /// # Errors
+    /// Returns an error if the activity file exists but cannot be read
+    pub fn list_activity(&self, repo_name: &str) -> Result<ActivityMap> {
+        read_activity(
+            &self
+                .get_repo_storage_dir(repo_name)
+                .join(ACTIVITY_FILE_NAME),
+        )
+    }
+
+    /// When a worktree was created and last used: the recorded times, filled in or
+    /// updated from the filesystem (see the module documentation)
+    #[must_use]
+    pub fn worktree_activity(&self, repo_name: &str, feature_name: &str) -> Activity {
+        let recorded = self
+            .list_activity(repo_name)
+            .ok()
+            .and_then(|activity| activity.get(feature_name).copied())
+            .unwrap_or_default();
+        let path = self.get_worktree_path(repo_name, feature_name);
+        Activity {
+            created: recorded
+                .created
+                .or_else(|| {
+                    self.recorded_meta(repo_name, feature_name)
+                        .and_then(|meta| meta.created_at)
+                })
+                .or_else(|| created_on_disk(&path)),
+            last_used: recorded.last_used.max(index_modified(&path)),
+        }
+    }
+
+    /// Drops a worktree's activity record
+    ///
+    /// # Errors
+    /// Returns an error if the storage lock cannot be acquired or the activity file
+    /// cannot be read or written
+    pub fn forget_activity(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        let path = self
+            .get_repo_storage_dir(repo_name)
+            .join(ACTIVITY_FILE_NAME);
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let _lock = self.lock()?;
+        let mut activity = read_activity(&path)?;
+        if activity.remove(feature_name).is_some() {
+            write_activity(&path, &activity)?;
+        }
+        Ok(())
+    }
+
+    fn update_activity(
+        &self,
+        repo_name: &str,
+        feature_name: &str,
+        update: impl FnOnce(&mut Activity),
+    ) -> Result<()> {
+        let repo_dir = self.get_repo_storage_dir(repo_name);
+        std::fs::create_dir_all(&repo_dir)
+            .map_err(io_error("Failed to create storage directory", &repo_dir))?;
+
+        let _lock = self.lock()?;
+        let path = repo_dir.join(ACTIVITY_FILE_NAME);
+        let mut activity = read_activity(&path)?;
+        update(activity.entry(feature_name.to_string()).or_default());
+        write_activity(&path, &activity)
+    }
+}
+
+/// How long ago `timestamp` was, compactly: "just now", "5m ago", "3h ago", "2d ago",
+/// "3w ago", "4mo ago", or "2y ago"
+#[must_use]
+pub fn format_age(timestamp: u64) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+
+    let age = unix_now().saturating_sub(timestamp);
+    let (count, unit) = match age {
+        age if age < MINUTE => return "just now".to_string(),
+        age if age < HOUR => (age / MINUTE, "m"),
+        age if age < DAY => (age / HOUR, "h"),
+        age if age < 14 * DAY => (age / DAY, "d"),
+        age if age < 60 * DAY => (age / (7 * DAY), "w"),
+        age if age < 365 * DAY => (age / (30 * DAY), "mo"),
+        age => (age / (365 * DAY), "y"),
+    };
+    format!("{}{} ago", count, unit)
+}
+
+/// The worktree directory's birth time, where the filesystem records one
+fn created_on_disk(path: &Path) -> Option<u64> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.created())
+        .ok()
+        .and_then(unix_secs)
+}
+
+/// When `path` was last modified
+fn modified(path: &Path) -> Option<u64> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(unix_secs)
+}
+
+/// When the worktree's git index last changed
+fn index_modified(path: &Path) -> Option<u64> {
+    let repo = git2::Repository::open(path).ok()?;
+    std::fs::metadata(repo.path().join("index"))
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(unix_secs)
+}
+
+fn unix_secs(time: SystemTime) -> Option<u64> {
+    time.duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|duration| duration.as_secs())
+}
+
+fn read_activity(path: &Path) -> Result<ActivityMap> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(ActivityMap::new()),
+        Err(e) => return Err(io_error("Failed to read worktree activity", path)(e)),
+    };
+    Ok(content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.rsplitn(3, ' ');
+            let last_used = fields.next()?.parse().ok();
+            let created = fields.next()?.parse().ok();
+            let feature = fields.next()?;
+            Some((feature.to_string(), Activity { created, last_used }))
+        })
+        .collect())
+}
+
+/// Replaces the activity file atomically: write to a temp file, then rename
+fn write_activity(path: &Path, activity: &ActivityMap) -> Result<()> {
+    let field = |time: Option<u64>| time.map_or_else(|| "-".to_string(), |t| t.to_string());
+    let content: String = activity
+        .iter()
+        .map(|(feature, a)| format!("{} {} {}\n", feature, field(a.created), field(a.last_used)))
+        .collect();
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, content)
+        .map_err(io_error("Failed to write worktree activity", &tmp_path))?;
+    std::fs::rename(&tmp_path, path).map_err(io_error("Failed to write worktree activity", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_and_forget_activity() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = WorktreeStorage::with_root_dir(tmp.path().to_path_buf())?;
+
+        storage.record_use("repo", "old")?;
+        storage.record_created("repo", "new")?;
+        let activity = storage.list_activity("repo")?;
+        assert_eq!(activity["old"].created, None);
+        assert!(activity["old"].last_used.is_some());
+        assert!(activity["new"].created.is_some());
+
+        storage.forget_activity("repo", "new")?;
+        assert!(!storage.list_activity("repo")?.contains_key("new"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_last_activity_counts_last_use() -> Result<()> {
+        let tmp = TempDir::new()?;
+        git2::Repository::init(tmp.path())?;
+        let git = crate::git::GitRepo::open(tmp.path())?;
+        let activity = Activity {
+            created: Some(1_000),
+            last_used: Some(5_000),
+        };
+        assert_eq!(
+            activity.last_activity(&git, &tmp.path().join("gone")),
+            5_000
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_age() {
+        let now = unix_now();
+        assert_eq!(format_age(now), "just now");
+        assert_eq!(format_age(now - 5 * 60), "5m ago");
+        assert_eq!(format_age(now - 2 * 24 * 60 * 60), "2d ago");
+        assert_eq!(format_age(now - 21 * 24 * 60 * 60), "3w ago");
+        assert_eq!(format_age(now - 400 * 24 * 60 * 60), "1y ago");
+    }
+}
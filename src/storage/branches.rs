@@ -0,0 +1,119 @@
+//! Branches created by this tool.
+//!
+//! `.managed-branches` in the repository's storage directory lists one branch per line:
+//! the branches `create` made for new worktrees, as opposed to existing branches a
+//! worktree adopted. Deleting a branch through `remove --delete-branch`, `finish`, or
+//! `cleanup --merged` drops it, and `cleanup` drops entries for branches deleted by other
+//! means. `list` marks worktrees on branches missing from it as adopted.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use super::WorktreeStorage;
+use super::error::{Result, io_error};
+
+/// Name of the managed branch file in a repository's storage directory
+pub const MANAGED_BRANCHES_FILE_NAME: &str = ".managed-branches";
+
+impl WorktreeStorage {
+    /// Returns the branches this tool created in the repository
+    ///
+    /// # Errors
+    /// Returns an error if the managed branch file exists but cannot be read
+    pub fn managed_branches(&self, repo_name: &str) -> Result<BTreeSet<String>> {
+        read_branches(
+            &self
+                .get_repo_storage_dir(repo_name)
+                .join(MANAGED_BRANCHES_FILE_NAME),
+        )
+    }
+
+    /// Records that this tool created `branch`
+    ///
+    /// # Errors
+    /// Returns an error if the storage lock cannot be acquired or the managed branch
+    /// file cannot be read or written
+    pub fn record_managed_branch(&self, repo_name: &str, branch: &str) -> Result<()> {
+        let repo_dir = self.get_repo_storage_dir(repo_name);
+        std::fs::create_dir_all(&repo_dir)
+            .map_err(io_error("Failed to create storage directory", &repo_dir))?;
+
+        let _lock = self.lock()?;
+        let path = repo_dir.join(MANAGED_BRANCHES_FILE_NAME);
+        let mut branches = read_branches(&path)?;
+        if branches.insert(branch.to_string()) {
+            write_branches(&path, &branches)?;
+        }
+        Ok(())
+    }
+
+    /// Drops `branch` from the branches this tool created, after it was deleted
+    ///
+    /// # Errors
+    /// Returns an error if the storage lock cannot be acquired or the managed branch
+    /// file cannot be read or written
+    pub fn forget_managed_branch(&self, repo_name: &str, branch: &str) -> Result<()> {
+        let path = self
+            .get_repo_storage_dir(repo_name)
+            .join(MANAGED_BRANCHES_FILE_NAME);
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let _lock = self.lock()?;
+        let mut branches = read_branches(&path)?;
+        if branches.remove(branch) {
+            write_branches(&path, &branches)?;
+        }
+        Ok(())
+    }
+}
+
+fn read_branches(path: &Path) -> Result<BTreeSet<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(content
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeSet::new()),
+        Err(e) => Err(io_error("Failed to read managed branches", path)(e)),
+    }
+}
+
+/// Replaces the managed branch file atomically: write to a temp file, then rename
+fn write_branches(path: &Path, branches: &BTreeSet<String>) -> Result<()> {
+    let content: String = branches
+        .iter()
+        .map(|branch| format!("{}\n", branch))
+        .collect();
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, content)
+        .map_err(io_error("Failed to write managed branches", &tmp_path))?;
+    std::fs::rename(&tmp_path, path).map_err(io_error("Failed to write managed branches", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_and_forget_managed_branches() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = WorktreeStorage::with_root_dir(tmp.path().to_path_buf())?;
+
+        storage.record_managed_branch("repo", "feature/auth")?;
+        storage.record_managed_branch("repo", "feature/billing")?;
+        storage.record_managed_branch("repo", "feature/auth")?;
+        assert_eq!(storage.managed_branches("repo")?.len(), 2);
+
+        storage.forget_managed_branch("repo", "feature/auth")?;
+        assert_eq!(
+            storage.managed_branches("repo")?,
+            BTreeSet::from(["feature/billing".to_string()])
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+//! Short-lived cache for shell completion candidates.
+//!
+//! Completion runs on every tab press, and listing worktrees or git references in a big
+//! repository is noticeable. Candidate lists are kept in `.completion-cache/` under the
+//! storage root for a few seconds ([`completion_ttl`]) and dropped whenever a worktree is
+//! created or removed. The cache is best-effort: an entry that is missing, stale, or
+//! unreadable just means computing the list again.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use super::WorktreeStorage;
+
+/// Directory under the storage root holding one file per cached candidate list
+pub const CACHE_DIR: &str = ".completion-cache";
+
+/// How long cached candidates are used when `WORKTREE_COMPLETION_TTL` is not set
+pub const DEFAULT_TTL: Duration = Duration::from_secs(10);
+
+/// Environment variable overriding the cache lifetime in seconds (`0` disables caching)
+pub const TTL_ENV: &str = "WORKTREE_COMPLETION_TTL";
+
+/// How long cached candidates stay valid
+#[must_use]
+pub fn completion_ttl() -> Duration {
+    std::env::var(TTL_ENV)
+        .ok()
+        .and_then(|secs| secs.trim().parse().ok())
+        .map_or(DEFAULT_TTL, Duration::from_secs)
+}
+
+impl WorktreeStorage {
+    /// Returns the candidates cached under `key`, if they are younger than
+    /// [`completion_ttl`]
+    #[must_use]
+    pub fn cached_completions(&self, key: &str) -> Option<Vec<String>> {
+        let path = self.completion_cache_path(key);
+        let age = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())?;
+        if age >= completion_ttl() {
+            return None;
+        }
+        let content = std::fs::read_to_string(path).ok()?;
+        Some(content.lines().map(str::to_string).collect())
+    }
+
+    /// Caches `candidates` under `key`, ignoring failures
+    pub fn cache_completions(&self, key: &str, candidates: &[String]) {
+        if completion_ttl().is_zero() {
+            return;
+        }
+        let dir = self.root_dir.join(CACHE_DIR);
+        if std::fs::create_dir_all(&dir).is_err() {
+            return;
+        }
+        let content: String = candidates
+            .iter()
+            .map(|candidate| format!("{}\n", candidate))
+            .collect();
+        let _ = std::fs::write(self.completion_cache_path(key), content);
+    }
+
+    /// Drops every cached candidate list, ignoring failures
+    pub fn invalidate_completion_cache(&self) {
+        let _ = std::fs::remove_dir_all(self.root_dir.join(CACHE_DIR));
+    }
+
+    fn completion_cache_path(&self, key: &str) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        self.root_dir
+            .join(CACHE_DIR)
+            .join(format!("{:016x}", hasher.finish()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cache_round_trip_and_invalidation() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = WorktreeStorage::with_root_dir(tmp.path().to_path_buf())?;
+        let candidates = vec!["auth".to_string(), "payments".to_string()];
+
+        assert_eq!(storage.cached_completions("worktrees"), None);
+        storage.cache_completions("worktrees", &candidates);
+        assert_eq!(storage.cached_completions("worktrees"), Some(candidates));
+        assert_eq!(storage.cached_completions("refs"), None);
+
+        storage.invalidate_completion_cache();
+        assert_eq!(storage.cached_completions("worktrees"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_worktree_changes_invalidate_cache() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = WorktreeStorage::with_root_dir(tmp.path().to_path_buf())?;
+
+        storage.cache_completions("worktrees", &["old".to_string()]);
+        storage.store_worktree_origin("repo", "new", "/origin")?;
+        assert_eq!(storage.cached_completions("worktrees"), None);
+
+        storage.cache_completions("worktrees", &["new".to_string()]);
+        storage.remove_worktree_origin("repo", "new")?;
+        assert_eq!(storage.cached_completions("worktrees"), None);
+        Ok(())
+    }
+}
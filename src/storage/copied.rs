@@ -0,0 +1,139 @@
+//! Manifest of the config files `create` copied into each worktree.
+//!
+//! `.worktree-copied` in the repository's storage directory holds one
+//! `<feature>\t<path>` line per copied file or directory, relative to the worktree root.
+//! `worktree info` shows it, and removing a worktree drops its lines. Worktrees created
+//! before the manifest existed have no entry, which differs from an empty manifest.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::WorktreeStorage;
+use super::error::{Result, io_error};
+
+/// Name of the manifest file in a repository's storage directory
+pub const COPIED_FILE_NAME: &str = ".worktree-copied";
+
+/// Copied files of a repository's worktrees, keyed by feature name
+pub type CopiedMap = BTreeMap<String, Vec<PathBuf>>;
+
+impl WorktreeStorage {
+    /// Returns the files `create` copied into the worktree, or `None` if none were
+    /// recorded
+    ///
+    /// # Errors
+    /// Returns an error if the manifest exists but cannot be read
+    pub fn copied_files(
+        &self,
+        repo_name: &str,
+        feature_name: &str,
+    ) -> Result<Option<Vec<PathBuf>>> {
+        let path = self.get_repo_storage_dir(repo_name).join(COPIED_FILE_NAME);
+        Ok(read_copied(&path)?.remove(feature_name))
+    }
+
+    /// Records the files `create` copied into the worktree, replacing earlier entries
+    ///
+    /// # Errors
+    /// Returns an error if the storage lock cannot be acquired or the manifest cannot be
+    /// read or written
+    pub fn record_copied_files(
+        &self,
+        repo_name: &str,
+        feature_name: &str,
+        files: &[PathBuf],
+    ) -> Result<()> {
+        let repo_dir = self.get_repo_storage_dir(repo_name);
+        std::fs::create_dir_all(&repo_dir)
+            .map_err(io_error("Failed to create storage directory", &repo_dir))?;
+
+        let _lock = self.lock()?;
+        let path = repo_dir.join(COPIED_FILE_NAME);
+        let mut copied = read_copied(&path)?;
+        copied.insert(feature_name.to_string(), files.to_vec());
+        write_copied(&path, &copied)
+    }
+
+    /// Drops the worktree's manifest
+    ///
+    /// # Errors
+    /// Returns an error if the storage lock cannot be acquired or the manifest cannot be
+    /// read or written
+    pub fn forget_copied_files(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        let path = self.get_repo_storage_dir(repo_name).join(COPIED_FILE_NAME);
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let _lock = self.lock()?;
+        let mut copied = read_copied(&path)?;
+        if copied.remove(feature_name).is_some() {
+            write_copied(&path, &copied)?;
+        }
+        Ok(())
+    }
+}
+
+fn read_copied(path: &Path) -> Result<CopiedMap> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(CopiedMap::new()),
+        Err(e) => return Err(io_error("Failed to read copied file manifest", path)(e)),
+    };
+    let mut copied = CopiedMap::new();
+    for line in content.lines() {
+        let Some((feature, file)) = line.split_once('\t') else {
+            continue;
+        };
+        let files = copied.entry(feature.to_string()).or_default();
+        if !file.is_empty() {
+            files.push(PathBuf::from(file));
+        }
+    }
+    Ok(copied)
+}
+
+/// Replaces the manifest atomically: write to a temp file, then rename. A worktree with
+/// no copied files keeps a `<feature>\t` line so it reads back as an empty manifest.
+fn write_copied(path: &Path, copied: &CopiedMap) -> Result<()> {
+    let mut content = String::new();
+    for (feature, files) in copied {
+        if files.is_empty() {
+            content.push_str(&format!("{}\t\n", feature));
+        }
+        for file in files {
+            content.push_str(&format!("{}\t{}\n", feature, file.display()));
+        }
+    }
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, content)
+        .map_err(io_error("Failed to write copied file manifest", &tmp_path))?;
+    std::fs::rename(&tmp_path, path).map_err(io_error("Failed to write copied file manifest", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_and_forget_copied_files() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = WorktreeStorage::with_root_dir(tmp.path().to_path_buf())?;
+
+        let files = [PathBuf::from(".env"), PathBuf::from(".vscode")];
+        storage.record_copied_files("repo", "auth flow", &files)?;
+        storage.record_copied_files("repo", "billing", &[])?;
+        assert_eq!(
+            storage.copied_files("repo", "auth flow")?.as_deref(),
+            Some(&files[..])
+        );
+        assert_eq!(storage.copied_files("repo", "billing")?, Some(Vec::new()));
+        assert_eq!(storage.copied_files("repo", "search")?, None);
+
+        storage.forget_copied_files("repo", "auth flow")?;
+        assert_eq!(storage.copied_files("repo", "auth flow")?, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use std::path::{Path, PathBuf};
+
+/// Result type for storage operations
+pub type Result<T, E = StorageError> = std::result::Result<T, E>;
+
+/// Errors from the storage layer
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum StorageError {
+    /// `WORKTREE_STORAGE_ROOT` points somewhere that cannot be used
+    #[error(
+        "WORKTREE_STORAGE_ROOT is set to '{}', but it is not a writable directory",
+        path.display()
+    )]
+    CustomRootUnwritable {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    /// `storage-root` in the global config points somewhere that cannot be used
+    #[error(
+        "storage-root in the global config is set to '{}', but it is not a writable directory",
+        path.display()
+    )]
+    ConfiguredRootUnwritable {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    /// None of the default storage locations is writable; `tried` lists each location
+    /// with the reason it was rejected
+    #[error(
+        "Could not find a writable location for worktree storage. Tried:\n{tried}\n\
+         Set WORKTREE_STORAGE_ROOT to a writable directory (or set HOME or XDG_DATA_HOME)."
+    )]
+    NoWritableRoot { tried: String },
+    /// The repository path has no final component to name its storage directory after
+    #[error("Could not determine repository name from path: {}", path.display())]
+    InvalidRepoPath { path: PathBuf },
+    /// The feature name is empty or whitespace
+    #[error("Feature name cannot be empty")]
+    EmptyFeatureName,
+    /// The feature name contains a character that is not allowed in directory names
+    #[error(
+        "Feature name '{name}' contains invalid character '{invalid}'. \
+         Feature names cannot contain: / \\ : * ? \" < > |"
+    )]
+    InvalidFeatureName { name: String, invalid: char },
+    /// Another process holds the storage lock
+    #[error(
+        "Storage is locked by {holder} (waited {waited_secs}s).\n\
+         Run 'worktree lock-status' to inspect the lock, or 'worktree lock-status --break' \
+         to remove it if the holder is no longer running."
+    )]
+    Locked { holder: String, waited_secs: u64 },
+    /// Worktree metadata in the storage directory cannot be read
+    #[error("Storage metadata is corrupt: {} ({reason})", path.display())]
+    Corrupt { path: PathBuf, reason: String },
+    /// The storage root was written by a newer version of worktree
+    #[error(
+        "Storage format version {found} is newer than this worktree supports ({supported}). \
+         Upgrade worktree to use it."
+    )]
+    NewerVersion { found: u32, supported: u32 },
+    /// A filesystem operation on `path` failed
+    #[error("{action}: {}", path.display())]
+    Io {
+        action: &'static str,
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// Builds a `map_err` adapter that records which filesystem operation failed on `path`
+pub fn io_error(action: &'static str, path: &Path) -> impl FnOnce(std::io::Error) -> StorageError {
+    let path = path.to_path_buf();
+    move |source| StorageError::Io {
+        action,
+        path,
+        source,
+    }
+}
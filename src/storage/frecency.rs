@@ -0,0 +1,158 @@
+//! Visit tracking for frecency ordering in `jump`.
+//!
+//! Every jump records a visit in `.worktree-frecency` in the storage root: one
+//! `<repo>/<feature> <count> <last access>` line per worktree. Scores combine the visit
+//! count with how recently the worktree was visited, so worktrees used constantly sort
+//! first.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::error::{Result, io_error};
+use super::{WorktreeStorage, unix_now};
+
+/// Name of the visit tracking file in the storage root
+pub const FRECENCY_FILE_NAME: &str = ".worktree-frecency";
+
+/// Visits recorded for one worktree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Visits {
+    /// How many times the worktree was jumped to
+    pub count: u64,
+    /// Seconds since the Unix epoch of the most recent visit
+    pub last_access: u64,
+}
+
+impl Visits {
+    /// Frecency score at `now`: the visit count weighted by recency
+    #[must_use]
+    pub fn score(&self, now: u64) -> f64 {
+        const HOUR: u64 = 60 * 60;
+        let age = now.saturating_sub(self.last_access);
+        let weight = if age < HOUR {
+            4.0
+        } else if age < 24 * HOUR {
+            2.0
+        } else if age < 7 * 24 * HOUR {
+            0.5
+        } else {
+            0.25
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let count = self.count as f64;
+        count * weight
+    }
+}
+
+/// Visits keyed by `(repo, feature)`
+pub type VisitMap = HashMap<(String, String), Visits>;
+
+impl WorktreeStorage {
+    /// Records a visit to a worktree, bumping its count and last access time
+    ///
+    /// # Errors
+    /// Returns an error if the storage lock cannot be acquired or the visit file cannot
+    /// be read or written
+    pub fn record_visit(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        let _lock = self.lock()?;
+        let path = self.get_root_dir().join(FRECENCY_FILE_NAME);
+        let mut visits = read_visits(&path)?;
+        let entry = visits
+            .entry((repo_name.to_string(), feature_name.to_string()))
+            .or_insert(Visits {
+                count: 0,
+                last_access: 0,
+            });
+        entry.count += 1;
+        entry.last_access = unix_now();
+        write_visits(&path, &visits)
+    }
+
+    /// Returns the recorded visits of every worktree
+    ///
+    /// # Errors
+    /// Returns an error if the visit file exists but cannot be read
+    pub fn visits(&self) -> Result<VisitMap> {
+        read_visits(&self.get_root_dir().join(FRECENCY_FILE_NAME))
+    }
+}
+
+/// Sorts `items` by descending frecency, keeping the existing order among equal scores
+/// (including worktrees never visited)
+pub fn sort_by_frecency<T>(items: &mut [T], visits: &VisitMap, key: impl Fn(&T) -> (&str, &str)) {
+    let now = unix_now();
+    let score = |item: &T| {
+        let (repo, feature) = key(item);
+        visits
+            .get(&(repo.to_string(), feature.to_string()))
+            .map_or(0.0, |v| v.score(now))
+    };
+    items.sort_by(|a, b| score(b).total_cmp(&score(a)));
+}
+
+fn read_visits(path: &Path) -> Result<VisitMap> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(VisitMap::new()),
+        Err(e) => return Err(io_error("Failed to read visit history", path)(e)),
+    };
+    Ok(content.lines().filter_map(parse_line).collect())
+}
+
+fn parse_line(line: &str) -> Option<((String, String), Visits)> {
+    let mut fields = line.rsplitn(3, ' ');
+    let last_access = fields.next()?.parse().ok()?;
+    let count = fields.next()?.parse().ok()?;
+    let (repo, feature) = fields.next()?.split_once('/')?;
+    Some((
+        (repo.to_string(), feature.to_string()),
+        Visits { count, last_access },
+    ))
+}
+
+fn write_visits(path: &Path, visits: &VisitMap) -> Result<()> {
+    let mut lines: Vec<String> = visits
+        .iter()
+        .map(|((repo, feature), v)| format!("{}/{} {} {}\n", repo, feature, v.count, v.last_access))
+        .collect();
+    lines.sort();
+    std::fs::write(path, lines.concat()).map_err(io_error("Failed to write visit history", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_visit_counts_and_sorts() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = WorktreeStorage::with_root_dir(tmp.path().to_path_buf())?;
+
+        storage.record_visit("repo", "often")?;
+        storage.record_visit("repo", "often")?;
+        storage.record_visit("repo", "once")?;
+
+        let visits = storage.visits()?;
+        let often = visits[&("repo".to_string(), "often".to_string())];
+        assert_eq!(often.count, 2);
+
+        let mut names = vec!["never", "once", "often"];
+        sort_by_frecency(&mut names, &visits, |name| ("repo", name));
+        assert_eq!(names, vec!["often", "once", "never"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_score_decays_with_age() {
+        let visits = Visits {
+            count: 4,
+            last_access: 0,
+        };
+        let day = 24 * 60 * 60;
+        assert!(visits.score(60) > visits.score(2 * 60 * 60));
+        assert!(visits.score(2 * day) > visits.score(30 * day));
+    }
+}
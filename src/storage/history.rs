@@ -0,0 +1,187 @@
+//! Per-shell navigation history for `back`.
+//!
+//! Each shell session (identified by `$WORKTREE_SESSION`, which the shell integration sets
+//! to the shell's PID) gets a stack file under `<storage root>/.history/`. `jump` pushes
+//! the directory it leaves, and `back` pops the most recent one. Only that shell writes
+//! its file, so no storage lock is needed.
+
+use std::path::{Path, PathBuf};
+
+use super::error::{Result, io_error};
+use super::lock::process_is_running;
+
+/// Directory under the storage root holding one stack file per shell session
+pub const HISTORY_DIR: &str = ".history";
+
+/// Environment variable naming the current shell session
+pub const SESSION_ENV: &str = "WORKTREE_SESSION";
+
+/// Most entries kept per session; older ones are dropped
+pub const MAX_HISTORY: usize = 50;
+
+/// Returns the current shell session from `$WORKTREE_SESSION`, if the shell integration
+/// set it. Only PIDs are accepted, since the value becomes a file name.
+#[must_use]
+pub fn session_id() -> Option<u32> {
+    std::env::var(SESSION_ENV).ok()?.trim().parse().ok()
+}
+
+/// Path of the stack file for `session`
+#[must_use]
+pub fn history_file_path(root_dir: &Path, session: u32) -> PathBuf {
+    root_dir.join(HISTORY_DIR).join(session.to_string())
+}
+
+/// Pushes `dir` onto the session's stack, unless it is already on top
+///
+/// Also removes the stacks of shells that are no longer running.
+///
+/// # Errors
+/// Returns an error if the history directory or stack file cannot be written
+pub fn push(root_dir: &Path, session: u32, dir: &Path) -> Result<()> {
+    let history_dir = root_dir.join(HISTORY_DIR);
+    std::fs::create_dir_all(&history_dir)
+        .map_err(io_error("Failed to create history directory", &history_dir))?;
+    prune_stale_sessions(&history_dir, session);
+
+    let path = history_file_path(root_dir, session);
+    let mut entries = read_entries(&path)?;
+    if entries.last().map(PathBuf::as_path) != Some(dir) {
+        entries.push(dir.to_path_buf());
+    }
+    let excess = entries.len().saturating_sub(MAX_HISTORY);
+    entries.drain(..excess);
+    write_entries(&path, &entries)
+}
+
+/// Pops entries off the session's stack until one is an existing directory other than
+/// `current_dir`, and returns it
+///
+/// # Errors
+/// Returns an error if the stack file cannot be read or written
+pub fn pop(root_dir: &Path, session: u32, current_dir: &Path) -> Result<Option<PathBuf>> {
+    let path = history_file_path(root_dir, session);
+    let mut entries = read_entries(&path)?;
+    let mut found = None;
+    while let Some(entry) = entries.pop() {
+        if entry.is_dir() && entry != current_dir {
+            found = Some(entry);
+            break;
+        }
+    }
+    write_entries(&path, &entries)?;
+    Ok(found)
+}
+
+/// Returns the most recent entry that is an existing directory other than `current_dir`,
+/// leaving the stack unchanged
+///
+/// # Errors
+/// Returns an error if the stack file exists but cannot be read
+pub fn peek(root_dir: &Path, session: u32, current_dir: &Path) -> Result<Option<PathBuf>> {
+    Ok(read_entries(&history_file_path(root_dir, session))?
+        .into_iter()
+        .rev()
+        .find(|entry| entry.is_dir() && entry != current_dir))
+}
+
+/// Returns the session's stack, oldest entry first
+///
+/// # Errors
+/// Returns an error if the stack file exists but cannot be read
+pub fn entries(root_dir: &Path, session: u32) -> Result<Vec<PathBuf>> {
+    read_entries(&history_file_path(root_dir, session))
+}
+
+fn read_entries(path: &Path) -> Result<Vec<PathBuf>> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(content
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(PathBuf::from)
+            .collect()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(io_error("Failed to read navigation history", path)(e)),
+    }
+}
+
+fn write_entries(path: &Path, entries: &[PathBuf]) -> Result<()> {
+    if entries.is_empty() {
+        return match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(io_error("Failed to remove navigation history", path)(e))
+            }
+            _ => Ok(()),
+        };
+    }
+    let content: String = entries
+        .iter()
+        .map(|entry| format!("{}\n", entry.display()))
+        .collect();
+    std::fs::write(path, content).map_err(io_error("Failed to write navigation history", path))
+}
+
+/// Best-effort removal of stack files whose shell has exited
+fn prune_stale_sessions(history_dir: &Path, current: u32) {
+    let Ok(entries) = std::fs::read_dir(history_dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let pid = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse().ok());
+        if let Some(pid) = pid {
+            if pid != current && !process_is_running(pid) {
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_push_and_pop_walk_back_in_order() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let (a, b, c) = (
+            tmp.path().join("a"),
+            tmp.path().join("b"),
+            tmp.path().join("c"),
+        );
+        for dir in [&a, &b, &c] {
+            std::fs::create_dir(dir)?;
+        }
+        let session = std::process::id();
+
+        push(tmp.path(), session, &a)?;
+        push(tmp.path(), session, &b)?;
+        push(tmp.path(), session, &b)?;
+        assert_eq!(entries(tmp.path(), session)?, vec![a.clone(), b.clone()]);
+
+        assert_eq!(pop(tmp.path(), session, &c)?, Some(b));
+        assert_eq!(pop(tmp.path(), session, &c)?, Some(a));
+        assert_eq!(pop(tmp.path(), session, &c)?, None);
+        assert!(!history_file_path(tmp.path(), session).exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_pop_skips_missing_and_current_directories() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let a = tmp.path().join("a");
+        std::fs::create_dir(&a)?;
+        let session = std::process::id();
+
+        push(tmp.path(), session, &a)?;
+        push(tmp.path(), session, &tmp.path().join("gone"))?;
+        push(tmp.path(), session, tmp.path())?;
+
+        assert_eq!(pop(tmp.path(), session, tmp.path())?, Some(a));
+        Ok(())
+    }
+}
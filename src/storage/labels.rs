@@ -0,0 +1,239 @@
+//! Free-form labels on worktrees.
+//!
+//! `.worktree-labels` in the repository's storage directory holds one
+//! `<feature>\t<label> <label>...` line per labeled worktree (feature names may contain
+//! spaces, labels may not). `worktree label add` and
+//! `label remove` edit them, and removing a worktree drops its line. `list --label` and
+//! `jump --label` filter worktrees by them: `experiment` keeps worktrees carrying the
+//! label, and `!archived` keeps those without it.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use super::WorktreeStorage;
+use super::error::{Result, io_error};
+
+/// Name of the label file in a repository's storage directory
+pub const LABELS_FILE_NAME: &str = ".worktree-labels";
+
+/// Labels of a repository's worktrees, keyed by feature name
+pub type LabelMap = BTreeMap<String, BTreeSet<String>>;
+
+/// A label a worktree must carry, or with a leading `!`, must not carry
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LabelFilter {
+    /// The label to look for
+    pub label: String,
+    /// Whether worktrees carrying the label are excluded instead
+    pub negated: bool,
+}
+
+/// Whether `label` can be stored: not empty, no whitespace, and no leading `!` (which
+/// negates a filter)
+#[must_use]
+pub fn is_valid_label(label: &str) -> bool {
+    !label.is_empty() && !label.starts_with('!') && !label.contains(char::is_whitespace)
+}
+
+impl LabelFilter {
+    /// Whether a worktree with `labels` passes this filter
+    #[must_use]
+    pub fn matches(&self, labels: &BTreeSet<String>) -> bool {
+        labels.contains(&self.label) != self.negated
+    }
+}
+
+impl FromStr for LabelFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (negated, label) = match s.strip_prefix('!') {
+            Some(label) => (true, label),
+            None => (false, s),
+        };
+        if !is_valid_label(label) {
+            return Err(format!("invalid label filter '{}'", s));
+        }
+        Ok(Self {
+            label: label.to_string(),
+            negated,
+        })
+    }
+}
+
+impl fmt::Display for LabelFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negated {
+            write!(f, "!")?;
+        }
+        write!(f, "{}", self.label)
+    }
+}
+
+/// Whether a worktree with `labels` passes every filter
+#[must_use]
+pub fn matches_all(filters: &[LabelFilter], labels: &BTreeSet<String>) -> bool {
+    filters.iter().all(|filter| filter.matches(labels))
+}
+
+impl WorktreeStorage {
+    /// Returns the labels of a repository's worktrees
+    ///
+    /// # Errors
+    /// Returns an error if the label file exists but cannot be read
+    pub fn list_labels(&self, repo_name: &str) -> Result<LabelMap> {
+        read_labels(&self.get_repo_storage_dir(repo_name).join(LABELS_FILE_NAME))
+    }
+
+    /// Adds a label to a worktree. Returns false if the worktree already had it.
+    ///
+    /// # Errors
+    /// Returns an error if the storage lock cannot be acquired or the label file cannot
+    /// be read or written
+    pub fn add_label(&self, repo_name: &str, feature_name: &str, label: &str) -> Result<bool> {
+        let repo_dir = self.get_repo_storage_dir(repo_name);
+        std::fs::create_dir_all(&repo_dir)
+            .map_err(io_error("Failed to create storage directory", &repo_dir))?;
+
+        let _lock = self.lock()?;
+        let path = repo_dir.join(LABELS_FILE_NAME);
+        let mut labels = read_labels(&path)?;
+        let added = labels
+            .entry(feature_name.to_string())
+            .or_default()
+            .insert(label.to_string());
+        if added {
+            write_labels(&path, &labels)?;
+            self.refresh_meta(repo_name, feature_name);
+        }
+        Ok(added)
+    }
+
+    /// Removes a label from a worktree. Returns false if the worktree did not have it.
+    ///
+    /// # Errors
+    /// Returns an error if the storage lock cannot be acquired or the label file cannot
+    /// be read or written
+    pub fn remove_label(&self, repo_name: &str, feature_name: &str, label: &str) -> Result<bool> {
+        let path = self.get_repo_storage_dir(repo_name).join(LABELS_FILE_NAME);
+        if !path.exists() {
+            return Ok(false);
+        }
+
+        let _lock = self.lock()?;
+        let mut labels = read_labels(&path)?;
+        let Some(worktree_labels) = labels.get_mut(feature_name) else {
+            return Ok(false);
+        };
+        let removed = worktree_labels.remove(label);
+        if worktree_labels.is_empty() {
+            labels.remove(feature_name);
+        }
+        if removed {
+            write_labels(&path, &labels)?;
+            self.refresh_meta(repo_name, feature_name);
+        }
+        Ok(removed)
+    }
+
+    /// Drops all labels of a worktree
+    ///
+    /// # Errors
+    /// Returns an error if the storage lock cannot be acquired or the label file cannot
+    /// be read or written
+    pub fn forget_labels(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        let path = self.get_repo_storage_dir(repo_name).join(LABELS_FILE_NAME);
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let _lock = self.lock()?;
+        let mut labels = read_labels(&path)?;
+        if labels.remove(feature_name).is_some() {
+            write_labels(&path, &labels)?;
+        }
+        Ok(())
+    }
+}
+
+fn read_labels(path: &Path) -> Result<LabelMap> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(LabelMap::new()),
+        Err(e) => return Err(io_error("Failed to read worktree labels", path)(e)),
+    };
+    Ok(content
+        .lines()
+        .filter_map(|line| {
+            let (feature, labels) = line.split_once('\t')?;
+            Some((
+                feature.to_string(),
+                labels.split_whitespace().map(str::to_string).collect(),
+            ))
+        })
+        .collect())
+}
+
+/// Replaces the label file atomically: write to a temp file, then rename
+fn write_labels(path: &Path, labels: &LabelMap) -> Result<()> {
+    let content: String = labels
+        .iter()
+        .filter(|(_, worktree_labels)| !worktree_labels.is_empty())
+        .map(|(feature, worktree_labels)| {
+            let worktree_labels: Vec<&str> = worktree_labels.iter().map(String::as_str).collect();
+            format!("{}\t{}\n", feature, worktree_labels.join(" "))
+        })
+        .collect();
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, content)
+        .map_err(io_error("Failed to write worktree labels", &tmp_path))?;
+    std::fs::rename(&tmp_path, path).map_err(io_error("Failed to write worktree labels", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_add_remove_and_forget_labels() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = WorktreeStorage::with_root_dir(tmp.path().to_path_buf())?;
+
+        assert!(storage.add_label("repo", "auth", "review")?);
+        assert!(!storage.add_label("repo", "auth", "review")?);
+        storage.add_label("repo", "auth", "client-x")?;
+        storage.add_label("repo", "billing v2", "review")?;
+        assert_eq!(
+            storage.list_labels("repo")?["auth"],
+            ["client-x".to_string(), "review".to_string()].into()
+        );
+
+        assert!(storage.list_labels("repo")?.contains_key("billing v2"));
+        assert!(storage.remove_label("repo", "billing v2", "review")?);
+        assert!(!storage.remove_label("repo", "billing v2", "review")?);
+        assert!(!storage.list_labels("repo")?.contains_key("billing v2"));
+
+        storage.forget_labels("repo", "auth")?;
+        assert!(storage.list_labels("repo")?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_label_filter() -> Result<()> {
+        let labels: BTreeSet<String> = ["experiment".to_string()].into();
+        let wanted: LabelFilter = "experiment".parse().map_err(anyhow::Error::msg)?;
+        let unwanted: LabelFilter = "!archived".parse().map_err(anyhow::Error::msg)?;
+        assert!(wanted.matches(&labels));
+        assert!(unwanted.matches(&labels));
+        assert!(!wanted.matches(&BTreeSet::new()));
+        assert_eq!(unwanted.to_string(), "!archived");
+        assert!(matches_all(&[wanted, unwanted], &labels));
+        assert!("!".parse::<LabelFilter>().is_err());
+        Ok(())
+    }
+}
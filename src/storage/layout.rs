@@ -0,0 +1,149 @@
+//! Where a repository's worktrees live.
+//!
+//! By default a repository's worktrees sit in its storage directory,
+//! `<root>/<repo>/<feature>`. With `layout = "sibling"` in `.worktree-config.toml`, `create`
+//! puts them in `<repo>-worktrees/` next to the main repository instead, and records that
+//! directory in `.worktree-location` in the repository's storage directory. All metadata
+//! stays in the storage directory either way; only the worktree directories move.
+
+use std::path::{Path, PathBuf};
+
+use super::error::{Result, io_error};
+use super::{BARE_REPO_DIR, WorktreeStorage};
+
+/// Name of the file recording a repository's worktree directory, when it is not the
+/// storage directory
+pub const LOCATION_FILE_NAME: &str = ".worktree-location";
+
+/// The `<repo>-worktrees` directory next to the main repository at `repo_path`. For a
+/// bare clone (`<repo>/.bare`) it sits next to `<repo>`.
+#[must_use]
+pub fn sibling_worktree_dir(repo_path: &Path, repo_name: &str) -> PathBuf {
+    let repo_dir = if repo_path
+        .file_name()
+        .is_some_and(|name| name == BARE_REPO_DIR)
+    {
+        repo_path.parent().unwrap_or(repo_path)
+    } else {
+        repo_path
+    };
+    repo_dir
+        .parent()
+        .unwrap_or(repo_dir)
+        .join(format!("{}-worktrees", repo_name))
+}
+
+impl WorktreeStorage {
+    /// Directory holding the repository's worktrees: the one recorded in
+    /// `.worktree-location`, else its storage directory
+    #[must_use]
+    pub fn worktree_dir(&self, repo_name: &str) -> PathBuf {
+        let repo_dir = self.get_repo_storage_dir(repo_name);
+        std::fs::read_to_string(repo_dir.join(LOCATION_FILE_NAME))
+            .ok()
+            .map(|content| content.trim().to_string())
+            .filter(|location| !location.is_empty())
+            .map_or(repo_dir, PathBuf::from)
+    }
+
+    /// Records where the repository's worktrees go; `None` puts them back in its storage
+    /// directory. Existing worktrees are not moved.
+    ///
+    /// # Errors
+    /// Returns an error if the storage lock cannot be acquired or the location file
+    /// cannot be written or removed
+    pub fn set_worktree_dir(&self, repo_name: &str, dir: Option<&Path>) -> Result<()> {
+        let repo_dir = self.get_repo_storage_dir(repo_name);
+        std::fs::create_dir_all(&repo_dir)
+            .map_err(io_error("Failed to create storage directory", &repo_dir))?;
+
+        let _lock = self.lock()?;
+        let path = repo_dir.join(LOCATION_FILE_NAME);
+        match dir {
+            Some(dir) => std::fs::write(&path, format!("{}\n", dir.display()))
+                .map_err(io_error("Failed to write worktree location", &path)),
+            None => match std::fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    Err(io_error("Failed to remove worktree location", &path)(e))
+                }
+                _ => Ok(()),
+            },
+        }
+    }
+
+    /// Finds the managed worktree containing `path`, as `(repo, feature)`, in the
+    /// storage root or in a repository's recorded worktree directory
+    #[must_use]
+    pub fn locate_worktree(&self, path: &Path) -> Option<(String, String)> {
+        let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let path = canonical(path);
+        let first_two = |relative: &Path| {
+            let mut components = relative.components();
+            let first = components.next()?.as_os_str().to_string_lossy().to_string();
+            let second = components.next()?.as_os_str().to_string_lossy().to_string();
+            Some((first, second))
+        };
+
+        if let Ok(relative) = path.strip_prefix(canonical(self.get_root_dir())) {
+            if let Some((repo_name, feature_name)) = first_two(relative) {
+                if !self
+                    .get_repo_storage_dir(&repo_name)
+                    .join(LOCATION_FILE_NAME)
+                    .exists()
+                {
+                    return Some((repo_name, feature_name));
+                }
+            }
+        }
+
+        let entries = std::fs::read_dir(self.get_root_dir()).ok()?;
+        entries.flatten().find_map(|entry| {
+            let repo_name = entry.file_name().to_str()?.to_string();
+            if repo_name.starts_with('.') || !entry.path().join(LOCATION_FILE_NAME).exists() {
+                return None;
+            }
+            let relative = path
+                .strip_prefix(canonical(&self.worktree_dir(&repo_name)))
+                .ok()?;
+            let feature_name = relative.components().next()?.as_os_str().to_string_lossy();
+            Some((repo_name, feature_name.to_string()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_recorded_worktree_dir_is_used_for_paths_and_lookup() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = WorktreeStorage::with_root_dir(tmp.path().join("root"))?;
+        let sibling = sibling_worktree_dir(&tmp.path().join("app"), "app");
+        assert_eq!(sibling, tmp.path().join("app-worktrees"));
+        assert_eq!(
+            sibling_worktree_dir(&tmp.path().join("app").join(BARE_REPO_DIR), "app"),
+            sibling
+        );
+
+        storage.set_worktree_dir("app", Some(&sibling))?;
+        let worktree = storage.get_worktree_path("app", "auth");
+        assert_eq!(worktree, sibling.join("auth"));
+        std::fs::create_dir_all(worktree.join("src"))?;
+        assert_eq!(storage.list_repo_worktrees("app")?, ["auth"]);
+        assert_eq!(
+            storage.locate_worktree(&worktree.join("src")),
+            Some(("app".to_string(), "auth".to_string()))
+        );
+
+        storage.set_worktree_dir("app", None)?;
+        assert_eq!(
+            storage.get_worktree_path("app", "auth"),
+            storage.get_repo_storage_dir("app").join("auth")
+        );
+        assert_eq!(storage.locate_worktree(&worktree), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,279 @@
+//! Advisory locking for storage metadata.
+//!
+//! Metadata files under the storage root (e.g. `.worktree-origins`) are updated with a
+//! read-modify-write cycle. A lock file in the storage root serializes those updates
+//! across concurrent `worktree` processes.
+
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use super::error::{Result, StorageError, io_error};
+use super::unix_now;
+
+/// Name of the lock file created in the storage root
+pub const LOCK_FILE_NAME: &str = ".worktree.lock";
+
+/// How long to wait for the lock when no timeout has been configured
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+static LOCK_TIMEOUT: OnceLock<Duration> = OnceLock::new();
+
+/// Sets the process-wide lock timeout (from `--lock-timeout`).
+/// Only the first call has an effect.
+pub fn set_lock_timeout(timeout: Duration) {
+    let _ = LOCK_TIMEOUT.set(timeout);
+}
+
+/// Returns the configured lock timeout, or [`DEFAULT_LOCK_TIMEOUT`]
+#[must_use]
+pub fn lock_timeout() -> Duration {
+    LOCK_TIMEOUT.get().copied().unwrap_or(DEFAULT_LOCK_TIMEOUT)
+}
+
+/// Information recorded in the lock file by the process holding it
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LockInfo {
+    /// Process ID of the lock holder
+    pub pid: u32,
+    /// Seconds since the Unix epoch when the lock was acquired
+    pub acquired_at: u64,
+}
+
+impl LockInfo {
+    fn current() -> Self {
+        Self {
+            pid: std::process::id(),
+            acquired_at: unix_now(),
+        }
+    }
+
+    fn parse(content: &str) -> Option<Self> {
+        let mut pid = None;
+        let mut acquired_at = None;
+
+        for line in content.lines() {
+            if let Some((key, value)) = line.split_once('=') {
+                match key.trim() {
+                    "pid" => pid = value.trim().parse().ok(),
+                    "acquired" => acquired_at = value.trim().parse().ok(),
+                    _ => {}
+                }
+            }
+        }
+
+        Some(Self {
+            pid: pid?,
+            acquired_at: acquired_at.unwrap_or(0),
+        })
+    }
+
+    fn serialize(&self) -> String {
+        format!("pid={}\nacquired={}\n", self.pid, self.acquired_at)
+    }
+
+    /// Returns true if the process holding the lock is still running
+    #[must_use]
+    pub fn is_holder_running(&self) -> bool {
+        process_is_running(self.pid)
+    }
+
+    /// Seconds elapsed since the lock was acquired
+    #[must_use]
+    pub fn age_secs(&self) -> u64 {
+        unix_now().saturating_sub(self.acquired_at)
+    }
+}
+
+/// Guard for the storage lock. The lock file is removed when the guard is dropped.
+#[derive(Debug)]
+pub struct StorageLock {
+    path: PathBuf,
+}
+
+impl StorageLock {
+    /// Acquires the lock in `root_dir`, waiting up to `timeout` for another holder to release it
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The lock is still held by another process after `timeout`
+    /// - The lock file cannot be created
+    pub fn acquire(root_dir: &Path, timeout: Duration) -> Result<Self> {
+        let path = lock_file_path(root_dir);
+        let start = Instant::now();
+
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    let guard = Self { path };
+                    file.write_all(LockInfo::current().serialize().as_bytes())
+                        .map_err(io_error("Failed to write lock file", &guard.path))?;
+                    return Ok(guard);
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    if start.elapsed() >= timeout {
+                        return Err(locked_error(root_dir, timeout));
+                    }
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+                Err(e) => return Err(io_error("Failed to create lock file", &path)(e)),
+            }
+        }
+    }
+}
+
+impl Drop for StorageLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Returns the path of the lock file for a storage root
+#[must_use]
+pub fn lock_file_path(root_dir: &Path) -> PathBuf {
+    root_dir.join(LOCK_FILE_NAME)
+}
+
+/// Reads the current lock holder, if any.
+///
+/// Returns `Ok(None)` when no lock file exists. A lock file that exists but cannot be
+/// parsed (e.g. caught mid-write) is reported with PID 0.
+///
+/// # Errors
+/// Returns an error if the lock file exists but cannot be read
+pub fn read_lock_info(root_dir: &Path) -> Result<Option<LockInfo>> {
+    let path = lock_file_path(root_dir);
+    match std::fs::read_to_string(&path) {
+        Ok(content) => Ok(Some(LockInfo::parse(&content).unwrap_or(LockInfo {
+            pid: 0,
+            acquired_at: 0,
+        }))),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(io_error("Failed to read lock file", &path)(e)),
+    }
+}
+
+/// Removes the lock file regardless of who holds it.
+/// Returns true if a lock file was removed.
+///
+/// # Errors
+/// Returns an error if the lock file exists but cannot be removed
+pub fn break_lock(root_dir: &Path) -> Result<bool> {
+    let path = lock_file_path(root_dir);
+    match std::fs::remove_file(&path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(io_error("Failed to remove lock file", &path)(e)),
+    }
+}
+
+fn locked_error(root_dir: &Path, timeout: Duration) -> StorageError {
+    let holder = match read_lock_info(root_dir) {
+        Ok(Some(info)) if info.pid != 0 => format!("PID {}", info.pid),
+        _ => "another process".to_string(),
+    };
+
+    StorageError::Locked {
+        holder,
+        waited_secs: timeout.as_secs(),
+    }
+}
+
+/// Whether a process with `pid` is alive (`kill(pid, 0)`). A process owned by another
+/// user (`EPERM`) is alive; only `ESRCH` means it is gone.
+#[must_use]
+pub fn process_is_running(pid: u32) -> bool {
+    if pid == 0 {
+        return false;
+    }
+    if pid == std::process::id() {
+        return true;
+    }
+    // A pid beyond `pid_t` would wrap negative and address a process group
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+
+    // SAFETY: signal 0 delivers nothing; the kernel only checks that the process exists
+    if unsafe { libc::kill(pid, 0) } == 0 {
+        return true;
+    }
+    std::io::Error::last_os_error().raw_os_error() != Some(libc::ESRCH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_acquire_and_release() -> Result<()> {
+        let tmp = TempDir::new()?;
+        {
+            let _lock = StorageLock::acquire(tmp.path(), Duration::ZERO)?;
+            assert!(lock_file_path(tmp.path()).exists());
+
+            let info = read_lock_info(tmp.path())?;
+            assert_eq!(info.map(|i| i.pid), Some(std::process::id()));
+        }
+        assert!(!lock_file_path(tmp.path()).exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_acquire_times_out_when_held() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let _lock = StorageLock::acquire(tmp.path(), Duration::ZERO)?;
+
+        let result = StorageLock::acquire(tmp.path(), Duration::from_millis(100));
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert!(matches!(e, StorageError::Locked { .. }));
+            let msg = e.to_string();
+            assert!(msg.contains(&format!("PID {}", std::process::id())));
+            assert!(msg.contains("lock-status"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_lock_info_roundtrip() {
+        let info = LockInfo {
+            pid: 42,
+            acquired_at: 1_700_000_000,
+        };
+        assert_eq!(LockInfo::parse(&info.serialize()), Some(info));
+        assert_eq!(LockInfo::parse("garbage"), None);
+    }
+
+    #[test]
+    fn test_break_lock() -> Result<()> {
+        let tmp = TempDir::new()?;
+        assert!(!break_lock(tmp.path())?);
+
+        std::fs::write(lock_file_path(tmp.path()), "pid=1\nacquired=0\n")?;
+        assert!(break_lock(tmp.path())?);
+        assert!(read_lock_info(tmp.path())?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_is_running() -> Result<()> {
+        assert!(process_is_running(std::process::id()));
+        // init is alive whether or not we may signal it (EPERM for non-root users)
+        assert!(process_is_running(1));
+        assert!(!process_is_running(0));
+
+        let mut child = std::process::Command::new("true").spawn()?;
+        let pid = child.id();
+        child.wait()?;
+        assert!(!process_is_running(pid));
+        Ok(())
+    }
+}
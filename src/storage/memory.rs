@@ -0,0 +1,334 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use super::error::Result;
+use super::slots::{SlotMap, lowest_free_slot};
+use crate::traits::StorageOperations;
+
+/// In-memory [`StorageOperations`] for tests: worktrees, origins, slots, creations,
+/// labels, notes, copied files, created branches, and worktree directories are recorded in maps and nothing touches the filesystem. Paths are
+/// computed under a root that need not exist.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    root_dir: PathBuf,
+    worktrees: RefCell<BTreeMap<String, BTreeSet<String>>>,
+    origins: RefCell<BTreeMap<String, Vec<(String, String)>>>,
+    slots: RefCell<BTreeMap<String, SlotMap>>,
+    created: RefCell<BTreeSet<(String, String)>>,
+    labels: RefCell<BTreeMap<(String, String), BTreeSet<String>>>,
+    notes: RefCell<BTreeMap<(String, String), String>>,
+    copied: RefCell<BTreeMap<(String, String), Vec<PathBuf>>>,
+    managed_branches: RefCell<BTreeSet<(String, String)>>,
+    worktree_dirs: RefCell<BTreeMap<String, PathBuf>>,
+}
+
+impl MemoryStorage {
+    /// Creates an empty storage whose paths are computed under `root_dir`
+    #[must_use]
+    pub fn new(root_dir: PathBuf) -> Self {
+        Self {
+            root_dir,
+            ..Self::default()
+        }
+    }
+
+    /// Marks `feature_name` as a managed worktree of the repository
+    pub fn add_worktree(&self, repo_name: &str, feature_name: &str) {
+        self.worktrees
+            .borrow_mut()
+            .entry(repo_name.to_string())
+            .or_default()
+            .insert(feature_name.to_string());
+    }
+
+    /// Returns true if the creation of the worktree was recorded and not forgotten since
+    #[must_use]
+    pub fn is_recorded_created(&self, repo_name: &str, feature_name: &str) -> bool {
+        self.created
+            .borrow()
+            .contains(&(repo_name.to_string(), feature_name.to_string()))
+    }
+
+    /// Labels a worktree
+    pub fn add_label(&self, repo_name: &str, feature_name: &str, label: &str) {
+        self.labels
+            .borrow_mut()
+            .entry((repo_name.to_string(), feature_name.to_string()))
+            .or_default()
+            .insert(label.to_string());
+    }
+
+    /// Returns the worktree's labels
+    #[must_use]
+    pub fn labels(&self, repo_name: &str, feature_name: &str) -> BTreeSet<String> {
+        self.labels
+            .borrow()
+            .get(&(repo_name.to_string(), feature_name.to_string()))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Attaches a note to a worktree
+    pub fn set_note(&self, repo_name: &str, feature_name: &str, note: &str) {
+        self.notes.borrow_mut().insert(
+            (repo_name.to_string(), feature_name.to_string()),
+            note.to_string(),
+        );
+    }
+
+    /// Returns the worktree's note, if it has one
+    #[must_use]
+    pub fn note(&self, repo_name: &str, feature_name: &str) -> Option<String> {
+        self.notes
+            .borrow()
+            .get(&(repo_name.to_string(), feature_name.to_string()))
+            .cloned()
+    }
+
+    /// Returns the files recorded as copied into the worktree, if any were recorded
+    #[must_use]
+    pub fn copied_files(&self, repo_name: &str, feature_name: &str) -> Option<Vec<PathBuf>> {
+        self.copied
+            .borrow()
+            .get(&(repo_name.to_string(), feature_name.to_string()))
+            .cloned()
+    }
+
+    /// Returns true if `branch` is recorded as created by this tool
+    #[must_use]
+    pub fn is_managed_branch(&self, repo_name: &str, branch: &str) -> bool {
+        self.managed_branches
+            .borrow()
+            .contains(&(repo_name.to_string(), branch.to_string()))
+    }
+
+    /// Forgets a managed worktree; its origin entry is kept
+    pub fn remove_worktree(&self, repo_name: &str, feature_name: &str) {
+        if let Some(features) = self.worktrees.borrow_mut().get_mut(repo_name) {
+            features.remove(feature_name);
+        }
+    }
+}
+
+impl StorageOperations for MemoryStorage {
+    fn get_root_dir(&self) -> &Path {
+        &self.root_dir
+    }
+
+    fn get_worktree_path(&self, repo_name: &str, feature_name: &str) -> PathBuf {
+        self.worktree_dir(repo_name).join(feature_name)
+    }
+
+    fn get_repo_storage_dir(&self, repo_name: &str) -> PathBuf {
+        self.root_dir.join(repo_name)
+    }
+
+    fn is_managed(&self, repo_name: &str, feature_name: &str) -> bool {
+        self.worktrees
+            .borrow()
+            .get(repo_name)
+            .is_some_and(|features| features.contains(feature_name))
+    }
+
+    fn list_repo_worktrees(&self, repo_name: &str) -> Result<Vec<String>> {
+        Ok(self
+            .worktrees
+            .borrow()
+            .get(repo_name)
+            .map(|features| features.iter().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    fn list_all_worktrees(&self) -> Result<Vec<(String, Vec<String>)>> {
+        Ok(self
+            .worktrees
+            .borrow()
+            .iter()
+            .map(|(repo, features)| (repo.clone(), features.iter().cloned().collect()))
+            .collect())
+    }
+
+    fn store_worktree_origin(
+        &self,
+        repo_name: &str,
+        feature_name: &str,
+        origin_path: &str,
+    ) -> Result<()> {
+        let mut origins = self.origins.borrow_mut();
+        let entries = origins.entry(repo_name.to_string()).or_default();
+        let entry = (feature_name.to_string(), origin_path.to_string());
+        if !entries.contains(&entry) {
+            entries.push(entry);
+        }
+        Ok(())
+    }
+
+    fn set_worktree_origin(
+        &self,
+        repo_name: &str,
+        feature_name: &str,
+        origin_path: &str,
+    ) -> Result<()> {
+        self.remove_worktree_origin(repo_name, feature_name)?;
+        self.store_worktree_origin(repo_name, feature_name, origin_path)
+    }
+
+    fn get_worktree_origin(&self, repo_name: &str, feature_name: &str) -> Result<Option<String>> {
+        Ok(self
+            .list_worktree_origins(repo_name)?
+            .into_iter()
+            .find(|(key, _)| key == feature_name)
+            .map(|(_, origin)| origin))
+    }
+
+    fn list_worktree_origins(&self, repo_name: &str) -> Result<Vec<(String, String)>> {
+        Ok(self
+            .origins
+            .borrow()
+            .get(repo_name)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    fn remove_worktree_origin(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        if let Some(entries) = self.origins.borrow_mut().get_mut(repo_name) {
+            entries.retain(|(key, _)| key != feature_name);
+        }
+        Ok(())
+    }
+
+    fn allocate_slot(&self, repo_name: &str, feature_name: &str) -> Result<u32> {
+        let mut slots = self.slots.borrow_mut();
+        let slots = slots.entry(repo_name.to_string()).or_default();
+        if let Some(slot) = slots.get(feature_name) {
+            return Ok(*slot);
+        }
+        let slot = lowest_free_slot(slots);
+        slots.insert(feature_name.to_string(), slot);
+        Ok(slot)
+    }
+
+    fn get_slot(&self, repo_name: &str, feature_name: &str) -> Result<Option<u32>> {
+        Ok(self
+            .slots
+            .borrow()
+            .get(repo_name)
+            .and_then(|slots| slots.get(feature_name).copied()))
+    }
+
+    fn release_slot(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        if let Some(slots) = self.slots.borrow_mut().get_mut(repo_name) {
+            slots.remove(feature_name);
+        }
+        Ok(())
+    }
+
+    fn record_created(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        self.created
+            .borrow_mut()
+            .insert((repo_name.to_string(), feature_name.to_string()));
+        Ok(())
+    }
+
+    fn forget_activity(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        self.created
+            .borrow_mut()
+            .remove(&(repo_name.to_string(), feature_name.to_string()));
+        Ok(())
+    }
+
+    fn forget_labels(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        self.labels
+            .borrow_mut()
+            .remove(&(repo_name.to_string(), feature_name.to_string()));
+        Ok(())
+    }
+
+    fn forget_note(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        self.notes
+            .borrow_mut()
+            .remove(&(repo_name.to_string(), feature_name.to_string()));
+        Ok(())
+    }
+
+    fn record_copied_files(
+        &self,
+        repo_name: &str,
+        feature_name: &str,
+        files: &[PathBuf],
+    ) -> Result<()> {
+        self.copied.borrow_mut().insert(
+            (repo_name.to_string(), feature_name.to_string()),
+            files.to_vec(),
+        );
+        Ok(())
+    }
+
+    fn forget_copied_files(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        self.copied
+            .borrow_mut()
+            .remove(&(repo_name.to_string(), feature_name.to_string()));
+        Ok(())
+    }
+
+    fn record_managed_branch(&self, repo_name: &str, branch: &str) -> Result<()> {
+        self.managed_branches
+            .borrow_mut()
+            .insert((repo_name.to_string(), branch.to_string()));
+        Ok(())
+    }
+
+    fn forget_managed_branch(&self, repo_name: &str, branch: &str) -> Result<()> {
+        self.managed_branches
+            .borrow_mut()
+            .remove(&(repo_name.to_string(), branch.to_string()));
+        Ok(())
+    }
+
+    /// Everything lives in memory here, so there is no file to write
+    fn write_worktree_meta(&self, _repo_name: &str, _feature_name: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn worktree_dir(&self, repo_name: &str) -> PathBuf {
+        self.worktree_dirs
+            .borrow()
+            .get(repo_name)
+            .cloned()
+            .unwrap_or_else(|| self.root_dir.join(repo_name))
+    }
+
+    fn set_worktree_dir(&self, repo_name: &str, dir: Option<&Path>) -> Result<()> {
+        let mut dirs = self.worktree_dirs.borrow_mut();
+        match dir {
+            Some(dir) => dirs.insert(repo_name.to_string(), dir.to_path_buf()),
+            None => dirs.remove(repo_name),
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_origins_match_filesystem_semantics() -> Result<()> {
+        let storage = MemoryStorage::new(PathBuf::from("/worktrees"));
+        storage.store_worktree_origin("repo", "feat", "/src/repo")?;
+        storage.store_worktree_origin("repo", "feat", "/src/repo")?;
+        assert_eq!(storage.list_worktree_origins("repo")?.len(), 1);
+
+        storage.set_worktree_origin("repo", "feat", "/moved/repo")?;
+        assert_eq!(
+            storage.get_worktree_origin("repo", "feat")?.as_deref(),
+            Some("/moved/repo")
+        );
+
+        storage.remove_worktree_origin("repo", "feat")?;
+        assert_eq!(storage.get_worktree_origin("repo", "feat")?, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,209 @@
+//! Per-worktree metadata.
+//!
+//! `.worktree-meta.toml` at the root of each worktree repeats what the repository's
+//! shared metadata files record about it: its branch, origin repository, creation time,
+//! whether `create` made the branch, and its labels. The file moves with the directory,
+//! and when a shared file loses the worktree's entry, origins and creation times are
+//! read from it instead. It is rewritten whenever `create`, `label`, or `repair` change
+//! that data, and listed in the repository's `info/exclude` so git ignores it.
+//!
+//! Worktrees created before the file existed have none; [`WorktreeStorage::worktree_meta`]
+//! then reads the shared files, as before.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+use tracing::warn;
+
+use super::error::{Result, StorageError, io_error};
+use super::{WorktreeStorage, read_worktree_head_branch};
+
+/// Name of the metadata file at the root of each worktree
+pub const META_FILE_NAME: &str = ".worktree-meta.toml";
+
+/// What is known about one worktree
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct WorktreeMeta {
+    /// Branch checked out in the worktree
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Main repository the worktree was created from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+    /// When the worktree was created, in seconds since the Unix epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<u64>,
+    /// Whether `create` made the branch, rather than the worktree adopting it
+    #[serde(default)]
+    pub managed: bool,
+    /// Labels of the worktree
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub labels: BTreeSet<String>,
+}
+
+impl WorktreeStorage {
+    /// Returns the worktree's metadata: its `.worktree-meta.toml`, or for worktrees
+    /// without one, the same data gathered from the shared metadata files
+    ///
+    /// # Errors
+    /// Returns an error if the metadata file or a shared file cannot be read or parsed
+    pub fn worktree_meta(&self, repo_name: &str, feature_name: &str) -> Result<WorktreeMeta> {
+        let path = self
+            .get_worktree_path(repo_name, feature_name)
+            .join(META_FILE_NAME);
+        match read_meta(&path)? {
+            Some(meta) => Ok(meta),
+            None => self.shared_meta(repo_name, feature_name),
+        }
+    }
+
+    /// Rewrites the worktree's `.worktree-meta.toml` from the shared metadata files and
+    /// its checked-out branch. Does nothing if the worktree directory does not exist.
+    ///
+    /// # Errors
+    /// Returns an error if the shared files cannot be read or the metadata file cannot
+    /// be written
+    pub fn write_worktree_meta(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        let worktree_path = self.get_worktree_path(repo_name, feature_name);
+        if !worktree_path.is_dir() {
+            return Ok(());
+        }
+
+        let meta = self.shared_meta(repo_name, feature_name)?;
+        let content = toml::to_string(&meta).map_err(|e| StorageError::Corrupt {
+            path: worktree_path.join(META_FILE_NAME),
+            reason: e.to_string(),
+        })?;
+        let path = worktree_path.join(META_FILE_NAME);
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, content)
+            .map_err(io_error("Failed to write worktree metadata", &tmp_path))?;
+        std::fs::rename(&tmp_path, &path)
+            .map_err(io_error("Failed to write worktree metadata", &path))?;
+        exclude_from_git(&worktree_path);
+        Ok(())
+    }
+
+    /// Rewrites the worktree's metadata file after a shared file changed. Failures only
+    /// warn: the shared files remain the primary record.
+    pub(crate) fn refresh_meta(&self, repo_name: &str, feature_name: &str) {
+        if let Err(e) = self.write_worktree_meta(repo_name, feature_name) {
+            warn!("Failed to update metadata of {}: {}", feature_name, e);
+        }
+    }
+
+    /// The worktree's metadata file, if it has a readable one. Used as the fallback when
+    /// a shared file has no entry for the worktree.
+    pub(crate) fn recorded_meta(
+        &self,
+        repo_name: &str,
+        feature_name: &str,
+    ) -> Option<WorktreeMeta> {
+        read_meta(
+            &self
+                .get_worktree_path(repo_name, feature_name)
+                .join(META_FILE_NAME),
+        )
+        .ok()
+        .flatten()
+    }
+
+    fn shared_meta(&self, repo_name: &str, feature_name: &str) -> Result<WorktreeMeta> {
+        let branch = read_worktree_head_branch(&self.get_worktree_path(repo_name, feature_name));
+        let managed = match &branch {
+            Some(branch) => self.managed_branches(repo_name)?.contains(branch),
+            None => false,
+        };
+        Ok(WorktreeMeta {
+            branch,
+            origin: self.get_worktree_origin(repo_name, feature_name)?,
+            created_at: self.worktree_activity(repo_name, feature_name).created,
+            managed,
+            labels: self
+                .list_labels(repo_name)?
+                .remove(feature_name)
+                .unwrap_or_default(),
+        })
+    }
+}
+
+fn read_meta(path: &Path) -> Result<Option<WorktreeMeta>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(io_error("Failed to read worktree metadata", path)(e)),
+    };
+    toml::from_str(&content)
+        .map(Some)
+        .map_err(|e| StorageError::Corrupt {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })
+}
+
+/// Lists the metadata file in the repository's `info/exclude`, which git applies to all
+/// of its worktrees, so `status` and the dirty checks ignore it. The worktree's own git
+/// directory gets the entry too, since gix reads `info/exclude` from there. Failures are
+/// ignored: the file then merely shows up as untracked.
+fn exclude_from_git(worktree_path: &Path) {
+    let Ok(repo) = git2::Repository::open(worktree_path) else {
+        return;
+    };
+    add_exclude(&repo.commondir().join("info").join("exclude"));
+    add_exclude(&repo.path().join("info").join("exclude"));
+}
+
+fn add_exclude(exclude: &Path) {
+    let entry = format!("/{}", META_FILE_NAME);
+    let content = std::fs::read_to_string(exclude).unwrap_or_default();
+    if content.lines().any(|line| line == entry) {
+        return;
+    }
+    let separator = if content.is_empty() || content.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    if let Some(parent) = exclude.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let _ = std::fs::write(exclude, format!("{}{}{}\n", content, separator, entry));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_meta_round_trips_and_falls_back_to_shared_files() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = WorktreeStorage::with_root_dir(tmp.path().to_path_buf())?;
+        std::fs::create_dir_all(storage.get_worktree_path("repo", "auth"))?;
+        storage.store_worktree_origin("repo", "auth", "/src/repo")?;
+        storage.add_label("repo", "auth", "review")?;
+
+        // add_label wrote the file; without it the shared files give the same answer
+        let written = storage.worktree_meta("repo", "auth")?;
+        assert_eq!(written.origin.as_deref(), Some("/src/repo"));
+        assert_eq!(written.labels, BTreeSet::from(["review".to_string()]));
+        std::fs::remove_file(
+            storage
+                .get_worktree_path("repo", "auth")
+                .join(META_FILE_NAME),
+        )?;
+        assert_eq!(storage.worktree_meta("repo", "auth")?, written);
+
+        // The file keeps the origin when the shared entry is lost
+        storage.write_worktree_meta("repo", "auth")?;
+        storage.remove_worktree_origin("repo", "auth")?;
+        assert_eq!(
+            storage.get_worktree_origin("repo", "auth")?.as_deref(),
+            Some("/src/repo")
+        );
+        Ok(())
+    }
+}
@@ -1,6 +1,37 @@
-use anyhow::{Context, Result};
+pub mod activity;
+pub mod branches;
+pub mod completion_cache;
+pub mod copied;
+mod error;
+pub mod frecency;
+pub mod history;
+pub mod labels;
+pub mod layout;
+pub mod lock;
+mod memory;
+pub mod meta;
+pub mod notes;
+pub mod pr_cache;
+pub mod sizes;
+pub mod slots;
+pub mod version;
+
 use std::path::{Path, PathBuf};
 
+use error::io_error;
+pub use error::{Result, StorageError};
+use lock::StorageLock;
+pub use memory::MemoryStorage;
+use tracing::{debug, warn};
+
+use crate::config::GlobalConfig;
+use crate::traits::StorageOperations;
+
+/// Directory under a repository's storage folder that holds the bare clone made by
+/// `worktree clone`. Hidden, so it is never listed as a worktree.
+pub const BARE_REPO_DIR: &str = ".bare";
+
+#[derive(Debug, Clone)]
 pub struct WorktreeStorage {
     root_dir: PathBuf,
 }
@@ -8,36 +39,74 @@ pub struct WorktreeStorage {
 impl WorktreeStorage {
     /// Creates a new WorktreeStorage instance
     ///
+    /// The storage root is resolved in order from `$WORKTREE_STORAGE_ROOT`, `storage-root`
+    /// in the global config, an existing `~/.worktrees`, `$XDG_DATA_HOME/worktree`
+    /// (default `~/.local/share/worktree`), `~/.worktrees`, and finally `./.worktrees`
+    /// (with a warning). An existing directory is used as is, so read-only commands never
+    /// write to the root; a missing one is created and checked for writability once.
+    ///
     /// # Errors
     /// Returns an error if:
-    /// - Failed to determine home directory
-    /// - Failed to create storage directory
+    /// - `$WORKTREE_STORAGE_ROOT` or `storage-root` is set but is neither an existing
+    ///   directory nor one that can be created and written to
+    /// - None of the fallback locations exists or can be created
     pub fn new() -> Result<Self> {
-        let root_dir = if let Ok(custom_root) = std::env::var("WORKTREE_STORAGE_ROOT") {
-            PathBuf::from(custom_root)
-        } else {
-            dirs::home_dir()
-                .context("Failed to get user home directory")?
-                .join(".worktrees")
+        let env_path = |name: &str| {
+            std::env::var_os(name)
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
         };
 
-        std::fs::create_dir_all(&root_dir).context("Failed to create worktrees directory")?;
+        let root_dir = resolve_root_dir(&RootCandidates {
+            custom_root: env_path("WORKTREE_STORAGE_ROOT"),
+            configured_root: GlobalConfig::load()
+                .ok()
+                .and_then(|config| config.storage_root),
+            home_dir: dirs::home_dir(),
+            xdg_data_home: env_path("XDG_DATA_HOME"),
+            current_dir: std::env::current_dir().ok(),
+        })?;
+        debug!("Storage root: {}", root_dir.display());
 
         Ok(Self { root_dir })
     }
 
-    /// Extracts repository name from a path
+    /// Creates a WorktreeStorage rooted at an explicit directory, bypassing root resolution
+    ///
+    /// # Errors
+    /// Returns an error if the directory cannot be created
+    pub fn with_root_dir(root_dir: PathBuf) -> Result<Self> {
+        std::fs::create_dir_all(&root_dir)
+            .map_err(io_error("Failed to create worktrees directory", &root_dir))?;
+        Ok(Self { root_dir })
+    }
+
+    /// Extracts repository name from a path. A bare clone made by `worktree clone`
+    /// (`<repo>/.bare`) is named after its parent directory.
     ///
     /// # Errors
     /// Returns an error if the path doesn't have a valid file name
     pub fn get_repo_name(repo_path: &Path) -> Result<String> {
+        let repo_path = if repo_path.file_name() == Some(BARE_REPO_DIR.as_ref()) {
+            repo_path.parent().unwrap_or(repo_path)
+        } else {
+            repo_path
+        };
         if let Some(name) = repo_path.file_name() {
             Ok(name.to_string_lossy().to_string())
         } else {
-            anyhow::bail!("Could not determine repository name from path")
+            Err(StorageError::InvalidRepoPath {
+                path: repo_path.to_path_buf(),
+            })
         }
     }
 
+    /// Returns where `worktree clone` puts the bare repository for `repo_name`
+    #[must_use]
+    pub fn get_bare_repo_path(&self, repo_name: &str) -> PathBuf {
+        self.root_dir.join(repo_name).join(BARE_REPO_DIR)
+    }
+
     /// Validates a feature name, rejecting characters that are invalid for directory names.
     ///
     /// Feature names must not contain: `/`, `\`, `:`, `*`, `?`, `"`, `<`, `>`, `|`
@@ -46,28 +115,27 @@ impl WorktreeStorage {
     /// Returns an error if the name contains invalid characters or is empty.
     pub fn validate_feature_name(name: &str) -> Result<()> {
         if name.trim().is_empty() {
-            anyhow::bail!("Feature name cannot be empty");
+            return Err(StorageError::EmptyFeatureName);
         }
 
         let invalid_chars = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
         for ch in invalid_chars {
             if name.contains(ch) {
-                anyhow::bail!(
-                    "Feature name '{}' contains invalid character '{}'. \
-                     Feature names cannot contain: / \\ : * ? \" < > |",
-                    name,
-                    ch
-                );
+                return Err(StorageError::InvalidFeatureName {
+                    name: name.to_string(),
+                    invalid: ch,
+                });
             }
         }
 
         Ok(())
     }
 
-    /// Returns the worktree path for the given feature name (no sanitization)
+    /// Returns the worktree path for the given feature name (no sanitization), inside
+    /// the repository's [worktree directory](Self::worktree_dir)
     #[must_use]
     pub fn get_worktree_path(&self, repo_name: &str, feature_name: &str) -> PathBuf {
-        self.root_dir.join(repo_name).join(feature_name)
+        self.worktree_dir(repo_name).join(feature_name)
     }
 
     /// Lists all worktrees for a specific repository
@@ -77,16 +145,17 @@ impl WorktreeStorage {
     /// - Failed to read the repository directory
     /// - Directory access issues
     pub fn list_repo_worktrees(&self, repo_name: &str) -> Result<Vec<String>> {
-        let repo_dir = self.root_dir.join(repo_name);
+        let repo_dir = self.worktree_dir(repo_name);
 
         if !repo_dir.exists() {
             return Ok(vec![]);
         }
 
+        let read_error = || io_error("Failed to read storage directory", &repo_dir);
         let mut worktrees = Vec::new();
-        for entry in std::fs::read_dir(&repo_dir)? {
-            let entry = entry?;
-            if entry.file_type()?.is_dir() {
+        for entry in std::fs::read_dir(&repo_dir).map_err(read_error())? {
+            let entry = entry.map_err(read_error())?;
+            if entry.file_type().map_err(read_error())?.is_dir() {
                 if let Some(name) = entry.file_name().to_str() {
                     // Skip hidden directories (e.g. .git metadata)
                     if !name.starts_with('.') {
@@ -112,10 +181,15 @@ impl WorktreeStorage {
             return Ok(all_worktrees);
         }
 
-        for entry in std::fs::read_dir(&self.root_dir)? {
-            let entry = entry?;
-            if entry.file_type()?.is_dir() {
+        let read_error = || io_error("Failed to read storage directory", &self.root_dir);
+        for entry in std::fs::read_dir(&self.root_dir).map_err(read_error())? {
+            let entry = entry.map_err(read_error())?;
+            if entry.file_type().map_err(read_error())?.is_dir() {
                 if let Some(repo_name) = entry.file_name().to_str() {
+                    // Skip hidden directories (e.g. .history)
+                    if repo_name.starts_with('.') {
+                        continue;
+                    }
                     let worktrees = self.list_repo_worktrees(repo_name)?;
                     all_worktrees.push((repo_name.to_string(), worktrees));
                 }
@@ -131,17 +205,98 @@ impl WorktreeStorage {
         self.root_dir.join(repo_name)
     }
 
+    /// Splits a `repo/name` target into the repository and the rest when `repo` is a
+    /// repository in storage. Anything else, including branch names such as
+    /// `feature/auth`, comes back unqualified.
+    #[must_use]
+    pub fn split_repo_target<'a>(&self, target: &'a str) -> (Option<&'a str>, &'a str) {
+        match target.split_once('/') {
+            Some((repo, rest))
+                if !repo.is_empty()
+                    && !rest.is_empty()
+                    && !repo.starts_with('.')
+                    && self.get_repo_storage_dir(repo).is_dir() =>
+            {
+                (Some(repo), rest)
+            }
+            _ => (None, target),
+        }
+    }
+
+    /// Gets the directory holding archived worktrees of a repository
+    #[must_use]
+    pub fn get_archive_dir(&self, repo_name: &str) -> PathBuf {
+        self.get_repo_storage_dir(repo_name).join(".archives")
+    }
+
+    /// Returns true if a repository's storage directory holds no worktrees and nothing
+    /// besides origin, slot, and activity metadata, i.e. it is safe to delete
+    #[must_use]
+    pub fn is_repo_dir_empty(&self, repo_name: &str) -> bool {
+        let Ok(entries) = std::fs::read_dir(self.get_repo_storage_dir(repo_name)) else {
+            return false;
+        };
+
+        entries.flatten().all(|entry| {
+            let name = entry.file_name();
+            name == ".worktree-origins"
+                || name == ".worktree-origins.tmp"
+                || name == slots::SLOTS_FILE_NAME
+                || name == activity::ACTIVITY_FILE_NAME
+                || name == labels::LABELS_FILE_NAME
+                || name == notes::NOTES_FILE_NAME
+                || name == copied::COPIED_FILE_NAME
+                || name == branches::MANAGED_BRANCHES_FILE_NAME
+                || name == layout::LOCATION_FILE_NAME
+        })
+    }
+
+    /// Deletes a repository's storage directory if it is still empty (see
+    /// [`Self::is_repo_dir_empty`]). Returns true if the directory was removed.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The storage lock cannot be acquired
+    /// - Failed to remove the directory
+    pub fn remove_repo_dir_if_empty(&self, repo_name: &str) -> Result<bool> {
+        let _lock = self.lock()?;
+
+        if !self.is_repo_dir_empty(repo_name) {
+            return Ok(false);
+        }
+
+        // An emptied `<repo>-worktrees` directory goes with it
+        let worktree_dir = self.worktree_dir(repo_name);
+        if worktree_dir != self.get_repo_storage_dir(repo_name) {
+            let _ = std::fs::remove_dir(&worktree_dir);
+        }
+
+        let repo_dir = self.get_repo_storage_dir(repo_name);
+        std::fs::remove_dir_all(&repo_dir)
+            .map_err(io_error("Failed to remove storage directory", &repo_dir))?;
+        Ok(true)
+    }
+
     /// Gets the root storage directory
     #[must_use]
     pub fn get_root_dir(&self) -> &PathBuf {
         &self.root_dir
     }
 
+    /// Acquires the storage metadata lock, waiting up to the configured lock timeout
+    ///
+    /// # Errors
+    /// Returns an error if the lock is held by another process past the timeout
+    pub fn lock(&self) -> Result<StorageLock> {
+        StorageLock::acquire(&self.root_dir, lock::lock_timeout())
+    }
+
     /// Stores origin information for a worktree (keyed by feature name)
     ///
     /// # Errors
     /// Returns an error if:
     /// - Failed to create the storage directory
+    /// - The storage lock cannot be acquired
     /// - Failed to write the origin mapping file
     pub fn store_worktree_origin(
         &self,
@@ -150,32 +305,71 @@ impl WorktreeStorage {
         origin_path: &str,
     ) -> Result<()> {
         let repo_dir = self.root_dir.join(repo_name);
-        std::fs::create_dir_all(&repo_dir)?;
+        std::fs::create_dir_all(&repo_dir)
+            .map_err(io_error("Failed to create storage directory", &repo_dir))?;
 
+        let _lock = self.lock()?;
         let origin_mapping_file = repo_dir.join(".worktree-origins");
         let mapping_entry = format!("{} -> {}\n", feature_name, origin_path);
 
         // Read existing mappings
-        let mut existing_content = if origin_mapping_file.exists() {
-            std::fs::read_to_string(&origin_mapping_file)?
-        } else {
-            String::new()
-        };
+        let mut existing_content = read_origin_mappings(&origin_mapping_file)?;
 
         // Check if mapping already exists (exact line match)
         let search_line = format!("{} -> {}", feature_name, origin_path);
         if !existing_content.lines().any(|line| line == search_line) {
             existing_content.push_str(&mapping_entry);
-            // Write atomically: write to temp then rename
-            let tmp_path = origin_mapping_file.with_extension("tmp");
-            std::fs::write(&tmp_path, &existing_content)?;
-            std::fs::rename(&tmp_path, &origin_mapping_file)?;
+            write_origin_mappings(&origin_mapping_file, &existing_content)?;
         }
+        self.invalidate_completion_cache();
+
+        Ok(())
+    }
+
+    /// Sets the origin for a worktree, replacing any existing entry for the feature
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Failed to create the storage directory
+    /// - The storage lock cannot be acquired
+    /// - Failed to read or write the origin mapping file
+    pub fn set_worktree_origin(
+        &self,
+        repo_name: &str,
+        feature_name: &str,
+        origin_path: &str,
+    ) -> Result<()> {
+        let repo_dir = self.root_dir.join(repo_name);
+        std::fs::create_dir_all(&repo_dir)
+            .map_err(io_error("Failed to create storage directory", &repo_dir))?;
+
+        let _lock = self.lock()?;
+        let origin_mapping_file = repo_dir.join(".worktree-origins");
+
+        let existing_content = read_origin_mappings(&origin_mapping_file)?;
+
+        let mut new_content: String = existing_content
+            .lines()
+            .filter(|line| {
+                if let Some((key, _)) = line.split_once(" -> ") {
+                    key != feature_name
+                } else {
+                    true // Keep malformed lines
+                }
+            })
+            .map(|line| format!("{}\n", line))
+            .collect();
+        new_content.push_str(&format!("{} -> {}\n", feature_name, origin_path));
+
+        write_origin_mappings(&origin_mapping_file, &new_content)?;
+        self.invalidate_completion_cache();
+        self.refresh_meta(repo_name, feature_name);
 
         Ok(())
     }
 
-    /// Retrieves origin information for a worktree (keyed by feature name)
+    /// Retrieves origin information for a worktree (keyed by feature name), from
+    /// `.worktree-origins` or else the worktree's own metadata file
     ///
     /// # Errors
     /// Returns an error if:
@@ -185,38 +379,64 @@ impl WorktreeStorage {
         repo_name: &str,
         feature_name: &str,
     ) -> Result<Option<String>> {
-        let origin_mapping_file = self.root_dir.join(repo_name).join(".worktree-origins");
+        Ok(self
+            .list_worktree_origins(repo_name)?
+            .into_iter()
+            .find(|(key, _)| key == feature_name)
+            .map(|(_, origin)| origin))
+    }
 
-        if !origin_mapping_file.exists() {
-            return Ok(None);
-        }
+    /// Lists all recorded `(feature name, origin path)` pairs for a repository. Worktrees
+    /// missing from `.worktree-origins` contribute the origin in their metadata file.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Failed to read the origin mapping file
+    pub fn list_worktree_origins(&self, repo_name: &str) -> Result<Vec<(String, String)>> {
+        let origin_mapping_file = self.root_dir.join(repo_name).join(".worktree-origins");
 
-        let content = std::fs::read_to_string(&origin_mapping_file)?;
+        let mut origins: Vec<(String, String)> = if origin_mapping_file.exists() {
+            read_origin_mappings(&origin_mapping_file)?
+                .lines()
+                .filter_map(|line| line.split_once(" -> "))
+                .map(|(key, origin)| (key.to_string(), origin.to_string()))
+                .collect()
+        } else {
+            Vec::new()
+        };
 
-        for line in content.lines() {
-            if let Some((key, origin)) = line.split_once(" -> ") {
-                if key == feature_name {
-                    return Ok(Some(origin.to_string()));
-                }
+        for feature_name in self.list_repo_worktrees(repo_name)? {
+            if origins.iter().any(|(key, _)| *key == feature_name) {
+                continue;
+            }
+            if let Some(origin) = self
+                .recorded_meta(repo_name, &feature_name)
+                .and_then(|meta| meta.origin)
+            {
+                origins.push((feature_name, origin));
             }
         }
 
-        Ok(None)
+        Ok(origins)
     }
 
     /// Removes origin information for a worktree (keyed by feature name)
     ///
     /// # Errors
     /// Returns an error if:
+    /// - The storage lock cannot be acquired
     /// - Failed to read or write the origin mapping file
     pub fn remove_worktree_origin(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        self.invalidate_completion_cache();
         let origin_mapping_file = self.root_dir.join(repo_name).join(".worktree-origins");
 
         if !origin_mapping_file.exists() {
             return Ok(()); // Nothing to remove
         }
 
-        let content = std::fs::read_to_string(&origin_mapping_file)?;
+        let _lock = self.lock()?;
+
+        let content = read_origin_mappings(&origin_mapping_file)?;
 
         // Filter out the line for this feature name
         let new_content: String = content
@@ -238,15 +458,270 @@ impl WorktreeStorage {
             format!("{}\n", new_content)
         };
 
-        // Write atomically: write to temp then rename
-        let tmp_path = origin_mapping_file.with_extension("tmp");
-        std::fs::write(&tmp_path, &final_content)?;
-        std::fs::rename(&tmp_path, &origin_mapping_file)?;
+        write_origin_mappings(&origin_mapping_file, &final_content)?;
 
         Ok(())
     }
 }
 
+impl StorageOperations for WorktreeStorage {
+    fn get_root_dir(&self) -> &Path {
+        &self.root_dir
+    }
+
+    fn get_worktree_path(&self, repo_name: &str, feature_name: &str) -> PathBuf {
+        self.get_worktree_path(repo_name, feature_name)
+    }
+
+    fn get_repo_storage_dir(&self, repo_name: &str) -> PathBuf {
+        self.get_repo_storage_dir(repo_name)
+    }
+
+    fn is_managed(&self, repo_name: &str, feature_name: &str) -> bool {
+        self.get_worktree_path(repo_name, feature_name).is_dir()
+    }
+
+    fn list_repo_worktrees(&self, repo_name: &str) -> Result<Vec<String>> {
+        self.list_repo_worktrees(repo_name)
+    }
+
+    fn list_all_worktrees(&self) -> Result<Vec<(String, Vec<String>)>> {
+        self.list_all_worktrees()
+    }
+
+    fn store_worktree_origin(
+        &self,
+        repo_name: &str,
+        feature_name: &str,
+        origin_path: &str,
+    ) -> Result<()> {
+        self.store_worktree_origin(repo_name, feature_name, origin_path)
+    }
+
+    fn set_worktree_origin(
+        &self,
+        repo_name: &str,
+        feature_name: &str,
+        origin_path: &str,
+    ) -> Result<()> {
+        self.set_worktree_origin(repo_name, feature_name, origin_path)
+    }
+
+    fn get_worktree_origin(&self, repo_name: &str, feature_name: &str) -> Result<Option<String>> {
+        self.get_worktree_origin(repo_name, feature_name)
+    }
+
+    fn list_worktree_origins(&self, repo_name: &str) -> Result<Vec<(String, String)>> {
+        self.list_worktree_origins(repo_name)
+    }
+
+    fn remove_worktree_origin(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        self.remove_worktree_origin(repo_name, feature_name)
+    }
+
+    fn allocate_slot(&self, repo_name: &str, feature_name: &str) -> Result<u32> {
+        self.allocate_slot(repo_name, feature_name)
+    }
+
+    fn get_slot(&self, repo_name: &str, feature_name: &str) -> Result<Option<u32>> {
+        self.get_slot(repo_name, feature_name)
+    }
+
+    fn release_slot(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        self.release_slot(repo_name, feature_name)
+    }
+
+    fn record_created(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        self.record_created(repo_name, feature_name)
+    }
+
+    fn forget_activity(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        self.forget_activity(repo_name, feature_name)
+    }
+
+    fn forget_labels(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        self.forget_labels(repo_name, feature_name)
+    }
+
+    fn forget_note(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        self.forget_note(repo_name, feature_name)
+    }
+
+    fn record_copied_files(
+        &self,
+        repo_name: &str,
+        feature_name: &str,
+        files: &[PathBuf],
+    ) -> Result<()> {
+        self.record_copied_files(repo_name, feature_name, files)
+    }
+
+    fn forget_copied_files(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        self.forget_copied_files(repo_name, feature_name)
+    }
+
+    fn record_managed_branch(&self, repo_name: &str, branch: &str) -> Result<()> {
+        self.record_managed_branch(repo_name, branch)
+    }
+
+    fn forget_managed_branch(&self, repo_name: &str, branch: &str) -> Result<()> {
+        self.forget_managed_branch(repo_name, branch)
+    }
+
+    fn write_worktree_meta(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        self.write_worktree_meta(repo_name, feature_name)
+    }
+
+    fn worktree_dir(&self, repo_name: &str) -> PathBuf {
+        self.worktree_dir(repo_name)
+    }
+
+    fn set_worktree_dir(&self, repo_name: &str, dir: Option<&Path>) -> Result<()> {
+        self.set_worktree_dir(repo_name, dir)
+    }
+}
+
+/// Reads a `.worktree-origins` file; a missing file reads as empty
+fn read_origin_mappings(path: &Path) -> Result<String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) if e.kind() == std::io::ErrorKind::InvalidData => Err(StorageError::Corrupt {
+            path: path.to_path_buf(),
+            reason: "not valid UTF-8".to_string(),
+        }),
+        Err(e) => Err(io_error("Failed to read origin mappings", path)(e)),
+    }
+}
+
+/// Replaces a `.worktree-origins` file atomically: write to a temp file, then rename
+fn write_origin_mappings(path: &Path, content: &str) -> Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, content)
+        .map_err(io_error("Failed to write origin mappings", &tmp_path))?;
+    std::fs::rename(&tmp_path, path).map_err(io_error("Failed to write origin mappings", path))
+}
+
+/// Candidate locations for the storage root, in priority order
+struct RootCandidates {
+    custom_root: Option<PathBuf>,
+    configured_root: Option<PathBuf>,
+    home_dir: Option<PathBuf>,
+    xdg_data_home: Option<PathBuf>,
+    current_dir: Option<PathBuf>,
+}
+
+/// Picks the first usable storage root from the candidates.
+/// An explicit `WORKTREE_STORAGE_ROOT` or `storage-root` is never silently replaced by a
+/// fallback.
+fn resolve_root_dir(candidates: &RootCandidates) -> Result<PathBuf> {
+    if let Some(custom_root) = &candidates.custom_root {
+        ensure_root_dir(custom_root).map_err(|source| StorageError::CustomRootUnwritable {
+            path: custom_root.clone(),
+            source,
+        })?;
+        return Ok(custom_root.clone());
+    }
+    if let Some(configured_root) = &candidates.configured_root {
+        ensure_root_dir(configured_root).map_err(|source| {
+            StorageError::ConfiguredRootUnwritable {
+                path: configured_root.clone(),
+                source,
+            }
+        })?;
+        return Ok(configured_root.clone());
+    }
+
+    let mut tried = Vec::new();
+
+    let legacy = candidates
+        .home_dir
+        .as_ref()
+        .map(|home| home.join(".worktrees"));
+    let data_dir = candidates
+        .xdg_data_home
+        .clone()
+        .or_else(|| {
+            candidates
+                .home_dir
+                .as_ref()
+                .map(|home| home.join(".local").join("share"))
+        })
+        .map(|data| data.join("worktree"));
+    // A `~/.worktrees` from before the XDG default stays in use until it is relocated
+    let preferred = match legacy {
+        Some(legacy) if legacy.is_dir() => {
+            debug!(
+                "Using existing {}; 'worktree relocate' moves it to the XDG data directory",
+                legacy.display()
+            );
+            [Some(legacy), data_dir]
+        }
+        legacy => [data_dir, legacy],
+    };
+    for candidate in preferred.into_iter().flatten() {
+        match ensure_root_dir(&candidate) {
+            Ok(()) => return Ok(candidate),
+            Err(e) => tried.push(format!("{} ({})", candidate.display(), e)),
+        }
+    }
+
+    if let Some(current_dir) = &candidates.current_dir {
+        let candidate = current_dir.join(".worktrees");
+        match ensure_root_dir(&candidate) {
+            Ok(()) => {
+                warn!(
+                    "No writable home directory found; storing worktrees in {}\n  Set WORKTREE_STORAGE_ROOT to choose a permanent location.",
+                    candidate.display()
+                );
+                return Ok(candidate);
+            }
+            Err(e) => tried.push(format!("{} ({})", candidate.display(), e)),
+        }
+    }
+
+    let tried = if tried.is_empty() {
+        "  (no candidate locations: HOME, XDG_DATA_HOME, and the current directory are unavailable)"
+            .to_string()
+    } else {
+        tried
+            .iter()
+            .map(|t| format!("  {}", t))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    Err(StorageError::NoWritableRoot { tried })
+}
+
+/// Accepts an existing directory without touching it; otherwise creates the directory
+/// and verifies once that files can be written inside it
+fn ensure_root_dir(dir: &Path) -> std::io::Result<()> {
+    if dir.is_dir() {
+        return Ok(());
+    }
+
+    let with_reason = |reason: &str, e: std::io::Error| {
+        std::io::Error::new(e.kind(), format!("{}: {}", reason, e))
+    };
+
+    std::fs::create_dir_all(dir).map_err(|e| with_reason("cannot create directory", e))?;
+
+    let probe = dir.join(format!(".write-probe-{}", std::process::id()));
+    std::fs::write(&probe, b"").map_err(|e| with_reason("directory is not writable", e))?;
+    let _ = std::fs::remove_file(&probe);
+
+    Ok(())
+}
+
+/// Seconds since the Unix epoch, or 0 if the system clock is set before it
+#[must_use]
+pub fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
 /// Reads the current HEAD branch name of a worktree directory.
 /// Returns None if the worktree is in detached HEAD state or cannot be opened.
 #[must_use]
@@ -263,12 +738,178 @@ pub fn read_worktree_head_branch(path: &Path) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use anyhow::Result;
     use tempfile::TempDir;
 
     fn make_storage(tmp: &TempDir) -> Result<WorktreeStorage> {
-        let root = tmp.path().join("worktrees");
-        std::fs::create_dir_all(&root)?;
-        Ok(WorktreeStorage { root_dir: root })
+        Ok(WorktreeStorage::with_root_dir(
+            tmp.path().join("worktrees"),
+        )?)
+    }
+
+    #[test]
+    fn test_get_repo_name_of_bare_clone_uses_parent() -> Result<()> {
+        assert_eq!(
+            WorktreeStorage::get_repo_name(Path::new("/storage/my-repo/.bare"))?,
+            "my-repo"
+        );
+        assert_eq!(
+            WorktreeStorage::get_repo_name(Path::new("/code/my-repo"))?,
+            "my-repo"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_repo_target() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = make_storage(&tmp)?;
+        std::fs::create_dir_all(storage.get_repo_storage_dir("api").join("auth"))?;
+
+        assert_eq!(storage.split_repo_target("api/auth"), (Some("api"), "auth"));
+        assert_eq!(
+            storage.split_repo_target("api/feature/auth"),
+            (Some("api"), "feature/auth")
+        );
+        assert_eq!(
+            storage.split_repo_target("feature/auth"),
+            (None, "feature/auth")
+        );
+        assert_eq!(storage.split_repo_target("auth"), (None, "auth"));
+        Ok(())
+    }
+
+    // ── resolve_root_dir ─────────────────────────────────────────────────────
+
+    fn candidates(tmp: &TempDir) -> RootCandidates {
+        RootCandidates {
+            custom_root: None,
+            configured_root: None,
+            home_dir: Some(tmp.path().join("home")),
+            xdg_data_home: Some(tmp.path().join("xdg")),
+            current_dir: Some(tmp.path().join("cwd")),
+        }
+    }
+
+    /// Returns a path that can never be created (its parent is a regular file)
+    fn uncreatable(tmp: &TempDir) -> Result<PathBuf> {
+        let file = tmp.path().join("not-a-dir");
+        std::fs::write(&file, "")?;
+        Ok(file.join("nested"))
+    }
+
+    #[test]
+    fn test_resolve_root_dir_prefers_custom_root() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let mut c = candidates(&tmp);
+        c.custom_root = Some(tmp.path().join("custom"));
+
+        assert_eq!(resolve_root_dir(&c)?, tmp.path().join("custom"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_root_dir_configured_root_after_custom_root() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let mut c = candidates(&tmp);
+        c.configured_root = Some(tmp.path().join("configured"));
+        assert_eq!(resolve_root_dir(&c)?, tmp.path().join("configured"));
+
+        c.custom_root = Some(tmp.path().join("custom"));
+        assert_eq!(resolve_root_dir(&c)?, tmp.path().join("custom"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_root_dir_custom_root_unwritable_is_error() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let mut c = candidates(&tmp);
+        c.custom_root = Some(uncreatable(&tmp)?);
+
+        let result = resolve_root_dir(&c);
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert!(e.to_string().contains("WORKTREE_STORAGE_ROOT"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_root_dir_uses_xdg_data_dir() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let mut c = candidates(&tmp);
+        assert_eq!(resolve_root_dir(&c)?, tmp.path().join("xdg/worktree"));
+
+        c.xdg_data_home = None;
+        assert_eq!(
+            resolve_root_dir(&c)?,
+            tmp.path().join("home/.local/share/worktree")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_root_dir_keeps_existing_legacy_root() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let c = candidates(&tmp);
+        std::fs::create_dir_all(tmp.path().join("home/.worktrees"))?;
+
+        assert_eq!(resolve_root_dir(&c)?, tmp.path().join("home/.worktrees"));
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_root_dir_accepts_existing_read_only_root() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = TempDir::new()?;
+        let mut c = candidates(&tmp);
+        let root = tmp.path().join("read-only");
+        std::fs::create_dir(&root)?;
+        std::fs::set_permissions(&root, std::fs::Permissions::from_mode(0o555))?;
+        c.custom_root = Some(root.clone());
+
+        let resolved = resolve_root_dir(&c);
+        std::fs::set_permissions(&root, std::fs::Permissions::from_mode(0o755))?;
+        assert_eq!(resolved?, root);
+        assert_eq!(std::fs::read_dir(&root)?.count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_root_dir_falls_back_to_home_then_cwd() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let mut c = candidates(&tmp);
+        c.xdg_data_home = Some(uncreatable(&tmp)?);
+        assert_eq!(resolve_root_dir(&c)?, tmp.path().join("home/.worktrees"));
+
+        c.home_dir = Some(uncreatable(&tmp)?);
+        c.xdg_data_home = None;
+        assert_eq!(resolve_root_dir(&c)?, tmp.path().join("cwd/.worktrees"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_root_dir_error_names_env_vars() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let c = RootCandidates {
+            custom_root: None,
+            configured_root: None,
+            home_dir: Some(uncreatable(&tmp)?),
+            xdg_data_home: None,
+            current_dir: None,
+        };
+
+        let result = resolve_root_dir(&c);
+        assert!(result.is_err());
+        if let Err(e) = result {
+            let msg = e.to_string();
+            assert!(msg.contains("WORKTREE_STORAGE_ROOT"));
+            assert!(msg.contains("HOME"));
+            assert!(msg.contains("not-a-dir"));
+        }
+        Ok(())
     }
 
     // ── validate_feature_name ────────────────────────────────────────────────
@@ -352,6 +993,82 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_list_worktree_origins() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = make_storage(&tmp)?;
+        assert!(storage.list_worktree_origins("myrepo")?.is_empty());
+
+        storage.store_worktree_origin("myrepo", "auth", "/home/user/repo")?;
+        storage.store_worktree_origin("myrepo", "payments", "/home/user/repo")?;
+
+        let origins = storage.list_worktree_origins("myrepo")?;
+        assert_eq!(
+            origins,
+            vec![
+                ("auth".to_string(), "/home/user/repo".to_string()),
+                ("payments".to_string(), "/home/user/repo".to_string()),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unreadable_origins_file_is_storage_corruption() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = make_storage(&tmp)?;
+        let repo_dir = storage.get_repo_storage_dir("myrepo");
+        std::fs::create_dir_all(&repo_dir)?;
+        std::fs::write(repo_dir.join(".worktree-origins"), [0xff, 0xfe, 0x00])?;
+
+        let Err(error) = storage.list_worktree_origins("myrepo") else {
+            anyhow::bail!("expected corrupt origins file to be rejected");
+        };
+        assert!(matches!(error, StorageError::Corrupt { .. }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_worktree_origin_replaces_entry() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = make_storage(&tmp)?;
+
+        storage.store_worktree_origin("myrepo", "auth", "/old/repo")?;
+        storage.store_worktree_origin("myrepo", "payments", "/old/repo")?;
+        storage.set_worktree_origin("myrepo", "auth", "/new/repo")?;
+
+        assert_eq!(
+            storage.get_worktree_origin("myrepo", "auth")?,
+            Some("/new/repo".to_string())
+        );
+        assert_eq!(
+            storage.get_worktree_origin("myrepo", "payments")?,
+            Some("/old/repo".to_string())
+        );
+        assert_eq!(storage.list_worktree_origins("myrepo")?.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_repo_dir_if_empty() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = make_storage(&tmp)?;
+
+        storage.store_worktree_origin("myrepo", "auth", "/home/user/repo")?;
+        std::fs::create_dir_all(storage.get_worktree_path("myrepo", "auth"))?;
+        assert!(!storage.is_repo_dir_empty("myrepo"));
+        assert!(!storage.remove_repo_dir_if_empty("myrepo")?);
+
+        std::fs::remove_dir(storage.get_worktree_path("myrepo", "auth"))?;
+        assert!(storage.is_repo_dir_empty("myrepo"));
+        assert!(storage.remove_repo_dir_if_empty("myrepo")?);
+        assert!(!storage.get_repo_storage_dir("myrepo").exists());
+
+        // A missing directory is not "empty"
+        assert!(!storage.is_repo_dir_empty("myrepo"));
+        Ok(())
+    }
+
     #[test]
     fn test_store_worktree_origin_different_features_independent() -> Result<()> {
         let tmp = TempDir::new()?;
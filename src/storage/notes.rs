@@ -0,0 +1,143 @@
+//! Free-form notes on worktrees.
+//!
+//! `.worktree-notes` in the repository's storage directory holds one `<feature>\t<note>`
+//! line per worktree with a note; backslashes and line breaks in the note are escaped
+//! as `\\` and `\n`. `worktree note` sets and clears them, and removing a worktree drops
+//! its note.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::WorktreeStorage;
+use super::error::{Result, io_error};
+
+/// Name of the note file in a repository's storage directory
+pub const NOTES_FILE_NAME: &str = ".worktree-notes";
+
+/// Notes of a repository's worktrees, keyed by feature name
+pub type NoteMap = BTreeMap<String, String>;
+
+impl WorktreeStorage {
+    /// Returns the notes of a repository's worktrees
+    ///
+    /// # Errors
+    /// Returns an error if the note file exists but cannot be read
+    pub fn list_notes(&self, repo_name: &str) -> Result<NoteMap> {
+        read_notes(&self.get_repo_storage_dir(repo_name).join(NOTES_FILE_NAME))
+    }
+
+    /// Returns the worktree's note, if it has one (unreadable notes count as none)
+    #[must_use]
+    pub fn get_note(&self, repo_name: &str, feature_name: &str) -> Option<String> {
+        self.list_notes(repo_name).ok()?.remove(feature_name)
+    }
+
+    /// Replaces the worktree's note
+    ///
+    /// # Errors
+    /// Returns an error if the storage lock cannot be acquired or the note file cannot
+    /// be read or written
+    pub fn set_note(&self, repo_name: &str, feature_name: &str, note: &str) -> Result<()> {
+        let repo_dir = self.get_repo_storage_dir(repo_name);
+        std::fs::create_dir_all(&repo_dir)
+            .map_err(io_error("Failed to create storage directory", &repo_dir))?;
+
+        let _lock = self.lock()?;
+        let path = repo_dir.join(NOTES_FILE_NAME);
+        let mut notes = read_notes(&path)?;
+        notes.insert(feature_name.to_string(), note.to_string());
+        write_notes(&path, &notes)
+    }
+
+    /// Drops the worktree's note
+    ///
+    /// # Errors
+    /// Returns an error if the storage lock cannot be acquired or the note file cannot
+    /// be read or written
+    pub fn forget_note(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        let path = self.get_repo_storage_dir(repo_name).join(NOTES_FILE_NAME);
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let _lock = self.lock()?;
+        let mut notes = read_notes(&path)?;
+        if notes.remove(feature_name).is_some() {
+            write_notes(&path, &notes)?;
+        }
+        Ok(())
+    }
+}
+
+fn escape(note: &str) -> String {
+    note.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(escaped: &str) -> String {
+    let mut note = String::with_capacity(escaped.len());
+    let mut chars = escaped.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                note.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                note.push('\\');
+                chars.next();
+            }
+            _ => note.push(c),
+        }
+    }
+    note
+}
+
+fn read_notes(path: &Path) -> Result<NoteMap> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(NoteMap::new()),
+        Err(e) => return Err(io_error("Failed to read worktree notes", path)(e)),
+    };
+    Ok(content
+        .lines()
+        .filter_map(|line| {
+            let (feature, note) = line.split_once('\t')?;
+            Some((feature.to_string(), unescape(note)))
+        })
+        .collect())
+}
+
+/// Replaces the note file atomically: write to a temp file, then rename
+fn write_notes(path: &Path, notes: &NoteMap) -> Result<()> {
+    let content: String = notes
+        .iter()
+        .map(|(feature, note)| format!("{}\t{}\n", feature, escape(note)))
+        .collect();
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, content)
+        .map_err(io_error("Failed to write worktree notes", &tmp_path))?;
+    std::fs::rename(&tmp_path, path).map_err(io_error("Failed to write worktree notes", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_set_and_forget_notes() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = WorktreeStorage::with_root_dir(tmp.path().to_path_buf())?;
+
+        let note = "Waiting on API review\nthen rebase (see C:\\notes)";
+        storage.set_note("repo", "auth flow", note)?;
+        storage.set_note("repo", "billing", "scratch")?;
+        assert_eq!(storage.get_note("repo", "auth flow").as_deref(), Some(note));
+
+        storage.forget_note("repo", "billing")?;
+        assert_eq!(storage.get_note("repo", "billing"), None);
+        assert_eq!(storage.list_notes("repo")?.len(), 1);
+        Ok(())
+    }
+}
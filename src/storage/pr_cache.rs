@@ -0,0 +1,68 @@
+//! Cache for pull request states shown by `list` and `status`.
+//!
+//! Asking the forge about every branch takes a network round trip each, so answers are
+//! kept in `.pr-cache/` under the storage root for a few minutes ([`pr_cache_ttl`]).
+//! Like the completion cache, it is best-effort: a missing, stale, or unreadable entry
+//! just means asking the forge again.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use super::WorktreeStorage;
+
+/// Directory under the storage root holding one file per cached pull request state
+pub const CACHE_DIR: &str = ".pr-cache";
+
+/// How long pull request states are reused when `WORKTREE_PR_CACHE_TTL` is not set
+pub const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+/// Environment variable overriding the cache lifetime in seconds (`0` disables caching)
+pub const TTL_ENV: &str = "WORKTREE_PR_CACHE_TTL";
+
+/// How long cached pull request states stay valid
+#[must_use]
+pub fn pr_cache_ttl() -> Duration {
+    std::env::var(TTL_ENV)
+        .ok()
+        .and_then(|secs| secs.trim().parse().ok())
+        .map_or(DEFAULT_TTL, Duration::from_secs)
+}
+
+impl WorktreeStorage {
+    /// Returns the pull request state cached under `key`, if it is younger than
+    /// [`pr_cache_ttl`]
+    #[must_use]
+    pub fn cached_pr_state(&self, key: &str) -> Option<String> {
+        let path = self.pr_cache_path(key);
+        let age = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())?;
+        if age >= pr_cache_ttl() {
+            return None;
+        }
+        std::fs::read_to_string(path).ok()
+    }
+
+    /// Caches `state` under `key`, ignoring failures
+    pub fn cache_pr_state(&self, key: &str, state: &str) {
+        if pr_cache_ttl().is_zero() {
+            return;
+        }
+        let dir = self.root_dir.join(CACHE_DIR);
+        if std::fs::create_dir_all(&dir).is_err() {
+            return;
+        }
+        let _ = std::fs::write(self.pr_cache_path(key), state);
+    }
+
+    fn pr_cache_path(&self, key: &str) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        self.root_dir
+            .join(CACHE_DIR)
+            .join(format!("{:016x}", hasher.finish()))
+    }
+}
@@ -0,0 +1,94 @@
+//! Cached worktree sizes for `list --sort size`.
+//!
+//! Measuring a worktree means walking every file in it, so sizes are kept in
+//! `.worktree-sizes` in the storage root: one `<repo>/<feature> <bytes> <measured at>`
+//! line per worktree. `du` refreshes every entry; `list` measures only worktrees whose
+//! entry is missing or older than [`SIZE_TTL`]. The cache is best-effort.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::{WorktreeStorage, unix_now};
+
+/// Name of the size cache file in the storage root
+pub const SIZES_FILE_NAME: &str = ".worktree-sizes";
+
+/// How long a measured size is trusted
+pub const SIZE_TTL: Duration = Duration::from_secs(60 * 60);
+
+impl WorktreeStorage {
+    /// Returns the cached size in bytes of a worktree, if it was measured within
+    /// [`SIZE_TTL`]
+    #[must_use]
+    pub fn cached_size(&self, repo_name: &str, feature_name: &str) -> Option<u64> {
+        let now = unix_now();
+        self.read_sizes()
+            .get(&(repo_name.to_string(), feature_name.to_string()))
+            .filter(|(_, measured_at)| now.saturating_sub(*measured_at) < SIZE_TTL.as_secs())
+            .map(|(bytes, _)| *bytes)
+    }
+
+    /// Records freshly measured `(feature, bytes)` sizes of a repository's worktrees,
+    /// ignoring failures
+    pub fn cache_sizes(&self, repo_name: &str, sizes: &[(String, u64)]) {
+        if sizes.is_empty() {
+            return;
+        }
+        let Ok(_lock) = self.lock() else {
+            return;
+        };
+        let now = unix_now();
+        let mut entries = self.read_sizes();
+        for (feature_name, bytes) in sizes {
+            entries.insert((repo_name.to_string(), feature_name.clone()), (*bytes, now));
+        }
+        let mut lines: Vec<String> = entries
+            .iter()
+            .map(|((repo, feature), (bytes, measured_at))| {
+                format!("{}/{} {} {}\n", repo, feature, bytes, measured_at)
+            })
+            .collect();
+        lines.sort();
+        let _ = std::fs::write(self.get_root_dir().join(SIZES_FILE_NAME), lines.concat());
+    }
+
+    fn read_sizes(&self) -> HashMap<(String, String), (u64, u64)> {
+        std::fs::read_to_string(self.get_root_dir().join(SIZES_FILE_NAME))
+            .map(|content| content.lines().filter_map(parse_line).collect())
+            .unwrap_or_default()
+    }
+}
+
+fn parse_line(line: &str) -> Option<((String, String), (u64, u64))> {
+    let mut fields = line.rsplitn(3, ' ');
+    let measured_at = fields.next()?.parse().ok()?;
+    let bytes = fields.next()?.parse().ok()?;
+    let (repo, feature) = fields.next()?.split_once('/')?;
+    Some((
+        (repo.to_string(), feature.to_string()),
+        (bytes, measured_at),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_size_cache_round_trip_and_expiry() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = WorktreeStorage::with_root_dir(tmp.path().to_path_buf())?;
+
+        assert_eq!(storage.cached_size("repo", "auth"), None);
+        storage.cache_sizes("repo", &[("auth".to_string(), 2048)]);
+        storage.cache_sizes("other", &[("auth".to_string(), 10)]);
+        assert_eq!(storage.cached_size("repo", "auth"), Some(2048));
+        assert_eq!(storage.cached_size("other", "auth"), Some(10));
+
+        std::fs::write(tmp.path().join(SIZES_FILE_NAME), "repo/auth 2048 0\n")?;
+        assert_eq!(storage.cached_size("repo", "auth"), None);
+        Ok(())
+    }
+}
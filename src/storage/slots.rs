@@ -0,0 +1,146 @@
+//! Per-worktree slot numbers.
+//!
+//! Each worktree gets a small, stable number when it is created, recorded in
+//! `.worktree-slots` in the repository's storage directory as one `<feature> <slot>`
+//! line per worktree. Templates and hooks derive per-worktree resources from it (a port
+//! such as `3000 + slot`, a database name such as `app_{slot}`), so several worktrees
+//! can run their dev servers at once. Slot 0 is left to the main repository; worktrees
+//! get the lowest free number from 1, and removing a worktree frees its slot.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::WorktreeStorage;
+use super::error::{Result, io_error};
+
+/// Name of the slot file in a repository's storage directory
+pub const SLOTS_FILE_NAME: &str = ".worktree-slots";
+
+/// Slots of a repository's worktrees, keyed by feature name
+pub type SlotMap = BTreeMap<String, u32>;
+
+/// The lowest slot from 1 that is not in `used`
+#[must_use]
+pub fn lowest_free_slot(used: &SlotMap) -> u32 {
+    let mut taken: Vec<u32> = used.values().copied().collect();
+    taken.sort_unstable();
+    let mut slot = 1;
+    for taken in taken {
+        if taken == slot {
+            slot += 1;
+        } else if taken > slot {
+            break;
+        }
+    }
+    slot
+}
+
+impl WorktreeStorage {
+    /// Returns the worktree's slot, assigning the lowest free one if it has none
+    ///
+    /// # Errors
+    /// Returns an error if the storage lock cannot be acquired or the slot file cannot
+    /// be read or written
+    pub fn allocate_slot(&self, repo_name: &str, feature_name: &str) -> Result<u32> {
+        let repo_dir = self.get_repo_storage_dir(repo_name);
+        std::fs::create_dir_all(&repo_dir)
+            .map_err(io_error("Failed to create storage directory", &repo_dir))?;
+
+        let _lock = self.lock()?;
+        let path = repo_dir.join(SLOTS_FILE_NAME);
+        let mut slots = read_slots(&path)?;
+        if let Some(slot) = slots.get(feature_name) {
+            return Ok(*slot);
+        }
+        let slot = lowest_free_slot(&slots);
+        slots.insert(feature_name.to_string(), slot);
+        write_slots(&path, &slots)?;
+        Ok(slot)
+    }
+
+    /// Returns the worktree's slot, if it has one
+    ///
+    /// # Errors
+    /// Returns an error if the slot file exists but cannot be read
+    pub fn get_slot(&self, repo_name: &str, feature_name: &str) -> Result<Option<u32>> {
+        Ok(self.list_slots(repo_name)?.get(feature_name).copied())
+    }
+
+    /// Returns the slots of a repository's worktrees
+    ///
+    /// # Errors
+    /// Returns an error if the slot file exists but cannot be read
+    pub fn list_slots(&self, repo_name: &str) -> Result<SlotMap> {
+        read_slots(&self.get_repo_storage_dir(repo_name).join(SLOTS_FILE_NAME))
+    }
+
+    /// Frees the worktree's slot for the next new worktree
+    ///
+    /// # Errors
+    /// Returns an error if the storage lock cannot be acquired or the slot file cannot
+    /// be read or written
+    pub fn release_slot(&self, repo_name: &str, feature_name: &str) -> Result<()> {
+        let path = self.get_repo_storage_dir(repo_name).join(SLOTS_FILE_NAME);
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let _lock = self.lock()?;
+        let mut slots = read_slots(&path)?;
+        if slots.remove(feature_name).is_some() {
+            write_slots(&path, &slots)?;
+        }
+        Ok(())
+    }
+}
+
+fn read_slots(path: &Path) -> Result<SlotMap> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(SlotMap::new()),
+        Err(e) => return Err(io_error("Failed to read worktree slots", path)(e)),
+    };
+    Ok(content
+        .lines()
+        .filter_map(|line| {
+            let (feature, slot) = line.rsplit_once(' ')?;
+            Some((feature.to_string(), slot.parse().ok()?))
+        })
+        .collect())
+}
+
+/// Replaces the slot file atomically: write to a temp file, then rename
+fn write_slots(path: &Path, slots: &SlotMap) -> Result<()> {
+    let content: String = slots
+        .iter()
+        .map(|(feature, slot)| format!("{} {}\n", feature, slot))
+        .collect();
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, content)
+        .map_err(io_error("Failed to write worktree slots", &tmp_path))?;
+    std::fs::rename(&tmp_path, path).map_err(io_error("Failed to write worktree slots", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_slots_are_stable_and_reused_after_release() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = WorktreeStorage::with_root_dir(tmp.path().to_path_buf())?;
+
+        assert_eq!(storage.allocate_slot("repo", "a")?, 1);
+        assert_eq!(storage.allocate_slot("repo", "b")?, 2);
+        assert_eq!(storage.allocate_slot("repo", "a")?, 1);
+        assert_eq!(storage.allocate_slot("other", "a")?, 1);
+
+        storage.release_slot("repo", "a")?;
+        assert_eq!(storage.get_slot("repo", "a")?, None);
+        assert_eq!(storage.allocate_slot("repo", "c")?, 1);
+        assert_eq!(storage.allocate_slot("repo", "d")?, 3);
+        Ok(())
+    }
+}
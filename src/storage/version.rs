@@ -0,0 +1,142 @@
+//! Storage format version and migrations.
+//!
+//! `.storage-version` in the storage root records which format the tree under it uses.
+//! Roots without the file predate it and count as version 1. Each [`Migration`] brings
+//! the tree up by one version; `worktree migrate` runs the pending ones in order and
+//! stamps the new version after each, so an interrupted run resumes where it stopped.
+
+use std::path::Path;
+
+use super::WorktreeStorage;
+use super::error::{Result, StorageError, io_error};
+use super::meta::META_FILE_NAME;
+
+/// Name of the version file in the storage root
+pub const VERSION_FILE_NAME: &str = ".storage-version";
+
+/// Storage format this build writes
+pub const CURRENT_VERSION: u32 = 2;
+
+/// One step of the storage format
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Migration {
+    /// Version the tree is at once the migration has run
+    pub version: u32,
+    /// What the migration changes
+    pub description: &'static str,
+    run: fn(&WorktreeStorage) -> Result<usize>,
+}
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: 2,
+    description: "Write .worktree-meta.toml into each worktree",
+    run: write_missing_meta,
+}];
+
+impl WorktreeStorage {
+    /// Returns the version of the storage format, 1 if the root has no version file
+    ///
+    /// # Errors
+    /// Returns an error if the version file cannot be read or does not hold a number
+    pub fn storage_version(&self) -> Result<u32> {
+        let path = self.get_root_dir().join(VERSION_FILE_NAME);
+        match std::fs::read_to_string(&path) {
+            Ok(content) => content.trim().parse().map_err(|_| StorageError::Corrupt {
+                path,
+                reason: format!("expected a version number, found '{}'", content.trim()),
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(1),
+            Err(e) => Err(io_error("Failed to read storage version", &path)(e)),
+        }
+    }
+
+    /// Returns the migrations that have not run yet, oldest first
+    ///
+    /// # Errors
+    /// Returns an error if the version cannot be read, or if the storage was written by
+    /// a newer version of worktree
+    pub fn pending_migrations(&self) -> Result<Vec<&'static Migration>> {
+        let version = self.storage_version()?;
+        if version > CURRENT_VERSION {
+            return Err(StorageError::NewerVersion {
+                found: version,
+                supported: CURRENT_VERSION,
+            });
+        }
+        Ok(MIGRATIONS
+            .iter()
+            .filter(|migration| migration.version > version)
+            .collect())
+    }
+
+    /// Runs `migration` under the storage lock and stamps its version. Returns how many
+    /// items it changed.
+    ///
+    /// # Errors
+    /// Returns an error if the lock cannot be acquired, the migration fails, or the
+    /// version file cannot be written
+    pub fn run_migration(&self, migration: &Migration) -> Result<usize> {
+        let _lock = self.lock()?;
+        let changed = (migration.run)(self)?;
+        write_version(self.get_root_dir(), migration.version)?;
+        Ok(changed)
+    }
+}
+
+fn write_version(root_dir: &Path, version: u32) -> Result<()> {
+    let path = root_dir.join(VERSION_FILE_NAME);
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, format!("{}\n", version))
+        .map_err(io_error("Failed to write storage version", &tmp_path))?;
+    std::fs::rename(&tmp_path, &path).map_err(io_error("Failed to write storage version", &path))
+}
+
+/// Version 2: worktrees created before per-worktree metadata get their file
+fn write_missing_meta(storage: &WorktreeStorage) -> Result<usize> {
+    let mut written = 0;
+    for (repo_name, features) in storage.list_all_worktrees()? {
+        for feature_name in features {
+            let path = storage.get_worktree_path(&repo_name, &feature_name);
+            if !path.join(META_FILE_NAME).exists() {
+                storage.write_worktree_meta(&repo_name, &feature_name)?;
+                written += 1;
+            }
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_migrations_run_in_order_and_stamp_version() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let storage = WorktreeStorage::with_root_dir(tmp.path().to_path_buf())?;
+        std::fs::create_dir_all(storage.get_worktree_path("repo", "auth"))?;
+        assert_eq!(storage.storage_version()?, 1);
+
+        let pending = storage.pending_migrations()?;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(storage.run_migration(pending[0])?, 1);
+        assert_eq!(storage.storage_version()?, CURRENT_VERSION);
+        assert!(storage.pending_migrations()?.is_empty());
+        assert!(
+            storage
+                .get_worktree_path("repo", "auth")
+                .join(META_FILE_NAME)
+                .exists()
+        );
+
+        write_version(tmp.path(), CURRENT_VERSION + 1)?;
+        assert!(matches!(
+            storage.pending_migrations(),
+            Err(StorageError::NewerVersion { .. })
+        ));
+        Ok(())
+    }
+}
@@ -1,6 +1,28 @@
-use anyhow::Result;
 use std::path::{Path, PathBuf};
 
+use crate::git::Result;
+use crate::storage;
+
+/// Commits a branch has that its upstream lacks (`ahead`), and the reverse (`behind`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AheadBehind {
+    pub ahead: usize,
+    pub behind: usize,
+}
+
+/// Summary of a single commit
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CommitInfo {
+    /// Full hex object id
+    pub id: String,
+    /// First line of the commit message
+    pub summary: String,
+    pub author: String,
+    /// Commit time in seconds since the Unix epoch
+    pub time: i64,
+}
+
 /// Trait for Git operations to enable mocking in tests
 pub trait GitOperations {
     fn get_repo_path(&self) -> PathBuf;
@@ -79,4 +101,250 @@ pub trait GitOperations {
     /// # Errors
     /// Returns an error if git operations fail
     fn list_tags(&self) -> Result<Vec<String>>;
+
+    /// Returns true if the worktree at `worktree_path` has uncommitted changes,
+    /// including untracked files
+    ///
+    /// # Errors
+    /// Returns an error if the worktree cannot be opened or its status read
+    fn is_dirty(&self, worktree_path: &Path) -> Result<bool>;
+    /// Lists the files of the worktree at `worktree_path` with uncommitted changes,
+    /// including untracked files, relative to the worktree root
+    ///
+    /// # Errors
+    /// Returns an error if the worktree cannot be opened or its status read
+    fn changed_files(&self, worktree_path: &Path) -> Result<Vec<PathBuf>>;
+    /// Returns the upstream of local `branch` (e.g. `origin/feature`), if it has one
+    ///
+    /// # Errors
+    /// Returns an error if the branch does not exist
+    fn upstream(&self, branch: &str) -> Result<Option<String>>;
+    /// Counts commits between local `branch` and its upstream; `None` if it has no
+    /// upstream
+    ///
+    /// # Errors
+    /// Returns an error if the branch does not exist or git operations fail
+    fn ahead_behind(&self, branch: &str) -> Result<Option<AheadBehind>>;
+    /// Counts commits on local `branch` that exist only locally: ahead of its upstream,
+    /// or, without one, not on the default branch
+    ///
+    /// # Errors
+    /// Returns an error if the branch does not exist, or it has no upstream and no
+    /// default branch can be found
+    fn unpushed_commits(&self, branch: &str) -> Result<usize>;
+    /// Returns true if local `branch` has been merged into the `target` revision
+    ///
+    /// # Errors
+    /// Returns an error if either revision cannot be resolved
+    fn is_merged_into(&self, branch: &str, target: &str) -> Result<bool>;
+    /// Returns the HEAD commit of the worktree at `worktree_path`
+    ///
+    /// # Errors
+    /// Returns an error if the worktree cannot be opened or has no HEAD commit
+    fn last_commit(&self, worktree_path: &Path) -> Result<CommitInfo>;
+}
+
+/// Trait for worktree storage to enable mocking in tests
+///
+/// [`WorktreeStorage`](crate::storage::WorktreeStorage) is the filesystem implementation;
+/// [`MemoryStorage`](crate::storage::MemoryStorage) keeps everything in memory.
+pub trait StorageOperations {
+    /// The storage root under which each repository has a directory
+    fn get_root_dir(&self) -> &Path;
+    /// Returns the worktree path for the given feature name
+    fn get_worktree_path(&self, repo_name: &str, feature_name: &str) -> PathBuf;
+    /// Returns the storage directory for a repository
+    fn get_repo_storage_dir(&self, repo_name: &str) -> PathBuf;
+    /// Returns true if `feature_name` is a managed worktree of the repository
+    fn is_managed(&self, repo_name: &str, feature_name: &str) -> bool;
+    /// Lists the managed worktrees of a repository
+    ///
+    /// # Errors
+    /// Returns an error if the storage cannot be read
+    fn list_repo_worktrees(&self, repo_name: &str) -> storage::Result<Vec<String>>;
+    /// Lists the managed worktrees of every repository
+    ///
+    /// # Errors
+    /// Returns an error if the storage cannot be read
+    fn list_all_worktrees(&self) -> storage::Result<Vec<(String, Vec<String>)>>;
+    /// Records the origin repository of a worktree, keeping any existing entry
+    ///
+    /// # Errors
+    /// Returns an error if the origin mappings cannot be updated
+    fn store_worktree_origin(
+        &self,
+        repo_name: &str,
+        feature_name: &str,
+        origin_path: &str,
+    ) -> storage::Result<()>;
+    /// Sets the origin repository of a worktree, replacing any existing entry
+    ///
+    /// # Errors
+    /// Returns an error if the origin mappings cannot be updated
+    fn set_worktree_origin(
+        &self,
+        repo_name: &str,
+        feature_name: &str,
+        origin_path: &str,
+    ) -> storage::Result<()>;
+    /// Returns the origin repository recorded for a worktree
+    ///
+    /// # Errors
+    /// Returns an error if the origin mappings cannot be read
+    fn get_worktree_origin(
+        &self,
+        repo_name: &str,
+        feature_name: &str,
+    ) -> storage::Result<Option<String>>;
+    /// Lists all recorded `(feature name, origin path)` pairs for a repository
+    ///
+    /// # Errors
+    /// Returns an error if the origin mappings cannot be read
+    fn list_worktree_origins(&self, repo_name: &str) -> storage::Result<Vec<(String, String)>>;
+    /// Removes the origin entry of a worktree
+    ///
+    /// # Errors
+    /// Returns an error if the origin mappings cannot be updated
+    fn remove_worktree_origin(&self, repo_name: &str, feature_name: &str) -> storage::Result<()>;
+    /// Returns the worktree's slot number, assigning the lowest free one from 1 if it has
+    /// none
+    ///
+    /// # Errors
+    /// Returns an error if the slot records cannot be updated
+    fn allocate_slot(&self, repo_name: &str, feature_name: &str) -> storage::Result<u32>;
+    /// Returns the worktree's slot number, if it has one
+    ///
+    /// # Errors
+    /// Returns an error if the slot records cannot be read
+    fn get_slot(&self, repo_name: &str, feature_name: &str) -> storage::Result<Option<u32>>;
+    /// Frees the worktree's slot number
+    ///
+    /// # Errors
+    /// Returns an error if the slot records cannot be updated
+    fn release_slot(&self, repo_name: &str, feature_name: &str) -> storage::Result<()>;
+    /// Records that the worktree was just created
+    ///
+    /// # Errors
+    /// Returns an error if the activity records cannot be updated
+    fn record_created(&self, repo_name: &str, feature_name: &str) -> storage::Result<()>;
+    /// Drops the worktree's creation and last-use times
+    ///
+    /// # Errors
+    /// Returns an error if the activity records cannot be updated
+    fn forget_activity(&self, repo_name: &str, feature_name: &str) -> storage::Result<()>;
+    /// Drops the worktree's labels
+    ///
+    /// # Errors
+    /// Returns an error if the label records cannot be updated
+    fn forget_labels(&self, repo_name: &str, feature_name: &str) -> storage::Result<()>;
+    /// Drops the worktree's note
+    ///
+    /// # Errors
+    /// Returns an error if the note records cannot be updated
+    fn forget_note(&self, repo_name: &str, feature_name: &str) -> storage::Result<()>;
+    /// Records the files `create` copied into the worktree
+    ///
+    /// # Errors
+    /// Returns an error if the manifest cannot be updated
+    fn record_copied_files(
+        &self,
+        repo_name: &str,
+        feature_name: &str,
+        files: &[PathBuf],
+    ) -> storage::Result<()>;
+    /// Drops the worktree's manifest of copied files
+    ///
+    /// # Errors
+    /// Returns an error if the manifest cannot be updated
+    fn forget_copied_files(&self, repo_name: &str, feature_name: &str) -> storage::Result<()>;
+    /// Records that this tool created `branch`
+    ///
+    /// # Errors
+    /// Returns an error if the managed branch records cannot be updated
+    fn record_managed_branch(&self, repo_name: &str, branch: &str) -> storage::Result<()>;
+    /// Drops `branch` from the branches this tool created, after it was deleted
+    ///
+    /// # Errors
+    /// Returns an error if the managed branch records cannot be updated
+    fn forget_managed_branch(&self, repo_name: &str, branch: &str) -> storage::Result<()>;
+    /// Writes the worktree's own metadata file from the records above
+    ///
+    /// # Errors
+    /// Returns an error if the records cannot be read or the file cannot be written
+    fn write_worktree_meta(&self, repo_name: &str, feature_name: &str) -> storage::Result<()>;
+    /// Directory holding the repository's worktrees
+    fn worktree_dir(&self, repo_name: &str) -> PathBuf;
+    /// Records where the repository's worktrees go; `None` means its storage directory
+    ///
+    /// # Errors
+    /// Returns an error if the location cannot be recorded
+    fn set_worktree_dir(&self, repo_name: &str, dir: Option<&Path>) -> storage::Result<()>;
+}
+
+impl<T: GitOperations + ?Sized> GitOperations for &T {
+    fn get_repo_path(&self) -> PathBuf {
+        (**self).get_repo_path()
+    }
+    fn branch_exists(&self, branch_name: &str) -> Result<bool> {
+        (**self).branch_exists(branch_name)
+    }
+    fn create_worktree(
+        &self,
+        branch_name: &str,
+        worktree_path: &Path,
+        create_branch: bool,
+    ) -> Result<()> {
+        (**self).create_worktree(branch_name, worktree_path, create_branch)
+    }
+    fn create_worktree_from(
+        &self,
+        branch_name: &str,
+        worktree_path: &Path,
+        create_branch: bool,
+        from_ref: Option<&str>,
+    ) -> Result<()> {
+        (**self).create_worktree_from(branch_name, worktree_path, create_branch, from_ref)
+    }
+    fn remove_worktree(&self, worktree_name: &str) -> Result<()> {
+        (**self).remove_worktree(worktree_name)
+    }
+    fn list_worktrees(&self) -> Result<Vec<String>> {
+        (**self).list_worktrees()
+    }
+    fn delete_branch(&self, branch_name: &str) -> Result<()> {
+        (**self).delete_branch(branch_name)
+    }
+    fn inherit_config(&self, worktree_path: &Path) -> Result<()> {
+        (**self).inherit_config(worktree_path)
+    }
+    fn list_local_branches(&self) -> Result<Vec<String>> {
+        (**self).list_local_branches()
+    }
+    fn list_remote_branches(&self) -> Result<Vec<String>> {
+        (**self).list_remote_branches()
+    }
+    fn list_tags(&self) -> Result<Vec<String>> {
+        (**self).list_tags()
+    }
+    fn is_dirty(&self, worktree_path: &Path) -> Result<bool> {
+        (**self).is_dirty(worktree_path)
+    }
+    fn changed_files(&self, worktree_path: &Path) -> Result<Vec<PathBuf>> {
+        (**self).changed_files(worktree_path)
+    }
+    fn upstream(&self, branch: &str) -> Result<Option<String>> {
+        (**self).upstream(branch)
+    }
+    fn ahead_behind(&self, branch: &str) -> Result<Option<AheadBehind>> {
+        (**self).ahead_behind(branch)
+    }
+    fn unpushed_commits(&self, branch: &str) -> Result<usize> {
+        (**self).unpushed_commits(branch)
+    }
+    fn is_merged_into(&self, branch: &str, target: &str) -> Result<bool> {
+        (**self).is_merged_into(branch, target)
+    }
+    fn last_commit(&self, worktree_path: &Path) -> Result<CommitInfo> {
+        (**self).last_commit(worktree_path)
+    }
 }
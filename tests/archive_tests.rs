@@ -0,0 +1,135 @@
+//! Integration tests for `worktree archive`, `remove --archive` and `worktree restore`
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+use test_support::CliTestEnvironment;
+
+/// Archiving keeps the worktree and writes the archive under .archives
+#[test]
+fn test_archive_keeps_worktree() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["create", "feature-a", "feature-a"])?
+        .assert()
+        .success();
+
+    env.run_command(&["archive", "feature-a"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("📦 Archived 'feature-a'"));
+
+    env.worktree_path("feature-a")
+        .assert(predicate::path::exists());
+    let archive_dir = env.storage_dir.path().join("test_repo").join(".archives");
+    let archives: Vec<_> = std::fs::read_dir(&archive_dir)?
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().ends_with(".tar.gz"))
+        .collect();
+    assert_eq!(archives.len(), 1);
+
+    // The archive directory is not mistaken for a worktree
+    env.run_command(&["list"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(".archives").not());
+
+    Ok(())
+}
+
+/// Archiving a missing worktree fails
+#[test]
+fn test_archive_missing_worktree_fails() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["archive", "nope"])?
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("does not exist"));
+
+    Ok(())
+}
+
+/// remove --archive followed by restore brings back uncommitted and ignored files
+#[test]
+fn test_remove_archive_then_restore_roundtrip() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["create", "feature-b", "feature-b"])?
+        .assert()
+        .success();
+    let worktree = env.worktree_path("feature-b");
+    worktree.child("notes.txt").write_str("work in progress")?;
+    worktree.child(".env.local").write_str("TOKEN=abc")?;
+
+    env.run_command(&["remove", "feature-b", "--archive"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("📦 Archived to"));
+    worktree.assert(predicate::path::missing());
+
+    env.run_command(&["restore"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feature-b (feature-b)"));
+
+    env.run_command(&["restore", "feature-b"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Using existing branch: feature-b"))
+        .stdout(predicate::str::contains(
+            "✓ Worktree restored successfully!",
+        ));
+
+    worktree
+        .child("notes.txt")
+        .assert(predicate::str::contains("work in progress"));
+    worktree
+        .child(".env.local")
+        .assert(predicate::str::contains("TOKEN=abc"));
+    worktree.child(".git").assert(predicate::path::is_file());
+
+    // Restoring over an existing worktree is refused
+    env.run_command(&["restore", "feature-b"])?
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+
+    Ok(())
+}
+
+/// A deleted branch is recreated at the archived commit
+#[test]
+fn test_restore_recreates_deleted_branch() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["create", "feature-c", "feature-c"])?
+        .assert()
+        .success();
+    env.run_command(&["remove", "feature-c", "--archive", "--delete-branch"])?
+        .assert()
+        .success();
+
+    env.run_command(&["restore", "feature-c"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Recreating branch feature-c"));
+    env.worktree_path("feature-c")
+        .assert(predicate::path::exists());
+
+    Ok(())
+}
+
+/// Restoring a feature without an archive fails
+#[test]
+fn test_restore_without_archive_fails() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["restore", "ghost"])?
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No archive found for 'ghost'"));
+
+    Ok(())
+}
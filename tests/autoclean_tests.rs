@@ -0,0 +1,185 @@
+//! Integration tests for `worktree autoclean` and the `[retention]` policy
+//!
+//! Worktree age is the latest of its last use, HEAD commit time, and creation, so "old"
+//! worktrees are simulated with a backdated commit, `.git` file, git index, and
+//! `.worktree-activity` entry.
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command;
+
+use test_support::CliTestEnvironment;
+
+const OLD_DATE: &str = "2020-01-01T00:00:00";
+/// `OLD_DATE` in seconds since the Unix epoch
+const OLD_TIMESTAMP: u64 = 1_577_836_800;
+
+fn run(dir: &Path, program: &str, args: &[&str], envs: &[(&str, &str)]) -> Result<()> {
+    let output = Command::new(program)
+        .args(args)
+        .envs(envs.iter().copied())
+        .current_dir(dir)
+        .output()?;
+    anyhow::ensure!(
+        output.status.success(),
+        "{} {:?} failed: {}",
+        program,
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(())
+}
+
+fn write_retention(env: &CliTestEnvironment, retention: &str) -> Result<()> {
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str(&format!("[retention]\n{}\n", retention))?;
+    Ok(())
+}
+
+/// Creates a worktree whose only activity is in 2020. With `merge`, its commit is
+/// fast-forwarded into main so it has nothing unpushed.
+fn create_old_worktree(env: &CliTestEnvironment, feature: &str, merge: bool) -> Result<()> {
+    let branch = format!("feature/{}", feature);
+    env.run_command(&["create", feature, &branch])?
+        .assert()
+        .success();
+
+    let path = env.worktree_path(feature);
+    path.child(format!("{}.txt", feature)).write_str(feature)?;
+    run(path.path(), "git", &["add", "."], &[])?;
+    run(
+        path.path(),
+        "git",
+        &["commit", "-m", feature],
+        &[
+            ("GIT_AUTHOR_DATE", OLD_DATE),
+            ("GIT_COMMITTER_DATE", OLD_DATE),
+        ],
+    )?;
+    run(path.path(), "touch", &["-d", "2020-01-01", ".git"], &[])?;
+    env.backdate_recorded_activity(feature, OLD_TIMESTAMP)?;
+
+    if merge {
+        run(
+            env.repo_dir.path(),
+            "git",
+            &["merge", "--ff-only", &branch],
+            &[],
+        )?;
+    }
+    Ok(())
+}
+
+/// Without a [retention] section, autoclean explains how to configure one
+#[test]
+fn test_autoclean_without_policy() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["autoclean"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No retention policy configured"))
+        .stdout(predicate::str::contains("[retention]"));
+
+    Ok(())
+}
+
+/// max-age-days removes idle worktrees but keeps their branches
+#[test]
+fn test_autoclean_removes_idle_worktrees() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    write_retention(&env, "max-age-days = 30")?;
+
+    create_old_worktree(&env, "old", true)?;
+    env.run_command(&["create", "recent", "feature/recent"])?
+        .assert()
+        .success();
+
+    env.run_command(&["autoclean", "--dry-run"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("would remove old (idle"))
+        .stdout(predicate::str::contains("recent").not());
+    env.worktree_path("old").assert(predicate::path::is_dir());
+
+    env.run_command(&["autoclean"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed old"))
+        .stdout(predicate::str::contains("branch feature/old kept"));
+
+    env.worktree_path("old").assert(predicate::path::missing());
+    env.worktree_path("recent")
+        .assert(predicate::path::is_dir());
+    run(
+        env.repo_dir.path(),
+        "git",
+        &["rev-parse", "--verify", "feature/old"],
+        &[],
+    )?;
+
+    Ok(())
+}
+
+/// max-worktrees keeps the most recently active worktrees
+#[test]
+fn test_autoclean_enforces_max_worktrees() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    write_retention(&env, "max-worktrees = 1")?;
+
+    create_old_worktree(&env, "older", true)?;
+    env.run_command(&["create", "newer", "feature/newer"])?
+        .assert()
+        .success();
+
+    env.run_command(&["autoclean"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Removed older (beyond the 1 most recently active",
+        ));
+
+    env.worktree_path("older")
+        .assert(predicate::path::missing());
+    env.worktree_path("newer").assert(predicate::path::is_dir());
+
+    Ok(())
+}
+
+/// Worktrees with unpushed commits or uncommitted changes are never removed
+#[test]
+fn test_autoclean_keeps_unsafe_worktrees() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    write_retention(&env, "max-age-days = 30")?;
+
+    create_old_worktree(&env, "unpushed", false)?;
+    create_old_worktree(&env, "dirty", true)?;
+    let dirty = env.worktree_path("dirty");
+    dirty.child("scratch.txt").write_str("unsaved")?;
+    // Fresh uncommitted changes count as activity; these were left behind long ago
+    run(
+        dirty.path(),
+        "touch",
+        &["-d", "2020-01-01", "scratch.txt"],
+        &[],
+    )?;
+
+    env.run_command(&["autoclean"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Keeping unpushed (idle"))
+        .stdout(predicate::str::contains(
+            "1 unpushed commit(s) on feature/unpushed",
+        ))
+        .stdout(predicate::str::contains("uncommitted changes"))
+        .stdout(predicate::str::contains("Removed 0 worktree(s)"));
+
+    env.worktree_path("unpushed")
+        .assert(predicate::path::is_dir());
+    env.worktree_path("dirty").assert(predicate::path::is_dir());
+
+    Ok(())
+}
@@ -64,3 +64,65 @@ fn test_back_requires_worktree_context() -> Result<()> {
 
     Ok(())
 }
+
+/// With a shell session, back walks back through the jump history in order
+#[test]
+fn test_back_walks_jump_history() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    for (feature, branch) in [("hist-a", "feature/hist-a"), ("hist-b", "feature/hist-b")] {
+        env.run_command(&["create", feature, branch])?
+            .assert()
+            .success();
+    }
+    let session = std::process::id().to_string();
+    let a = env.worktree_path("hist-a").path().canonicalize()?;
+    let b = env.worktree_path("hist-b").path().canonicalize()?;
+    let repo = env.repo_dir.path().canonicalize()?;
+
+    // repo → hist-a → hist-b
+    env.run_command(&["jump", "hist-a"])?
+        .env("WORKTREE_SESSION", &session)
+        .assert()
+        .success();
+    env.run_command(&["jump", "hist-b"])?
+        .current_dir(&a)
+        .env("WORKTREE_SESSION", &session)
+        .assert()
+        .success();
+
+    let back = |from: &std::path::Path| -> Result<String> {
+        let assert_output = env
+            .run_command(&["back"])?
+            .current_dir(from)
+            .env("WORKTREE_SESSION", &session)
+            .assert()
+            .success();
+        Ok(String::from_utf8(
+            assert_output.get_output().stdout.clone(),
+        )?)
+    };
+    assert_eq!(std::path::Path::new(back(&b)?.trim()), a);
+    assert_eq!(std::path::Path::new(back(&a)?.trim()).canonicalize()?, repo);
+
+    // History exhausted: falls back to the worktree's origin
+    assert_eq!(std::path::Path::new(back(&a)?.trim()).canonicalize()?, repo);
+
+    Ok(())
+}
+
+/// The shell integration identifies its session for the jump history
+#[test]
+fn test_shell_integration_sets_session() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    for shell in ["bash", "zsh", "fish"] {
+        let script = get_stdout(&env, &["init", shell])?;
+        assert!(
+            script.contains("WORKTREE_SESSION"),
+            "{} integration should export WORKTREE_SESSION",
+            shell
+        );
+    }
+
+    Ok(())
+}
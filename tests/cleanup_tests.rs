@@ -4,7 +4,7 @@ use anyhow::Result;
 use assert_fs::prelude::*;
 use predicates::prelude::*;
 
-use test_support::CliTestEnvironment;
+use test_support::{CliTestEnvironment, git};
 
 /// When a managed worktree dir is removed manually, cleanup removes orphaned git references
 #[test]
@@ -17,20 +17,9 @@ fn test_cleanup_removes_orphaned_git_references() -> Result<()> {
         .success();
 
     // Create an independent branch NOT via CLI (unmanaged)
-    {
-        let output = std::process::Command::new("git")
-            .args(["checkout", "-b", "feature/unmanaged-b"])
-            .current_dir(env.repo_dir.path())
-            .output()
-            .unwrap();
-        assert!(output.status.success());
-        // Switch back to default branch to avoid detachment
-        let _ = std::process::Command::new("git")
-            .args(["checkout", "main"])
-            .current_dir(env.repo_dir.path())
-            .output()
-            .unwrap();
-    }
+    env.git(&["checkout", "-b", "feature/unmanaged-b"])?;
+    // Switch back to default branch to avoid detachment
+    env.git(&["checkout", "main"])?;
 
     // Simulate orphaning: remove the managed worktree directory
     let managed_path = env.worktree_path("managed-a");
@@ -56,30 +45,8 @@ fn test_cleanup_prunes_orphaned_directories_for_deleted_branches() -> Result<()>
     wt_path.assert(predicate::path::is_dir());
 
     // Remove the worktree using git's own command (using the directory name)
-    let remove_worktree_output = std::process::Command::new("git")
-        .args(["worktree", "remove", "to-be-deleted"])
-        .current_dir(env.repo_dir.path())
-        .output()
-        .unwrap();
-    assert!(
-        remove_worktree_output.status.success(),
-        "Failed to remove worktree: {}",
-        String::from_utf8_lossy(&remove_worktree_output.stderr)
-    );
-
-    let output = std::process::Command::new("git")
-        .args(["branch", "-D", "feature/to-be-deleted"])
-        .current_dir(env.repo_dir.path())
-        .output()
-        .unwrap();
-
-    if !output.status.success() {
-        eprintln!("Git command failed:");
-        eprintln!("stdout: {}", String::from_utf8_lossy(&output.stdout));
-        eprintln!("stderr: {}", String::from_utf8_lossy(&output.stderr));
-        eprintln!("status: {:?}", output.status);
-    }
-    assert!(output.status.success());
+    env.git(&["worktree", "remove", "to-be-deleted"])?;
+    env.git(&["branch", "-D", "feature/to-be-deleted"])?;
 
     // Run cleanup
     env.run_command(&["cleanup"])?.assert().success();
@@ -89,3 +56,365 @@ fn test_cleanup_prunes_orphaned_directories_for_deleted_branches() -> Result<()>
 
     Ok(())
 }
+
+/// --dry-run reports stale git worktree references without removing them
+#[test]
+fn test_cleanup_dry_run_reports_without_removing() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["create", "dry-run", "feature/dry-run"])?
+        .assert()
+        .success();
+    std::fs::remove_dir_all(env.worktree_path("dry-run").path())?;
+
+    let git_ref = env
+        .repo_dir
+        .child(".git")
+        .child("worktrees")
+        .child("dry-run");
+    git_ref.assert(predicate::path::is_dir());
+
+    env.run_command(&["cleanup", "--dry-run"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Dry run"))
+        .stdout(predicate::str::contains("git worktree reference 'dry-run'"));
+    git_ref.assert(predicate::path::is_dir());
+
+    env.run_command(&["cleanup"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Removed git worktree reference: dry-run",
+        ));
+    git_ref.assert(predicate::path::missing());
+
+    Ok(())
+}
+
+/// --dry-run on a clean repository reports nothing to do
+#[test]
+fn test_cleanup_dry_run_clean_repo() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["cleanup", "--dry-run"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Everything looks clean"));
+
+    Ok(())
+}
+
+/// --interactive cannot be combined with --dry-run
+#[test]
+fn test_cleanup_interactive_conflicts_with_dry_run() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["cleanup", "--interactive", "--dry-run"])?
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    Ok(())
+}
+
+/// --dry-run lists configured protected branches and the default branch from origin/HEAD
+#[test]
+fn test_cleanup_dry_run_shows_protected_branches() -> Result<()> {
+    let env = CliTestEnvironment::builder()
+        .config("protected-branches = [\"release/*\"]\n")
+        .build()?;
+    env.git(&[
+        "symbolic-ref",
+        "refs/remotes/origin/HEAD",
+        "refs/remotes/origin/trunk",
+    ])?;
+
+    env.run_command(&["cleanup", "--dry-run"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Protected branches (never deleted): main, master, trunk, release/*",
+        ));
+
+    Ok(())
+}
+
+/// --all finds repositories through their recorded origins, even from outside any repo
+#[test]
+fn test_cleanup_all_from_outside_repo() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["create", "gone", "feature/gone"])?
+        .assert()
+        .success();
+    std::fs::remove_dir_all(env.worktree_path("gone").path())?;
+
+    // A storage directory with no recorded origin is skipped, not an error
+    env.storage_dir
+        .child("unknown_repo")
+        .child("feature")
+        .create_dir_all()?;
+
+    env.run_command(&["cleanup", "--all"])?
+        .current_dir(env.storage_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("📦 test_repo"))
+        .stdout(predicate::str::contains(
+            "Removed git worktree reference: gone",
+        ))
+        .stdout(predicate::str::contains("Skipping unknown_repo"));
+
+    Ok(())
+}
+
+/// --all combined with --dry-run reports per repository without removing anything
+#[test]
+fn test_cleanup_all_dry_run() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["create", "gone", "feature/gone"])?
+        .assert()
+        .success();
+    std::fs::remove_dir_all(env.worktree_path("gone").path())?;
+
+    env.run_command(&["cleanup", "--all", "--dry-run"])?
+        .current_dir(env.storage_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("git worktree reference 'gone'"))
+        .stdout(predicate::str::contains("Removed git worktree reference").not());
+
+    env.repo_dir
+        .child(".git")
+        .child("worktrees")
+        .child("gone")
+        .assert(predicate::path::exists());
+
+    Ok(())
+}
+
+/// --all covers every repository sharing the storage root, bare ones included
+#[test]
+fn test_cleanup_all_covers_every_repository() -> Result<()> {
+    let env = CliTestEnvironment::builder()
+        .other_repo("other_repo")
+        .bare_repo("bare_repo")
+        .build()?;
+    let other = env.other_repo("other_repo");
+    let bare = env.bare_repo("bare_repo");
+
+    for dir in [env.repo_dir.path(), other.path(), bare.path()] {
+        env.run_command(&["create", "gone", "feature/gone"])?
+            .current_dir(dir)
+            .assert()
+            .success();
+    }
+    for repo in ["test_repo", "other_repo", "bare_repo"] {
+        std::fs::remove_dir_all(env.repo_worktree_path(repo, "gone").path())?;
+    }
+
+    env.run_command(&["cleanup", "--all"])?
+        .current_dir(env.storage_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("📦 test_repo"))
+        .stdout(predicate::str::contains("📦 other_repo"))
+        .stdout(predicate::str::contains("📦 bare_repo"));
+
+    for dir in [other.path(), bare.path()] {
+        assert!(!git(dir, &["worktree", "list"])?.contains("gone"));
+    }
+
+    Ok(())
+}
+
+/// Origin entries for deleted worktrees are pruned, and the emptied repo directory removed
+#[test]
+fn test_cleanup_removes_stale_origins_and_empty_repo_dir() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["create", "kept", "feature/kept"])?
+        .assert()
+        .success();
+    env.run_command(&["create", "gone", "feature/gone"])?
+        .assert()
+        .success();
+    std::fs::remove_dir_all(env.worktree_path("gone").path())?;
+
+    let origins = env
+        .storage_dir
+        .child("test_repo")
+        .child(".worktree-origins");
+
+    env.run_command(&["cleanup"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed stale origin entry: gone"));
+    origins.assert(predicate::str::contains("gone ->").not());
+    origins.assert(predicate::str::contains("kept ->"));
+    env.storage_dir
+        .child("test_repo")
+        .assert(predicate::path::is_dir());
+
+    env.run_command(&["remove", "kept"])?.assert().success();
+
+    env.run_command(&["cleanup", "--dry-run"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("empty storage directory"));
+    env.storage_dir
+        .child("test_repo")
+        .assert(predicate::path::is_dir());
+
+    env.run_command(&["cleanup"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed empty storage directory"));
+    env.storage_dir
+        .child("test_repo")
+        .assert(predicate::path::missing());
+
+    Ok(())
+}
+
+/// Entries in `.managed-branches` for branches deleted outside the tool are pruned, and
+/// shown by `cleanup --dry-run` and the `status` preview first
+#[test]
+fn test_cleanup_prunes_stale_managed_branches() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    for feature in ["kept", "gone"] {
+        env.run_command(&["create", feature, &format!("feature/{}", feature)])?
+            .assert()
+            .success();
+    }
+    env.run_command(&["remove", "gone"])?.assert().success();
+    env.git(&["branch", "-D", "feature/gone"])?;
+
+    let managed = env
+        .storage_dir
+        .child("test_repo")
+        .child(".managed-branches");
+    managed.assert(predicate::str::contains("feature/gone"));
+
+    let entry = "managed branch entry 'feature/gone'";
+    env.run_command(&["cleanup", "--dry-run"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(entry));
+    env.run_command(&["status"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(entry));
+
+    env.run_command(&["cleanup"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Removed stale managed branch entry: feature/gone",
+        ));
+    managed.assert(predicate::str::contains("feature/gone").not());
+    managed.assert(predicate::str::contains("feature/kept"));
+
+    Ok(())
+}
+
+/// Creates a worktree and commits one file on its branch
+fn create_with_commit(env: &CliTestEnvironment, feature: &str, branch: &str) -> Result<()> {
+    env.run_command(&["create", feature, branch])?
+        .assert()
+        .success();
+    let path = env.worktree_path(feature);
+    path.child(format!("{}.txt", feature)).write_str(feature)?;
+    git(path.path(), &["add", "."])?;
+    git(path.path(), &["commit", "-m", feature])?;
+    Ok(())
+}
+
+/// --merged removes clean worktrees merged into the default branch, and their branches
+#[test]
+fn test_cleanup_merged_removes_merged_worktrees() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    create_with_commit(&env, "done", "feature/done")?;
+    create_with_commit(&env, "wip", "feature/wip")?;
+    create_with_commit(&env, "ff", "feature/ff")?;
+    git(env.repo_dir.path(), &["merge", "--ff-only", "feature/ff"])?;
+    env.run_command(&["create", "fresh", "feature/fresh"])?
+        .assert()
+        .success();
+    git(
+        env.repo_dir.path(),
+        &["merge", "--no-ff", "-m", "merge", "feature/done"],
+    )?;
+
+    env.run_command(&["cleanup", "--merged", "--dry-run"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("merged worktree 'done'"))
+        .stdout(predicate::str::contains("merged worktree 'ff'"))
+        .stdout(predicate::str::contains("'wip'").not())
+        .stdout(predicate::str::contains("'fresh'").not());
+    env.worktree_path("done").assert(predicate::path::is_dir());
+
+    env.run_command(&["cleanup", "--merged"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Removed merged worktree: done (branch feature/done deleted)",
+        ));
+
+    env.worktree_path("done").assert(predicate::path::missing());
+    env.worktree_path("wip").assert(predicate::path::is_dir());
+    env.worktree_path("fresh").assert(predicate::path::is_dir());
+    assert!(
+        git(
+            env.repo_dir.path(),
+            &["rev-parse", "--verify", "feature/done"]
+        )
+        .is_err()
+    );
+
+    Ok(())
+}
+
+/// Merged worktrees with uncommitted changes or protected branches are kept
+#[test]
+fn test_cleanup_merged_keeps_dirty_and_protected() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str("protected-branches = [\"release/*\"]\n")?;
+    git(env.repo_dir.path(), &["add", "."])?;
+    git(env.repo_dir.path(), &["commit", "-m", "config"])?;
+
+    create_with_commit(&env, "dirty", "feature/dirty")?;
+    create_with_commit(&env, "release", "release/1.0")?;
+    git(
+        env.repo_dir.path(),
+        &["merge", "--no-ff", "-m", "merge", "feature/dirty"],
+    )?;
+    git(
+        env.repo_dir.path(),
+        &["merge", "--no-ff", "-m", "merge", "release/1.0"],
+    )?;
+    env.worktree_path("dirty")
+        .child("scratch.txt")
+        .write_str("unsaved")?;
+
+    env.run_command(&["cleanup", "--merged"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "dirty is merged into main but has uncommitted changes",
+        ))
+        .stdout(predicate::str::contains("Removed merged worktree").not());
+
+    env.worktree_path("dirty").assert(predicate::path::is_dir());
+    env.worktree_path("release")
+        .assert(predicate::path::is_dir());
+
+    Ok(())
+}
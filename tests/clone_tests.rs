@@ -0,0 +1,108 @@
+#![allow(clippy::unwrap_used)]
+
+//! Integration tests for the clone command (bare-repo workflow)
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+use test_support::CliTestEnvironment;
+
+/// Cloning puts a bare repository in storage and checks out the default branch as a worktree
+#[test]
+fn test_clone_creates_bare_repo_and_default_worktree() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    let url = env.repo_dir.path().to_string_lossy().to_string();
+
+    env.run_command(&["clone", &url, "--name", "cloned"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Repository cloned successfully"));
+
+    let repo_storage = env.storage_dir.child("cloned");
+    repo_storage
+        .child(".bare")
+        .child("HEAD")
+        .assert(predicate::path::is_file());
+    let main_worktree = repo_storage.child("main");
+    main_worktree
+        .child(".git")
+        .assert(predicate::path::is_file());
+    main_worktree
+        .child("README.md")
+        .assert(predicate::path::exists());
+
+    Ok(())
+}
+
+/// Worktrees created from inside a cloned checkout belong to the bare repository
+#[test]
+fn test_create_from_cloned_worktree() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    let url = env.repo_dir.path().to_string_lossy().to_string();
+
+    env.run_command(&["clone", &url, "--name", "cloned"])?
+        .assert()
+        .success();
+
+    let main_worktree = env.storage_dir.child("cloned").child("main");
+    env.run_command(&["create", "feature-a", "feature/a"])?
+        .current_dir(main_worktree.path())
+        .assert()
+        .success();
+
+    env.storage_dir
+        .child("cloned")
+        .child("feature-a")
+        .child(".git")
+        .assert(predicate::path::is_file());
+
+    env.run_command(&["list", "--current"])?
+        .current_dir(main_worktree.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feature-a"))
+        .stdout(predicate::str::contains(".bare").not());
+
+    Ok(())
+}
+
+/// A bare repository outside storage works as the main repository too
+#[test]
+fn test_create_from_bare_repository() -> Result<()> {
+    let env = CliTestEnvironment::builder()
+        .bare_repo("bare_repo")
+        .build()?;
+    let bare = env.bare_repo("bare_repo");
+
+    env.run_command(&["create", "feature-a", "feature/a"])?
+        .current_dir(bare.path())
+        .assert()
+        .success();
+    env.repo_worktree_path("bare_repo", "feature-a")
+        .child("README.md")
+        .assert(predicate::path::exists());
+
+    env.run_command(&["list", "--current"])?
+        .current_dir(bare.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feature-a"));
+
+    Ok(())
+}
+
+/// Cloning refuses to overwrite a repository already in storage
+#[test]
+fn test_clone_existing_repository_fails() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    let url = env.repo_dir.path().to_string_lossy().to_string();
+    env.storage_dir.child("cloned").create_dir_all()?;
+
+    env.run_command(&["clone", &url, "--name", "cloned"])?
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists in storage"));
+
+    Ok(())
+}
@@ -460,3 +460,175 @@ fn test_completion_performance_many_worktrees() -> Result<()> {
 
     Ok(())
 }
+
+/// Test sync-config completion lists the current repository's worktrees
+#[test]
+fn test_sync_config_completion_output() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    let empty_output = get_stdout(&env, &["sync-config", "--list-completions"])?;
+    assert!(
+        empty_output.trim().is_empty(),
+        "sync-config completions should be empty when no worktrees exist"
+    );
+
+    for feature in ["sync-a", "sync-b"] {
+        env.run_command(&["create", feature, &format!("feature/{}", feature)])?
+            .assert()
+            .success();
+    }
+
+    let output = get_stdout(&env, &["sync-config", "--list-completions"])?;
+    let mut lines: Vec<&str> = output.lines().collect();
+    lines.sort_unstable();
+    assert_eq!(lines, vec!["sync-a", "sync-b"]);
+
+    Ok(())
+}
+
+/// Cached completions are reused between calls and dropped on create and remove
+#[test]
+fn test_completion_cache_invalidated_on_create_and_remove() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "cached", "feature/cached"])?
+        .assert()
+        .success();
+
+    assert_eq!(
+        get_stdout(&env, &["jump", "--list-completions"])?,
+        "cached\n"
+    );
+    assert!(
+        env.storage_dir.child(".completion-cache").exists(),
+        "completion results should be cached in storage"
+    );
+
+    env.run_command(&["create", "fresh", "feature/fresh"])?
+        .assert()
+        .success();
+    let output = get_stdout(&env, &["jump", "--list-completions"])?;
+    let mut lines: Vec<&str> = output.lines().collect();
+    lines.sort_unstable();
+    assert_eq!(lines, vec!["cached", "fresh"]);
+
+    env.run_command(&["remove", "cached"])?.assert().success();
+    assert_eq!(
+        get_stdout(&env, &["jump", "--list-completions"])?,
+        "fresh\n"
+    );
+
+    Ok(())
+}
+
+/// `--list-completions` only needs storage, so it works outside any repository
+#[test]
+fn test_list_completions_outside_repository() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "anywhere", "feature/anywhere"])?
+        .assert()
+        .success();
+
+    for command in ["jump", "remove"] {
+        env.run_command(&[command, "--list-completions"])?
+            .current_dir(env.temp_dir().path())
+            .env("WORKTREE_COMPLETION_TTL", "0")
+            .assert()
+            .success()
+            .stdout("anywhere\n");
+    }
+
+    Ok(())
+}
+
+/// `completions carapace` prints a spec whose dynamic values call back into the binary
+#[test]
+fn test_carapace_spec_output() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    let spec = get_stdout(&env, &["completions", "carapace"])?;
+
+    assert!(spec.starts_with("name: \"worktree\"\n"));
+    assert!(spec.contains("  - name: \"jump\"\n    aliases: [\"switch\"]\n"));
+    assert!(
+        spec.contains("from: [\"$(COMPLETE=fish worktree-bin -- worktree create --from '')\"]")
+    );
+    assert!(spec.contains(
+        "positionalany: [\"$(COMPLETE=fish worktree-bin -- worktree remove '' | grep -v '^-')\"]"
+    ));
+    assert!(
+        !spec.contains("list-completions"),
+        "hidden flags stay out of the spec"
+    );
+
+    Ok(())
+}
+
+/// The shell integration registers `worktree-bin` as the dynamic completer
+#[test]
+fn test_shell_integration_registers_dynamic_completions() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    for shell in ["bash", "zsh", "fish"] {
+        let script = get_stdout(&env, &["init", shell])?;
+        assert!(
+            script.contains(&format!("COMPLETE=\"{}\"", shell))
+                || script.contains(&format!("COMPLETE={}", shell)),
+            "{} integration should call back into the binary for completions:\n{}",
+            shell,
+            script
+        );
+        assert!(script.contains("worktree-bin"));
+        assert!(!script.contains("--list-completions"));
+    }
+
+    Ok(())
+}
+
+/// Runs the binary in completion mode (the fish protocol, one candidate per line with
+/// tab-separated help) for the command line `words`
+fn complete(env: &CliTestEnvironment, words: &[&str]) -> Result<Vec<String>> {
+    let mut args = vec!["--", "worktree"];
+    args.extend_from_slice(words);
+    let output = env
+        .run_command(&args)?
+        .env("COMPLETE", "fish")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    Ok(String::from_utf8(output)?
+        .lines()
+        .map(|line| line.split('\t').next().unwrap_or_default().to_string())
+        .collect())
+}
+
+/// The binary completes worktree names, git references, and subcommands itself
+#[test]
+fn test_dynamic_completion_candidates() -> Result<()> {
+    let env = CliTestEnvironment::builder().tag("v1.0").build()?;
+    for feature in ["auth", "payments"] {
+        env.run_command(&["create", feature, &format!("feature/{}", feature)])?
+            .assert()
+            .success();
+    }
+
+    let jump = complete(&env, &["jump", "pa"])?;
+    assert_eq!(jump, vec!["payments"]);
+
+    let remove = complete(&env, &["remove", ""])?;
+    assert!(remove.contains(&"auth".to_string()));
+    assert!(remove.contains(&"payments".to_string()));
+
+    let sync = complete(&env, &["sync-config", "auth", "p"])?;
+    assert_eq!(sync, vec!["payments"]);
+
+    let refs = complete(&env, &["create", "x", "y", "--from", ""])?;
+    for reference in ["main", "feature/auth", "v1.0"] {
+        assert!(refs.contains(&reference.to_string()), "missing {reference}");
+    }
+
+    let subcommands = complete(&env, &["sy"])?;
+    assert_eq!(subcommands, vec!["sync-config", "sync"]);
+
+    Ok(())
+}
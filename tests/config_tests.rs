@@ -265,6 +265,43 @@ invalid syntax here
     Ok(())
 }
 
+/// The invalid-config warning goes to stderr; `-q` hides it, and `-vv` or `WORKTREE_LOG`
+/// add debug lines about which config was loaded
+#[test]
+fn test_invalid_toml_warning_follows_verbosity() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str("[copy-patterns\n")?;
+
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "⚠ Warning: Invalid TOML syntax in .worktree-config.toml",
+        ))
+        .stderr(predicate::str::contains("debug").not());
+
+    env.run_command(&["-q", "create", "billing", "feature/billing"])?
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Invalid TOML").not());
+
+    env.run_command(&["create", "search", "feature/search", "-vv"])?
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("debug worktree::config: Loading "));
+
+    env.run_command(&["create", "ui", "feature/ui"])?
+        .env("WORKTREE_LOG", "worktree::provision=debug")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Copy pattern '.env*' matched"))
+        .stderr(predicate::str::contains("worktree::config").not());
+
+    Ok(())
+}
+
 #[test]
 fn test_partial_toml_missing_copy_patterns_section() -> Result<()> {
     let env = CliTestEnvironment::new()?;
@@ -293,6 +330,44 @@ key = "value"
     Ok(())
 }
 
+#[test]
+fn test_protected_branches_config() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.repo_dir.child(".worktree-config.toml").write_str(
+        r#"
+protected-branches = ["develop", "release/*"]
+
+[copy-patterns]
+include = ["custom.conf"]
+"#,
+    )?;
+
+    let config = WorktreeConfig::load_from_repo(&env.repo_dir)?;
+    let protected = config.protected_branches(Some("trunk"));
+
+    for branch in ["main", "master", "trunk", "develop", "release/1.2"] {
+        assert!(
+            protected.is_protected(branch),
+            "{} should be protected",
+            branch
+        );
+    }
+    for branch in ["feature/auth", "developer", "release"] {
+        assert!(
+            !protected.is_protected(branch),
+            "{} should not be protected",
+            branch
+        );
+    }
+
+    // Built-in protection applies without any config
+    let defaults = WorktreeConfig::default().protected_branches(None);
+    assert_eq!(defaults.patterns(), ["main", "master"]);
+
+    Ok(())
+}
+
 // ==================== CONFIGURATION INTEGRATION TESTS ====================
 
 #[test]
@@ -558,5 +633,61 @@ include = [".env*"]
     // On-create commands should be None (empty default)
     assert!(config.on_create.commands.is_none());
 
+    // Remote branches are kept unless configured otherwise
+    assert!(!config.remove.delete_remote);
+
+    Ok(())
+}
+
+/// `--config <dir>` replaces both the global config and the repository's config; a
+/// file in `WORKTREE_CONFIG` replaces only the global config
+#[test]
+fn test_config_override_file_and_directory() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth-v2", "feature/auth"])?
+        .assert()
+        .success();
+
+    let profile = env.temp_dir().child("profile");
+    profile.create_dir_all()?;
+    profile
+        .child("config.toml")
+        .write_str("match = \"exact\"\n")?;
+    profile
+        .child(".worktree-config.toml")
+        .write_str("[copy-patterns]\ninclude = [\"secret.txt\"]\n")?;
+    env.repo_dir.child("secret.txt").write_str("token")?;
+
+    // The global config from the directory turns prefix matching off
+    env.run_command(&["path", "auth"])?.assert().success();
+    env.run_command(&[
+        "--config",
+        &profile.path().to_string_lossy(),
+        "path",
+        "auth",
+    ])?
+    .assert()
+    .failure();
+
+    // Its repository config applies instead of the repository's own
+    env.run_command(&[
+        "create",
+        "billing",
+        "feature/billing",
+        "--config",
+        &profile.path().to_string_lossy(),
+    ])?
+    .assert()
+    .success();
+    env.worktree_path("billing")
+        .child("secret.txt")
+        .assert(predicate::path::exists());
+
+    // A file only stands in for the global config
+    env.run_command(&["path", "auth"])?
+        .env("WORKTREE_CONFIG", profile.child("config.toml").path())
+        .assert()
+        .failure();
+
     Ok(())
 }
@@ -58,11 +58,27 @@ fn test_create_worktree_directory_already_exists() -> Result<()> {
     env.run_command(&["create", "existing", "feature/existing"])?
         .assert()
         .failure()
+        .code(6)
         .stderr(predicate::str::contains("already exists"));
 
     Ok(())
 }
 
+/// Running outside a git repository fails with the not-a-repo exit code
+#[test]
+fn test_create_outside_repository_exit_code() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["create", "feature", "feature"])?
+        .current_dir(env.storage_dir.path())
+        .assert()
+        .failure()
+        .code(3)
+        .stderr(predicate::str::contains("Not a git repository"));
+
+    Ok(())
+}
+
 /// Test feature name validation - slash should be rejected
 #[test]
 fn test_create_feature_name_with_slash_rejected() -> Result<()> {
@@ -96,10 +112,7 @@ fn test_create_worktree_smart_mode_existing_branch() -> Result<()> {
     let env = CliTestEnvironment::new()?;
 
     // Create a branch first
-    std::process::Command::new("git")
-        .args(["branch", "feature/existing-test"])
-        .current_dir(env.repo_dir.path())
-        .output()?;
+    env.git(&["branch", "feature/existing-test"])?;
 
     env.run_command(&["create", "existing-test", "feature/existing-test"])?
         .assert()
@@ -117,10 +130,7 @@ fn test_create_worktree_smart_mode_existing_branch() -> Result<()> {
 fn test_git_config_inheritance() -> Result<()> {
     let env = CliTestEnvironment::new()?;
 
-    std::process::Command::new("git")
-        .args(["config", "core.editor", "nano"])
-        .current_dir(env.repo_dir.path())
-        .output()?;
+    env.git(&["config", "core.editor", "nano"])?;
 
     env.run_command(&["create", "config-inherit", "feature/config-inherit"])?
         .assert()
@@ -129,31 +139,169 @@ fn test_git_config_inheritance() -> Result<()> {
     let worktree_path = env.worktree_path("config-inherit");
     worktree_path.assert(predicate::path::exists());
 
-    let output = std::process::Command::new("git")
-        .args(["config", "extensions.worktreeConfig"])
-        .current_dir(env.repo_dir.path())
-        .output()?;
-
-    let config_value = String::from_utf8(output.stdout)?;
+    let config_value = env.git(&["config", "extensions.worktreeConfig"])?;
     assert_eq!(config_value.trim(), "true");
 
     Ok(())
 }
 
+/// Values from `includeIf` files are not baked into the worktree: the directives are
+/// copied instead, so their conditions are evaluated for the worktree
+#[test]
+fn test_git_config_inheritance_keeps_conditional_includes() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    let main_branch = env.git(&["branch", "--show-current"])?;
+    env.repo_dir
+        .child("main-only.gitconfig")
+        .write_str("[user]\n\temail = main@example.com\n")?;
+    env.repo_dir
+        .child("shared.gitconfig")
+        .write_str("[user]\n\tname = Shared Name\n")?;
+    env.git(&["config", "user.email", "local@example.com"])?;
+    env.git(&[
+        "config",
+        &format!("includeIf.onbranch:{}.path", main_branch.trim()),
+        &env.repo_dir
+            .path()
+            .join("main-only.gitconfig")
+            .to_string_lossy(),
+    ])?;
+    env.git(&["config", "include.path", "../shared.gitconfig"])?;
+    assert_eq!(
+        env.git(&["config", "user.email"])?.trim(),
+        "main@example.com"
+    );
+
+    for (feature, backend) in [("auth", "libgit2"), ("billing", "cli")] {
+        env.repo_dir
+            .child(".worktree-config.toml")
+            .write_str(&format!("git-backend = \"{}\"\n", backend))?;
+        env.run_command(&["create", feature, &format!("feature/{}", feature)])?
+            .assert()
+            .success();
+
+        let worktree = env.worktree_path(feature);
+        let worktree_git = |args: &[&str]| test_support::git(worktree.path(), args);
+        assert_eq!(
+            worktree_git(&["config", "user.email"])?.trim(),
+            "local@example.com"
+        );
+        assert_eq!(
+            worktree_git(&["config", "user.name"])?.trim(),
+            "Shared Name"
+        );
+        let include = worktree_git(&["config", "--worktree", "--get-all", "include.path"])?;
+        assert!(
+            std::path::Path::new(include.trim()).is_absolute(),
+            "{}",
+            include
+        );
+        assert!(include.trim().ends_with("shared.gitconfig"), "{}", include);
+    }
+
+    // The main repository's own config is left as it was
+    assert_eq!(
+        env.git(&["config", "--local", "user.email"])?.trim(),
+        "local@example.com"
+    );
+
+    Ok(())
+}
+
+/// `--no-inherit-config` and `inherit-git-config = false` leave the main repository's
+/// config alone
+#[test]
+fn test_create_without_config_inheritance() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["create", "auth", "feature/auth", "--no-inherit-config"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Git configuration inherited").not());
+    assert!(env.git(&["config", "extensions.worktreeConfig"]).is_err());
+
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str("inherit-git-config = false\n")?;
+    env.run_command(&["create", "billing", "feature/billing"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Git configuration inherited").not());
+    assert!(env.git(&["config", "extensions.worktreeConfig"]).is_err());
+
+    Ok(())
+}
+
+/// `--description`, the `[branch-description]` template, and its prompt write
+/// `branch.<name>.description`, which `list` and `status` show
+#[test]
+fn test_create_sets_branch_description() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&[
+        "create",
+        "auth",
+        "feature/auth",
+        "--description",
+        "Rework the login flow",
+    ])?
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "Description: Rework the login flow",
+    ));
+    assert_eq!(
+        env.git(&["config", "branch.feature/auth.description"])?
+            .trim(),
+        "Rework the login flow"
+    );
+
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str("[branch-description]\ntemplate = \"Worktree {name}\"\n")?;
+    env.run_command(&["create", "billing", "feature/billing"])?
+        .assert()
+        .success();
+    assert_eq!(
+        env.git(&["config", "branch.feature/billing.description"])?
+            .trim(),
+        "Worktree billing"
+    );
+
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str("[branch-description]\nprompt = true\n")?;
+    env.run_command(&["create", "search", "feature/search"])?
+        .env("WORKTREE_SELECT", "Faster search")
+        .assert()
+        .success();
+    assert_eq!(
+        env.git(&["config", "branch.feature/search.description"])?
+            .trim(),
+        "Faster search"
+    );
+
+    env.run_command(&["list", "--current"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Rework the login flow"))
+        .stdout(predicate::str::contains("Worktree billing"));
+    env.run_command(&["status"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Faster search"));
+
+    Ok(())
+}
+
 /// Test creating worktree with --from flag
 #[test]
 fn test_create_worktree_with_from_flag() -> Result<()> {
     let env = CliTestEnvironment::new()?;
 
-    std::process::Command::new("git")
-        .args(["branch", "test-source-branch"])
-        .current_dir(env.repo_dir.path())
-        .output()?;
+    env.git(&["branch", "test-source-branch"])?;
 
-    std::process::Command::new("git")
-        .args(["tag", "test-tag-v1.0"])
-        .current_dir(env.repo_dir.path())
-        .output()?;
+    env.git(&["tag", "test-tag-v1.0"])?;
 
     // From specific branch
     env.run_command(&[
@@ -217,15 +365,9 @@ fn test_create_worktree_from_invalid_reference() -> Result<()> {
 fn test_list_from_completions() -> Result<()> {
     let env = CliTestEnvironment::new()?;
 
-    std::process::Command::new("git")
-        .args(["branch", "completion-test-branch"])
-        .current_dir(env.repo_dir.path())
-        .output()?;
+    env.git(&["branch", "completion-test-branch"])?;
 
-    std::process::Command::new("git")
-        .args(["tag", "completion-test-tag"])
-        .current_dir(env.repo_dir.path())
-        .output()?;
+    env.git(&["tag", "completion-test-tag"])?;
 
     let output = env
         .run_command(&["create", "dummy", "dummy-branch", "--list-from-completions"])?
@@ -248,12 +390,7 @@ fn test_list_from_completions() -> Result<()> {
 fn test_create_worktree_from_commit_hash() -> Result<()> {
     let env = CliTestEnvironment::new()?;
 
-    let output = std::process::Command::new("git")
-        .args(["rev-parse", "HEAD"])
-        .current_dir(env.repo_dir.path())
-        .output()?;
-
-    let commit_hash = String::from_utf8(output.stdout)?.trim().to_string();
+    let commit_hash = env.git(&["rev-parse", "HEAD"])?.trim().to_string();
 
     env.run_command(&[
         "create",
@@ -423,3 +560,583 @@ mod integration_tests {
         Ok(())
     }
 }
+
+/// `git-backend = "cli"` creates the worktree through the git binary
+#[test]
+fn test_create_with_cli_git_backend() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str("git-backend = \"cli\"\n")?;
+
+    env.run_command(&["create", "via-cli", "feature/via-cli"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Creating new branch: feature/via-cli",
+        ));
+
+    let worktree_path = env.worktree_path("via-cli");
+    worktree_path
+        .child(".git")
+        .assert(predicate::path::exists());
+
+    env.run_command(&["status"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Git worktrees (1)"));
+
+    Ok(())
+}
+
+/// `[direnv]` writes the worktree variables ahead of the copied `.envrc` and renders
+/// templates from the repository
+#[test]
+fn test_create_writes_direnv_envrc() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.repo_dir.child(".envrc").write_str("dotenv\n")?;
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str("[direnv]\nenabled = true\n")?;
+
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Wrote .envrc for direnv"));
+
+    let envrc = env.worktree_path("auth").child(".envrc");
+    envrc.assert(predicate::str::contains("export WORKTREE_NAME='auth'"));
+    envrc.assert(predicate::str::contains(
+        "export WORKTREE_BRANCH='feature/auth'",
+    ));
+    envrc.assert(predicate::str::ends_with("\ndotenv\n"));
+    env.repo_dir.child(".envrc").assert("dotenv\n");
+
+    env.repo_dir
+        .child("envrc.template")
+        .write_str("export COMPOSE_PROJECT_NAME={repo}-{name}\n")?;
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str("[direnv]\nenabled = true\ntemplate = \"envrc.template\"\n")?;
+
+    env.run_command(&["create", "billing", "feature/billing"])?
+        .assert()
+        .success();
+    env.worktree_path("billing")
+        .child(".envrc")
+        .assert("export COMPOSE_PROJECT_NAME=test_repo-billing\n");
+
+    Ok(())
+}
+
+/// `[devcontainer] adapt` copies the untracked `.devcontainer/` and gives each worktree
+/// its own names, leaving the main repository's configuration untouched
+#[test]
+fn test_create_adapts_devcontainer() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    let original = "{\n  // App container\n  \"name\": \"App\",\n  \"image\": \"rust:1\",\n  \"runArgs\": [\"--name\", \"app\"],\n}\n";
+    env.repo_dir
+        .child(".devcontainer")
+        .child("devcontainer.json")
+        .write_str(original)?;
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str("[devcontainer]\nadapt = true\n")?;
+
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+
+    let adapted = env
+        .worktree_path("auth")
+        .child(".devcontainer")
+        .child("devcontainer.json");
+    adapted.assert(predicate::str::contains(
+        "\"name\": \"test_repo (feature/auth)\"",
+    ));
+    adapted.assert(predicate::str::contains("\"--name=test_repo-auth\""));
+    adapted.assert(predicate::str::contains(
+        "\"--label=dev.worktree.branch=feature/auth\"",
+    ));
+    adapted.assert(predicate::str::contains("\"image\": \"rust:1\""));
+    env.repo_dir
+        .child(".devcontainer")
+        .child("devcontainer.json")
+        .assert(original);
+
+    Ok(())
+}
+
+/// `[compose]` sets a per-worktree project name in `.env`, replacing a symlinked `.env`
+/// with a copy so the main repository keeps its own name
+#[test]
+fn test_create_sets_compose_project_name() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.repo_dir
+        .child(".env")
+        .write_str("DB_HOST=localhost\nCOMPOSE_PROJECT_NAME=app\n")?;
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str("[symlink-patterns]\ninclude = [\".env\"]\n\n[compose]\nenabled = true\n")?;
+
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Compose project name: test_repo-auth",
+        ));
+
+    let worktree_env = env.worktree_path("auth").child(".env");
+    assert!(!worktree_env.path().is_symlink());
+    worktree_env.assert("DB_HOST=localhost\nCOMPOSE_PROJECT_NAME=test_repo-auth\n");
+    env.repo_dir
+        .child(".env")
+        .assert("DB_HOST=localhost\nCOMPOSE_PROJECT_NAME=app\n");
+
+    env.repo_dir.child(".worktree-config.toml").write_str(
+        "[compose]\nenabled = true\ntarget = \"override\"\nproject-name = \"{branch}\"\n",
+    )?;
+    env.run_command(&["create", "billing", "feature/billing"])?
+        .assert()
+        .success();
+    env.worktree_path("billing")
+        .child("compose.override.yaml")
+        .assert("name: feature-billing\n");
+
+    Ok(())
+}
+
+/// `[build-cache]` links configured directories to a cache shared by all worktrees and
+/// writes templated files pointing at it
+#[cfg(unix)]
+#[test]
+fn test_create_sets_up_shared_build_cache() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.repo_dir.child(".worktree-config.toml").write_str(
+        "[build-cache]\nlink = [\"node_cache/\"]\n\n[build-cache.files]\n\".cargo/config.toml\" = \"[build]\\ntarget-dir = \\\"{cache}/target\\\"\\n\"\n",
+    )?;
+
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    env.run_command(&["create", "billing", "feature/billing"])?
+        .assert()
+        .success();
+
+    let cache_dir = env.storage_dir.child("test_repo").child(".build-cache");
+    for feature in ["auth", "billing"] {
+        let worktree = env.worktree_path(feature);
+        assert_eq!(
+            std::fs::read_link(worktree.child("node_cache").path())?,
+            cache_dir.child("node_cache").path()
+        );
+        worktree
+            .child(".cargo")
+            .child("config.toml")
+            .assert(format!(
+                "[build]\ntarget-dir = \"{}/target\"\n",
+                cache_dir.path().display()
+            ));
+    }
+
+    env.run_command(&["list"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(".build-cache").not());
+
+    Ok(())
+}
+
+/// `[dependency-dirs]` hard-link clones `node_modules` from the main repository, or runs
+/// the install command in the new worktree with the `WORKTREE_*` variables set
+#[cfg(unix)]
+#[test]
+fn test_create_dependency_dirs_strategies() -> Result<()> {
+    use std::os::unix::fs::MetadataExt;
+
+    let env = CliTestEnvironment::new()?;
+    let package = env.repo_dir.child("node_modules").child("left-pad");
+    package
+        .child("index.js")
+        .write_str("module.exports = pad")?;
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str("[dependency-dirs]\nstrategy = \"hardlink-clone\"\n")?;
+
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Cloned node_modules (1 files hard-linked)",
+        ));
+
+    let cloned = env
+        .worktree_path("auth")
+        .child("node_modules")
+        .child("left-pad")
+        .child("index.js");
+    assert_eq!(
+        std::fs::metadata(cloned.path())?.ino(),
+        std::fs::metadata(package.child("index.js").path())?.ino()
+    );
+
+    env.repo_dir.child(".worktree-config.toml").write_str(
+        "[dependency-dirs]\nstrategy = \"command\"\ncommand = \"mkdir node_modules && echo $WORKTREE_NAME > node_modules/.installed\"\n",
+    )?;
+    env.run_command(&["create", "billing", "feature/billing"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Dependencies installed"));
+    env.worktree_path("billing")
+        .child("node_modules")
+        .child(".installed")
+        .assert("billing\n");
+
+    Ok(())
+}
+
+/// `[toolchain]` copies the untracked `mise.toml`, then trusts and installs it with mise
+#[cfg(unix)]
+#[test]
+fn test_create_sets_up_mise_toolchain() -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let env = CliTestEnvironment::new()?;
+    let bin_dir = env.temp_dir().child("bin");
+    bin_dir.create_dir_all()?;
+    let log = env.temp_dir().child("mise.log");
+    let mise = bin_dir.child("mise");
+    mise.write_str(&format!(
+        "#!/bin/sh\necho \"$(basename \"$PWD\") $@\" >> '{}'\n",
+        log.path().display()
+    ))?;
+    std::fs::set_permissions(mise.path(), std::fs::Permissions::from_mode(0o755))?;
+    let path = format!(
+        "{}:{}",
+        bin_dir.path().display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+
+    env.repo_dir
+        .child("mise.toml")
+        .write_str("[tools]\nnode = \"22\"\n")?;
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str("[toolchain]\nenabled = true\ninstall = true\n")?;
+
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .env("PATH", path)
+        .assert()
+        .success();
+
+    env.worktree_path("auth")
+        .child("mise.toml")
+        .assert("[tools]\nnode = \"22\"\n");
+    log.assert(predicate::str::contains("auth trust\nauth install\n"));
+
+    Ok(())
+}
+
+/// Each worktree gets a stable slot, passed to hooks as `WORKTREE_SLOT` and to templates
+/// as `{slot}`; removing a worktree frees its slot for the next one
+#[test]
+fn test_create_allocates_and_remove_releases_slots() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.repo_dir.child(".worktree-config.toml").write_str(
+        "[on-create]\ncommands = [\"echo $WORKTREE_SLOT > slot.txt\"]\n\n[compose]\nenabled = true\nproject-name = \"app_{slot}_{slot+3000}\"\n",
+    )?;
+
+    for feature in ["auth", "billing"] {
+        env.run_command(&["create", feature, &format!("feature/{}", feature)])?
+            .assert()
+            .success();
+    }
+    env.worktree_path("auth").child("slot.txt").assert("1\n");
+    env.worktree_path("billing").child("slot.txt").assert("2\n");
+    env.worktree_path("billing")
+        .child(".env")
+        .assert("COMPOSE_PROJECT_NAME=app_2_3002\n");
+
+    env.run_command(&["remove", "auth"])?.assert().success();
+    env.run_command(&["create", "search", "feature/search"])?
+        .assert()
+        .success();
+    env.worktree_path("search").child("slot.txt").assert("1\n");
+
+    Ok(())
+}
+
+/// A worktree of an LFS repository gets its LFS files pulled, or, without git-lfs, an
+/// actionable warning; `[lfs] mode = "off"` skips both
+#[test]
+fn test_create_handles_lfs_repositories() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.repo_dir
+        .child(".gitattributes")
+        .write_str("*.bin filter=lfs diff=lfs merge=lfs -text\n")?;
+    env.git(&["add", ".gitattributes"])?;
+    env.git(&["commit", "-m", "Track binaries with LFS"])?;
+
+    let lfs_installed = std::process::Command::new("git")
+        .args(["lfs", "version"])
+        .output()
+        .is_ok_and(|output| output.status.success());
+    let expected = if lfs_installed {
+        "Git LFS files checked out"
+    } else {
+        "run `git lfs pull`"
+    };
+    let assert = env
+        .run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    let output = assert.get_output();
+    let combined = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(combined.contains(expected), "{}", combined);
+
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str("[lfs]\nmode = \"off\"\n")?;
+    env.run_command(&["create", "billing", "feature/billing"])?
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Git LFS").not());
+
+    Ok(())
+}
+
+/// `[git-hooks] share` points the worktree at the main repository's relative
+/// `core.hooksPath` without changing the main repository's setting, and `install` runs
+/// the hook installer in the worktree
+#[test]
+fn test_create_sets_up_git_hooks() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.git(&["config", "core.hooksPath", ".husky/_"])?;
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str("[git-hooks]\nshare = true\ninstall = \"touch hooks-installed\"\n")?;
+
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Git hooks installed"));
+
+    let worktree = env.worktree_path("auth");
+    let hooks_path = test_support::git(worktree.path(), &["config", "core.hooksPath"])?;
+    assert_eq!(
+        std::path::Path::new(hooks_path.trim()),
+        env.repo_dir.path().join(".husky/_")
+    );
+    assert_eq!(env.git(&["config", "core.hooksPath"])?.trim(), ".husky/_");
+    worktree
+        .child("hooks-installed")
+        .assert(predicate::path::exists());
+
+    Ok(())
+}
+
+/// `--json-events` writes NDJSON progress events to stderr: steps, the new branch, each
+/// copied file, warnings, and the finished worktree; `sync-config` reports its copies too
+#[test]
+fn test_create_json_events() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    create_sample_config_files(&env.repo_dir)?;
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str("[symlink-patterns]\ninclude = [\"missing.txt\"]\n")?;
+
+    let events = |args: &[&str]| -> Result<Vec<serde_json::Value>> {
+        let assert = env.run_command(args)?.assert().success();
+        let stderr = String::from_utf8(assert.get_output().stderr.clone())?;
+        Ok(stderr
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?)
+    };
+    let kinds = |events: &[serde_json::Value]| -> Vec<String> {
+        events
+            .iter()
+            .map(|event| event["event"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let created = events(&["create", "auth", "feature/auth", "--json-events"])?;
+    let kinds_created = kinds(&created);
+    assert_eq!(kinds_created[0], "step-started");
+    assert_eq!(created[0]["step"], "create-worktree");
+    assert!(
+        created
+            .iter()
+            .any(|event| event["event"] == "branch-created" && event["branch"] == "feature/auth")
+    );
+    assert!(
+        created
+            .iter()
+            .any(|event| event["event"] == "file-copied" && event["path"] == ".env")
+    );
+    assert!(created.iter().any(|event| event["event"] == "warning"
+        && event["message"].as_str().unwrap().contains("missing.txt")));
+    let last = created.last().unwrap();
+    assert_eq!(last["event"], "worktree-created");
+    assert_eq!(last["path"], env.worktree_path("auth").to_str().unwrap());
+
+    std::fs::remove_file(env.worktree_path("auth").join(".env"))?;
+    let synced = events(&[
+        "sync-config",
+        "--json-events",
+        env.repo_dir.to_str().unwrap(),
+        "auth",
+    ])?;
+    assert!(kinds(&synced).contains(&"file-copied".to_string()));
+    assert_eq!(synced.last().unwrap()["event"], "config-synced");
+
+    Ok(())
+}
+
+#[test]
+fn test_create_quiet_print_path() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    create_sample_config_files(&env.repo_dir)?;
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str("[on-create]\ncommands = [\"echo hook-output\"]\n")?;
+
+    // Neither progress lines nor hook output reach stdout
+    env.run_command(&["create", "auth", "feature/auth", "-q", "--print-path"])?
+        .assert()
+        .success()
+        .stdout(format!("{}\n", env.worktree_path("auth").display()));
+
+    // Errors still reach stderr
+    env.run_command(&["create", "auth", "feature/auth", "-q", "--print-path"])?
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(predicate::str::contains("Error"));
+    Ok(())
+}
+
+#[test]
+fn test_create_writes_worktree_metadata() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    env.run_command(&["label", "add", "auth", "review"])?
+        .assert()
+        .success();
+
+    let meta = env.worktree_path("auth").child(".worktree-meta.toml");
+    let content = std::fs::read_to_string(meta.path())?;
+    assert!(content.contains("branch = \"feature/auth\""), "{}", content);
+    assert!(content.contains("origin = "), "{}", content);
+    assert!(content.contains("created-at = "), "{}", content);
+    assert!(content.contains("managed = true"), "{}", content);
+    assert!(content.contains("labels = [\"review\"]"), "{}", content);
+
+    // Git ignores the file, so the worktree still counts as clean
+    let status = test_support::git(env.worktree_path("auth").path(), &["status", "--porcelain"])?;
+    assert_eq!(status, "");
+    Ok(())
+}
+
+/// `layout = "sibling"` puts worktrees in `<repo>-worktrees/` next to the repository,
+/// where the other commands find them too
+#[test]
+fn test_create_sibling_layout() -> Result<()> {
+    let env = CliTestEnvironment::builder()
+        .config("layout = \"sibling\"\n")
+        .build()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+
+    let sibling = env
+        .temp_dir()
+        .path()
+        .join("test_repo-worktrees")
+        .join("auth");
+    assert!(sibling.join("README.md").exists());
+    assert!(!env.worktree_path("auth").exists());
+
+    env.run_command(&["path", "auth"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(&*sibling.to_string_lossy()));
+    env.run_command(&["root"])?
+        .current_dir(&sibling)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            &*env.repo_dir.path().canonicalize()?.to_string_lossy(),
+        ));
+
+    env.run_command(&["remove", "auth"])?.assert().success();
+    assert!(!sibling.exists());
+    Ok(())
+}
+
+/// `fetch` in the [create] config fetches the remotes first, unless --no-fetch is passed
+#[test]
+fn test_create_fetch_config_default() -> Result<()> {
+    let env = CliTestEnvironment::builder()
+        .remote("origin")
+        .config("[create]\nfetch = true\n")
+        .build()?;
+    env.git(&["push", "origin", "HEAD:refs/heads/upstream-only"])?;
+    env.git(&["update-ref", "-d", "refs/remotes/origin/upstream-only"])?;
+    let fetched = || env.git(&["branch", "-r", "--list", "origin/upstream-only"]);
+
+    env.run_command(&["create", "offline", "offline", "--no-fetch"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Fetching...").not());
+    assert!(fetched()?.is_empty());
+
+    env.run_command(&["create", "online", "online"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Fetching..."));
+    assert!(!fetched()?.is_empty());
+    Ok(())
+}
+
+/// Without a branch argument, `branch-prefix` names the branch after the feature instead
+/// of prompting; the repository's prefix overrides the global one
+#[test]
+fn test_create_branch_prefix() -> Result<()> {
+    let env = CliTestEnvironment::builder()
+        .config("branch-prefix = \"{user}/\"\n")
+        .build()?;
+    let global = env.temp_dir().child("config.toml");
+    global.write_str("branch-prefix = \"team/\"\n")?;
+
+    env.run_command(&["create", "login-fix"])?
+        .env("USER", "cf")
+        .env("WORKTREE_CONFIG", global.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Creating new branch: cf/login-fix",
+        ));
+    env.worktree_path("login-fix")
+        .assert(predicate::path::is_dir());
+
+    env.run_command(&["create", "explicit", "explicit"])?
+        .env("USER", "cf")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Creating new branch: explicit"));
+
+    std::fs::remove_file(env.repo_dir.path().join(".worktree-config.toml"))?;
+    env.run_command(&["create", "shared"])?
+        .env("WORKTREE_CONFIG", global.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Creating new branch: team/shared"));
+    Ok(())
+}
@@ -0,0 +1,59 @@
+//! Integration tests for `worktree du`
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+use test_support::CliTestEnvironment;
+
+/// An empty storage root reports no worktrees
+#[test]
+fn test_du_empty_storage() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["du"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No worktrees found"));
+
+    Ok(())
+}
+
+/// Per-worktree sizes, the repo total, and --sort size ordering
+#[test]
+fn test_du_reports_sizes_sorted_by_size() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["create", "aaa-small", "feature/small"])?
+        .assert()
+        .success();
+    env.run_command(&["create", "zzz-large", "feature/large"])?
+        .assert()
+        .success();
+    env.worktree_path("zzz-large")
+        .child("blob.bin")
+        .write_binary(&vec![0u8; 2 * 1024 * 1024])?;
+
+    let output = env
+        .run_command(&["du", "--sort", "size"])?
+        .assert()
+        .success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout).to_string();
+
+    assert!(stdout.contains("test_repo"));
+    assert!(stdout.contains("MiB"));
+    assert!(stdout.contains("Total"));
+    let large = stdout.find("zzz-large").unwrap_or(usize::MAX);
+    let small = stdout.find("aaa-small").unwrap_or(0);
+    assert!(
+        large < small,
+        "largest worktree should be listed first:\n{}",
+        stdout
+    );
+
+    let output = env.run_command(&["du"])?.assert().success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout).to_string();
+    assert!(stdout.find("aaa-small") < stdout.find("zzz-large"));
+
+    Ok(())
+}
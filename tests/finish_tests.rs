@@ -0,0 +1,88 @@
+use anyhow::Result;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use test_support::{CliTestEnvironment, commit_file};
+
+/// `finish` merges the branch into main, then removes the worktree and the branch
+#[test]
+fn test_finish_merges_and_removes() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    let worktree = env.worktree_path("auth");
+    commit_file(worktree.path(), "auth.txt", "auth")?;
+
+    env.run_command(&["finish", "auth"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✓ Merged feature/auth into main"))
+        .stdout(predicate::str::contains("✓ Deleted branch feature/auth"));
+
+    env.repo_dir
+        .child("auth.txt")
+        .assert(predicate::path::exists());
+    worktree.assert(predicate::path::missing());
+    assert!(env.git(&["rev-parse", "--verify", "feature/auth"]).is_err());
+
+    Ok(())
+}
+
+/// `--squash` commits the branch as one commit; `--keep-branch` keeps it
+#[test]
+fn test_finish_squash_keep_branch() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    let worktree = env.worktree_path("auth");
+    commit_file(worktree.path(), "auth.txt", "auth")?;
+    commit_file(worktree.path(), "login.txt", "login")?;
+    commit_file(env.repo_dir.path(), "main.txt", "main")?;
+
+    env.run_command(&["finish", "auth", "--squash", "--keep-branch"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Kept branch feature/auth"));
+
+    let parents = env.git(&["rev-list", "--parents", "-n", "1", "HEAD"])?;
+    assert_eq!(parents.split_whitespace().count(), 2);
+    env.repo_dir
+        .child("login.txt")
+        .assert(predicate::path::exists());
+    env.git(&["rev-parse", "--verify", "feature/auth"])?;
+    worktree.assert(predicate::path::missing());
+
+    Ok(())
+}
+
+/// Conflicts and uncommitted changes leave the worktree and main untouched
+#[test]
+fn test_finish_keeps_worktree_on_failure() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    let worktree = env.worktree_path("auth");
+    commit_file(worktree.path(), "README.md", "# Auth")?;
+    commit_file(env.repo_dir.path(), "README.md", "# Main")?;
+    let main_before = env.git(&["rev-parse", "HEAD"])?;
+
+    env.run_command(&["finish", "auth"])?
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Conflicts merging feature/auth"))
+        .stderr(predicate::str::contains("README.md"));
+    assert_eq!(env.git(&["rev-parse", "HEAD"])?, main_before);
+    assert!(env.git(&["status", "--porcelain"])?.is_empty());
+    worktree.assert(predicate::path::is_dir());
+
+    worktree.child("notes.txt").write_str("uncommitted")?;
+    env.run_command(&["finish", "auth"])?
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("uncommitted changes"));
+    worktree.assert(predicate::path::is_dir());
+
+    Ok(())
+}
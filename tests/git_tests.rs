@@ -0,0 +1,152 @@
+//! Integration tests for the status queries on `GitOperations`
+//!
+//! Each check runs against libgit2 (`GitRepo`), the `git` binary (`CliGitRepo`), and, with
+//! the `gix` feature, gitoxide (`GixRepo`), so all backends answer the same way.
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use std::path::Path;
+
+use test_support::{CliTestEnvironment, git};
+use worktree::git::GitRepo;
+use worktree::traits::{AheadBehind, GitOperations};
+
+type Open = fn(&Path) -> worktree::git::Result<Box<dyn GitOperations>>;
+
+fn open_git2(path: &Path) -> worktree::git::Result<Box<dyn GitOperations>> {
+    Ok(Box::new(GitRepo::open(path)?))
+}
+
+fn open_cli(path: &Path) -> worktree::git::Result<Box<dyn GitOperations>> {
+    Ok(Box::new(worktree::git::CliGitRepo::open(path)?))
+}
+
+#[cfg(feature = "gix")]
+fn open_gix(path: &Path) -> worktree::git::Result<Box<dyn GitOperations>> {
+    Ok(Box::new(worktree::git::GixRepo::open(path)?))
+}
+
+fn check_upstream_and_ahead_behind(open: Open) -> Result<()> {
+    let env = CliTestEnvironment::builder().remote("origin").build()?;
+    let repo = env.repo_dir.path();
+    let ops = open(repo)?;
+
+    assert_eq!(ops.upstream("main")?.as_deref(), Some("origin/main"));
+    assert_eq!(ops.ahead_behind("main")?, Some(AheadBehind::default()));
+
+    git(repo, &["commit", "--allow-empty", "-m", "local work"])?;
+    git(repo, &["commit", "--allow-empty", "-m", "more local work"])?;
+    assert_eq!(
+        ops.ahead_behind("main")?,
+        Some(AheadBehind {
+            ahead: 2,
+            behind: 0
+        })
+    );
+    assert_eq!(ops.unpushed_commits("main")?, 2);
+
+    git(repo, &["branch", "untracked"])?;
+    assert_eq!(ops.upstream("untracked")?, None);
+    assert_eq!(ops.ahead_behind("untracked")?, None);
+    assert!(ops.upstream("no-such-branch").is_err());
+    Ok(())
+}
+
+fn check_ref_listing(open: Open) -> Result<()> {
+    let env = CliTestEnvironment::builder()
+        .remote("origin")
+        .branch("feature")
+        .tag("v1.0")
+        .build()?;
+    let repo = env.repo_dir.path();
+    let ops = open(repo)?;
+
+    assert!(ops.branch_exists("feature")?);
+    assert!(!ops.branch_exists("missing")?);
+
+    let mut local = ops.list_local_branches()?;
+    local.sort();
+    assert_eq!(local, ["feature", "main"]);
+    assert_eq!(ops.list_remote_branches()?, ["origin/main"]);
+    assert_eq!(ops.list_tags()?, ["v1.0"]);
+    Ok(())
+}
+
+fn check_dirty_merged_and_last_commit(open: Open) -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    let repo = env.repo_dir.path();
+    let ops = open(repo)?;
+
+    assert!(!ops.is_dirty(repo)?);
+    assert!(ops.changed_files(repo)?.is_empty());
+    env.repo_dir.child("scratch.txt").write_str("notes")?;
+    env.repo_dir.child("docs/new file.md").write_str("draft")?;
+    assert!(ops.is_dirty(repo)?);
+    let mut changed = ops.changed_files(repo)?;
+    changed.sort();
+    assert_eq!(
+        changed,
+        [Path::new("docs/new file.md"), Path::new("scratch.txt")]
+    );
+
+    git(repo, &["checkout", "-b", "feature"])?;
+    git(repo, &["commit", "--allow-empty", "-m", "Add feature"])?;
+    assert!(!ops.is_merged_into("feature", "main")?);
+    git(repo, &["checkout", "main"])?;
+    git(repo, &["merge", "--ff-only", "feature"])?;
+    assert!(ops.is_merged_into("feature", "main")?);
+
+    let commit = ops.last_commit(repo)?;
+    assert_eq!(commit.summary, "Add feature");
+    assert_eq!(commit.author, "Test User");
+    assert_eq!(commit.id.len(), 40);
+    Ok(())
+}
+
+#[test]
+fn test_upstream_and_ahead_behind() -> Result<()> {
+    check_upstream_and_ahead_behind(open_git2)
+}
+
+#[test]
+fn test_dirty_merged_and_last_commit() -> Result<()> {
+    check_dirty_merged_and_last_commit(open_git2)
+}
+
+#[test]
+fn test_ref_listing() -> Result<()> {
+    check_ref_listing(open_git2)
+}
+
+#[test]
+fn test_cli_upstream_and_ahead_behind() -> Result<()> {
+    check_upstream_and_ahead_behind(open_cli)
+}
+
+#[test]
+fn test_cli_dirty_merged_and_last_commit() -> Result<()> {
+    check_dirty_merged_and_last_commit(open_cli)
+}
+
+#[test]
+fn test_cli_ref_listing() -> Result<()> {
+    check_ref_listing(open_cli)
+}
+
+#[cfg(feature = "gix")]
+#[test]
+fn test_gix_upstream_and_ahead_behind() -> Result<()> {
+    check_upstream_and_ahead_behind(open_gix)
+}
+
+#[cfg(feature = "gix")]
+#[test]
+fn test_gix_dirty_merged_and_last_commit() -> Result<()> {
+    check_dirty_merged_and_last_commit(open_gix)
+}
+
+#[cfg(feature = "gix")]
+#[test]
+fn test_gix_ref_listing() -> Result<()> {
+    check_ref_listing(open_gix)
+}
@@ -0,0 +1,82 @@
+//! Integration tests for `worktree info` and `status <target>`
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use test_support::{CliTestEnvironment, create_sample_config_files};
+
+/// `info` shows the branch, base, origin, metadata, uncommitted files, and the files
+/// copied at creation, as text and JSON
+#[test]
+fn test_info_shows_worktree_details() -> Result<()> {
+    let env = CliTestEnvironment::builder().branch("develop").build()?;
+    create_sample_config_files(&env.repo_dir)?;
+    env.run_command(&["create", "auth", "feature/auth", "--from", "develop"])?
+        .assert()
+        .success();
+    env.run_command(&["label", "add", "auth", "review"])?
+        .assert()
+        .success();
+    env.run_command(&["note", "auth", "Waiting on API review"])?
+        .assert()
+        .success();
+    env.worktree_path("auth")
+        .child("scratch.txt")
+        .write_str("wip")?;
+
+    env.run_command(&["info", "auth"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("test_repo/auth\n"))
+        .stdout(predicate::str::contains(
+            "  Branch:        feature/auth (no upstream)\n",
+        ))
+        .stdout(predicate::str::contains("  Base:          develop\n"))
+        .stdout(predicate::str::contains(format!(
+            "  Origin:        {}\n",
+            env.repo_dir.path().display()
+        )))
+        .stdout(predicate::str::contains("  Labels:        review\n"))
+        .stdout(predicate::str::contains(
+            "  Note:          Waiting on API review\n",
+        ))
+        .stdout(predicate::str::contains("  Uncommitted changes (4):\n"))
+        .stdout(predicate::str::contains("    scratch.txt\n"))
+        .stdout(predicate::str::contains("  Copied at creation (3):\n"))
+        .stdout(predicate::str::contains("    .env\n"));
+
+    let output = env.run_command(&["status", "auth", "--json"])?.output()?;
+    assert!(output.status.success());
+    let info: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(info["feature"], "auth");
+    assert_eq!(info["base"], "develop");
+    // The copied config files are untracked in the test repository too
+    assert!(
+        info["dirty_files"]
+            .as_array()
+            .is_some_and(|files| files.contains(&serde_json::json!("scratch.txt")))
+    );
+    assert_eq!(info["labels"], serde_json::json!(["review"]));
+    assert!(info["created"].is_u64());
+
+    // The current worktree is the default
+    let mut cmd = env.run_command(&["info"])?;
+    cmd.current_dir(env.worktree_path("auth").path());
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("test_repo/auth\n"));
+
+    env.run_command(&["info"])?
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Not inside a worktree"));
+
+    // Removing the worktree drops its manifest
+    env.run_command(&["remove", "auth"])?.assert().success();
+    env.storage_dir
+        .child("test_repo")
+        .child(".worktree-copied")
+        .assert(predicate::str::contains("auth").not());
+
+    Ok(())
+}
@@ -75,6 +75,7 @@ fn test_jump_ambiguous_match() -> Result<()> {
     env.run_command(&["jump", "test"])?
         .assert()
         .failure()
+        .code(5)
         .stderr(predicate::str::contains("Ambiguous worktree name"))
         .stderr(predicate::str::contains("test-alpha"))
         .stderr(predicate::str::contains("test-beta"));
@@ -91,6 +92,7 @@ fn test_jump_nonexistent_worktree() -> Result<()> {
     env.run_command(&["jump", "nonexistent"])?
         .assert()
         .failure()
+        .code(4)
         .stderr(predicate::str::contains("No worktree found matching"));
 
     Ok(())
@@ -226,3 +228,379 @@ fn test_jump_multiple_worktrees() -> Result<()> {
 
     Ok(())
 }
+
+/// `jump -` toggles between the last two locations, like `cd -`
+#[test]
+fn test_jump_previous_toggles() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    for (feature, branch) in [
+        ("toggle-a", "feature/toggle-a"),
+        ("toggle-b", "feature/toggle-b"),
+    ] {
+        env.run_command(&["create", feature, branch])?
+            .assert()
+            .success();
+    }
+    let session = std::process::id().to_string();
+    let a = env.worktree_path("toggle-a").path().canonicalize()?;
+    let b = env.worktree_path("toggle-b").path().canonicalize()?;
+
+    let jump = |from: &std::path::Path, args: &[&str]| -> Result<std::path::PathBuf> {
+        let assert_output = env
+            .run_command(args)?
+            .current_dir(from)
+            .env("WORKTREE_SESSION", &session)
+            .assert()
+            .success();
+        let stdout = String::from_utf8(assert_output.get_output().stdout.clone())?;
+        Ok(std::path::Path::new(stdout.trim()).canonicalize()?)
+    };
+
+    assert_eq!(jump(&a, &["jump", "toggle-b"])?, b);
+    assert_eq!(jump(&b, &["jump", "-"])?, a);
+    assert_eq!(jump(&a, &["jump", "-"])?, b);
+    assert_eq!(jump(&b, &["jump", "--last"])?, a);
+
+    Ok(())
+}
+
+/// `jump -` without any recorded history fails
+#[test]
+fn test_jump_previous_without_history_fails() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["jump", "-"])?
+        .env("WORKTREE_SESSION", std::process::id().to_string())
+        .assert()
+        .failure();
+    env.run_command(&["jump", "-"])?
+        .env_remove("WORKTREE_SESSION")
+        .assert()
+        .failure();
+
+    Ok(())
+}
+
+/// Ambiguous matches are listed most frecently visited first
+#[test]
+fn test_jump_ambiguous_candidates_ordered_by_frecency() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    for (feature, branch) in [("freq-a", "feature/freq-a"), ("freq-b", "feature/freq-b")] {
+        env.run_command(&["create", feature, branch])?
+            .assert()
+            .success();
+    }
+
+    for _ in 0..2 {
+        env.run_command(&["jump", "freq-b"])?.assert().success();
+    }
+    env.storage_dir
+        .child(".worktree-frecency")
+        .assert(predicate::str::contains("test_repo/freq-b 2"));
+
+    let assert_output = env.run_command(&["jump", "freq"])?.assert().failure();
+    let stderr = String::from_utf8(assert_output.get_output().stderr.clone())?;
+    let b = stderr.find("test_repo/freq-b").unwrap();
+    let a = stderr.find("test_repo/freq-a").unwrap();
+    assert!(b < a, "frecent worktree should be listed first:\n{stderr}");
+
+    Ok(())
+}
+
+/// `repo/name` and `--repo` address a worktree of a specific repository when two
+/// repositories use the same feature name
+#[test]
+fn test_jump_qualified_by_repo() -> Result<()> {
+    let env = CliTestEnvironment::builder()
+        .other_repo("other_repo")
+        .build()?;
+    let other = env.other_repo("other_repo");
+
+    env.run_command(&["create", "shared", "feature/shared"])?
+        .assert()
+        .success();
+    env.run_command(&["create", "shared", "feature/shared"])?
+        .current_dir(other.path())
+        .assert()
+        .success();
+    let other_path = env.repo_worktree_path("other_repo", "shared");
+
+    let qualified = get_stdout(&env, &["jump", "other_repo/shared"])?;
+    assert_eq!(qualified.trim(), other_path.to_string_lossy());
+
+    let flagged = get_stdout(&env, &["jump", "shared", "--repo", "other_repo"])?;
+    assert_eq!(flagged.trim(), other_path.to_string_lossy());
+
+    // The branch checked out in the worktree also works after the repo prefix
+    let by_branch = get_stdout(&env, &["jump", "other_repo/feature/shared"])?;
+    assert_eq!(by_branch.trim(), other_path.to_string_lossy());
+
+    let own = get_stdout(&env, &["jump", "test_repo/shared"])?;
+    assert_eq!(own.trim(), env.worktree_path("shared").to_string_lossy());
+
+    Ok(())
+}
+
+/// `WORKTREE_SELECT` answers the interactive picker when there is no terminal
+#[test]
+fn test_jump_interactive_scripted_selection() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    for (feature, branch) in [("auth", "feature/auth"), ("payments", "feature/payments")] {
+        env.run_command(&["create", feature, branch])?
+            .assert()
+            .success();
+    }
+
+    let assert_output = env
+        .run_command(&["jump", "--interactive"])?
+        .env("WORKTREE_SELECT", "feature/payments")
+        .assert()
+        .success();
+    let output_path = String::from_utf8(assert_output.get_output().stdout.clone())?;
+    assert_eq!(
+        output_path.trim(),
+        env.worktree_path("payments").to_string_lossy()
+    );
+
+    env.run_command(&["jump", "--interactive"])?
+        .env_remove("WORKTREE_SELECT")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("WORKTREE_SELECT"));
+
+    Ok(())
+}
+
+/// A stand-in `zellij` on PATH that records its arguments, and the PATH to run it with
+#[cfg(unix)]
+fn fake_zellij(env: &CliTestEnvironment) -> Result<(assert_fs::fixture::ChildPath, String)> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let bin_dir = env.temp_dir().child("bin");
+    bin_dir.create_dir_all()?;
+    let log = env.temp_dir().child("zellij.log");
+    let script = bin_dir.child("zellij");
+    script.write_str(&format!(
+        "#!/bin/sh\necho \"$@\" >> '{}'\n",
+        log.path().display()
+    ))?;
+    std::fs::set_permissions(script.path(), std::fs::Permissions::from_mode(0o755))?;
+
+    let path = format!(
+        "{}:{}",
+        bin_dir.path().display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    Ok((log, path))
+}
+
+/// --zellij opens a new tab at the worktree instead of printing a path to cd to
+#[cfg(unix)]
+#[test]
+fn test_jump_zellij_opens_tab() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    let (log, path) = fake_zellij(&env)?;
+
+    env.run_command(&["jump", "auth", "--zellij"])?
+        .env("PATH", &path)
+        .env("ZELLIJ", "0")
+        .env("WORKTREE_CONFIG", env.temp_dir().child("none.toml").path())
+        .assert()
+        .success()
+        .stdout("");
+    log.assert(format!(
+        "action new-tab --cwd {} --name auth\n",
+        env.worktree_path("auth").display()
+    ));
+
+    // Tabs only exist inside a session
+    env.run_command(&["jump", "auth", "--zellij"])?
+        .env("PATH", &path)
+        .env_remove("ZELLIJ")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Not inside a Zellij session"));
+
+    Ok(())
+}
+
+/// `[zellij] auto = true` in the global config opens worktrees in Zellij inside a
+/// session, using the configured mode; --no-zellij goes back to changing directory
+#[cfg(unix)]
+#[test]
+fn test_jump_zellij_from_global_config() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    let (log, path) = fake_zellij(&env)?;
+    let config = env.temp_dir().child("config.toml");
+    config.write_str("[zellij]\nauto = true\nmode = \"pane\"\n")?;
+
+    env.run_command(&["jump", "auth"])?
+        .env("PATH", &path)
+        .env("ZELLIJ", "0")
+        .env("WORKTREE_CONFIG", config.path())
+        .assert()
+        .success()
+        .stdout("");
+    log.assert(format!(
+        "action new-pane --cwd {}\n",
+        env.worktree_path("auth").display()
+    ));
+
+    env.run_command(&["jump", "auth", "--no-zellij"])?
+        .env("PATH", &path)
+        .env("ZELLIJ", "0")
+        .env("WORKTREE_CONFIG", config.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            env.worktree_path("auth").to_string_lossy().to_string(),
+        ));
+
+    Ok(())
+}
+
+/// --create makes the worktree of an existing branch that has none, then prints its path
+#[test]
+fn test_jump_create_missing_worktree() -> Result<()> {
+    let env = CliTestEnvironment::builder().remote("origin").build()?;
+    env.git(&["branch", "feature/billing"])?;
+    env.git(&["branch", "feature/remote-only"])?;
+    env.git(&["push", "origin", "feature/remote-only"])?;
+    env.git(&["branch", "-D", "feature/remote-only"])?;
+
+    // Without --create and without a terminal to ask on, nothing is created
+    env.run_command(&["jump", "feature/billing"])?
+        .assert()
+        .failure()
+        .code(4);
+    env.run_command(&["jump", "feature/billing"])?
+        .env("WORKTREE_SELECT", "n")
+        .assert()
+        .failure()
+        .code(4);
+    env.worktree_path("billing")
+        .assert(predicate::path::missing());
+
+    // Creation output goes to stderr so the shell function only sees the path
+    env.run_command(&["jump", "feature/billing"])?
+        .env("WORKTREE_SELECT", "y")
+        .assert()
+        .success()
+        .stdout(format!("{}\n", env.worktree_path("billing").display()))
+        .stderr(predicate::str::contains("Worktree created successfully"));
+
+    env.run_command(&["jump", "feature/remote-only", "--create"])?
+        .assert()
+        .success()
+        .stdout(format!("{}\n", env.worktree_path("remote-only").display()));
+    assert_eq!(
+        env.git(&[
+            "rev-parse",
+            "--abbrev-ref",
+            "feature/remote-only@{upstream}"
+        ])?
+        .trim(),
+        "origin/feature/remote-only"
+    );
+
+    // Targets that are not branches still fail
+    env.run_command(&["jump", "nonexistent", "--create"])?
+        .assert()
+        .failure()
+        .code(4);
+    Ok(())
+}
+
+/// Prefixes of feature names win over substrings; `match` in the global config narrows
+/// matching for jump and remove alike
+#[test]
+fn test_jump_match_precedence_and_mode() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    for (feature, branch) in [
+        ("oauth-legacy", "feature/oauth-legacy"),
+        ("auth-v2", "feature/auth-v2"),
+    ] {
+        env.run_command(&["create", feature, branch])?
+            .assert()
+            .success();
+    }
+    let config = env.temp_dir().child("config.toml");
+
+    config.write_str("")?;
+    let jump = |target: &str| -> Result<assert_cmd::assert::Assert> {
+        Ok(env
+            .run_command(&["jump", target])?
+            .env("WORKTREE_CONFIG", config.path())
+            .assert())
+    };
+    jump("auth")?
+        .success()
+        .stdout(format!("{}\n", env.worktree_path("auth-v2").display()));
+    jump("legacy")?.success();
+
+    config.write_str("match = \"prefix\"\n")?;
+    jump("auth")?.success();
+    jump("legacy")?.failure().code(4);
+
+    config.write_str("match = \"exact\"\n")?;
+    jump("auth")?.failure().code(4);
+    jump("feature/auth-v2")?.success();
+    env.run_command(&["remove", "auth"])?
+        .env("WORKTREE_CONFIG", config.path())
+        .assert()
+        .failure()
+        .code(4);
+    env.worktree_path("auth-v2")
+        .assert(predicate::path::is_dir());
+
+    Ok(())
+}
+
+/// `match` in the repository's [jump] config takes precedence over the global one
+#[test]
+fn test_jump_match_repo_config() -> Result<()> {
+    let env = CliTestEnvironment::builder()
+        .config("[jump]\nmatch = \"exact\"\n")
+        .build()?;
+    env.run_command(&["create", "auth-v2", "feature/auth-v2"])?
+        .assert()
+        .success();
+    let config = env.temp_dir().child("config.toml");
+    config.write_str("match = \"prefix\"\n")?;
+
+    env.run_command(&["jump", "auth"])?
+        .env("WORKTREE_CONFIG", config.path())
+        .assert()
+        .failure()
+        .code(4);
+    env.run_command(&["jump", "auth-v2"])?
+        .env("WORKTREE_CONFIG", config.path())
+        .assert()
+        .success();
+    Ok(())
+}
+
+/// A repository config that cannot be read fails `jump` instead of silently falling back
+/// to the global `match`
+#[test]
+fn test_jump_match_unreadable_repo_config_fails() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .create_dir_all()?;
+
+    env.run_command(&["jump", "au"])?
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to read config file"));
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anyhow::Result;
+use predicates::prelude::*;
+use test_support::CliTestEnvironment;
+
+/// Labels are added, listed, filtered on, removed, and dropped with their worktree
+#[test]
+fn test_label_add_list_remove() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    for name in ["auth", "billing"] {
+        env.run_command(&["create", name, &format!("feature/{}", name)])?
+            .assert()
+            .success();
+    }
+
+    env.run_command(&["label", "add", "auth", "review", "client-x"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "✓ Labeled test_repo/auth 'review'",
+        ));
+    env.run_command(&["label", "add", "billing", "review"])?
+        .assert()
+        .success();
+    env.run_command(&["label", "add", "billing", "!bad"])?
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid label '!bad'"));
+
+    env.run_command(&["label", "list", "auth"])?
+        .assert()
+        .success()
+        .stdout("client-x\nreview\n");
+    env.run_command(&["label", "list"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "review    test_repo/auth, test_repo/billing",
+        ));
+    env.run_command(&["list", "--label", "client-x"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("labels: client-x, review"))
+        .stdout(predicate::str::contains("billing").not());
+
+    env.run_command(&["label", "remove", "auth", "review"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "✓ Removed label 'review' from test_repo/auth",
+        ));
+    env.run_command(&["label", "list", "auth"])?
+        .assert()
+        .success()
+        .stdout("client-x\n");
+
+    env.run_command(&["remove", "auth"])?.assert().success();
+    env.run_command(&["label", "list"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("client-x").not());
+
+    Ok(())
+}
@@ -3,6 +3,7 @@
 //! These tests validate the list command CLI behavior using real command execution.
 
 use anyhow::Result;
+use assert_fs::prelude::*;
 
 use test_support::CliTestEnvironment;
 
@@ -102,3 +103,209 @@ fn test_list_current_repo() -> Result<()> {
 
     Ok(())
 }
+
+/// Feature names in the order `list` printed them
+fn listed_order(output: &str, names: &[&str]) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            names
+                .iter()
+                .find(|name| line.contains(&format!(" {} (", name)))
+                .map(|name| (*name).to_string())
+        })
+        .collect()
+}
+
+/// `--sort` orders worktrees by creation time or size, and `--reverse` flips the order
+#[test]
+fn test_list_sort() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    let names = ["auth", "billing", "search"];
+    for name in names {
+        env.run_command(&["create", name, &format!("feature/{}", name)])?
+            .assert()
+            .success();
+    }
+    std::fs::write(env.worktree_path("search").join("big.bin"), [0u8; 100_000])?;
+
+    let output = get_stdout(&env, &["list", "--current", "--sort", "size"])?;
+    assert_eq!(listed_order(&output, &names)[0], "search");
+
+    let day = 24 * 60 * 60;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs();
+    env.storage_dir
+        .child("test_repo")
+        .child(".worktree-activity")
+        .write_str(&format!(
+            "auth {} -\nbilling {} -\nsearch {} -\n",
+            now - 3 * day,
+            now - day,
+            now - 2 * day
+        ))?;
+    let output = get_stdout(&env, &["list", "--sort", "created"])?;
+    assert_eq!(
+        listed_order(&output, &names),
+        vec!["billing", "search", "auth"]
+    );
+    assert!(output.contains("      created 3d ago, last used just now"));
+
+    let output = get_stdout(&env, &["list", "--current", "--reverse"])?;
+    assert_eq!(
+        listed_order(&output, &names),
+        vec!["search", "billing", "auth"]
+    );
+
+    Ok(())
+}
+
+/// `--label` keeps worktrees with a label, `--label '!name'` those without it, and `jump`
+/// takes the same filters
+#[test]
+fn test_list_label_filter() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    let names = ["auth", "billing", "search"];
+    for name in names {
+        env.run_command(&["create", name, &format!("feature/{}", name)])?
+            .assert()
+            .success();
+    }
+    env.storage_dir
+        .child("test_repo")
+        .child(".worktree-labels")
+        .write_str("auth\texperiment\nbilling\texperiment archived\n")?;
+
+    let output = get_stdout(&env, &["list", "--label", "experiment"])?;
+    assert_eq!(listed_order(&output, &names), vec!["auth", "billing"]);
+
+    let output = get_stdout(
+        &env,
+        &[
+            "list",
+            "--current",
+            "--label",
+            "experiment",
+            "--label",
+            "!archived",
+        ],
+    )?;
+    assert_eq!(listed_order(&output, &names), vec!["auth"]);
+
+    let output = get_stdout(&env, &["list", "--label", "missing"])?;
+    assert!(output.contains("No worktrees match the label filters."));
+
+    let output = env
+        .run_command(&["jump", "--interactive", "--label", "!experiment"])?
+        .env("WORKTREE_SELECT", "feature/")
+        .output()?;
+    assert_eq!(
+        String::from_utf8(output.stdout)?.trim(),
+        env.worktree_path("search").to_string_lossy()
+    );
+
+    Ok(())
+}
+
+/// `--format` prints one templated line per worktree, with `\t` escapes and status fields
+#[test]
+fn test_list_format() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    for name in ["auth", "billing"] {
+        env.run_command(&["create", name, &format!("feature/{}", name)])?
+            .assert()
+            .success();
+    }
+    std::fs::write(env.worktree_path("billing").join("scratch.txt"), "wip")?;
+    env.run_command(&["label", "add", "auth", "review", "client-x"])?
+        .assert()
+        .success();
+
+    let output = get_stdout(
+        &env,
+        &["list", "--format", r"{repo}\t{branch}{dirty}\t{labels}"],
+    )?;
+    assert_eq!(
+        output,
+        "test_repo\tfeature/auth\tclient-x,review\ntest_repo\tfeature/billing*\t\n"
+    );
+
+    let output = get_stdout(
+        &env,
+        &[
+            "list",
+            "--current",
+            "--label",
+            "review",
+            "--format",
+            "{feature} {path} {age}",
+        ],
+    )?;
+    assert_eq!(
+        output.trim(),
+        format!("auth {} just now", env.worktree_path("auth").display())
+    );
+
+    Ok(())
+}
+
+/// Branches `create` made are listed plainly; existing branches a worktree checked out
+/// are marked adopted, in the list and through `{managed}`
+#[test]
+fn test_list_marks_adopted_branches() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.git(&["branch", "hotfix"])?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    env.run_command(&["create", "review", "hotfix"])?
+        .assert()
+        .success();
+
+    for args in [&["list"][..], &["list", "--current"]] {
+        let output = get_stdout(&env, args)?;
+        assert!(output.contains("auth (feature/auth)  "), "{}", output);
+        assert!(output.contains("review (hotfix) [adopted]"), "{}", output);
+    }
+
+    let output = get_stdout(&env, &["list", "--format", "{feature} {managed}"])?;
+    assert_eq!(output, "auth managed\nreview adopted\n");
+
+    env.run_command(&["remove", "auth", "--delete-branch"])?
+        .assert()
+        .success();
+    let managed = std::fs::read_to_string(env.storage_dir.join("test_repo/.managed-branches"))?;
+    assert_eq!(managed, "");
+
+    Ok(())
+}
+
+/// `--color always` colors the status markers even when piped; `auto` leaves piped output
+/// plain, and `NO_COLOR` turns color off
+#[test]
+fn test_list_color_choice() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+
+    let output = get_stdout(&env, &["list", "--color", "always"])?;
+    assert!(output.contains("\u{1b}[1m\u{1b}[32m✓"), "{:?}", output);
+
+    let output = get_stdout(&env, &["list"])?;
+    assert!(
+        output.contains("✓ auth") && !output.contains('\u{1b}'),
+        "{:?}",
+        output
+    );
+
+    let assert = env
+        .run_command(&["list", "--color", "auto"])?
+        .env("NO_COLOR", "1")
+        .assert()
+        .success();
+    assert!(!String::from_utf8_lossy(&assert.get_output().stdout).contains('\u{1b}'));
+
+    Ok(())
+}
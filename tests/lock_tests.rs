@@ -0,0 +1,128 @@
+//! Integration tests for the storage metadata lock
+//!
+//! These tests simulate a second `worktree` process holding the lock by writing the lock
+//! file directly, then verify `--lock-timeout` and `lock-status` behavior.
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+use test_support::CliTestEnvironment;
+
+const LOCK_FILE: &str = ".worktree.lock";
+
+/// Returns the PID of a process that has already exited
+fn dead_pid() -> Result<u32> {
+    let mut child = std::process::Command::new("true").spawn()?;
+    let pid = child.id();
+    child.wait()?;
+    Ok(pid)
+}
+
+fn write_lock(env: &CliTestEnvironment, pid: u32) -> Result<()> {
+    env.storage_dir
+        .child(LOCK_FILE)
+        .write_str(&format!("pid={}\nacquired=0\n", pid))?;
+    Ok(())
+}
+
+/// lock-status reports an unlocked storage root
+#[test]
+fn test_lock_status_unlocked() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["lock-status"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("not locked"));
+
+    Ok(())
+}
+
+/// A held lock makes metadata writes fail after --lock-timeout with a helpful message
+#[test]
+fn test_create_fails_when_lock_held() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    write_lock(&env, std::process::id())?;
+
+    env.run_command(&["--lock-timeout", "0", "create", "locked", "feature/locked"])?
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(format!(
+            "locked by PID {}",
+            std::process::id()
+        )))
+        .stderr(predicate::str::contains("lock-status"));
+
+    Ok(())
+}
+
+/// The lock timeout can also be supplied via WORKTREE_LOCK_TIMEOUT
+#[test]
+fn test_lock_timeout_from_env() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    write_lock(&env, std::process::id())?;
+
+    env.run_command(&["create", "locked", "feature/locked"])?
+        .env("WORKTREE_LOCK_TIMEOUT", "0")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Storage is locked"));
+
+    Ok(())
+}
+
+/// A lock held by a running process is reported and not broken without --force
+#[test]
+fn test_lock_status_refuses_to_break_live_lock() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    write_lock(&env, std::process::id())?;
+
+    env.run_command(&["lock-status"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("running"));
+
+    env.run_command(&["lock-status", "--break"])?
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--force"));
+    env.storage_dir
+        .child(LOCK_FILE)
+        .assert(predicate::path::exists());
+
+    env.run_command(&["lock-status", "--break", "--force"])?
+        .assert()
+        .success();
+    env.storage_dir
+        .child(LOCK_FILE)
+        .assert(predicate::path::missing());
+
+    Ok(())
+}
+
+/// A stale lock is detected and can be broken, after which commands succeed again
+#[test]
+fn test_lock_status_breaks_stale_lock() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    write_lock(&env, dead_pid()?)?;
+
+    env.run_command(&["lock-status"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("stale"));
+
+    env.run_command(&["lock-status", "--break"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Lock removed"));
+
+    env.run_command(&["create", "after-break", "feature/after-break"])?
+        .assert()
+        .success();
+    env.storage_dir
+        .child(LOCK_FILE)
+        .assert(predicate::path::missing());
+
+    Ok(())
+}
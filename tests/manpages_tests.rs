@@ -0,0 +1,37 @@
+//! Integration tests for the hidden manpages command
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+use test_support::CliTestEnvironment;
+
+/// Writes one page per visible command, skipping hidden ones, into a new directory
+#[test]
+fn test_manpages_written_for_visible_commands() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    let out_dir = env.temp_dir().child("man").child("man1");
+
+    env.run_command(&["manpages", &out_dir.path().to_string_lossy()])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("worktree-jump.1"));
+
+    out_dir
+        .child("worktree.1")
+        .assert(predicate::path::is_file());
+    out_dir
+        .child("worktree-jump.1")
+        .assert(predicate::str::contains("Jump to a worktree directory"));
+    out_dir
+        .child("worktree-skill-install.1")
+        .assert(predicate::path::is_file());
+    out_dir
+        .child("worktree-manpages.1")
+        .assert(predicate::path::missing());
+    out_dir
+        .child("worktree-jump.1")
+        .assert(predicate::str::contains("list-completions").not());
+
+    Ok(())
+}
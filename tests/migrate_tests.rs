@@ -0,0 +1,49 @@
+//! Integration tests for `worktree migrate`
+
+use anyhow::Result;
+use predicates::prelude::*;
+
+use test_support::CliTestEnvironment;
+
+/// A root from before the version file is migrated; `--dry-run` only lists the steps
+#[test]
+fn test_migrate_dry_run_then_upgrade() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "old", "feature/old"])?
+        .assert()
+        .success();
+    let meta = env.worktree_path("old").join(".worktree-meta.toml");
+    let version_file = env.storage_dir.path().join(".storage-version");
+    std::fs::remove_file(&meta)?;
+
+    env.run_command(&["migrate", "--dry-run"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Format version: 1"))
+        .stdout(predicate::str::contains("→ 2: Write .worktree-meta.toml"));
+    assert!(!meta.exists());
+    assert!(!version_file.exists());
+
+    env.run_command(&["migrate"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(1 changed)"));
+    assert!(meta.exists());
+    assert_eq!(std::fs::read_to_string(&version_file)?.trim(), "2");
+
+    env.run_command(&["migrate"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Storage is up to date"));
+
+    // A root written by a newer release is left alone
+    std::fs::write(&version_file, "99\n")?;
+    env.run_command(&["migrate"])?
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "newer than this worktree supports",
+        ));
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use anyhow::Result;
+use predicates::prelude::*;
+use test_support::CliTestEnvironment;
+
+/// A note shows in `list -v`, `status`, and the jump picker, and can be cleared
+#[test]
+fn test_note_set_show_clear() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    for name in ["auth", "billing"] {
+        env.run_command(&["create", name, &format!("feature/{}", name)])?
+            .assert()
+            .success();
+    }
+
+    env.run_command(&["note", "auth", "Waiting on API review\nthen rebase"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✓ Noted on test_repo/auth"));
+    env.run_command(&["note", "auth"])?
+        .assert()
+        .success()
+        .stdout("Waiting on API review\nthen rebase\n");
+
+    env.run_command(&["list"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Waiting on API review").not());
+    env.run_command(&["list", "-v"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "      note: Waiting on API review\n            then rebase\n",
+        ));
+    env.run_command(&["status"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("note: Waiting on API review"));
+
+    let output = env
+        .run_command(&["jump", "--interactive"])?
+        .env("WORKTREE_SELECT", "API review")
+        .output()?;
+    assert_eq!(
+        String::from_utf8(output.stdout)?.trim(),
+        env.worktree_path("auth").to_string_lossy()
+    );
+
+    env.run_command(&["note", "auth", "--clear"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "✓ Cleared the note on test_repo/auth",
+        ));
+    env.run_command(&["note", "auth"])?
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("test_repo/auth has no note"));
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+//! Integration tests for the open command and `create --open`
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+use test_support::CliTestEnvironment;
+
+/// An editor template that records the path it was given
+fn recording_editor(env: &CliTestEnvironment) -> (assert_fs::fixture::ChildPath, String) {
+    let log = env.temp_dir().child("editor.log");
+    let editor = format!("printf '%s\\n' {{path}} >> '{}'", log.path().display());
+    (log, editor)
+}
+
+/// `open` runs the global config's editor on the worktree; the alias `code` does too
+#[test]
+fn test_open_uses_global_editor() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    let (log, editor) = recording_editor(&env);
+    let config = env.temp_dir().child("config.toml");
+    config.write_str(&format!("editor = \"{}\"\n", editor.replace('\\', "\\\\")))?;
+
+    for command in ["open", "code"] {
+        env.run_command(&[command, "auth"])?
+            .env("WORKTREE_CONFIG", config.path())
+            .assert()
+            .success();
+    }
+
+    let path = env.worktree_path("auth").display().to_string();
+    log.assert(format!("{}\n{}\n", path, path));
+
+    Ok(())
+}
+
+/// The repository's `editor` wins over the global one, and `create --open` uses it
+#[test]
+fn test_create_open_uses_repo_editor() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    let (log, editor) = recording_editor(&env);
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str(&format!("editor = \"{}\"\n", editor.replace('\\', "\\\\")))?;
+    let config = env.temp_dir().child("config.toml");
+    config.write_str("editor = \"false\"\n")?;
+
+    env.run_command(&["create", "auth", "feature/auth", "--open"])?
+        .env("WORKTREE_CONFIG", config.path())
+        .assert()
+        .success();
+
+    log.assert(format!("{}\n", env.worktree_path("auth").display()));
+
+    Ok(())
+}
+
+/// Without any configured editor, open explains how to set one
+#[test]
+fn test_open_without_editor() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+
+    env.run_command(&["open", "auth"])?
+        .env("WORKTREE_CONFIG", env.temp_dir().child("none.toml").path())
+        .env_remove("VISUAL")
+        .env_remove("EDITOR")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No editor configured"));
+
+    Ok(())
+}
+
+/// `open --with` runs the named `[open]` target, with repository targets overriding
+/// global ones, and lists the available targets for an unknown name
+#[test]
+fn test_open_with_named_target() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    let (log, recorder) = recording_editor(&env);
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str(&format!(
+            "[open.files]\ncommand = \"{}\"\n",
+            recorder.replace('\\', "\\\\")
+        ))?;
+    let config = env.temp_dir().child("config.toml");
+    config.write_str("[open.files]\ncommand = \"false\"\n[open.term]\ncommand = \"false\"\n")?;
+
+    env.run_command(&["open", "--with", "files", "auth"])?
+        .env("WORKTREE_CONFIG", config.path())
+        .assert()
+        .success();
+    log.assert(format!("{}\n", env.worktree_path("auth").display()));
+
+    env.run_command(&["open", "--with", "finder", "auth"])?
+        .env("WORKTREE_CONFIG", config.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Available: files, term"));
+
+    Ok(())
+}
@@ -541,3 +541,64 @@ fn test_stress_isolation() -> Result<()> {
 
     Ok(())
 }
+
+/// Test that concurrent commands sharing one storage root don't lose metadata updates
+///
+/// Each thread uses its own repository (all named `test_repo`) so that git itself is not
+/// contended; only the shared `.worktree-origins` file in the common storage root is.
+#[test]
+fn test_concurrent_creates_share_storage_lock() -> Result<()> {
+    let num_threads = 4;
+    let env = Arc::new(CliTestEnvironment::new()?);
+    let barrier = Arc::new(Barrier::new(num_threads));
+    let mut handles = Vec::new();
+
+    for i in 0..num_threads {
+        let env = Arc::clone(&env);
+        let barrier_clone = Arc::clone(&barrier);
+
+        let handle = thread::spawn(move || -> Result<()> {
+            let repo_env = CliTestEnvironment::new()?;
+            barrier_clone.wait();
+
+            let feature_name = format!("locked-{}", i);
+            let branch_name = format!("feature/locked-{}", i);
+            repo_env
+                .run_command(&["create", &feature_name, &branch_name])?
+                .env("WORKTREE_STORAGE_ROOT", env.storage_dir.path())
+                .assert()
+                .success();
+
+            Ok(())
+        });
+
+        handles.push(handle);
+    }
+
+    for (i, handle) in handles.into_iter().enumerate() {
+        match handle.join() {
+            Ok(result) => result.context(format!("Thread {} failed", i))?,
+            Err(_) => anyhow::bail!("Thread {} panicked", i),
+        }
+    }
+
+    // Every create must have recorded its origin, and the lock must be released
+    let origins = std::fs::read_to_string(
+        env.storage_dir
+            .child("test_repo")
+            .child(".worktree-origins")
+            .path(),
+    )?;
+    for i in 0..num_threads {
+        assert!(
+            origins.contains(&format!("locked-{} -> ", i)),
+            "Origin for locked-{} should be recorded",
+            i
+        );
+    }
+    env.storage_dir
+        .child(".worktree.lock")
+        .assert(predicates::path::missing());
+
+    Ok(())
+}
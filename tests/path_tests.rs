@@ -0,0 +1,70 @@
+//! Integration tests for the path command
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+use test_support::CliTestEnvironment;
+
+fn path_of(env: &CliTestEnvironment, target: &str) -> Result<String> {
+    let assert_output = env.run_command(&["path", target])?.assert().success();
+    Ok(String::from_utf8(
+        assert_output.get_output().stdout.clone(),
+    )?)
+}
+
+/// path resolves feature names, branches, and unique partial names like jump
+#[test]
+fn test_path_matches_like_jump() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    for (feature, branch) in [("auth", "feature/auth"), ("payments", "feature/payments")] {
+        env.run_command(&["create", feature, branch])?
+            .assert()
+            .success();
+    }
+
+    let auth = format!("{}\n", env.worktree_path("auth").display());
+    assert_eq!(path_of(&env, "auth")?, auth);
+    assert_eq!(path_of(&env, "feature/auth")?, auth);
+    assert_eq!(path_of(&env, "test_repo/auth")?, auth);
+    assert_eq!(
+        path_of(&env, "pay")?,
+        format!("{}\n", env.worktree_path("payments").display())
+    );
+
+    // Looking paths up is not a visit
+    env.storage_dir
+        .child(".worktree-frecency")
+        .assert(predicate::path::missing());
+
+    Ok(())
+}
+
+/// Missing and ambiguous targets fail without prompting, even with scripted answers
+#[test]
+fn test_path_never_prompts() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    for (feature, branch) in [("auth", "feature/auth"), ("author", "feature/author")] {
+        env.run_command(&["create", feature, branch])?
+            .assert()
+            .success();
+    }
+    env.git(&["branch", "feature/billing"])?;
+
+    env.run_command(&["path", "aut"])?
+        .env("WORKTREE_SELECT", "auth")
+        .assert()
+        .failure()
+        .code(5)
+        .stdout("");
+    env.run_command(&["path", "feature/billing"])?
+        .env("WORKTREE_SELECT", "y")
+        .assert()
+        .failure()
+        .code(4)
+        .stdout("");
+    env.worktree_path("billing")
+        .assert(predicate::path::missing());
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use anyhow::Result;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use test_support::{CliTestEnvironment, git};
+
+/// A stand-in `gh` on PATH that records its arguments one per line and prints `output`,
+/// and the PATH to run it with
+#[cfg(unix)]
+fn fake_gh(
+    env: &CliTestEnvironment,
+    output: &str,
+) -> Result<(assert_fs::fixture::ChildPath, String)> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let bin_dir = env.temp_dir().child("bin");
+    bin_dir.create_dir_all()?;
+    let log = env.temp_dir().child("gh.log");
+    let script = bin_dir.child("gh");
+    script.write_str(&format!(
+        "#!/bin/sh\nprintf '%s\\n' \"$@\" >> '{}'\nprintf '%s' '{}'\n",
+        log.path().display(),
+        output
+    ))?;
+    std::fs::set_permissions(script.path(), std::fs::Permissions::from_mode(0o755))?;
+
+    let path = format!(
+        "{}:{}",
+        bin_dir.path().display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    Ok((log, path))
+}
+
+/// `pr create` pushes the branch with upstream tracking and opens the pull request with
+/// the single commit's subject and body
+#[cfg(unix)]
+#[test]
+fn test_pr_create_pushes_and_opens_pull_request() -> Result<()> {
+    let env = CliTestEnvironment::builder().remote("origin").build()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    let worktree = env.worktree_path("auth");
+    worktree.child("auth.txt").write_str("auth")?;
+    git(worktree.path(), &["add", "auth.txt"])?;
+    git(
+        worktree.path(),
+        &["commit", "-m", "Add login", "-m", "Adds the login form."],
+    )?;
+    let (log, path) = fake_gh(&env, "")?;
+
+    env.run_command(&["pr", "create", "auth", "--draft"])?
+        .env("PATH", &path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✓ Pushed feature/auth to origin"));
+
+    assert_eq!(
+        git(
+            worktree.path(),
+            &["rev-parse", "--abbrev-ref", "@{upstream}"]
+        )?
+        .trim(),
+        "origin/feature/auth"
+    );
+    log.assert(
+        "pr\ncreate\n--head\nfeature/auth\n--base\nmain\n--title\nAdd login\n--body\n\
+        Adds the login form.\n--draft\n",
+    );
+
+    Ok(())
+}
+
+/// Without new commits there is nothing to open a pull request for, and nothing is
+/// pushed
+#[cfg(unix)]
+#[test]
+fn test_pr_create_without_commits() -> Result<()> {
+    let env = CliTestEnvironment::builder().remote("origin").build()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    let (log, path) = fake_gh(&env, "")?;
+
+    env.run_command(&["pr", "create", "auth"])?
+        .env("PATH", &path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("feature/auth has no commits"));
+
+    log.assert(predicate::path::missing());
+    assert!(
+        env.git(&["ls-remote", "--exit-code", "origin", "feature/auth"])
+            .is_err()
+    );
+
+    Ok(())
+}
+
+/// `list --pr` and `status --pr` show the branch's pull request state, asking `gh` only
+/// once thanks to the cache
+#[cfg(unix)]
+#[test]
+fn test_list_and_status_show_pull_request_state() -> Result<()> {
+    let env = CliTestEnvironment::builder().remote("origin").build()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    let (log, path) = fake_gh(
+        &env,
+        r#"[{"number":12,"state":"MERGED","isDraft":false,"reviewDecision":"APPROVED"}]"#,
+    )?;
+
+    env.run_command(&["list", "--current"])?
+        .env("PATH", &path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PR #").not());
+    log.assert(predicate::path::missing());
+
+    env.run_command(&["list", "--current", "--pr"])?
+        .env("PATH", &path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("      PR #12: merged\n"));
+    env.run_command(&["status", "--pr"])?
+        .env("PATH", &path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("      PR #12: merged\n"));
+
+    log.assert(predicate::str::contains("--head\nfeature/auth\n"));
+    let calls = std::fs::read_to_string(log.path())?
+        .matches("list\n")
+        .count();
+    assert_eq!(calls, 1);
+
+    Ok(())
+}
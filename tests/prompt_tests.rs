@@ -0,0 +1,75 @@
+//! Integration tests for the prompt command
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use test_support::CliTestEnvironment;
+
+fn prompt_in(env: &CliTestEnvironment, dir: &std::path::Path, args: &[&str]) -> Result<String> {
+    let mut command_args = vec!["prompt"];
+    command_args.extend_from_slice(args);
+    let assert_output = env
+        .run_command(&command_args)?
+        .current_dir(dir)
+        .assert()
+        .success();
+    Ok(String::from_utf8(
+        assert_output.get_output().stdout.clone(),
+    )?)
+}
+
+/// The main repository shows the origin marker, a managed worktree the worktree marker
+#[test]
+fn test_prompt_in_origin_and_worktree() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+
+    assert_eq!(
+        prompt_in(&env, env.repo_dir.path(), &[])?,
+        "⌂ test_repo:main\n"
+    );
+
+    let worktree = env.worktree_path("auth");
+    assert_eq!(
+        prompt_in(&env, worktree.path(), &[])?,
+        "⎇ test_repo:feature/auth\n"
+    );
+    assert_eq!(
+        prompt_in(&env, worktree.path(), &["--format", "{kind}:{feature}"])?,
+        "worktree:auth\n"
+    );
+
+    Ok(())
+}
+
+/// --dirty marks uncommitted changes; without it no status check is made
+#[test]
+fn test_prompt_dirty_marker() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.repo_dir.child("scratch.txt").write_str("wip")?;
+
+    assert_eq!(
+        prompt_in(&env, env.repo_dir.path(), &[])?,
+        "⌂ test_repo:main\n"
+    );
+    assert_eq!(
+        prompt_in(&env, env.repo_dir.path(), &["--dirty"])?,
+        "⌂ test_repo:main*\n"
+    );
+
+    Ok(())
+}
+
+/// Outside a repository the prompt prints nothing and succeeds
+#[test]
+fn test_prompt_outside_repository() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    let outside = env.temp_dir().child("elsewhere");
+    outside.create_dir_all()?;
+
+    assert_eq!(prompt_in(&env, outside.path(), &[])?, "");
+
+    Ok(())
+}
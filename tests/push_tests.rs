@@ -0,0 +1,94 @@
+use anyhow::Result;
+use predicates::prelude::*;
+use test_support::{CliTestEnvironment, commit_file, git};
+
+/// `push` publishes a new branch and sets its upstream
+#[test]
+fn test_push_sets_upstream() -> Result<()> {
+    let env = CliTestEnvironment::builder().remote("origin").build()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    let worktree = env.worktree_path("auth");
+    commit_file(worktree.path(), "auth.txt", "auth")?;
+
+    env.run_command(&["push", "auth"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "✓ Pushed feature/auth to origin/feature/auth",
+        ))
+        .stdout(predicate::str::contains(
+            "✓ Set upstream to origin/feature/auth",
+        ));
+
+    assert_eq!(
+        git(
+            worktree.path(),
+            &["rev-parse", "--abbrev-ref", "@{upstream}"]
+        )?
+        .trim(),
+        "origin/feature/auth"
+    );
+    assert_eq!(
+        git(worktree.path(), &["rev-parse", "origin/feature/auth"])?,
+        git(worktree.path(), &["rev-parse", "HEAD"])?
+    );
+
+    commit_file(worktree.path(), "login.txt", "login")?;
+    env.run_command(&["push", "auth"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Set upstream").not());
+
+    Ok(())
+}
+
+/// Rewritten history needs `--force-with-lease`, which refuses to overwrite commits
+/// pushed by someone else
+#[test]
+fn test_push_force_with_lease() -> Result<()> {
+    let env = CliTestEnvironment::builder().remote("origin").build()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    let worktree = env.worktree_path("auth");
+    commit_file(worktree.path(), "auth.txt", "auth")?;
+    env.run_command(&["push", "auth"])?.assert().success();
+
+    git(worktree.path(), &["commit", "--amend", "-m", "Add auth"])?;
+    env.run_command(&["push", "auth"])?
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("use --force-with-lease"));
+    env.run_command(&["push", "auth", "--force-with-lease"])?
+        .assert()
+        .success();
+    assert_eq!(
+        git(worktree.path(), &["rev-parse", "origin/feature/auth"])?,
+        git(worktree.path(), &["rev-parse", "HEAD"])?
+    );
+
+    // Someone else moves the remote branch
+    let remote = env.remote_path("origin");
+    git(
+        remote.path(),
+        &["update-ref", "refs/heads/feature/auth", "main"],
+    )?;
+    git(
+        worktree.path(),
+        &["commit", "--amend", "-m", "Add auth again"],
+    )?;
+    env.run_command(&["push", "auth", "--force-with-lease"])?
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "origin/feature/auth changed since it was last fetched",
+        ));
+
+    env.run_command(&["push", "main", "--force-with-lease"])?
+        .assert()
+        .failure();
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+//! Integration tests for `worktree relocate`
+
+use anyhow::Result;
+use predicates::prelude::*;
+
+use test_support::{CliTestEnvironment, git};
+
+/// Relocating moves the worktrees, repairs their links, and records the new root in the
+/// global config, which later commands then use
+#[test]
+fn test_relocate_moves_storage_and_updates_config() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+
+    let config = env.temp_dir().path().join("config.toml");
+    std::fs::write(
+        &config,
+        format!(
+            "# my settings\nstorage-root = {:?}\n",
+            env.storage_dir.path().to_string_lossy()
+        ),
+    )?;
+    let new_root = env.temp_dir().path().join("elsewhere").join("worktrees");
+    let run = |args: &[&str]| -> Result<assert_cmd::Command> {
+        let mut cmd = env.run_command(args)?;
+        cmd.env_remove("WORKTREE_STORAGE_ROOT")
+            .env("WORKTREE_CONFIG", &config);
+        Ok(cmd)
+    };
+
+    // A non-empty target is refused
+    std::fs::create_dir_all(env.temp_dir().path().join("full"))?;
+    std::fs::write(env.temp_dir().path().join("full").join("file"), "")?;
+    run(&[
+        "relocate",
+        &env.temp_dir().path().join("full").to_string_lossy(),
+    ])?
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("not empty"));
+
+    run(&["relocate", &new_root.to_string_lossy()])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Repaired 1 worktree(s)"));
+
+    assert!(!env.storage_dir.path().exists());
+    let worktree = new_root.join("test_repo").join("auth");
+    assert!(git(&worktree, &["status", "--short"]).is_ok());
+    let settings = std::fs::read_to_string(&config)?;
+    assert!(settings.contains("# my settings"));
+    assert!(settings.contains(&*new_root.to_string_lossy()));
+
+    run(&["path", "auth"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(&*worktree.to_string_lossy()));
+
+    Ok(())
+}
+
+/// Build cache symlinks and rendered `[build-cache.files]` follow the cache to the new
+/// root instead of dangling into the old one
+#[test]
+fn test_relocate_repoints_build_cache() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    std::fs::write(
+        env.repo_dir.path().join(".worktree-config.toml"),
+        "[build-cache]\nlink = [\"node_cache/\"]\n\n[build-cache.files]\n\".cargo/config.toml\" = \"[build]\\ntarget-dir = \\\"{cache}/target\\\"\\n\"\n",
+    )?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+
+    let config = env.temp_dir().path().join("config.toml");
+    std::fs::write(
+        &config,
+        format!(
+            "storage-root = {:?}\n",
+            env.storage_dir.path().to_string_lossy()
+        ),
+    )?;
+    let new_root = env.temp_dir().path().join("elsewhere").join("worktrees");
+    env.run_command(&["relocate", &new_root.to_string_lossy()])?
+        .env_remove("WORKTREE_STORAGE_ROOT")
+        .env("WORKTREE_CONFIG", &config)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Pointed 2 build cache link(s) and file(s) at the new root",
+        ));
+
+    let cache_dir = new_root.join("test_repo").join(".build-cache");
+    let worktree = new_root.join("test_repo").join("auth");
+    assert_eq!(
+        std::fs::read_link(worktree.join("node_cache"))?,
+        cache_dir.join("node_cache")
+    );
+    assert!(worktree.join("node_cache").is_dir());
+    assert_eq!(
+        std::fs::read_to_string(worktree.join(".cargo").join("config.toml"))?,
+        format!("[build]\ntarget-dir = \"{}/target\"\n", cache_dir.display())
+    );
+    Ok(())
+}
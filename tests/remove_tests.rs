@@ -7,7 +7,7 @@ use anyhow::Result;
 use assert_fs::prelude::*;
 use predicates::prelude::*;
 
-use test_support::CliTestEnvironment;
+use test_support::{CliTestEnvironment, git};
 
 /// Test basic removal of a worktree (branch preserved by default)
 #[test]
@@ -94,11 +94,7 @@ fn test_remove_detached_head_skips_branch_deletion() -> Result<()> {
     let wt = env.worktree_path("detached");
     wt.assert(predicate::path::is_dir());
 
-    std::process::Command::new("git")
-        .args(["checkout", "--detach"])
-        .current_dir(wt.path())
-        .status()
-        .expect("git checkout --detach should run");
+    git(wt.path(), &["checkout", "--detach"])?;
 
     // Remove with --delete-branch in detached HEAD — should warn but succeed
     env.run_command(&["remove", "detached", "--delete-branch"])?
@@ -117,6 +113,7 @@ fn test_remove_nonexistent_worktree() -> Result<()> {
     env.run_command(&["remove", "nonexistent"])?
         .assert()
         .failure()
+        .code(4)
         .stderr(predicate::str::contains("No worktree found"));
 
     Ok(())
@@ -159,3 +156,365 @@ fn test_remove_command_help() -> Result<()> {
 
     Ok(())
 }
+
+/// --delete-remote deletes the branch on the remote along with the worktree
+#[test]
+fn test_remove_delete_remote() -> Result<()> {
+    let env = CliTestEnvironment::builder().remote("origin").build()?;
+    let remote = env.remote_path("origin");
+
+    env.run_command(&["create", "shipped", "feature/shipped"])?
+        .assert()
+        .success();
+    git(
+        env.repo_dir.path(),
+        &["push", "-u", "origin", "feature/shipped"],
+    )?;
+
+    env.run_command(&["remove", "shipped", "--delete-branch", "--delete-remote"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "✓ Deleted remote branch origin/feature/shipped",
+        ))
+        .stdout(predicate::str::contains("✓ Branch deleted successfully"));
+
+    assert!(git(remote.path(), &["branch", "--list", "feature/shipped"])?.is_empty());
+    assert!(
+        git(
+            env.repo_dir.path(),
+            &["branch", "-r", "--list", "origin/feature/shipped"]
+        )?
+        .is_empty()
+    );
+
+    Ok(())
+}
+
+/// The [remove] config default applies unless --keep-remote is passed
+#[test]
+fn test_remove_delete_remote_config_default() -> Result<()> {
+    let env = CliTestEnvironment::builder()
+        .remote("origin")
+        .config("[remove]\ndelete-remote = true\n")
+        .build()?;
+    let remote = env.remote_path("origin");
+
+    for feature in ["kept", "dropped"] {
+        env.run_command(&["create", feature, feature])?
+            .assert()
+            .success();
+        git(env.repo_dir.path(), &["push", "origin", feature])?;
+    }
+
+    env.run_command(&["remove", "kept", "--keep-remote"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Deleted remote branch").not());
+    assert!(!git(remote.path(), &["branch", "--list", "kept"])?.is_empty());
+
+    env.run_command(&["remove", "dropped"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "✓ Deleted remote branch origin/dropped",
+        ));
+    assert!(git(remote.path(), &["branch", "--list", "dropped"])?.is_empty());
+
+    Ok(())
+}
+
+/// `delete-branch` in the [remove] config deletes branches unless --keep-branch is passed
+#[test]
+fn test_remove_delete_branch_config_default() -> Result<()> {
+    let env = CliTestEnvironment::builder()
+        .config("[remove]\ndelete-branch = true\n")
+        .build()?;
+
+    for feature in ["kept", "dropped"] {
+        env.run_command(&["create", feature, feature])?
+            .assert()
+            .success();
+    }
+
+    env.run_command(&["remove", "kept", "--keep-branch"])?
+        .assert()
+        .success();
+    assert!(!git(env.repo_dir.path(), &["branch", "--list", "kept"])?.is_empty());
+
+    env.run_command(&["remove", "dropped"])?.assert().success();
+    assert!(git(env.repo_dir.path(), &["branch", "--list", "dropped"])?.is_empty());
+
+    Ok(())
+}
+
+/// Branches that were never pushed and protected branches are left alone
+#[test]
+fn test_remove_delete_remote_skips_missing_and_protected() -> Result<()> {
+    let env = CliTestEnvironment::builder().remote("origin").build()?;
+    let remote = env.remote_path("origin");
+
+    env.run_command(&["create", "local-only", "local-only"])?
+        .assert()
+        .success();
+    env.run_command(&["remove", "local-only", "--delete-remote"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "No remote branch found for 'local-only'",
+        ));
+
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str("protected-branches = [\"release/*\"]\n")?;
+    env.run_command(&["create", "release", "release/1.0"])?
+        .assert()
+        .success();
+    git(env.repo_dir.path(), &["push", "origin", "release/1.0"])?;
+
+    env.run_command(&["remove", "release", "--delete-remote"])?
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("it is protected"));
+    assert!(!git(remote.path(), &["branch", "--list", "release/1.0"])?.is_empty());
+
+    Ok(())
+}
+
+/// Protected branches survive `--delete-branch` and the [remove] default; only
+/// --force-delete-branch deletes them
+#[test]
+fn test_remove_keeps_protected_branches() -> Result<()> {
+    let env = CliTestEnvironment::builder()
+        .config("protected-branches = [\"*-backup\"]\n[remove]\ndelete-branch = true\n")
+        .build()?;
+    for feature in ["main-backup", "old-backup", "forced-backup"] {
+        env.run_command(&["create", feature, feature])?
+            .assert()
+            .success();
+    }
+
+    env.run_command(&["remove", "main-backup"])?
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Not deleting branch 'main-backup': it is protected",
+        ));
+    env.run_command(&["remove", "old-backup", "--delete-branch"])?
+        .assert()
+        .success();
+    for branch in ["main-backup", "old-backup"] {
+        assert!(!git(env.repo_dir.path(), &["branch", "--list", branch])?.is_empty());
+    }
+
+    env.run_command(&["remove", "forced-backup", "--force-delete-branch"])?
+        .assert()
+        .success();
+    assert!(git(env.repo_dir.path(), &["branch", "--list", "forced-backup"])?.is_empty());
+
+    Ok(())
+}
+
+/// Deleting a branch with unpushed commits needs confirmation; without a terminal the
+/// removal is aborted before anything is touched
+#[test]
+fn test_remove_delete_branch_with_unpushed_commits_aborts() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["create", "wip", "feature/wip"])?
+        .assert()
+        .success();
+    let worktree = env.worktree_path("wip");
+    worktree.child("work.txt").write_str("work")?;
+    git(worktree.path(), &["add", "."])?;
+    git(worktree.path(), &["commit", "-m", "work"])?;
+
+    env.run_command(&["remove", "wip", "--delete-branch"])?
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("has 1 unpushed commit(s)"))
+        .stderr(predicate::str::contains("--force-delete-branch"));
+    worktree.assert(predicate::path::is_dir());
+
+    env.run_command(&["remove", "wip", "--force-delete-branch"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✓ Branch deleted successfully"));
+    worktree.assert(predicate::path::missing());
+    assert!(git(env.repo_dir.path(), &["branch", "--list", "feature/wip"])?.is_empty());
+
+    Ok(())
+}
+
+/// Several targets are removed in one invocation with a summary at the end
+#[test]
+fn test_remove_multiple_targets() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    for feature in ["multi-a", "multi-b", "multi-c"] {
+        env.run_command(&["create", feature, &format!("feature/{}", feature)])?
+            .assert()
+            .success();
+    }
+
+    env.run_command(&["remove", "multi-a", "multi-b", "multi-c", "--delete-branch"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Summary: removed 3 of 3 worktree(s)",
+        ))
+        .stdout(predicate::str::contains("  ✓ multi-b"));
+
+    for feature in ["multi-a", "multi-b", "multi-c"] {
+        env.worktree_path(feature)
+            .assert(predicate::path::missing());
+    }
+    assert!(
+        git(
+            env.repo_dir.path(),
+            &["branch", "--list", "feature/multi-*"]
+        )?
+        .is_empty()
+    );
+
+    Ok(())
+}
+
+/// A bad target does not stop the others; the command still fails at the end
+#[test]
+fn test_remove_multiple_targets_reports_failures() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["create", "keep-going", "keep-going"])?
+        .assert()
+        .success();
+
+    env.run_command(&["remove", "missing", "keep-going", "keep-going"])?
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "Summary: removed 1 of 2 worktree(s)",
+        ))
+        .stdout(predicate::str::contains(
+            "  ✗ missing: No worktree found matching 'missing'",
+        ))
+        .stderr(predicate::str::contains("Failed to remove 1 worktree(s)"));
+
+    env.worktree_path("keep-going")
+        .assert(predicate::path::missing());
+
+    Ok(())
+}
+
+/// remove --current removes the worktree containing the current directory and prints
+/// the origin repository
+#[test]
+fn test_remove_current_worktree() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["create", "here", "feature/here"])?
+        .assert()
+        .success();
+    let worktree = env.worktree_path("here");
+    worktree.child("src").create_dir_all()?;
+    let origin = env.repo_dir.path().canonicalize()?;
+
+    env.run_command(&["remove", "--current", "--delete-branch"])?
+        .current_dir(worktree.child("src").path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removing worktree 'here'"))
+        .stdout(predicate::str::contains("✓ Branch deleted successfully"))
+        .stdout(predicate::str::contains(format!(
+            "↩ Origin repository: {}",
+            origin.display()
+        )));
+
+    worktree.assert(predicate::path::missing());
+    assert!(git(&origin, &["worktree", "list"])?.lines().count() == 1);
+
+    Ok(())
+}
+
+/// remove --current outside a managed worktree fails without removing anything
+#[test]
+fn test_remove_current_outside_worktree_fails() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["create", "elsewhere", "elsewhere"])?
+        .assert()
+        .success();
+
+    env.run_command(&["remove", "--current"])?
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "must be run from inside a managed worktree",
+        ));
+    env.worktree_path("elsewhere")
+        .assert(predicate::path::is_dir());
+
+    Ok(())
+}
+
+/// --yes / -y is accepted globally, before or after the subcommand
+#[test]
+fn test_remove_accepts_yes_flag() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["create", "yes-a", "yes-a"])?
+        .assert()
+        .success();
+    env.run_command(&["create", "yes-b", "yes-b"])?
+        .assert()
+        .success();
+
+    env.run_command(&["-y", "remove", "yes-a"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cancelled").not());
+    env.run_command(&["remove", "yes-b", "--yes"])?
+        .assert()
+        .success();
+
+    env.worktree_path("yes-a")
+        .assert(predicate::path::missing());
+    env.worktree_path("yes-b")
+        .assert(predicate::path::missing());
+
+    Ok(())
+}
+
+/// A `repo/name` target removes a worktree of another repository
+#[test]
+fn test_remove_qualified_by_repo() -> Result<()> {
+    let env = CliTestEnvironment::builder()
+        .other_repo("other_repo")
+        .build()?;
+    let other = env.other_repo("other_repo");
+
+    env.run_command(&["create", "shared", "feature/shared"])?
+        .assert()
+        .success();
+    env.run_command(&["create", "shared", "feature/shared"])?
+        .current_dir(other.path())
+        .assert()
+        .success();
+
+    env.run_command(&["remove", "other_repo/shared"])?
+        .assert()
+        .success();
+
+    env.repo_worktree_path("other_repo", "shared")
+        .assert(predicate::path::missing());
+    env.worktree_path("shared")
+        .assert(predicate::path::is_dir());
+
+    // Targets in different repositories are rejected
+    env.run_command(&["remove", "test_repo/shared", "other_repo/x"])?
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("several repositories"));
+
+    Ok(())
+}
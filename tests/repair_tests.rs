@@ -0,0 +1,94 @@
+//! Integration tests for `worktree repair`
+//!
+//! These tests move the storage root or the main repository on disk, then verify that
+//! `repair` restores the git links in both directions and updates stored origins.
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use test_support::CliTestEnvironment;
+
+/// Returns true if `git status` succeeds in `dir`
+fn git_status_ok(dir: &std::path::Path) -> Result<bool> {
+    Ok(Command::new("git")
+        .args(["status", "--short"])
+        .current_dir(dir)
+        .output()?
+        .status
+        .success())
+}
+
+/// Nothing to do when no worktree has moved
+#[test]
+fn test_repair_intact_worktrees() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "intact", "feature/intact"])?
+        .assert()
+        .success();
+
+    env.run_command(&["repair"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nothing to repair"));
+
+    Ok(())
+}
+
+/// Moving the storage root breaks the main repository's gitdir pointers; repair fixes them
+#[test]
+fn test_repair_after_storage_root_moved() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "moved", "feature/moved"])?
+        .assert()
+        .success();
+
+    let new_root = env.storage_dir.path().with_file_name("moved_storage");
+    std::fs::rename(env.storage_dir.path(), &new_root)?;
+    let worktree = new_root.join("test_repo").join("moved");
+
+    env.run_command(&["repair"])?
+        .env("WORKTREE_STORAGE_ROOT", &new_root)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Repaired git links: moved"));
+
+    assert!(git_status_ok(&worktree)?);
+    let gitdir = std::fs::read_to_string(env.repo_dir.path().join(".git/worktrees/moved/gitdir"))?;
+    assert!(gitdir.contains("moved_storage"));
+
+    Ok(())
+}
+
+/// Moving the main repository breaks every worktree's .git file and the stored origins
+#[test]
+fn test_repair_after_main_repo_moved() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "relocated", "feature/relocated"])?
+        .assert()
+        .success();
+
+    let new_parent = env.repo_dir.path().with_file_name("elsewhere");
+    std::fs::create_dir_all(&new_parent)?;
+    let new_repo = new_parent.join("test_repo");
+    std::fs::rename(env.repo_dir.path(), &new_repo)?;
+
+    let worktree = env.worktree_path("relocated");
+    assert!(!git_status_ok(worktree.path())?);
+
+    env.run_command(&["repair"])?
+        .current_dir(&new_repo)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Repaired git links: relocated"))
+        .stdout(predicate::str::contains("Updated origin for relocated"));
+
+    assert!(git_status_ok(worktree.path())?);
+    env.storage_dir
+        .child("test_repo")
+        .child(".worktree-origins")
+        .assert(predicate::str::contains("elsewhere"));
+
+    Ok(())
+}
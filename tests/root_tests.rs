@@ -0,0 +1,64 @@
+//! Integration tests for the root command
+
+use anyhow::Result;
+use std::path::PathBuf;
+
+use test_support::CliTestEnvironment;
+
+fn root_from(env: &CliTestEnvironment, dir: &std::path::Path) -> Result<PathBuf> {
+    let assert_output = env
+        .run_command(&["root"])?
+        .current_dir(dir)
+        .assert()
+        .success();
+    let stdout = String::from_utf8(assert_output.get_output().stdout.clone())?;
+    Ok(PathBuf::from(stdout.trim()).canonicalize()?)
+}
+
+/// From the main repository (or a subdirectory of it), root prints the repository itself
+#[test]
+fn test_root_from_main_repo() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    let subdir = env.repo_dir.path().join("src");
+    std::fs::create_dir_all(&subdir)?;
+
+    assert_eq!(
+        root_from(&env, env.repo_dir.path())?,
+        env.repo_dir.path().canonicalize()?
+    );
+    assert_eq!(
+        root_from(&env, &subdir)?,
+        env.repo_dir.path().canonicalize()?
+    );
+
+    Ok(())
+}
+
+/// From a managed worktree, root prints the origin repository
+#[test]
+fn test_root_from_worktree() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "rooted", "feature/rooted"])?
+        .assert()
+        .success();
+
+    assert_eq!(
+        root_from(&env, env.worktree_path("rooted").path())?,
+        env.repo_dir.path().canonicalize()?
+    );
+
+    Ok(())
+}
+
+/// Outside any repository, root fails
+#[test]
+fn test_root_outside_repo_fails() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["root"])?
+        .current_dir(env.storage_dir.path())
+        .assert()
+        .failure();
+
+    Ok(())
+}
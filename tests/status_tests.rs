@@ -3,6 +3,7 @@
 //! These tests validate the status command CLI behavior using real command execution.
 
 use anyhow::Result;
+use assert_fs::prelude::*;
 
 use test_support::CliTestEnvironment;
 
@@ -82,3 +83,161 @@ fn test_status_basic() -> Result<()> {
 
     Ok(())
 }
+
+/// `--json` reports each worktree's branch state and flags directories that went missing
+#[test]
+fn test_status_json() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    for (feature, branch) in [("auth", "feature/auth"), ("billing", "feature/billing")] {
+        env.run_command(&["create", feature, branch])?
+            .assert()
+            .success();
+    }
+    std::fs::write(env.worktree_path("auth").join("scratch.txt"), "wip")?;
+    env.run_command(&["label", "add", "auth", "review"])?
+        .assert()
+        .success();
+    std::fs::remove_dir_all(env.worktree_path("billing"))?;
+
+    let report: serde_json::Value =
+        serde_json::from_str(&get_stdout(&env, &["status", "--json"])?)?;
+    assert_eq!(report["repo"], "test_repo");
+    let auth = &report["worktrees"][0];
+    assert_eq!(auth["feature"], "auth");
+    assert_eq!(auth["branch"], "feature/auth");
+    assert_eq!(auth["dirty"], true);
+    assert_eq!(auth["labels"], serde_json::json!(["review"]));
+    assert!(auth["created"].is_u64());
+    assert_eq!(report["worktrees"][1]["exists"], false);
+    assert_eq!(
+        report["warnings"],
+        serde_json::json!([{ "kind": "missing", "feature": "billing" }])
+    );
+
+    Ok(())
+}
+
+/// `--watch` clears the screen and redraws the report until it is stopped
+#[test]
+fn test_status_watch_redraws() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+
+    let assert = env
+        .run_command(&["status", "--watch", "--interval", "1"])?
+        .timeout(std::time::Duration::from_millis(2500))
+        .assert()
+        .interrupted();
+    let output = String::from_utf8(assert.get_output().stdout.clone())?;
+    assert!(output.starts_with("\x1b[2J\x1b[H"));
+    assert!(output.matches("Git Worktree Status").count() >= 2);
+
+    Ok(())
+}
+
+/// Backdates a worktree's only activity to 2020: a commit made then, its `.git` file, and
+/// its recorded creation and last use
+fn backdate_worktree(env: &CliTestEnvironment, feature: &str) -> Result<()> {
+    const OLD_DATE: &str = "2020-01-01T00:00:00";
+    let path = env.worktree_path(feature);
+    std::fs::write(path.join(format!("{}.txt", feature)), feature)?;
+    test_support::git(&path, &["add", "."])?;
+    let commit = std::process::Command::new("git")
+        .args(["commit", "-m", feature])
+        .env("GIT_AUTHOR_DATE", OLD_DATE)
+        .env("GIT_COMMITTER_DATE", OLD_DATE)
+        .current_dir(&path)
+        .output()?;
+    anyhow::ensure!(commit.status.success(), "backdated commit failed");
+    let touch = std::process::Command::new("touch")
+        .args(["-d", "2020-01-01", ".git"])
+        .current_dir(&path)
+        .output()?;
+    anyhow::ensure!(touch.status.success(), "touch failed");
+    env.backdate_recorded_activity(feature, 1_577_836_800)
+}
+
+/// Worktrees with no commits or file changes for `stale-after-days` are summarized with
+/// a command removing them; a recently changed file keeps a worktree fresh
+#[test]
+fn test_status_flags_stale_worktrees() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    for (feature, branch) in [
+        ("old-spike", "spike/old"),
+        ("wip", "feature/wip"),
+        ("fresh", "feature/fresh"),
+    ] {
+        env.run_command(&["create", feature, branch])?
+            .assert()
+            .success();
+    }
+    backdate_worktree(&env, "old-spike")?;
+    backdate_worktree(&env, "wip")?;
+    std::fs::write(env.worktree_path("wip").join("scratch.txt"), "today")?;
+
+    let output = get_stdout(&env, &["status"])?;
+    assert!(output.contains("Stale worktrees (1, no commits or file changes in 30+ days):"));
+    assert!(output.contains("  old-spike  last activity "));
+    assert!(output.contains("Remove them with: worktree remove old-spike\n"));
+
+    let report: serde_json::Value =
+        serde_json::from_str(&get_stdout(&env, &["status", "--json"])?)?;
+    assert_eq!(report["stale_after_days"], 30);
+    let stale: Vec<&str> = report["worktrees"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|w| w["stale"] == true)
+        .filter_map(|w| w["feature"].as_str())
+        .collect();
+    assert_eq!(stale, ["old-spike"]);
+
+    let output = get_stdout(&env, &["status", "--stale-days", "0"])?;
+    assert!(!output.contains("Stale worktrees"));
+
+    env.repo_dir
+        .child(".worktree-config.toml")
+        .write_str("[status]\nstale-after-days = 0\n")?;
+    let output = get_stdout(&env, &["status"])?;
+    assert!(!output.contains("Stale worktrees"));
+
+    Ok(())
+}
+
+/// `status` previews what `cleanup` would remove without removing it
+#[test]
+fn test_status_previews_cleanup() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    for (feature, branch) in [("auth", "feature/auth"), ("billing", "feature/billing")] {
+        env.run_command(&["create", feature, branch])?
+            .assert()
+            .success();
+    }
+    std::fs::remove_dir_all(env.worktree_path("billing"))?;
+
+    let output = get_stdout(&env, &["status"])?;
+    assert!(output.contains("Cleanup preview (2):"));
+    assert!(output.contains("  • git worktree reference 'billing'"));
+    assert!(output.contains("  • origin entry 'billing' (test_repo)"));
+    assert!(output.contains("Remove them with: worktree cleanup (add --dry-run"));
+
+    let report: serde_json::Value =
+        serde_json::from_str(&get_stdout(&env, &["status", "--json"])?)?;
+    let kinds: Vec<&str> = report["cleanup"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| item["kind"].as_str())
+        .collect();
+    assert_eq!(kinds, ["stale-git-reference", "stale-origin"]);
+
+    // Nothing was cleaned up
+    assert!(
+        env.git(&["worktree", "list"])?
+            .contains(&env.worktree_path("billing").display().to_string())
+    );
+
+    Ok(())
+}
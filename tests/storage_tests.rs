@@ -0,0 +1,72 @@
+//! Integration tests for storage root resolution
+//!
+//! These tests run the CLI without `WORKTREE_STORAGE_ROOT` and with a missing or broken
+//! `HOME`, as happens in containers and CI, and verify the documented fallbacks.
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+
+use test_support::CliTestEnvironment;
+
+/// With an unusable HOME, storage falls back to $XDG_DATA_HOME/worktree
+#[test]
+fn test_storage_falls_back_to_xdg_data_home() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    let xdg = env.storage_dir.child("xdg");
+    let bogus_home = env.storage_dir.child("home-is-a-file");
+    bogus_home.write_str("")?;
+
+    env.run_command(&["create", "no-home", "feature/no-home"])?
+        .env_remove("WORKTREE_STORAGE_ROOT")
+        .env("HOME", bogus_home.path())
+        .env("XDG_DATA_HOME", xdg.path())
+        .assert()
+        .success();
+
+    xdg.child("worktree")
+        .child("test_repo")
+        .child("no-home")
+        .assert(predicate::path::is_dir());
+
+    Ok(())
+}
+
+/// With an unusable HOME and no XDG_DATA_HOME, storage falls back to ./.worktrees with a warning
+#[test]
+fn test_storage_falls_back_to_current_dir_with_warning() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    let bogus_home = env.storage_dir.child("home-is-a-file");
+    bogus_home.write_str("")?;
+
+    env.run_command(&["list"])?
+        .env_remove("WORKTREE_STORAGE_ROOT")
+        .env_remove("XDG_DATA_HOME")
+        .env("HOME", bogus_home.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Warning"))
+        .stderr(predicate::str::contains("WORKTREE_STORAGE_ROOT"));
+
+    env.repo_dir
+        .child(".worktrees")
+        .assert(predicate::path::is_dir());
+
+    Ok(())
+}
+
+/// An unwritable WORKTREE_STORAGE_ROOT is reported rather than silently replaced
+#[test]
+fn test_unwritable_storage_root_is_an_error() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    let file = env.storage_dir.child("root-is-a-file");
+    file.write_str("")?;
+
+    env.run_command(&["list"])?
+        .env("WORKTREE_STORAGE_ROOT", file.child("nested").path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("WORKTREE_STORAGE_ROOT"));
+
+    Ok(())
+}
@@ -383,3 +383,86 @@ fn test_sync_config_empty_source() -> Result<()> {
 
     Ok(())
 }
+
+/// Test that a missing target falls back to interactive selection, which fails without a TTY
+#[test]
+fn test_sync_config_missing_target_requires_tty() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["create", "only-source", "feature/only-source"])?
+        .assert()
+        .success();
+
+    env.run_command(&["sync-config", "only-source"])?
+        .assert()
+        .failure();
+
+    env.worktree_path("only-source")
+        .assert(predicate::path::is_dir());
+
+    Ok(())
+}
+
+/// Test that --only limits syncing to the given pattern
+#[test]
+fn test_sync_config_only_pattern() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["create", "only-src", "feature/only-src"])?
+        .assert()
+        .success();
+    env.run_command(&["create", "only-dst", "feature/only-dst"])?
+        .assert()
+        .success();
+
+    let source_path = env.worktree_path("only-src");
+    let target_path = env.worktree_path("only-dst");
+    create_sample_config_files(&source_path)?;
+
+    env.run_command(&["sync-config", "only-src", "only-dst", "--only", ".env*"])?
+        .assert()
+        .success();
+
+    target_path
+        .child(".env")
+        .assert(predicate::str::contains("TEST_VAR"));
+    target_path
+        .child(".vscode")
+        .assert(predicate::path::missing());
+    target_path
+        .child("config.local.json")
+        .assert(predicate::path::missing());
+
+    Ok(())
+}
+
+/// Test that --only still honors configured exclude patterns and warns on no matches
+#[test]
+fn test_sync_config_only_respects_excludes_and_warns() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    create_worktree_config(&env.repo_dir, &[], &["*.log"])?;
+
+    env.run_command(&["create", "excl-src", "feature/excl-src"])?
+        .assert()
+        .success();
+    env.run_command(&["create", "excl-dst", "feature/excl-dst"])?
+        .assert()
+        .success();
+
+    let source_path = env.worktree_path("excl-src");
+    source_path.child("debug.log").write_str("noise")?;
+
+    env.run_command(&["sync-config", "excl-src", "excl-dst", "--only", "*.log"])?
+        .assert()
+        .success();
+    env.worktree_path("excl-dst")
+        .child("debug.log")
+        .assert(predicate::path::missing());
+
+    env.run_command(&["sync-config", "excl-src", "excl-dst", "--only", "nope.txt"])?
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("No files matched 'nope.txt'"));
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use test_support::{CliTestEnvironment, commit_file, git};
+
+/// `sync` updates clean worktrees, skips dirty ones, and reports conflicts without
+/// touching the conflicted branch
+#[test]
+fn test_sync_updates_every_worktree() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    for feature in ["auth", "billing", "search"] {
+        env.run_command(&["create", feature, &format!("feature/{}", feature)])?
+            .assert()
+            .success();
+    }
+    let auth = env.worktree_path("auth");
+    let billing = env.worktree_path("billing");
+    let search = env.worktree_path("search");
+    commit_file(auth.path(), "auth.txt", "auth")?;
+    billing.child("notes.txt").write_str("uncommitted")?;
+    commit_file(search.path(), "README.md", "# Search")?;
+    commit_file(env.repo_dir.path(), "README.md", "# Main")?;
+    let search_before = git(search.path(), &["rev-parse", "HEAD"])?;
+
+    env.run_command(&["sync"])?
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "✓ auth (feature/auth): updated onto main",
+        ))
+        .stdout(predicate::str::contains(
+            "⚠ billing (feature/billing): skipped (uncommitted changes)",
+        ))
+        .stdout(predicate::str::contains(
+            "✗ search (feature/search): conflicts",
+        ))
+        .stdout(predicate::str::contains(
+            "Summary: 1 updated, 0 up to date, 1 skipped, 1 failed",
+        ));
+
+    git(
+        auth.path(),
+        &["merge-base", "--is-ancestor", "main", "HEAD"],
+    )?;
+    assert!(
+        git(
+            billing.path(),
+            &["merge-base", "--is-ancestor", "main", "HEAD"]
+        )
+        .is_err()
+    );
+    assert_eq!(git(search.path(), &["rev-parse", "HEAD"])?, search_before);
+
+    Ok(())
+}
+
+/// With nothing to do, `sync` succeeds
+#[test]
+fn test_sync_up_to_date() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+
+    env.run_command(&["sync", "--merge"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "= auth (feature/auth): up to date with main",
+        ));
+
+    Ok(())
+}
@@ -0,0 +1,249 @@
+//! Builder for test environments that need more than a single-commit repository
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use crate::test_env::{git, CliTestEnvironment};
+
+/// One setup step, applied in the order it was added
+enum Step {
+    Commits(usize),
+    Branch(String),
+    Tag(String),
+    Remote(String),
+    PushedBranch { remote: String, branch: String },
+    RemoteBranch { remote: String, branch: String },
+    Submodule(String),
+    Config(String),
+    OtherRepo(String),
+    BareRepo(String),
+}
+
+/// Builds a [`CliTestEnvironment`] whose repository is set up beyond the initial commit.
+/// Steps run in the order they are added, so a remote must be added before branches are
+/// pushed to it.
+///
+/// ```no_run
+/// # use test_support::CliTestEnvironment;
+/// let env = CliTestEnvironment::builder()
+///     .commits(2)
+///     .remote("origin")
+///     .pushed_branch("origin", "feature/shared")
+///     .tag("v1.0")
+///     .build()?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Default)]
+pub struct CliTestEnvironmentBuilder {
+    steps: Vec<Step>,
+}
+
+impl CliTestEnvironmentBuilder {
+    /// Adds `count` commits on top of the current branch, each adding a file
+    pub fn commits(mut self, count: usize) -> Self {
+        self.steps.push(Step::Commits(count));
+        self
+    }
+
+    /// Creates a local branch at the current commit
+    pub fn branch(mut self, name: &str) -> Self {
+        self.steps.push(Step::Branch(name.to_string()));
+        self
+    }
+
+    /// Creates a lightweight tag at the current commit
+    pub fn tag(mut self, name: &str) -> Self {
+        self.steps.push(Step::Tag(name.to_string()));
+        self
+    }
+
+    /// Adds a bare remote `name` (at [`CliTestEnvironment::remote_path`]) and pushes
+    /// `main` to it with upstream tracking
+    pub fn remote(mut self, name: &str) -> Self {
+        self.steps.push(Step::Remote(name.to_string()));
+        self
+    }
+
+    /// Creates a local branch at the current commit and pushes it to `remote` with
+    /// upstream tracking
+    pub fn pushed_branch(mut self, remote: &str, branch: &str) -> Self {
+        self.steps.push(Step::PushedBranch {
+            remote: remote.to_string(),
+            branch: branch.to_string(),
+        });
+        self
+    }
+
+    /// Pushes the current commit to `branch` on `remote` and fetches it, without a
+    /// local branch (so it only shows up as `<remote>/<branch>`)
+    pub fn remote_branch(mut self, remote: &str, branch: &str) -> Self {
+        self.steps.push(Step::RemoteBranch {
+            remote: remote.to_string(),
+            branch: branch.to_string(),
+        });
+        self
+    }
+
+    /// Adds a freshly created repository as a committed submodule at `path`
+    pub fn submodule(mut self, path: &str) -> Self {
+        self.steps.push(Step::Submodule(path.to_string()));
+        self
+    }
+
+    /// Writes `content` to `.worktree-config.toml` in the repository (uncommitted)
+    pub fn config(mut self, content: &str) -> Self {
+        self.steps.push(Step::Config(content.to_string()));
+        self
+    }
+
+    /// Creates another repository (on `main`, with one commit) sharing the storage
+    /// directory, at [`CliTestEnvironment::other_repo`]
+    pub fn other_repo(mut self, name: &str) -> Self {
+        self.steps.push(Step::OtherRepo(name.to_string()));
+        self
+    }
+
+    /// Creates a bare clone of the test repository as it is at this step, at
+    /// [`CliTestEnvironment::bare_repo`]
+    pub fn bare_repo(mut self, name: &str) -> Self {
+        self.steps.push(Step::BareRepo(name.to_string()));
+        self
+    }
+
+    /// Creates the environment and applies every step
+    ///
+    /// # Errors
+    /// Returns an error if the environment cannot be created or a git command fails.
+    pub fn build(self) -> Result<CliTestEnvironment> {
+        let env = CliTestEnvironment::new()?;
+        let mut commit_count = 0;
+
+        for step in self.steps {
+            match step {
+                Step::Commits(count) => {
+                    for _ in 0..count {
+                        commit_count += 1;
+                        let file = format!("file-{commit_count}.txt");
+                        env.repo_dir.child(&file).write_str(&file)?;
+                        env.git(&["add", &file])?;
+                        env.git(&["commit", "-m", &format!("Commit {commit_count}")])?;
+                    }
+                }
+                Step::Branch(name) => {
+                    env.git(&["branch", &name])?;
+                }
+                Step::Tag(name) => {
+                    env.git(&["tag", &name])?;
+                }
+                Step::Remote(name) => {
+                    let remote = env.remote_path(&name);
+                    let url = remote.path().to_string_lossy().to_string();
+                    git(env.temp_dir().path(), &["init", "--bare", &url])?;
+                    env.git(&["remote", "add", &name, &url])?;
+                    env.git(&["push", "-u", &name, "main"])?;
+                }
+                Step::PushedBranch { remote, branch } => {
+                    env.git(&["branch", &branch])?;
+                    env.git(&["push", "-u", &remote, &branch])?;
+                }
+                Step::RemoteBranch { remote, branch } => {
+                    env.git(&["push", &remote, &format!("HEAD:refs/heads/{branch}")])?;
+                    env.git(&["fetch", &remote])?;
+                }
+                Step::Submodule(path) => {
+                    let name = format!("submodule-{}", path.replace('/', "-"));
+                    let submodule = env.create_other_repo(&name)?;
+                    let url = submodule.path().to_string_lossy().to_string();
+                    env.git(&[
+                        "-c",
+                        "protocol.file.allow=always",
+                        "submodule",
+                        "add",
+                        &url,
+                        &path,
+                    ])?;
+                    env.git(&["commit", "-m", &format!("Add submodule {path}")])?;
+                }
+                Step::Config(content) => {
+                    env.repo_dir
+                        .child(".worktree-config.toml")
+                        .write_str(&content)?;
+                }
+                Step::OtherRepo(name) => {
+                    env.create_other_repo(&name)?;
+                }
+                Step::BareRepo(name) => {
+                    env.create_bare_repo(&name)?;
+                }
+            }
+        }
+
+        Ok(env)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use predicates::prelude::*;
+
+    #[test]
+    fn test_builder_sets_up_repository() -> Result<()> {
+        let env = CliTestEnvironment::builder()
+            .commits(2)
+            .branch("feature/local")
+            .tag("v1.0")
+            .remote("origin")
+            .pushed_branch("origin", "feature/shared")
+            .remote_branch("origin", "feature/remote-only")
+            .submodule("libs/dep")
+            .config("[copy-patterns]\ninclude = [\".env\"]\n")
+            .build()?;
+
+        assert_eq!(env.git(&["rev-list", "--count", "HEAD"])?.trim(), "4");
+        assert_eq!(env.git(&["tag", "--list"])?.trim(), "v1.0");
+        assert!(!env.git(&["branch", "--list", "feature/local"])?.is_empty());
+        assert_eq!(
+            env.git(&["rev-parse", "--abbrev-ref", "feature/shared@{upstream}"])?
+                .trim(),
+            "origin/feature/shared"
+        );
+        assert!(env
+            .git(&["branch", "--list", "feature/remote-only"])?
+            .is_empty());
+        assert!(!env
+            .git(&["branch", "-r", "--list", "origin/feature/remote-only"])?
+            .is_empty());
+        env.repo_dir
+            .child("libs/dep/README.md")
+            .assert(predicate::path::exists());
+        env.repo_dir
+            .child(".worktree-config.toml")
+            .assert(predicate::str::contains(".env"));
+        env.remote_path("origin")
+            .child("HEAD")
+            .assert(predicate::path::exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_creates_other_and_bare_repos() -> Result<()> {
+        let env = CliTestEnvironment::builder()
+            .other_repo("other_repo")
+            .bare_repo("bare_repo")
+            .build()?;
+
+        env.other_repo("other_repo")
+            .child(".git")
+            .assert(predicate::path::exists());
+        let bare = env.bare_repo("bare_repo");
+        assert_eq!(
+            git(bare.path(), &["rev-parse", "--is-bare-repository"])?.trim(),
+            "true"
+        );
+        assert!(!git(bare.path(), &["branch", "--list", "main"])?.is_empty());
+
+        Ok(())
+    }
+}
@@ -4,27 +4,167 @@ use anyhow::{Context, Result};
 use assert_fs::prelude::*;
 use assert_fs::TempDir;
 
+use std::path::Path;
 use std::process::Command;
 
+use crate::builder::CliTestEnvironmentBuilder;
+
+/// Runs `git` with `args` in `dir` and returns its stdout
+///
+/// # Errors
+/// Returns an error if git cannot be run or exits unsuccessfully.
+pub fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("Failed to execute git command")?;
+    anyhow::ensure!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Writes `content` to `file` in `dir` and commits it as "Change <file>"
+///
+/// # Errors
+/// Returns an error if the file cannot be written or git fails.
+pub fn commit_file(dir: &Path, file: &str, content: &str) -> Result<()> {
+    std::fs::write(dir.join(file), content)?;
+    git(dir, &["add", file])?;
+    git(dir, &["commit", "-m", &format!("Change {}", file)])?;
+    Ok(())
+}
+
 /// Modern test environment using assert_fs for simplified setup and cleanup
 pub struct CliTestEnvironment {
     pub repo_dir: assert_fs::fixture::ChildPath,
     pub storage_dir: assert_fs::fixture::ChildPath,
-    _temp_dir: TempDir,
+    temp_dir: TempDir,
 }
 
 impl CliTestEnvironment {
+    /// Starts building an environment with remotes, branches, tags, extra commits,
+    /// submodules, or a worktree config set up before the test runs
+    pub fn builder() -> CliTestEnvironmentBuilder {
+        CliTestEnvironmentBuilder::default()
+    }
+
     /// Creates a new test environment with a real git repository and storage directory
     ///
     /// # Errors
     /// Returns an error if the temporary directory or git repository cannot be created.
     pub fn new() -> Result<Self> {
         let temp_dir = TempDir::new().context("Failed to create temporary directory")?;
-        let repo_dir = temp_dir.child("test_repo");
+        let repo_dir = Self::init_repo(&temp_dir, "test_repo")?;
         let storage_dir = temp_dir.child("worktrees");
+        storage_dir.create_dir_all()?;
 
+        Ok(Self {
+            repo_dir,
+            storage_dir,
+            temp_dir,
+        })
+    }
+
+    /// Creates another git repository (on `main`, with one commit) next to `repo_dir`
+    ///
+    /// # Errors
+    /// Returns an error if the directory or git repository cannot be created.
+    pub fn create_other_repo(&self, name: &str) -> Result<assert_fs::fixture::ChildPath> {
+        Self::init_repo(&self.temp_dir, name)
+    }
+
+    /// Path of another repository created with [`create_other_repo`](Self::create_other_repo)
+    /// or [`CliTestEnvironmentBuilder::other_repo`]
+    pub fn other_repo(&self, name: &str) -> assert_fs::fixture::ChildPath {
+        self.temp_dir.child(name)
+    }
+
+    /// Creates a bare clone of the test repository at `<name>/.bare`, the layout
+    /// `worktree clone` uses, so worktrees created from it are stored under `name`
+    ///
+    /// # Errors
+    /// Returns an error if the clone fails.
+    pub fn create_bare_repo(&self, name: &str) -> Result<assert_fs::fixture::ChildPath> {
+        let bare_dir = self.bare_repo(name);
+        git(
+            self.temp_dir.path(),
+            &[
+                "clone",
+                "--bare",
+                &self.repo_dir.path().to_string_lossy(),
+                &bare_dir.path().to_string_lossy(),
+            ],
+        )?;
+        Ok(bare_dir)
+    }
+
+    /// Path of a bare repository created with [`create_bare_repo`](Self::create_bare_repo)
+    /// or [`CliTestEnvironmentBuilder::bare_repo`]
+    pub fn bare_repo(&self, name: &str) -> assert_fs::fixture::ChildPath {
+        self.temp_dir.child(name).child(".bare")
+    }
+
+    /// Runs `git` with `args` in the test repository and returns its stdout
+    ///
+    /// # Errors
+    /// Returns an error if git cannot be run or exits unsuccessfully.
+    pub fn git(&self, args: &[&str]) -> Result<String> {
+        git(self.repo_dir.path(), args)
+    }
+
+    /// Moves a worktree's recorded creation and last use, and the last change to its git
+    /// index, back to `timestamp` (seconds since the Unix epoch)
+    ///
+    /// # Errors
+    /// Returns an error if the activity file cannot be rewritten or `touch` fails.
+    pub fn backdate_recorded_activity(&self, feature: &str, timestamp: u64) -> Result<()> {
+        let activity = self
+            .storage_dir
+            .child("test_repo")
+            .child(".worktree-activity");
+        let content: String = std::fs::read_to_string(activity.path())?
+            .lines()
+            .map(|line| match line.split_whitespace().next() {
+                Some(name) if name == feature => format!("{feature} {timestamp} {timestamp}\n"),
+                _ => format!("{line}\n"),
+            })
+            .collect();
+        activity.write_str(&content)?;
+
+        let index = self
+            .repo_dir
+            .path()
+            .join(".git/worktrees")
+            .join(feature)
+            .join("index");
+        let output = Command::new("touch")
+            .arg("-d")
+            .arg(format!("@{timestamp}"))
+            .arg(&index)
+            .output()?;
+        anyhow::ensure!(output.status.success(), "touch {} failed", index.display());
+        Ok(())
+    }
+
+    /// Path of the bare repository backing the remote `name` added by
+    /// [`CliTestEnvironmentBuilder::remote`]
+    pub fn remote_path(&self, name: &str) -> assert_fs::fixture::ChildPath {
+        self.temp_dir.child(format!("{name}.git"))
+    }
+
+    /// The temporary directory holding the repository, storage, and remotes
+    pub fn temp_dir(&self) -> &TempDir {
+        &self.temp_dir
+    }
+
+    fn init_repo(temp_dir: &TempDir, name: &str) -> Result<assert_fs::fixture::ChildPath> {
+        let repo_dir = temp_dir.child(name);
         repo_dir.create_dir_all()?;
-        storage_dir.create_dir_all()?;
 
         Self::run_git_command(&repo_dir, &["init"])?;
         Self::run_git_command(&repo_dir, &["config", "user.name", "Test User"])?;
@@ -35,25 +175,11 @@ impl CliTestEnvironment {
         Self::run_git_command(&repo_dir, &["commit", "-m", "Initial commit"])?;
         Self::run_git_command(&repo_dir, &["branch", "-M", "main"])?;
 
-        Ok(Self {
-            repo_dir,
-            storage_dir,
-            _temp_dir: temp_dir,
-        })
+        Ok(repo_dir)
     }
 
     fn run_git_command(repo_path: &assert_fs::fixture::ChildPath, args: &[&str]) -> Result<()> {
-        let output = Command::new("git")
-            .args(args)
-            .current_dir(repo_path.path())
-            .output()
-            .context("Failed to execute git command")?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("Git command failed: {}", stderr);
-        }
-
+        git(repo_path.path(), args)?;
         Ok(())
     }
 
@@ -75,7 +201,16 @@ impl CliTestEnvironment {
     /// Get the path to a worktree within the storage directory.
     /// Under the feature-named model, the path is the feature name directly (no sanitization).
     pub fn worktree_path(&self, feature_name: &str) -> assert_fs::fixture::ChildPath {
-        self.storage_dir.child("test_repo").child(feature_name)
+        self.repo_worktree_path("test_repo", feature_name)
+    }
+
+    /// Get the path to a worktree of any repository sharing the storage directory
+    pub fn repo_worktree_path(
+        &self,
+        repo_name: &str,
+        feature_name: &str,
+    ) -> assert_fs::fixture::ChildPath {
+        self.storage_dir.child(repo_name).child(feature_name)
     }
 
     /// Check if we're running in a CI environment or without a TTY
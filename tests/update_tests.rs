@@ -0,0 +1,106 @@
+use anyhow::Result;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use test_support::{CliTestEnvironment, commit_file, git};
+
+/// `update` rebases the worktree's branch onto the default branch and keeps
+/// uncommitted changes
+#[test]
+fn test_update_rebases_onto_default_branch() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    let worktree = env.worktree_path("auth");
+    commit_file(worktree.path(), "auth.txt", "auth")?;
+    commit_file(env.repo_dir.path(), "main.txt", "main")?;
+    worktree
+        .child("README.md")
+        .write_str("# Work in progress")?;
+
+    env.run_command(&["update", "auth"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Rebasing feature/auth onto main"))
+        .stdout(predicate::str::contains("✓ Updated feature/auth onto main"));
+
+    git(
+        worktree.path(),
+        &["merge-base", "--is-ancestor", "main", "HEAD"],
+    )?;
+    worktree.child("auth.txt").assert(predicate::path::exists());
+    worktree.child("README.md").assert("# Work in progress");
+
+    env.run_command(&["update", "auth"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("up to date"));
+
+    Ok(())
+}
+
+/// Conflicts abort the rebase, leave the branch as it was, and list the files
+#[test]
+fn test_update_reports_conflicts() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    let worktree = env.worktree_path("auth");
+    commit_file(worktree.path(), "README.md", "# Auth")?;
+    commit_file(env.repo_dir.path(), "README.md", "# Main")?;
+    let before = git(worktree.path(), &["rev-parse", "HEAD"])?;
+
+    env.run_command(&["update", "auth"])?
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Conflicts with main"))
+        .stderr(predicate::str::contains("README.md"));
+
+    assert_eq!(git(worktree.path(), &["rev-parse", "HEAD"])?, before);
+    assert!(git(worktree.path(), &["status", "--porcelain"])?.is_empty());
+
+    Ok(())
+}
+
+/// `strategy = "merge"` merges the base branch, which is the branch recorded by
+/// `create --from`
+#[test]
+fn test_update_merges_recorded_base() -> Result<()> {
+    let env = CliTestEnvironment::builder()
+        .branch("develop")
+        .config("[update]\nstrategy = \"merge\"\n")
+        .build()?;
+    env.run_command(&["create", "auth", "feature/auth", "--from", "develop"])?
+        .assert()
+        .success();
+    assert_eq!(
+        env.git(&["config", "branch.feature/auth.worktree-base"])?
+            .trim(),
+        "develop"
+    );
+
+    let worktree = env.worktree_path("auth");
+    commit_file(worktree.path(), "auth.txt", "auth")?;
+    env.git(&["checkout", "develop"])?;
+    commit_file(env.repo_dir.path(), "develop.txt", "develop")?;
+    env.git(&["checkout", "main"])?;
+
+    env.run_command(&["update", "auth"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Merging feature/auth onto develop",
+        ));
+
+    let parents = git(
+        worktree.path(),
+        &["rev-list", "--parents", "-n", "1", "HEAD"],
+    )?;
+    assert_eq!(parents.split_whitespace().count(), 3);
+    worktree
+        .child("develop.txt")
+        .assert(predicate::path::exists());
+
+    Ok(())
+}
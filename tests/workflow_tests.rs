@@ -419,3 +419,47 @@ fn test_scale_workflow() -> Result<()> {
 
     Ok(())
 }
+
+/// Commands run from inside a managed worktree act on the main repository
+#[test]
+fn test_commands_from_inside_worktree_use_main_repo() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+
+    env.run_command(&["create", "first", "feature/first"])?
+        .assert()
+        .success();
+    let first = env.worktree_path("first");
+
+    env.run_command(&["create", "second", "feature/second"])?
+        .current_dir(first.path())
+        .assert()
+        .success();
+
+    // Stored under the main repository's name, not the worktree's
+    env.worktree_path("second")
+        .assert(predicate::path::is_dir());
+    env.storage_dir
+        .child("first")
+        .assert(predicate::path::missing());
+
+    env.run_command(&["status"])?
+        .current_dir(first.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Repository: test_repo"))
+        .stdout(predicate::str::contains("second"));
+
+    // The origin of a worktree created from another worktree is the main repository
+    let back = env
+        .run_command(&["back"])?
+        .current_dir(env.worktree_path("second").path())
+        .assert()
+        .success();
+    let origin = String::from_utf8(back.get_output().stdout.clone())?;
+    assert_eq!(
+        std::path::Path::new(origin.trim()).canonicalize()?,
+        env.repo_dir.path().canonicalize()?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+//! Integration tests for the workspace command
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use serde_json::Value;
+
+use test_support::CliTestEnvironment;
+
+/// Names and paths of the folders in the repository's VS Code workspace
+fn workspace_folders(env: &CliTestEnvironment) -> Result<Vec<(String, String)>> {
+    let path = env
+        .storage_dir
+        .child("test_repo")
+        .child("test_repo.code-workspace");
+    let workspace: Value = serde_json::from_str(&std::fs::read_to_string(path.path())?)?;
+    Ok(workspace["folders"]
+        .as_array()
+        .map(|folders| {
+            folders
+                .iter()
+                .map(|folder| {
+                    (
+                        folder["name"].as_str().unwrap_or_default().to_string(),
+                        folder["path"].as_str().unwrap_or_default().to_string(),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// The workspace lists the main repository and worktrees named by branch
+#[test]
+fn test_workspace_vscode_lists_repo_and_worktrees() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+
+    env.run_command(&["workspace", "vscode"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("test_repo.code-workspace"));
+
+    let folders = workspace_folders(&env)?;
+    assert_eq!(folders.len(), 2);
+    assert_eq!(folders[0].0, "test_repo");
+    assert_eq!(
+        folders[1],
+        (
+            "feature/auth".to_string(),
+            env.worktree_path("auth").display().to_string()
+        )
+    );
+
+    Ok(())
+}
+
+/// Once generated, the workspace follows creates and removes and keeps its settings
+#[test]
+fn test_workspace_vscode_regenerates_on_create_and_remove() -> Result<()> {
+    let env = CliTestEnvironment::new()?;
+    env.run_command(&["workspace", "vscode"])?
+        .assert()
+        .success();
+    let path = env
+        .storage_dir
+        .child("test_repo")
+        .child("test_repo.code-workspace");
+    path.write_str("{\"folders\": [], \"settings\": {\"editor.tabSize\": 2}}")?;
+
+    env.run_command(&["create", "auth", "feature/auth"])?
+        .assert()
+        .success();
+    let folders = workspace_folders(&env)?;
+    assert_eq!(folders.len(), 2);
+    assert_eq!(folders[1].0, "feature/auth");
+
+    env.run_command(&["remove", "auth"])?.assert().success();
+    assert_eq!(workspace_folders(&env)?.len(), 1);
+
+    let workspace: Value = serde_json::from_str(&std::fs::read_to_string(path.path())?)?;
+    assert_eq!(workspace["settings"]["editor.tabSize"], 2);
+
+    Ok(())
+}